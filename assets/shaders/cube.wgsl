@@ -0,0 +1,73 @@
+struct Camera {
+  view_proj: mat4x4<f32>,
+  // x = Tageslichtfaktor fürs Himmelslicht
+  daylight: vec4<f32>,
+  // xyz = Kameraposition, w = Nebelbeginn
+  eye: vec4<f32>,
+  // rgb = Nebelfarbe, w = Nebelende
+  fog: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
+struct VSIn {
+  @location(0) pos: vec3<f32>,
+  @location(1) color: vec3<f32>,
+  @location(2) light: vec2<f32>,
+  @location(3) emissive: f32,
+};
+
+struct VSOut {
+  @builtin(position) clip_pos: vec4<f32>,
+  @location(0) color: vec3<f32>,
+  @location(1) light: f32,
+  @location(2) fog: f32,
+};
+
+// Lichtstufe 0..1 -> Helligkeit: pro Stufe 20% dunkler, etwas Grundhelligkeit
+fn brightness(level: f32) -> f32 {
+  return 0.05 + 0.95 * pow(0.8, (1.0 - level) * 15.0);
+}
+
+fn vertex(input: VSIn, pos: vec3<f32>) -> VSOut {
+  var out: VSOut;
+  out.clip_pos = camera.view_proj * vec4<f32>(pos, 1.0);
+  out.color = input.color;
+  // Himmel (x, nach Tageszeit skaliert) und Blocklicht (y): das hellere gewinnt
+  let sky = input.light.x * camera.daylight.x;
+  out.light = max(brightness(max(sky, input.light.y)), input.emissive);
+  // Linearer Nebel zur Himmelsfarbe, endet kurz vor der Fernebene
+  let dist = distance(pos, camera.eye.xyz);
+  out.fog = clamp((dist - camera.eye.w) / (camera.fog.w - camera.eye.w), 0.0, 1.0);
+  return out;
+}
+
+@vertex
+fn vs_main(input: VSIn) -> VSOut {
+  return vertex(input, input.pos);
+}
+
+// Chunks: ein Draw pro Chunk, der Versatz (Aufsteigen) kommt als Instanzattribut
+@vertex
+fn vs_chunk(input: VSIn, @location(4) offset: vec3<f32>) -> VSOut {
+  return vertex(input, input.pos + offset);
+}
+
+@fragment
+fn fs_main(input: VSOut) -> @location(0) vec4<f32> {
+  return vec4<f32>(mix(input.color * input.light, camera.fog.rgb, input.fog), 1.0);
+}
+
+// Sterne und Mond: liegen hinter allem, ohne Nebel
+@fragment
+fn fs_sky(input: VSOut) -> @location(0) vec4<f32> {
+  return vec4<f32>(input.color * input.light, 1.0);
+}
+
+
+// Schattenflecken: Farbe ist der Faktor fürs Multiplizieren, im Nebel verblasst er
+@fragment
+fn fs_shadow(input: VSOut) -> @location(0) vec4<f32> {
+  return vec4<f32>(mix(input.color, vec3<f32>(1.0), input.fog), 1.0);
+}
@@ -0,0 +1,26 @@
+// Post-Pass: die 3D-Szene (offscreen, evtl. mit kleinerer Auflösung) aufs Fenster ziehen
+
+@group(0) @binding(0)
+var scene: texture_2d<f32>;
+@group(0) @binding(1)
+var scene_sampler: sampler;
+
+struct VSOut {
+  @builtin(position) clip_pos: vec4<f32>,
+  @location(0) uv: vec2<f32>,
+};
+
+// Ein Dreieck über den ganzen Schirm, ohne Vertexbuffer
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VSOut {
+  var out: VSOut;
+  let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+  out.clip_pos = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+  out.uv = uv;
+  return out;
+}
+
+@fragment
+fn fs_main(input: VSOut) -> @location(0) vec4<f32> {
+  return textureSample(scene, scene_sampler, input.uv);
+}
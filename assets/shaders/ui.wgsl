@@ -0,0 +1,22 @@
+struct VSIn {
+  @location(0) pos: vec2<f32>,
+  @location(1) color: vec4<f32>,
+};
+
+struct VSOut {
+  @builtin(position) clip_pos: vec4<f32>,
+  @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(input: VSIn) -> VSOut {
+  var out: VSOut;
+  out.clip_pos = vec4<f32>(input.pos, 0.0, 1.0);
+  out.color = input.color;
+  return out;
+}
+
+@fragment
+fn fs_main(input: VSOut) -> @location(0) vec4<f32> {
+  return input.color;
+}
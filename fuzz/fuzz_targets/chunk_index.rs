@@ -0,0 +1,30 @@
+//! Block- und Chunk-Koordinaten an den Rändern des i32-Bereichs:
+//! `cargo +nightly fuzz run chunk_index`
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_game::chunk::{chunk_coord, idx, in_chunk, CHUNK_SIZE, CHUNK_VOL};
+use rust_game::world::World;
+
+thread_local! {
+    static WORLD: World = World::deterministic(0);
+}
+
+fuzz_target!(|pos: (i32, i32, i32)| {
+    let (x, y, z) = pos;
+    let [lx, ly, lz] = [x, y, z].map(|v| {
+        let (c, l) = (chunk_coord(v), in_chunk(v));
+        assert!((0..CHUNK_SIZE).contains(&l), "in_chunk({v}) = {l}");
+        // In i64 zurückrechnen: c * CHUNK_SIZE selbst läuft am Rand über
+        assert_eq!(c as i64 * CHUNK_SIZE as i64 + l as i64, v as i64);
+        l
+    });
+    assert!(idx(lx, ly, lz) < CHUNK_VOL);
+
+    // Lesen an beliebigen Stellen darf nie panicen, auch ohne geladenen Chunk
+    WORLD.with(|world| {
+        world.get_block(x, y, z);
+        world.get_state(x, y, z);
+        world.is_solid(x, y, z);
+    });
+});
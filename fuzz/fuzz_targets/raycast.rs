@@ -0,0 +1,27 @@
+//! Zielstrahl mit beliebigen Ursprüngen, Richtungen und Reichweiten (null, NaN, unendlich,
+//! riesig); hängt sich der Strahl auf, meldet libFuzzer einen Timeout:
+//! `cargo +nightly fuzz run raycast -- -timeout=1`
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_game::world::{World, MAX_RAYCAST_DIST};
+
+thread_local! {
+    static WORLD: World = World::deterministic(0);
+}
+
+fuzz_target!(|ray: ([f32; 3], [f32; 3], f32)| {
+    let ([ox, oy, oz], [dx, dy, dz], max_dist) = ray;
+    let (origin, dir) = ((ox, oy, oz), (dx, dy, dz));
+    WORLD.with(|world| {
+        let hits = [
+            world.raycast(origin, dir, max_dist),
+            world.raycast_fluid(origin, dir, max_dist),
+        ];
+        for hit in hits.into_iter().flatten() {
+            assert!(hit.distance >= 0.0 && hit.distance <= max_dist.min(MAX_RAYCAST_DIST));
+            let (px, py, pz) = hit.point;
+            assert!(px.is_finite() && py.is_finite() && pz.is_finite());
+        }
+    });
+});
@@ -0,0 +1,248 @@
+//! Dateien aus `assets/`: Shader, Texturen, Sounds, Blockdaten und der UI-Font. Alles
+//! Mitgelieferte ist zusätzlich eingebettet, das Spiel läuft also auch ohne das Verzeichnis;
+//! eine Datei dort ersetzt die eingebaute Fassung. Handles zählen mit, wer ein Asset noch
+//! braucht, und sehen neu geladene Fassungen sofort (Hot-Reload in Debug-Builds). Ausnahme
+//! sind die Blockdaten: sie werden einmal beim Start gelesen und bleiben dann fest.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+
+use crate::settings;
+
+pub const ASSET_DIR: &str = "assets";
+
+/// Eingebettete Fassung jeder mitgelieferten Datei
+const BUILTIN: &[(&str, &[u8])] = &[
+    ("blocks.toml", include_bytes!("../assets/blocks.toml")),
+    ("font.txt", include_bytes!("../assets/font.txt")),
+    ("shaders/cube.wgsl", include_bytes!("../assets/shaders/cube.wgsl")),
+    ("shaders/ui.wgsl", include_bytes!("../assets/shaders/ui.wgsl")),
+    ("shaders/post.wgsl", include_bytes!("../assets/shaders/post.wgsl")),
+];
+
+fn builtin(path: &str) -> Option<&'static [u8]> {
+    BUILTIN.iter().find(|(p, _)| *p == path).map(|(_, data)| *data)
+}
+
+/// Aus den Bytes einer Datei gebaut
+pub trait Asset: Send + Sync + Sized + 'static {
+    fn decode(bytes: &[u8]) -> Result<Self>;
+}
+
+/// WGSL-Quelltext
+pub struct Shader(pub String);
+
+impl Asset for Shader {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        Ok(Self(String::from_utf8(bytes.to_vec()).context("shader is not UTF-8")?))
+    }
+}
+
+/// Bild als RGBA8
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Asset for Texture {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().context("PNG header")?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).context("PNG data")?;
+        let pixels = &buf[..info.buffer_size()];
+        let rgba = match info.color_type {
+            png::ColorType::Rgba => pixels.to_vec(),
+            png::ColorType::Rgb => {
+                pixels.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect()
+            }
+            png::ColorType::GrayscaleAlpha => {
+                pixels.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect()
+            }
+            png::ColorType::Grayscale => pixels.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            png::ColorType::Indexed => bail!("indexed PNG was not expanded"),
+        };
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            rgba,
+        })
+    }
+}
+
+/// Kodierte Audiodatei; dekodiert wird beim Abspielen
+pub struct Sound(pub Vec<u8>);
+
+impl Asset for Sound {
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        Ok(Self(bytes.to_vec()))
+    }
+}
+
+struct Slot<T> {
+    value: RwLock<Arc<T>>,
+    /// Zählt Neuladungen, damit Nutzer abgeleitete Daten (Pipelines) neu bauen können
+    version: AtomicU32,
+}
+
+/// Geteilter Verweis auf ein geladenes Asset
+pub struct Handle<T> {
+    slot: Arc<Slot<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<T> Handle<T> {
+    /// Aktuelle Fassung; bleibt gültig, auch wenn inzwischen neu geladen wurde
+    pub fn get(&self) -> Arc<T> {
+        self.slot.value.read().expect("Asset-Lock").clone()
+    }
+
+    pub fn version(&self) -> u32 {
+        self.slot.version.load(Ordering::Relaxed)
+    }
+}
+
+/// Slot ohne bekannten Typ, damit `reload_changed` alle neu dekodieren kann
+trait AnySlot: Send + Sync {
+    fn replace(&self, bytes: &[u8]) -> Result<()>;
+    fn as_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+}
+
+impl<T: Asset> AnySlot for Slot<T> {
+    fn replace(&self, bytes: &[u8]) -> Result<()> {
+        let value = Arc::new(T::decode(bytes)?);
+        *self.value.write().expect("Asset-Lock") = value;
+        self.version.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn as_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+struct Entry {
+    slot: Arc<dyn AnySlot>,
+    /// Stand der Datei beim Laden (None = eingebaute Fassung)
+    modified: Option<SystemTime>,
+}
+
+pub struct Assets {
+    dir: PathBuf,
+    loaded: HashMap<(TypeId, String), Entry>,
+}
+
+impl Assets {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// `path` relativ zum Asset-Verzeichnis; schon geladene Assets werden geteilt
+    pub fn load<T: Asset>(&mut self, path: &str) -> Result<Handle<T>> {
+        let key = (TypeId::of::<T>(), path.to_string());
+        if let Some(entry) = self.loaded.get(&key) {
+            let slot = entry.slot.clone().as_any().downcast::<Slot<T>>();
+            return Ok(Handle {
+                slot: slot.expect("Slot passt zum Typ"),
+            });
+        }
+        let file = self.dir.join(path);
+        let modified = settings::modified(&file);
+        let value = decode_file_or_builtin(&file, path)?;
+        let slot = Arc::new(Slot {
+            value: RwLock::new(Arc::new(value)),
+            version: AtomicU32::new(0),
+        });
+        self.loaded.insert(
+            key,
+            Entry {
+                slot: slot.clone(),
+                modified,
+            },
+        );
+        Ok(Handle { slot })
+    }
+
+    /// Geänderte Dateien neu laden; liefert ihre Pfade. Fehlerhafte Fassungen werden
+    /// gemeldet, die alte bleibt dann in Gebrauch.
+    pub fn reload_changed(&mut self) -> Vec<String> {
+        let mut reloaded = Vec::new();
+        for ((_, path), entry) in &mut self.loaded {
+            let file = self.dir.join(path.as_str());
+            let modified = settings::modified(&file);
+            if modified == entry.modified {
+                continue;
+            }
+            entry.modified = modified;
+            let bytes = match modified {
+                Some(_) => fs::read(&file).with_context(|| format!("read {}", file.display())),
+                // Datei gelöscht: zurück zur eingebauten Fassung
+                None => builtin(path)
+                    .map(<[u8]>::to_vec)
+                    .with_context(|| format!("{path} removed and not built in")),
+            };
+            match bytes.and_then(|b| entry.slot.replace(&b)) {
+                Ok(()) => reloaded.push(path.clone()),
+                Err(e) => log::warn!("Asset {path} nicht neu geladen: {e:#}"),
+            }
+        }
+        reloaded
+    }
+
+    /// Assets vergessen, für die es keine Handles mehr gibt
+    pub fn release_unused(&mut self) -> usize {
+        let before = self.loaded.len();
+        self.loaded.retain(|_, entry| Arc::strong_count(&entry.slot) > 1);
+        before - self.loaded.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.loaded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.loaded.is_empty()
+    }
+}
+
+/// Einmalig laden, ohne Verwaltung (z.B. Daten, die nur beim Start gelesen werden)
+pub fn load_now<T: Asset>(path: &str) -> Result<T> {
+    decode_file_or_builtin(&Path::new(ASSET_DIR).join(path), path)
+}
+
+/// Datei, falls vorhanden und lesbar, sonst die eingebaute Fassung
+fn decode_file_or_builtin<T: Asset>(file: &Path, path: &str) -> Result<T> {
+    if file.exists() {
+        let decoded = fs::read(file)
+            .with_context(|| format!("read {}", file.display()))
+            .and_then(|bytes| T::decode(&bytes));
+        match decoded {
+            Ok(value) => return Ok(value),
+            Err(e) if builtin(path).is_some() => {
+                log::warn!("Asset {}: {e:#}; nehme eingebaute Fassung", file.display());
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    let bytes = builtin(path).with_context(|| format!("asset {path} not found"))?;
+    T::decode(bytes).with_context(|| format!("built-in asset {path}"))
+}
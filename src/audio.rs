@@ -0,0 +1,143 @@
+//! Tonausgabe über rodio. Geräusch-Ereignisse vom Client landen in einer Warteschlange und
+//! werden einmal pro Frame als kurze Einzelklänge abgespielt, leiser mit der Entfernung.
+//! Dazu laufen Ambient-Schleifen (überblendet nach Umgebung) und Musik aus `assets/music`.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use rodio::buffer::SamplesBuffer;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use rust_game::block::SoundGroup;
+use rust_game::settings::AudioSettings;
+use rust_game::sound::{self, SoundEvent, SoundKind};
+use rust_game::soundscape::{Ambience, Crossfade, Playlist};
+
+/// Höchstens so viele neue Klänge pro Frame (Massenabbau per `/fill` soll nicht dröhnen)
+const MAX_PER_FRAME: usize = 8;
+/// Längere Rückstände (Fenster verdeckt) verwerfen statt nachzuspielen
+const MAX_QUEUED: usize = 64;
+/// Ambient-Schleifen relativ zur Gesamtlautstärke
+const AMBIENT_GAIN: f32 = 0.35;
+
+pub struct Audio {
+    /// Muss leben, solange abgespielt wird
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    queue: VecDeque<SoundEvent>,
+    /// Erzeugte Klänge je Art und Gruppe
+    clips: HashMap<(SoundKind, SoundGroup), Vec<f32>>,
+    /// Je Umgebung eine endlos laufende Schleife, der Pegel folgt `crossfade`
+    ambience: Vec<(Ambience, Sink)>,
+    crossfade: Crossfade,
+    music: Option<Sink>,
+    playlist: Playlist,
+}
+
+impl Audio {
+    /// None ohne Ausgabegerät (Spiel läuft dann stumm)
+    pub fn new(settings: &AudioSettings) -> Option<Self> {
+        let (stream, handle) = match OutputStream::try_default() {
+            Ok(out) => out,
+            Err(e) => {
+                log::warn!("Keine Tonausgabe: {e}");
+                return None;
+            }
+        };
+        let ambience = Ambience::ALL
+            .into_iter()
+            .filter_map(|a| {
+                let sink = Sink::try_new(&handle)
+                    .inspect_err(|e| log::warn!("Ambient {a:?} nicht verfügbar: {e}"))
+                    .ok()?;
+                sink.set_volume(0.0);
+                let clip = SamplesBuffer::new(1, sound::SAMPLE_RATE, a.synthesize());
+                sink.append(clip.repeat_infinite());
+                Some((a, sink))
+            })
+            .collect();
+        let music = Sink::try_new(&handle)
+            .inspect_err(|e| log::warn!("Musik nicht verfügbar: {e}"))
+            .ok();
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Some(Self {
+            _stream: stream,
+            handle,
+            queue: VecDeque::new(),
+            clips: HashMap::new(),
+            ambience,
+            crossfade: Crossfade::default(),
+            music,
+            playlist: Playlist::scan(settings.shuffle, seed),
+        })
+    }
+
+    pub fn push(&mut self, ev: SoundEvent) {
+        if self.queue.len() == MAX_QUEUED {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(ev);
+    }
+
+    /// Warteschlange abspielen; `listener` ist die Kameraposition, `volume` die
+    /// Gesamtlautstärke. Was über `MAX_PER_FRAME` hinausgeht, verfällt.
+    pub fn play(&mut self, listener: (f32, f32, f32), volume: f32) {
+        for ev in self.queue.drain(..).take(MAX_PER_FRAME) {
+            let gain = ev.gain(listener, volume);
+            if gain <= 0.0 {
+                continue;
+            }
+            let clip = self
+                .clips
+                .entry((ev.kind, ev.group))
+                .or_insert_with(|| sound::synthesize(ev.kind, ev.group));
+            let source = SamplesBuffer::new(1, sound::SAMPLE_RATE, clip.clone())
+                .speed(ev.pitch)
+                .amplify(gain);
+            if let Err(e) = self.handle.play_raw(source) {
+                log::debug!("Sound {:?} nicht abgespielt: {e}", ev.kind);
+            }
+        }
+    }
+
+    /// Klangkulisse nachführen (einmal pro Tick): Ambient zur Umgebung am Zuhörer
+    /// überblenden, Lautstärken übernehmen und nach einem Musikstück das nächste starten
+    pub fn update(&mut self, daylight: f32, sky_light: u8, settings: &AudioSettings, dt: f32) {
+        self.crossfade.update(Ambience::at(daylight, sky_light), dt);
+        for (a, sink) in &self.ambience {
+            sink.set_volume(self.crossfade.level(*a) * AMBIENT_GAIN * settings.volume);
+        }
+
+        let Some(music) = &self.music else {
+            return;
+        };
+        music.set_volume(settings.volume * settings.music_volume);
+        self.playlist.set_shuffle(settings.shuffle);
+        if !music.empty() || settings.music_volume <= 0.0 {
+            return;
+        }
+        let Some(path) = self.playlist.next_track().map(Path::to_path_buf) else {
+            return;
+        };
+        match open_track(&path) {
+            Ok(track) => {
+                log::info!("Musik: {}", path.display());
+                music.append(track);
+            }
+            Err(e) => {
+                log::warn!("{e:#}");
+                self.playlist.remove(&path);
+            }
+        }
+    }
+}
+
+fn open_track(path: &Path) -> Result<Decoder<BufReader<File>>> {
+    let file = File::open(path).with_context(|| format!("open {}", path.display()))?;
+    Decoder::new(BufReader::new(file)).with_context(|| format!("decode {}", path.display()))
+}
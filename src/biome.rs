@@ -0,0 +1,58 @@
+use crate::noise::value_noise_2d;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Biome {
+    Plains,
+    Desert,
+    /// Kalt: bei Niederschlag fällt Schnee
+    Snowy,
+}
+
+/// Welche Färbung ein Block aus dem Biom übernimmt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tint {
+    None,
+    Grass,
+    Foliage,
+}
+
+/// Gitterabstand der Temperatur-Noise in Blöcken
+const BIOME_SCALE: f32 = 96.0;
+/// Darunter ist es kalt genug für Schnee
+const COLD_TEMPERATURE: f32 = 0.3;
+
+/// Temperatur 0..1 an einer (kontinuierlichen) Position – Grundlage für Biome und Tints
+pub fn temperature(seed: u64, x: f32, z: f32) -> f32 {
+    value_noise_2d(seed ^ 0xB10E, x, z, BIOME_SCALE)
+}
+
+pub fn biome_at(seed: u64, x: i32, z: i32) -> Biome {
+    let t = temperature(seed, x as f32 + 0.5, z as f32 + 0.5);
+    if t > 0.6 {
+        Biome::Desert
+    } else if t < COLD_TEMPERATURE {
+        Biome::Snowy
+    } else {
+        Biome::Plains
+    }
+}
+
+/// Fällt hier Schnee statt Regen?
+pub fn is_cold(seed: u64, x: f32, z: f32) -> bool {
+    temperature(seed, x, z) < COLD_TEMPERATURE
+}
+
+/// Farbmultiplikator für einen Vertex; weicher Übergang zwischen den Biomen
+pub fn tint_at(seed: u64, tint: Tint, x: f32, z: f32) -> [f32; 3] {
+    let (plains, desert) = match tint {
+        Tint::None => return [1.0, 1.0, 1.0],
+        Tint::Grass => ([1.0, 1.0, 1.0], [1.55, 1.15, 0.65]),
+        Tint::Foliage => ([1.0, 1.0, 1.0], [1.40, 1.05, 0.60]),
+    };
+    let t = ((temperature(seed, x, z) - 0.5) / 0.2).clamp(0.0, 1.0);
+    [
+        plains[0] + (desert[0] - plains[0]) * t,
+        plains[1] + (desert[1] - plains[1]) * t,
+        plains[2] + (desert[2] - plains[2]) * t,
+    ]
+}
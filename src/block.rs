@@ -1,12 +1,902 @@
+use std::sync::LazyLock;
+
+use anyhow::{bail, Context, Result};
+
+use crate::assets::{self, Asset};
+use crate::biome::Tint;
+use crate::block_entity::BlockEntityKind;
+use crate::inventory::ItemStack;
+use crate::item::{Item, Tool, ToolKind};
+use crate::settings::{parse_bool, parse_f32, parse_string};
+
+/// Wie ein Block Licht/Sicht durchlässt – steuert das Face-Culling im Mesher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opacity {
+    /// Wird gar nicht gerendert (Air)
+    Invisible,
+    /// Voll deckend: verdeckt Nachbar-Faces
+    Opaque,
+    /// Löchrig (Blätter): rendert immer alle Faces, verdeckt nichts
+    Cutout,
+    /// Durchscheinend (Glas, Wasser): Faces zwischen gleichen Blöcken entfallen
+    Translucent,
+}
+
+/// Blockeigenschaften unter `assets/` (eingebettet wie alle mitgelieferten Assets)
+pub const BLOCKS_ASSET: &str = "blocks.toml";
+
+/// Geometrie eines Blocks im Mesher
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Cube,
+    /// Halber Block, oben oder unten je nach State
+    Slab,
+    /// Dünner Stab, am Boden oder an der Wand (State)
+    Torch,
+    /// Ackerboden: 1/16 niedriger als ein Vollblock
+    Farmland,
+    /// Pflanze aus zwei gekreuzten Flächen, Höhe je nach Wachstumsstufe (State)
+    Crop,
+    /// Gekreuzte Flächen in voller Höhe (Gras, Blumen)
+    Cross,
+    /// Dünne Tür-Platte, Ausrichtung/offen aus dem State
+    Door,
+    /// Flache Schicht, Höhe in Achteln aus dem State (Schnee)
+    Layer,
+}
+
+/// Klanggruppe für Abbau-/Platzier-/Schrittgeräusche
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundGroup {
+    None,
+    Stone,
+    Wood,
+    Grass,
+    Gravel,
+    Glass,
+    Water,
+}
+
+/// Was ein Rechtsklick auf den Block tut, statt dort zu bauen (Fadenkreuz zeigt es an)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interaction {
+    /// Öffnet einen Screen (Kiste, Werkbank)
+    Open,
+    /// Schaltet den Block um (Tür)
+    Toggle,
+}
+
+impl Interaction {
+    /// Hinweis neben dem Fadenkreuz
+    pub fn label(self) -> &'static str {
+        match self {
+            Interaction::Open => "Open",
+            Interaction::Toggle => "Use",
+        }
+    }
+}
+
+/// Zustandswerte (Block-State) – Bedeutung hängt vom Blocktyp ab
+pub mod state {
+    /// Log: Achse des Stamms
+    pub const AXIS_Y: u8 = 0;
+    pub const AXIS_X: u8 = 1;
+    pub const AXIS_Z: u8 = 2;
+
+    /// Slab: untere/obere Hälfte
+    pub const SLAB_BOTTOM: u8 = 0;
+    pub const SLAB_TOP: u8 = 1;
+
+    /// Leaves: vom Spieler gesetzt -> verrotten nie
+    pub const LEAVES_PERSISTENT: u8 = 1;
+
+    /// Torch: Befestigung (Normale der Face, an der die Fackel hängt)
+    pub const TORCH_FLOOR: u8 = 0;
+    pub const TORCH_WALL_PX: u8 = 1;
+    pub const TORCH_WALL_NX: u8 = 2;
+    pub const TORCH_WALL_PZ: u8 = 3;
+    pub const TORCH_WALL_NZ: u8 = 4;
+
+    /// Crops: Wachstumsstufe 0..=CROP_MAX_STAGE
+    pub const CROP_MAX_STAGE: u8 = 7;
+    /// Setzling: Stufen 0..=1, danach wächst ein Baum
+    pub const SAPLING_MAX_STAGE: u8 = 1;
+
+    /// Zwei-Block-hohe Blöcke: Bit für die obere Hälfte
+    pub const UPPER_HALF: u8 = 0x8;
+    /// Tür: offen + Blickrichtung beim Platzieren (0 = -Z, 1 = +X, 2 = +Z, 3 = -X)
+    pub const DOOR_OPEN: u8 = 0x4;
+    pub const FACING_MASK: u8 = 0x3;
+
+    /// Schnee: Schichten - 1, also 0..=SNOW_MAX_LAYER (Höhe in Achteln)
+    pub const SNOW_MAX_LAYER: u8 = 7;
+
+    /// Richtung vom Torch zum tragenden Block
+    pub fn torch_support(st: u8) -> (i32, i32, i32) {
+        match st {
+            TORCH_WALL_PX => (-1, 0, 0),
+            TORCH_WALL_NX => (1, 0, 0),
+            TORCH_WALL_PZ => (0, 0, -1),
+            TORCH_WALL_NZ => (0, 0, 1),
+            _ => (0, -1, 0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
 pub enum Block {
+    #[default]
     Air,
     Dirt,
     Stone,
+    Leaves,
+    Glass,
+    Water,
+    Log,
+    StoneSlab,
+    Chest,
+    Grass,
+    Torch,
+    Farmland,
+    Wheat,
+    Door,
+    TallGrass,
+    Sapling,
+    Planks,
+    CraftingTable,
+    Snow,
+}
+
+/// Statische Eigenschaften eines Blocktyps (Registry-Eintrag)
+#[derive(Debug, PartialEq)]
+pub struct BlockDef {
+    pub name: &'static str,
+    pub opacity: Opacity,
+    pub shape: Shape,
+    /// Kollidiert mit dem Spieler
+    pub solid: bool,
+    pub color: [f32; 3],
+    /// Block trägt Zusatzdaten (wird beim Platzieren angelegt)
+    pub block_entity: Option<BlockEntityKind>,
+    /// Bekommt zufällige Ticks (Wachstum, Verfall)
+    pub random_ticks: bool,
+    /// Eigene Lichtstärke 0..15
+    pub light: u8,
+    pub sound: SoundGroup,
+    /// Biom-Färbung (Gras, Blätter)
+    pub tint: Tint,
+    /// Härte: Abbauzeit von Hand = 1.5 s × Härte (0 = sofort)
+    pub hardness: f32,
+    /// Werkzeug, das den Abbau beschleunigt
+    pub tool: Option<ToolKind>,
+    /// Droppt nur mit dem passenden Werkzeug (und bricht ohne es deutlich langsamer)
+    pub needs_tool: bool,
+    /// Belegt zwei Positionen übereinander (untere + obere Hälfte)
+    pub tall: bool,
+    /// Fängt den Zielstrahl; sonst zielt man hindurch (Wasser, hohes Gras)
+    pub targetable: bool,
+    /// Flüssigkeit (eigene Abfrage `World::raycast_fluid`)
+    pub fluid: bool,
+    pub interaction: Option<Interaction>,
+}
+
+/// Eingebaute Registry: Index = `Block as usize`
+const BLOCK_DEFS: [BlockDef; Block::COUNT] = [
+    BlockDef {
+        name: "air",
+        opacity: Opacity::Invisible,
+        shape: Shape::Cube,
+        solid: false,
+        color: [0.0, 0.0, 0.0],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::None,
+        tint: Tint::None,
+        hardness: 0.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: false,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "dirt",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.55, 0.40, 0.20],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Gravel,
+        tint: Tint::None,
+        hardness: 0.5,
+        tool: Some(ToolKind::Shovel),
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "stone",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.60, 0.60, 0.60],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Stone,
+        tint: Tint::None,
+        hardness: 1.5,
+        tool: Some(ToolKind::Pickaxe),
+        needs_tool: true,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "leaves",
+        opacity: Opacity::Cutout,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.20, 0.50, 0.15],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Grass,
+        tint: Tint::Foliage,
+        hardness: 0.2,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "glass",
+        opacity: Opacity::Translucent,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.75, 0.90, 0.95],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Glass,
+        tint: Tint::None,
+        hardness: 0.3,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "water",
+        opacity: Opacity::Translucent,
+        shape: Shape::Cube,
+        solid: false,
+        color: [0.15, 0.35, 0.80],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Water,
+        tint: Tint::None,
+        hardness: 0.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: false,
+        fluid: true,
+        interaction: None,
+    },
+    BlockDef {
+        name: "log",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.40, 0.28, 0.15],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Wood,
+        tint: Tint::None,
+        hardness: 2.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "stone_slab",
+        opacity: Opacity::Opaque,
+        shape: Shape::Slab,
+        solid: true,
+        color: [0.66, 0.66, 0.68],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Stone,
+        tint: Tint::None,
+        hardness: 1.5,
+        tool: Some(ToolKind::Pickaxe),
+        needs_tool: true,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "chest",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.70, 0.48, 0.20],
+        block_entity: Some(BlockEntityKind::Container { slots: 27 }),
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Wood,
+        tint: Tint::None,
+        hardness: 2.5,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: Some(Interaction::Open),
+    },
+    BlockDef {
+        name: "grass",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.30, 0.60, 0.20],
+        block_entity: None,
+        random_ticks: true,
+        light: 0,
+        sound: SoundGroup::Grass,
+        tint: Tint::Grass,
+        hardness: 0.6,
+        tool: Some(ToolKind::Shovel),
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "torch",
+        opacity: Opacity::Cutout,
+        shape: Shape::Torch,
+        solid: false,
+        color: [0.45, 0.32, 0.18],
+        block_entity: None,
+        random_ticks: false,
+        light: 14,
+        sound: SoundGroup::Wood,
+        tint: Tint::None,
+        hardness: 0.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "farmland",
+        opacity: Opacity::Opaque,
+        shape: Shape::Farmland,
+        solid: true,
+        color: [0.42, 0.28, 0.14],
+        block_entity: None,
+        random_ticks: true,
+        light: 0,
+        sound: SoundGroup::Gravel,
+        tint: Tint::None,
+        hardness: 0.6,
+        tool: Some(ToolKind::Shovel),
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "wheat",
+        opacity: Opacity::Cutout,
+        shape: Shape::Crop,
+        solid: false,
+        color: [0.35, 0.70, 0.20],
+        block_entity: None,
+        random_ticks: true,
+        light: 0,
+        sound: SoundGroup::Grass,
+        tint: Tint::None,
+        hardness: 0.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "door",
+        opacity: Opacity::Cutout,
+        shape: Shape::Door,
+        solid: true,
+        color: [0.58, 0.42, 0.24],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Wood,
+        tint: Tint::None,
+        hardness: 3.0,
+        tool: None,
+        needs_tool: false,
+        tall: true,
+        targetable: true,
+        fluid: false,
+        interaction: Some(Interaction::Toggle),
+    },
+    BlockDef {
+        name: "tall_grass",
+        opacity: Opacity::Cutout,
+        shape: Shape::Cross,
+        solid: false,
+        color: [0.30, 0.62, 0.22],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Grass,
+        tint: Tint::Grass,
+        hardness: 0.0,
+        tool: None,
+        needs_tool: false,
+        tall: true,
+        targetable: false,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "sapling",
+        opacity: Opacity::Cutout,
+        shape: Shape::Cross,
+        solid: false,
+        color: [0.22, 0.50, 0.16],
+        block_entity: None,
+        random_ticks: true,
+        light: 0,
+        sound: SoundGroup::Grass,
+        tint: Tint::Foliage,
+        hardness: 0.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "planks",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.72, 0.56, 0.34],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Wood,
+        tint: Tint::None,
+        hardness: 2.0,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+    BlockDef {
+        name: "crafting_table",
+        opacity: Opacity::Opaque,
+        shape: Shape::Cube,
+        solid: true,
+        color: [0.58, 0.40, 0.22],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Wood,
+        tint: Tint::None,
+        hardness: 2.5,
+        tool: None,
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: Some(Interaction::Open),
+    },
+    BlockDef {
+        name: "snow",
+        opacity: Opacity::Opaque,
+        shape: Shape::Layer,
+        solid: false,
+        color: [0.93, 0.95, 0.98],
+        block_entity: None,
+        random_ticks: false,
+        light: 0,
+        sound: SoundGroup::Grass,
+        tint: Tint::None,
+        hardness: 0.1,
+        tool: Some(ToolKind::Shovel),
+        needs_tool: false,
+        tall: false,
+        targetable: true,
+        fluid: false,
+        interaction: None,
+    },
+];
+
+/// Registry aus `assets/blocks.toml`, einmal beim ersten Zugriff geladen (Änderungen wirken
+/// nach einem Neustart). Ohne Datei oder bei Fehlern gilt die eingebaute Tabelle.
+static DEFS: LazyLock<BlockDefs> = LazyLock::new(|| match assets::load_now(BLOCKS_ASSET) {
+    Ok(defs) => defs,
+    Err(e) => {
+        log::warn!("{BLOCKS_ASSET}: {e:#}; nehme eingebaute Blockdaten");
+        BlockDefs(BLOCK_DEFS)
+    }
+});
+
+/// Alle Blockeigenschaften, Index = `Block as usize`
+#[derive(Debug, PartialEq)]
+pub struct BlockDefs(pub [BlockDef; Block::COUNT]);
+
+impl BlockDefs {
+    pub fn builtin() -> Self {
+        Self(BLOCK_DEFS)
+    }
+
+    /// FNV-1a über die Debug-Ausgabe: jedes Feld zählt, gleiche Daten ergeben denselben Wert
+    pub fn checksum(&self) -> u64 {
+        format!("{:?}", self.0).bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
 }
 
-impl Default for Block {
-    fn default() -> Self {
-        Block::Air
+/// Prüfsumme der geladenen Blockdaten; Client und Server vergleichen sie im Handshake
+pub fn table_checksum() -> u64 {
+    DEFS.checksum()
+}
+
+impl Asset for BlockDefs {
+    /// `[name]` je Block, darunter `feld = wert`; nicht genannte Blöcke und Felder behalten
+    /// die eingebauten Werte. Form, Block-Entity, Flüssigkeit, Zweihöhe und Rechtsklick hängen
+    /// an Code (States, Screens) und bleiben fest.
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(bytes).context("blocks.toml is not UTF-8")?;
+        let mut defs = BLOCK_DEFS;
+        let mut block = None;
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let b = Block::ALL.into_iter().find(|b| BLOCK_DEFS[*b as usize].name == name);
+                block = Some(b.with_context(|| format!("line {}: unknown block '{name}'", n + 1))?);
+                continue;
+            }
+            let Some(b) = block else {
+                bail!("line {}: value outside of a [block] section", n + 1);
+            };
+            apply_field(&mut defs[b as usize], line).with_context(|| format!("line {}", n + 1))?;
+        }
+        Ok(Self(defs))
+    }
+}
+
+fn apply_field(def: &mut BlockDef, line: &str) -> Result<()> {
+    let (key, value) = line
+        .split_once('=')
+        .with_context(|| format!("no '=' in '{line}'"))?;
+    let (key, value) = (key.trim(), value.trim());
+    match key {
+        "opacity" => {
+            def.opacity = match parse_string(value)?.as_str() {
+                "invisible" => Opacity::Invisible,
+                "opaque" => Opacity::Opaque,
+                "cutout" => Opacity::Cutout,
+                "translucent" => Opacity::Translucent,
+                other => bail!("unknown opacity '{other}'"),
+            }
+        }
+        "solid" => def.solid = parse_bool(value)?,
+        "color" => def.color = parse_color(value)?,
+        "random_ticks" => def.random_ticks = parse_bool(value)?,
+        "light" => {
+            let light: u8 = value.parse().with_context(|| format!("not a number: '{value}'"))?;
+            def.light = light.min(15);
+        }
+        "sound" => {
+            def.sound = match parse_string(value)?.as_str() {
+                "none" => SoundGroup::None,
+                "stone" => SoundGroup::Stone,
+                "wood" => SoundGroup::Wood,
+                "grass" => SoundGroup::Grass,
+                "gravel" => SoundGroup::Gravel,
+                "glass" => SoundGroup::Glass,
+                "water" => SoundGroup::Water,
+                other => bail!("unknown sound group '{other}'"),
+            }
+        }
+        "tint" => {
+            def.tint = match parse_string(value)?.as_str() {
+                "none" => Tint::None,
+                "grass" => Tint::Grass,
+                "foliage" => Tint::Foliage,
+                other => bail!("unknown tint '{other}'"),
+            }
+        }
+        "hardness" => def.hardness = parse_f32(value)?.max(0.0),
+        "tool" => {
+            def.tool = match parse_string(value)?.as_str() {
+                "none" => None,
+                "pickaxe" => Some(ToolKind::Pickaxe),
+                "shovel" => Some(ToolKind::Shovel),
+                other => bail!("unknown tool '{other}'"),
+            }
+        }
+        "needs_tool" => def.needs_tool = parse_bool(value)?,
+        "targetable" => def.targetable = parse_bool(value)?,
+        _ => bail!("unknown field '{key}'"),
+    }
+    Ok(())
+}
+
+/// `[r, g, b]` mit Werten 0..1
+fn parse_color(value: &str) -> Result<[f32; 3]> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .with_context(|| format!("not a color: '{value}'"))?;
+    let v: Vec<f32> = inner.split(',').map(|s| parse_f32(s.trim())).collect::<Result<_>>()?;
+    let [r, g, b] = v[..] else {
+        bail!("color needs three values");
+    };
+    Ok([r, g, b].map(|c| c.clamp(0.0, 1.0)))
+}
+
+impl Block {
+    pub const COUNT: usize = 19;
+
+    pub const ALL: [Block; Block::COUNT] = [
+        Block::Air,
+        Block::Dirt,
+        Block::Stone,
+        Block::Leaves,
+        Block::Glass,
+        Block::Water,
+        Block::Log,
+        Block::StoneSlab,
+        Block::Chest,
+        Block::Grass,
+        Block::Torch,
+        Block::Farmland,
+        Block::Wheat,
+        Block::Door,
+        Block::TallGrass,
+        Block::Sapling,
+        Block::Planks,
+        Block::CraftingTable,
+        Block::Snow,
+    ];
+
+    pub fn from_id(id: u8) -> Option<Block> {
+        Block::ALL.get(id as usize).copied()
+    }
+
+    /// Registry-Name -> Block (für Befehle)
+    pub fn by_name(name: &str) -> Option<Block> {
+        Block::ALL.into_iter().find(|b| b.name() == name)
+    }
+
+    #[inline]
+    pub fn def(self) -> &'static BlockDef {
+        &DEFS.0[self as usize]
+    }
+
+    #[inline]
+    pub fn opacity(self) -> Opacity {
+        self.def().opacity
+    }
+
+    #[inline]
+    pub fn is_solid(self) -> bool {
+        self.def().solid
+    }
+
+    #[inline]
+    pub fn color(self) -> [f32; 3] {
+        self.def().color
+    }
+
+    pub fn name(self) -> &'static str {
+        self.def().name
+    }
+
+    #[inline]
+    pub fn tint(self) -> Tint {
+        self.def().tint
+    }
+
+    pub fn interaction(self) -> Option<Interaction> {
+        self.def().interaction
+    }
+
+    #[inline]
+    pub fn sound(self) -> SoundGroup {
+        self.def().sound
+    }
+
+    #[inline]
+    pub fn light(self) -> u8 {
+        self.def().light
+    }
+
+    #[inline]
+    pub fn shape(self) -> Shape {
+        self.def().shape
+    }
+
+    /// Verdeckt dieser Block die angrenzende Face des Nachbarn vollständig?
+    #[inline]
+    pub fn occludes(self) -> bool {
+        self.opacity() == Opacity::Opaque && self.shape() == Shape::Cube
+    }
+
+    /// Was beim Abbauen/Abfallen ins Inventar wandert
+    pub fn drops(self, st: u8) -> Vec<ItemStack> {
+        match self {
+            // Reifer Weizen: Ernte plus Saatgut zum Nachpflanzen
+            Block::Wheat if st >= state::CROP_MAX_STAGE => {
+                vec![ItemStack::new(Item::Wheat, 1), ItemStack::new(Item::Seeds, 2)]
+            }
+            Block::Wheat => vec![ItemStack::new(Item::Seeds, 1)],
+            Block::Grass | Block::Farmland => vec![ItemStack::new(Block::Dirt, 1)],
+            // zerbricht bzw. nicht sammelbar
+            Block::Air
+            | Block::Water
+            | Block::Glass
+            | Block::Leaves
+            | Block::TallGrass
+            | Block::Snow => vec![],
+            // alles andere droppt sich selbst (Tür: nur einmal, die andere Hälfte fällt leer ab)
+            _ => vec![ItemStack::new(self, 1)],
+        }
+    }
+
+    /// State beim Platzieren aus dem Kontext ableiten:
+    /// `normal` = Normale der angeklickten Face, `look` = Blickrichtung des Spielers.
+    pub fn placement_state(self, normal: (i32, i32, i32), look: (f32, f32, f32)) -> u8 {
+        match self {
+            // Stamm zeigt von der angeklickten Face weg
+            Block::Log => match normal {
+                (0, 0, 0) => axis_from_dir(look),
+                (x, _, _) if x != 0 => state::AXIS_X,
+                (_, _, z) if z != 0 => state::AXIS_Z,
+                _ => state::AXIS_Y,
+            },
+            // Unterseite angeklickt oder nach oben geschaut -> obere Hälfte
+            Block::StoneSlab => {
+                if normal.1 < 0 || (normal.1 == 0 && look.1 > 0.0) {
+                    state::SLAB_TOP
+                } else {
+                    state::SLAB_BOTTOM
+                }
+            }
+            Block::Leaves => state::LEAVES_PERSISTENT,
+            // Wand-Fackel an seitlichen Faces, sonst Boden (Decke wird von World abgelehnt)
+            Block::Torch => match normal {
+                (1, 0, 0) => state::TORCH_WALL_PX,
+                (-1, 0, 0) => state::TORCH_WALL_NX,
+                (0, 0, 1) => state::TORCH_WALL_PZ,
+                (0, 0, -1) => state::TORCH_WALL_NZ,
+                _ => state::TORCH_FLOOR,
+            },
+            // Tür schaut zum Spieler
+            Block::Door => facing_from_dir(look),
+            _ => 0,
+        }
+    }
+
+    /// Leuchtende Blöcke werden immer voll hell gerendert
+    #[inline]
+    pub fn is_emissive(self) -> bool {
+        self.def().light > 0
+    }
+
+    /// Ist das Werkzeug das effektive für diesen Block?
+    pub fn tool_effective(self, tool: Option<Tool>) -> bool {
+        tool.is_some_and(|t| self.def().tool == Some(t.kind))
+    }
+
+    /// Gibt der Block mit diesem Werkzeug (oder von Hand) einen Drop?
+    pub fn harvestable_with(self, tool: Option<Tool>) -> bool {
+        !self.def().needs_tool || self.tool_effective(tool)
+    }
+
+    /// Abbauzeit in Ticks (20 TPS): von Hand 1.5 s pro Härtepunkt, 5 s ohne nötiges
+    /// Werkzeug, mit passendem Werkzeug geteilt durch dessen Tempo
+    pub fn break_ticks(self, tool: Option<Tool>) -> u32 {
+        let def = self.def();
+        let secs = match tool {
+            Some(t) if self.tool_effective(tool) => def.hardness * 1.5 / t.speed,
+            _ if def.needs_tool => def.hardness * 5.0,
+            _ => def.hardness * 1.5,
+        };
+        (secs * 20.0).round() as u32
+    }
+
+    #[inline]
+    pub fn is_tall(self) -> bool {
+        self.def().tall
+    }
+
+    #[inline]
+    pub fn is_targetable(self) -> bool {
+        self.def().targetable
+    }
+
+    #[inline]
+    pub fn is_fluid(self) -> bool {
+        self.def().fluid
+    }
+}
+
+/// Horizontale Blickrichtung als Facing-State (0 = -Z, 1 = +X, 2 = +Z, 3 = -X)
+pub fn facing_from_dir((dx, _dy, dz): (f32, f32, f32)) -> u8 {
+    if dx.abs() > dz.abs() {
+        if dx > 0.0 { 1 } else { 3 }
+    } else if dz > 0.0 {
+        2
+    } else {
+        0
+    }
+}
+
+/// Dominante Achse einer Richtung als Achsen-State
+fn axis_from_dir((dx, dy, dz): (f32, f32, f32)) -> u8 {
+    let (ax, ay, az) = (dx.abs(), dy.abs(), dz.abs());
+    if ax >= ay && ax >= az {
+        state::AXIS_X
+    } else if az >= ay {
+        state::AXIS_Z
+    } else {
+        state::AXIS_Y
+    }
+}
+
+/// Culling-Regel: soll die Face von `b` zum Nachbarn `neighbor` gezeichnet werden?
+pub fn face_visible(b: Block, neighbor: Block) -> bool {
+    if neighbor.occludes() {
+        // Deckender Vollblock verdeckt alles
+        return false;
+    }
+    match (b.opacity(), neighbor.opacity()) {
+        (Opacity::Invisible, _) => false,
+        // Glas an Glas / Wasser an Wasser: innere Faces weglassen
+        (Opacity::Translucent, Opacity::Translucent) => b != neighbor,
+        // Blätter rendern immer (auch Blatt an Blatt)
+        _ => true,
     }
 }
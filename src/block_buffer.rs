@@ -0,0 +1,185 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::block::Block;
+use crate::serial::{ByteReader, ByteWriter};
+use crate::world::{BlockPos, World};
+
+pub const SCHEMATIC_MAGIC: &[u8; 4] = b"VXSC";
+const SCHEMATIC_FORMAT_VERSION: u16 = 1;
+
+/// Losgelöster Blockquader (Zwischenablage, Schematics), unabhängig von Chunks.
+/// `origin` ist die Lage der Zelle (0,0,0) relativ zu einem Bezugspunkt (beim Kopieren: Spieler).
+/// Block-States werden unverändert übernommen; Block-Entity-Inhalte (Truhen) nicht.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockBuffer {
+    pub size: (i32, i32, i32),
+    pub origin: BlockPos,
+    /// x-major: Index = (x * h + y) * d + z
+    blocks: Vec<(Block, u8)>,
+}
+
+impl BlockBuffer {
+    pub fn new(size: (i32, i32, i32), origin: BlockPos) -> Self {
+        let len = (size.0 * size.1 * size.2).max(0) as usize;
+        Self {
+            size,
+            origin,
+            blocks: vec![(Block::Air, 0); len],
+        }
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.blocks.capacity() * size_of::<(Block, u8)>()
+    }
+
+    /// Quader min..=max aus der Welt kopieren; `anchor` wird zum Bezugspunkt
+    pub fn copy_from(world: &World, min: BlockPos, max: BlockPos, anchor: BlockPos) -> Self {
+        let size = (max.0 - min.0 + 1, max.1 - min.1 + 1, max.2 - min.2 + 1);
+        let origin = (min.0 - anchor.0, min.1 - anchor.1, min.2 - anchor.2);
+        let mut buf = Self::new(size, origin);
+        for x in 0..size.0 {
+            for y in 0..size.1 {
+                for z in 0..size.2 {
+                    let (wx, wy, wz) = (min.0 + x, min.1 + y, min.2 + z);
+                    let entry = (world.get_block(wx, wy, wz), world.get_state(wx, wy, wz));
+                    buf.set((x, y, z), entry);
+                }
+            }
+        }
+        buf
+    }
+
+    fn index(&self, (x, y, z): BlockPos) -> usize {
+        ((x * self.size.1 + y) * self.size.2 + z) as usize
+    }
+
+    pub fn get(&self, p: BlockPos) -> (Block, u8) {
+        self.blocks[self.index(p)]
+    }
+
+    pub fn set(&mut self, p: BlockPos, entry: (Block, u8)) {
+        let i = self.index(p);
+        self.blocks[i] = entry;
+    }
+
+    pub fn volume(&self) -> i64 {
+        self.blocks.len() as i64
+    }
+
+    /// Eintrag an einer Position relativ zum Bezugspunkt (None = außerhalb)
+    pub fn at_relative(&self, p: BlockPos) -> Option<(Block, u8)> {
+        let local = (p.0 - self.origin.0, p.1 - self.origin.1, p.2 - self.origin.2);
+        let inside = |v: i32, n: i32| (0..n).contains(&v);
+        let (w, h, d) = self.size;
+        (inside(local.0, w) && inside(local.1, h) && inside(local.2, d)).then(|| self.get(local))
+    }
+
+    /// Ecken (min, max) relativ zum Bezugspunkt
+    pub fn relative_bounds(&self) -> (BlockPos, BlockPos) {
+        let o = self.origin;
+        (o, (o.0 + self.size.0 - 1, o.1 + self.size.1 - 1, o.2 + self.size.2 - 1))
+    }
+
+    /// Um 90° im Uhrzeigersinn (von oben) um den Bezugspunkt drehen
+    pub fn rotated_cw(&self) -> Self {
+        // Zelle (x, z) -> (-z - 1, x): dreht die Einheitszellen um die Ecke des Bezugspunkts
+        self.transformed((self.size.2, self.size.1, self.size.0), |(x, y, z)| (-z - 1, y, x))
+    }
+
+    /// An der x = 0-Ebene durch den Bezugspunkt spiegeln
+    pub fn mirrored_x(&self) -> Self {
+        self.transformed(self.size, |(x, y, z)| (-x - 1, y, z))
+    }
+
+    /// Jede Zelle (relativ zum Bezugspunkt) über `map` umsetzen
+    fn transformed(&self, size: (i32, i32, i32), map: impl Fn(BlockPos) -> BlockPos) -> Self {
+        let (min, max) = self.relative_bounds();
+        let (a, b) = (map(min), map(max));
+        let origin = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        let mut out = Self::new(size, origin);
+        for x in 0..self.size.0 {
+            for y in 0..self.size.1 {
+                for z in 0..self.size.2 {
+                    let rel = (self.origin.0 + x, self.origin.1 + y, self.origin.2 + z);
+                    let t = map(rel);
+                    out.set((t.0 - origin.0, t.1 - origin.1, t.2 - origin.2), self.get((x, y, z)));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl BlockBuffer {
+    /// Schematic-Datei: Blöcke über eine Palette aus Registry-Namen, damit Dateien
+    /// eine Umsortierung der Block-IDs überstehen
+    pub fn encode(&self) -> Vec<u8> {
+        let mut palette: Vec<Block> = Vec::new();
+        let mut indices = Vec::with_capacity(self.blocks.len());
+        for (b, _) in &self.blocks {
+            let i = match palette.iter().position(|p| p == b) {
+                Some(i) => i,
+                None => {
+                    palette.push(*b);
+                    palette.len() - 1
+                }
+            };
+            indices.push(i as u16);
+        }
+
+        let mut w = ByteWriter::new();
+        w.bytes(SCHEMATIC_MAGIC);
+        w.u16(SCHEMATIC_FORMAT_VERSION);
+        for v in [self.size.0, self.size.1, self.size.2] {
+            w.i32(v);
+        }
+        for v in [self.origin.0, self.origin.1, self.origin.2] {
+            w.i32(v);
+        }
+        w.u16(palette.len() as u16);
+        for b in &palette {
+            w.str(b.name());
+        }
+        for (i, (_, st)) in indices.iter().zip(&self.blocks) {
+            w.u16(*i);
+            w.u8(*st);
+        }
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        if r.bytes(4)? != SCHEMATIC_MAGIC {
+            bail!("keine Schematic-Datei");
+        }
+        let version = r.u16()?;
+        if version != SCHEMATIC_FORMAT_VERSION {
+            bail!("Schematic-Format v{version} nicht unterstützt");
+        }
+        let size = (r.i32()?, r.i32()?, r.i32()?);
+        if size.0 <= 0 || size.1 <= 0 || size.2 <= 0 {
+            bail!("ungültige Größe {size:?}");
+        }
+        let origin = (r.i32()?, r.i32()?, r.i32()?);
+
+        let n = r.u16()?;
+        let mut palette = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            let name = r.str()?;
+            let b = Block::by_name(&name).ok_or_else(|| anyhow!("unbekannter Block '{name}'"))?;
+            palette.push(b);
+        }
+
+        let volume = size.0 as i64 * size.1 as i64 * size.2 as i64;
+        if volume * 3 > (data.len() as i64) {
+            bail!("Datei zu kurz für {size:?}");
+        }
+        let mut buf = Self::new(size, origin);
+        for entry in buf.blocks.iter_mut() {
+            let i = r.u16()? as usize;
+            let b = *palette.get(i).ok_or_else(|| anyhow!("Palettenindex {i} außerhalb"))?;
+            *entry = (b, r.u8()?);
+        }
+        Ok(buf)
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::{bail, Result};
+
+use crate::inventory::Inventory;
+use crate::serial::{ByteReader, ByteWriter};
+
+/// Zusatzdaten für Blöcke, die mehr als einen State brauchen (Kisten, Schilder).
+/// Liegen pro Chunk in einer Map, Schlüssel = lokaler Blockindex.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockEntity {
+    Container(Inventory),
+    Sign { text: String },
+}
+
+/// Registry-Eintrag: welche Art Block-Entity ein Blocktyp beim Platzieren bekommt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockEntityKind {
+    Container { slots: usize },
+    Sign,
+}
+
+impl BlockEntityKind {
+    pub fn create(self) -> BlockEntity {
+        match self {
+            BlockEntityKind::Container { slots } => BlockEntity::Container(Inventory::new(slots)),
+            BlockEntityKind::Sign => BlockEntity::Sign {
+                text: String::new(),
+            },
+        }
+    }
+}
+
+const TAG_CONTAINER: u8 = 1;
+const TAG_SIGN: u8 = 2;
+
+impl BlockEntity {
+    /// Speicher außerhalb des Enums (Slots bzw. Text)
+    pub fn heap_bytes(&self) -> usize {
+        match self {
+            BlockEntity::Container(inv) => inv.heap_bytes(),
+            BlockEntity::Sign { text } => text.capacity(),
+        }
+    }
+
+    pub fn write(&self, w: &mut ByteWriter) {
+        match self {
+            BlockEntity::Container(inv) => {
+                w.u8(TAG_CONTAINER);
+                inv.write(w);
+            }
+            BlockEntity::Sign { text } => {
+                w.u8(TAG_SIGN);
+                w.str(text);
+            }
+        }
+    }
+
+    pub fn read(r: &mut ByteReader) -> Result<Self> {
+        match r.u8()? {
+            TAG_CONTAINER => Ok(BlockEntity::Container(Inventory::read(r)?)),
+            TAG_SIGN => Ok(BlockEntity::Sign { text: r.str()? }),
+            tag => bail!("unbekannter Block-Entity-Typ {tag}"),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Blockdaten aus `assets/blocks.toml`
+
+use crate::assets::Asset;
+use crate::block::{Block, BlockDefs, Opacity, BLOCKS_ASSET};
+use crate::item::ToolKind;
+
+#[test]
+fn shipped_blocks_match_the_builtin_table() {
+    let bytes = std::fs::read(format!("assets/{BLOCKS_ASSET}")).unwrap();
+    let _: toml::Table = std::str::from_utf8(&bytes).unwrap().parse().unwrap();
+    assert_eq!(BlockDefs::decode(&bytes).unwrap(), BlockDefs::builtin());
+}
+
+#[test]
+fn blocks_file_overrides_single_fields() {
+    let text = "# nur Glas\n[glass]\nlight = 20\ntool = \"pickaxe\"\nopacity = \"opaque\"\n";
+    let defs = BlockDefs::decode(text.as_bytes()).unwrap();
+    let glass = &defs.0[Block::Glass as usize];
+    assert_eq!(glass.light, 15);
+    assert_eq!(glass.tool, Some(ToolKind::Pickaxe));
+    assert_eq!(glass.opacity, Opacity::Opaque);
+    assert_eq!(glass.color, BlockDefs::builtin().0[Block::Glass as usize].color);
+
+    for bad in ["[obsidian]\n", "light = 3\n", "[glass]\nshape = \"slab\"\n", "[glass]\nsolid\n"] {
+        assert!(BlockDefs::decode(bad.as_bytes()).is_err(), "{bad:?}");
+    }
+}
+
+#[test]
+fn checksum_follows_the_block_data() {
+    let builtin = BlockDefs::builtin();
+    assert_eq!(builtin.checksum(), BlockDefs::builtin().checksum());
+    let changed = BlockDefs::decode(b"[stone]\nhardness = 9\n").unwrap();
+    assert_ne!(changed.checksum(), builtin.checksum());
+}
@@ -1,139 +1,145 @@
-// src/camera.rs
-use crate::player::Player;
-
-#[derive(Clone, Copy, Debug)]
-pub struct Vec3 {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
-}
+//! Kamera für Rendering und Culling: Position, Blickrichtung und Projektion an einer
+//! Stelle, damit Gfx und der Chunk-Culler dasselbe Sichtfeld verwenden. Neben der
+//! Perspektive gibt es eine orthografische Projektion (Draufsicht).
 
-impl Vec3 {
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
-        Self { x, y, z }
-    }
-    pub fn add(self, o: Vec3) -> Vec3 {
-        Vec3::new(self.x + o.x, self.y + o.y, self.z + o.z)
-    }
-    pub fn sub(self, o: Vec3) -> Vec3 {
-        Vec3::new(self.x - o.x, self.y - o.y, self.z - o.z)
-    }
-    pub fn dot(self, o: Vec3) -> f32 {
-        self.x * o.x + self.y * o.y + self.z * o.z
-    }
-    pub fn cross(self, o: Vec3) -> Vec3 {
-        Vec3::new(
-            self.y * o.z - self.z * o.y,
-            self.z * o.x - self.x * o.z,
-            self.x * o.y - self.y * o.x,
-        )
-    }
-    pub fn len(self) -> f32 {
-        (self.dot(self)).sqrt()
-    }
-    pub fn norm(self) -> Vec3 {
-        let l = self.len();
-        if l > 1e-6 {
-            Vec3::new(self.x / l, self.y / l, self.z / l)
-        } else {
-            self
-        }
-    }
+use glam::{Mat4, Vec3};
+
+/// Nahebene in Blöcken
+pub const NEAR: f32 = 0.1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Vertikales Sichtfeld (Radiant)
+    Perspective { fov_y: f32 },
+    /// Halbe Höhe des Ausschnitts in Blöcken; die Breite folgt dem Seitenverhältnis
+    Ortho { half_height: f32 },
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct Mat4 {
-    // column-major 4x4 like WGSL expects
-    pub m: [[f32; 4]; 4],
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    pub pos: Vec3,
+    /// Normiert
+    pub dir: Vec3,
+    pub projection: Projection,
+    pub near: f32,
+    pub far: f32,
 }
 
-impl Mat4 {
-    pub fn identity() -> Self {
+impl Camera {
+    pub fn new(fov_y: f32, far: f32) -> Self {
         Self {
-            m: [
-                [1.0, 0.0, 0.0, 0.0],
-                [0.0, 1.0, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [0.0, 0.0, 0.0, 1.0],
-            ],
+            pos: Vec3::ZERO,
+            dir: Vec3::NEG_Z,
+            projection: Projection::Perspective { fov_y },
+            near: NEAR,
+            far,
         }
     }
 
-    pub fn mul(self, b: Mat4) -> Mat4 {
-        // column-major multiply: self * b
-        let mut r = [[0.0; 4]; 4];
-        for c in 0..4 {
-            for rrow in 0..4 {
-                r[c][rrow] =
-                    self.m[0][rrow] * b.m[c][0] +
-                    self.m[1][rrow] * b.m[c][1] +
-                    self.m[2][rrow] * b.m[c][2] +
-                    self.m[3][rrow] * b.m[c][3];
-            }
+    pub fn ortho(half_height: f32, far: f32) -> Self {
+        Self {
+            projection: Projection::Ortho { half_height },
+            ..Self::new(0.0, far)
         }
-        Mat4 { m: r }
     }
 
-    pub fn perspective(fov_y_rad: f32, aspect: f32, z_near: f32, z_far: f32) -> Mat4 {
-        let f = 1.0 / (fov_y_rad * 0.5).tan();
-        let nf = 1.0 / (z_near - z_far);
-
-        // Right-handed, clip-space Z 0..1 (wgpu)
-        Mat4 {
-            m: [
-                [f / aspect, 0.0, 0.0, 0.0],
-                [0.0, f, 0.0, 0.0],
-                [0.0, 0.0, z_far * nf, -1.0],
-                [0.0, 0.0, (z_far * z_near) * nf, 0.0],
-            ],
+    /// Gleiche Projektion, anderer Standpunkt; Nullrichtung blickt nach -Z
+    pub fn looking(self, (x, y, z): (f32, f32, f32), (dx, dy, dz): (f32, f32, f32)) -> Self {
+        let dir = Vec3::new(dx, dy, dz).try_normalize().unwrap_or(Vec3::NEG_Z);
+        Self {
+            pos: Vec3::new(x, y, z),
+            dir,
+            ..self
         }
     }
 
-    pub fn look_at(eye: Vec3, center: Vec3, up: Vec3) -> Mat4 {
-        let f = center.sub(eye).norm();
-        let s = f.cross(up).norm();
-        let u = s.cross(f);
-
-        // column-major
-        Mat4 {
-            m: [
-                [s.x, u.x, -f.x, 0.0],
-                [s.y, u.y, -f.y, 0.0],
-                [s.z, u.z, -f.z, 0.0],
-                [-s.dot(eye), -u.dot(eye), f.dot(eye), 1.0],
-            ],
-        }
+    pub fn view_proj(&self, aspect: f32) -> Mat4 {
+        let proj = match self.projection {
+            Projection::Perspective { fov_y } => {
+                Mat4::perspective_rh(fov_y, aspect, self.near, self.far)
+            }
+            Projection::Ortho { half_height } => {
+                let half_width = half_height * aspect;
+                let (near, far) = (self.near, self.far);
+                Mat4::orthographic_rh(-half_width, half_width, -half_height, half_height, near, far)
+            }
+        };
+        proj * self.view()
     }
-}
 
-pub struct Camera {
-    pub fov_y: f32,
-    pub z_near: f32,
-    pub z_far: f32,
-}
+    /// Senkrecht nach unten (Draufsicht) ist oben auf dem Bild Norden (-Z)
+    fn view(&self) -> Mat4 {
+        let vertical = self.dir.cross(Vec3::Y).length_squared() < 1e-5;
+        let up = if vertical { Vec3::NEG_Z } else { Vec3::Y };
+        Mat4::look_at_rh(self.pos, self.pos + self.dir, up)
+    }
 
-impl Camera {
-    pub fn new() -> Self {
-        Self {
-            fov_y: 70.0_f32.to_radians(),
-            z_near: 0.05,
-            z_far: 200.0,
+    /// Grober Sichttest für eine Kugel: Fernebene, dann horizontaler und vertikaler
+    /// Winkel zur Blickrichtung (mit Spielraum für den Radius)
+    pub fn sees_sphere(&self, center: Vec3, radius: f32, aspect: f32) -> bool {
+        let fov_y = match self.projection {
+            Projection::Perspective { fov_y } => fov_y,
+            Projection::Ortho { half_height } => {
+                return self.box_sees_sphere(center, radius, half_height * aspect, half_height);
+            }
+        };
+        let to_center = center - self.pos;
+        let dist = to_center.length();
+        if dist - radius > self.far {
+            return false;
         }
-    }
 
-    pub fn view_proj(&self, player: &Player, width: u32, height: u32) -> Mat4 {
-        let aspect = (width.max(1) as f32) / (height.max(1) as f32);
+        // Wenn Kamera in der Kugel oder sehr nah: immer sichtbar
+        if dist < radius {
+            return true;
+        }
 
-        let (ex, ey, ez) = player.eye_pos();
-        let (dx, dy, dz) = player.dir();
+        let dir_to = to_center / dist.max(1e-6);
 
-        let eye = Vec3::new(ex, ey, ez);
-        let center = Vec3::new(ex + dx, ey + dy, ez + dz);
-        let up = Vec3::new(0.0, 1.0, 0.0);
+        // FOV-Halbwinkel
+        let half_v = 0.5 * fov_y;
+        let half_h = (aspect * half_v.tan()).atan(); // tan(h/2) = aspect * tan(v/2)
 
-        let view = Mat4::look_at(eye, center, up);
-        let proj = Mat4::perspective(self.fov_y, aspect, self.z_near, self.z_far);
+        // Basisachsen
+        let up = Vec3::Y;
+        let mut right = self.dir.cross(up);
+        if right.length_squared() < 1e-5 {
+            right = Vec3::X; // Fallback wenn Blick senkrecht nach oben/unten
+        }
+        let right = right.normalize();
+
+        let ang_allow = (radius / dist).atan(); // Spielraum für die Größe
+
+        // Horizontal (XZ)
+        let forward_h = (self.dir - up * self.dir.dot(up)).normalize_or_zero();
+        let dir_h = (dir_to - up * dir_to.dot(up)).normalize_or_zero();
+        if forward_h.length_squared() > 0.0 && dir_h.length_squared() > 0.0 {
+            let ang_h = forward_h.dot(dir_h).clamp(-1.0, 1.0).acos();
+            if ang_h > half_h + ang_allow {
+                return false;
+            }
+        }
+
+        // Vertikal (Pitch)
+        let forward_v = (self.dir - right * self.dir.dot(right)).normalize_or_zero();
+        let dir_v = (dir_to - right * dir_to.dot(right)).normalize_or_zero();
+        if forward_v.length_squared() > 0.0 && dir_v.length_squared() > 0.0 {
+            let ang_v = forward_v.dot(dir_v).clamp(-1.0, 1.0).acos();
+            if ang_v > half_v + ang_allow {
+                return false;
+            }
+        }
+
+        true
+    }
 
-        proj.mul(view)
+    /// Ortho: das Sichtvolumen ist ein Quader, getestet im Kameraraum
+    fn box_sees_sphere(&self, center: Vec3, radius: f32, half_w: f32, half_h: f32) -> bool {
+        let p = self.view().transform_point3(center);
+        let depth = -p.z;
+        p.x.abs() <= half_w + radius
+            && p.y.abs() <= half_h + radius
+            && depth >= self.near - radius
+            && depth <= self.far + radius
     }
 }
@@ -1,6 +1,17 @@
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
+use anyhow::{anyhow, bail, Result};
+
+use crate::block::Block;
+use crate::block_entity::BlockEntity;
+use crate::serial::{ByteReader, ByteWriter};
+
+/// Kantenlänge eines Chunks in Blöcken; mit Feature `chunk32` 32 statt 16
+#[cfg(not(feature = "chunk32"))]
 pub const CHUNK_SIZE: i32 = 16;
+#[cfg(feature = "chunk32")]
+pub const CHUNK_SIZE: i32 = 32;
 pub const CHUNK_VOL: usize = (CHUNK_SIZE as usize) * (CHUNK_SIZE as usize) * (CHUNK_SIZE as usize);
 
 /// Chunk-Koordinate im Chunk-Raster (nicht in Block-Koordinaten!)
@@ -31,10 +42,10 @@ impl Hash for ChunkPos {
     }
 }
 
-/// Lokale Block-Koordinate im Chunk: 0..15
+/// Lokale Block-Koordinate im Chunk: 0..CHUNK_SIZE
 #[inline]
 pub fn in_chunk(v: i32) -> i32 {
-    // v mod 16, aber immer positiv
+    // v mod CHUNK_SIZE, aber immer positiv
     v.rem_euclid(CHUNK_SIZE)
 }
 
@@ -45,11 +56,11 @@ pub fn chunk_coord(v: i32) -> i32 {
     v.div_euclid(CHUNK_SIZE)
 }
 
-/// Linearisierter Index [0..4095] aus lokalen Koordinaten [0..15]
+/// Linearisierter Index [0..CHUNK_VOL) aus lokalen Koordinaten [0..CHUNK_SIZE)
 #[inline]
 pub fn idx(lx: i32, ly: i32, lz: i32) -> usize {
     // Layout: X läuft am schnellsten, dann Z, dann Y
-    // index = x + z*16 + y*16*16
+    // index = x + z*S + y*S*S
     (lx as usize)
         + (lz as usize) * (CHUNK_SIZE as usize)
         + (ly as usize) * (CHUNK_SIZE as usize) * (CHUNK_SIZE as usize)
@@ -58,8 +69,19 @@ pub fn idx(lx: i32, ly: i32, lz: i32) -> usize {
 #[derive(Debug, Clone)]
 pub struct Chunk<B: Copy + Default> {
     pub pos: ChunkPos,
-    blocks: Vec<B>, // Länge: 4096
+    blocks: Vec<B>, // Länge: CHUNK_VOL
+    /// Kleiner Zustandswert pro Block (Achse, Ausrichtung, Variante), parallel zu `blocks`
+    states: Vec<u8>,
+    /// Block-Entities nach lokalem Index (nur für Blöcke mit Zusatzdaten)
+    block_entities: HashMap<usize, BlockEntity>,
+    /// Licht pro Block: Himmelslicht im oberen, Blocklicht im unteren Nibble
+    /// (wird nicht gespeichert, nach dem Laden neu berechnet)
+    light: Vec<u8>,
     pub dirty: bool,
+    /// Licht neu berechnet, seit der Server es zuletzt verschickt hat
+    pub light_changed: bool,
+    /// Seit dem letzten Speichern verändert
+    pub modified: bool,
 }
 
 impl<B: Copy + Default> Chunk<B> {
@@ -67,7 +89,12 @@ impl<B: Copy + Default> Chunk<B> {
         Self {
             pos,
             blocks: vec![B::default(); CHUNK_VOL],
+            states: vec![0; CHUNK_VOL],
+            block_entities: HashMap::new(),
+            light: vec![0; CHUNK_VOL],
             dirty: true,
+            light_changed: false,
+            modified: false,
         }
     }
 
@@ -76,10 +103,149 @@ impl<B: Copy + Default> Chunk<B> {
         self.blocks[idx(lx, ly, lz)]
     }
 
+    #[inline]
+    pub fn get_state_local(&self, lx: i32, ly: i32, lz: i32) -> u8 {
+        self.states[idx(lx, ly, lz)]
+    }
+
+    /// Setzt Block mit Default-State (0)
     #[inline]
     pub fn set_local(&mut self, lx: i32, ly: i32, lz: i32, b: B) {
+        self.set_local_with_state(lx, ly, lz, b, 0);
+    }
+
+    #[inline]
+    pub fn set_local_with_state(&mut self, lx: i32, ly: i32, lz: i32, b: B, state: u8) {
         let i = idx(lx, ly, lz);
         self.blocks[i] = b;
+        self.states[i] = state;
         self.dirty = true;
+        self.modified = true;
+    }
+
+    #[inline]
+    pub fn sky_light_local(&self, lx: i32, ly: i32, lz: i32) -> u8 {
+        self.light[idx(lx, ly, lz)] >> 4
+    }
+
+    #[inline]
+    pub fn block_light_local(&self, lx: i32, ly: i32, lz: i32) -> u8 {
+        self.light[idx(lx, ly, lz)] & 0x0F
+    }
+
+    /// Alle Blöcke (Index siehe `idx`)
+    pub fn blocks(&self) -> &[B] {
+        &self.blocks
+    }
+
+    /// States parallel zu `blocks`
+    pub fn states(&self) -> &[u8] {
+        &self.states
+    }
+
+    /// Ergebnis der Lichtberechnung übernehmen; ändert nur das Mesh, nicht den Spielstand
+    pub fn set_light_data(&mut self, data: &[u8]) {
+        self.light.copy_from_slice(data);
+        self.dirty = true;
+        self.light_changed = true;
+    }
+
+    /// Licht pro Block wie in `set_light_data`
+    pub fn light_data(&self) -> &[u8] {
+        &self.light
+    }
+
+    pub fn block_entity_local(&self, lx: i32, ly: i32, lz: i32) -> Option<&BlockEntity> {
+        self.block_entities.get(&idx(lx, ly, lz))
+    }
+
+    /// Mutabler Zugriff markiert den Chunk als verändert (Kisteninhalt etc.)
+    pub fn block_entity_local_mut(&mut self, lx: i32, ly: i32, lz: i32) -> Option<&mut BlockEntity> {
+        let be = self.block_entities.get_mut(&idx(lx, ly, lz))?;
+        self.modified = true;
+        Some(be)
+    }
+
+    pub fn insert_block_entity(&mut self, lx: i32, ly: i32, lz: i32, be: BlockEntity) {
+        self.block_entities.insert(idx(lx, ly, lz), be);
+    }
+
+    /// Speicher für Blöcke, Zustände und Licht
+    pub fn heap_bytes(&self) -> usize {
+        size_of::<Self>()
+            + self.blocks.capacity() * size_of::<B>()
+            + self.states.capacity()
+            + self.light.capacity()
+    }
+
+    /// Speicher der Block-Entities (samt Map-Einträgen)
+    pub fn block_entity_bytes(&self) -> usize {
+        let entry = size_of::<(usize, BlockEntity)>();
+        self.block_entities.capacity() * entry
+            + self.block_entities.values().map(BlockEntity::heap_bytes).sum::<usize>()
+    }
+
+    pub fn remove_block_entity(&mut self, lx: i32, ly: i32, lz: i32) -> Option<BlockEntity> {
+        self.block_entities.remove(&idx(lx, ly, lz))
+    }
+}
+
+const CHUNK_MAGIC: &[u8; 4] = b"VXCH";
+const CHUNK_FORMAT_VERSION: u16 = 1;
+
+impl Chunk<Block> {
+    /// Serialisiert Blöcke, States und Block-Entities
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = ByteWriter::new();
+        w.bytes(CHUNK_MAGIC);
+        w.u16(CHUNK_FORMAT_VERSION);
+        w.i32(self.pos.cx);
+        w.i32(self.pos.cy);
+        w.i32(self.pos.cz);
+
+        for b in &self.blocks {
+            w.u8(*b as u8);
+        }
+        w.bytes(&self.states);
+
+        // sortiert, damit gleiche Chunks gleiche Bytes ergeben
+        let mut keys: Vec<usize> = self.block_entities.keys().copied().collect();
+        keys.sort_unstable();
+        w.u16(keys.len() as u16);
+        for i in keys {
+            w.u16(i as u16);
+            self.block_entities[&i].write(&mut w);
+        }
+
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        if r.bytes(4)? != CHUNK_MAGIC {
+            bail!("keine Chunk-Datei");
+        }
+        let version = r.u16()?;
+        if version != CHUNK_FORMAT_VERSION {
+            bail!("Chunk-Format v{version} nicht unterstützt");
+        }
+        let pos = ChunkPos::new(r.i32()?, r.i32()?, r.i32()?);
+
+        let mut ch = Chunk::new(pos);
+        for (i, id) in r.bytes(CHUNK_VOL)?.iter().enumerate() {
+            ch.blocks[i] = Block::from_id(*id).ok_or_else(|| anyhow!("unbekannte Block-ID {id}"))?;
+        }
+        ch.states.copy_from_slice(r.bytes(CHUNK_VOL)?);
+
+        let n = r.u16()?;
+        for _ in 0..n {
+            let i = r.u16()? as usize;
+            if i >= CHUNK_VOL {
+                bail!("Block-Entity-Index {i} außerhalb des Chunks");
+            }
+            ch.block_entities.insert(i, BlockEntity::read(&mut r)?);
+        }
+
+        Ok(ch)
     }
 }
@@ -0,0 +1,340 @@
+//! Vergleich von Speicherformaten für Chunks (`dev chunk-bench`): Größe sowie Zeit zum
+//! Kodieren und Dekodieren für Rohdaten, Palette, RLE, Deflate und zstd, gemessen an
+//! Chunks dieser Engine (frisch generiert oder aus einem Spielstand).
+//!
+//! Verglichen wird nur der Voxelinhalt (Block-ID und State je Zelle); Kopf und
+//! Block-Entities sind in allen Formaten gleich. zstd braucht das Feature `bench-zstd`,
+//! weil es libzstd mitbaut.
+
+use std::hint::black_box;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::block::Block;
+use crate::chunk::{Chunk, ChunkPos, CHUNK_SIZE, CHUNK_VOL};
+use crate::level::GENERATOR_NOISE;
+use crate::save::WorldStorage;
+use crate::serial::{ByteReader, ByteWriter};
+use crate::world::World;
+
+/// Durchläufe pro Format; die Zeiten sind der Mittelwert
+pub const ROUNDS: u32 = 5;
+
+/// Block-ID und State einer Zelle
+pub type Cell = (u8, u8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Wie die Chunk-Dateien: alle IDs, dann alle States
+    Raw,
+    /// Liste der vorkommenden Zellen, Indizes mit so wenig Bits wie nötig
+    Palette,
+    /// Läufe gleicher Zellen in Speicherreihenfolge (X, dann Z, dann Y)
+    Rle,
+    /// Rohdaten durch Deflate
+    Deflate,
+    /// Rohdaten durch zstd (Standardstufe)
+    #[cfg(feature = "bench-zstd")]
+    Zstd,
+}
+
+impl Encoding {
+    pub const ALL: &[Encoding] = &[
+        Self::Raw,
+        Self::Palette,
+        Self::Rle,
+        Self::Deflate,
+        #[cfg(feature = "bench-zstd")]
+        Self::Zstd,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Palette => "palette",
+            Self::Rle => "rle",
+            Self::Deflate => "deflate",
+            #[cfg(feature = "bench-zstd")]
+            Self::Zstd => "zstd",
+        }
+    }
+
+    pub fn encode(self, cells: &[Cell]) -> Vec<u8> {
+        match self {
+            Self::Raw => encode_raw(cells),
+            Self::Palette => encode_palette(cells),
+            Self::Rle => encode_rle(cells),
+            Self::Deflate => {
+                let mut enc = DeflateEncoder::new(Vec::new(), Compression::default());
+                enc.write_all(&encode_raw(cells)).expect("Schreiben in Vec");
+                enc.finish().expect("Schreiben in Vec")
+            }
+            #[cfg(feature = "bench-zstd")]
+            Self::Zstd => zstd::bulk::compress(&encode_raw(cells), zstd::DEFAULT_COMPRESSION_LEVEL)
+                .expect("Schreiben in Vec"),
+        }
+    }
+
+    pub fn decode(self, data: &[u8]) -> Result<Vec<Cell>> {
+        let cells = match self {
+            Self::Raw => decode_raw(data)?,
+            Self::Palette => decode_palette(data)?,
+            Self::Rle => decode_rle(data)?,
+            Self::Deflate => {
+                let mut raw = Vec::with_capacity(CHUNK_VOL * 2);
+                DeflateDecoder::new(data).read_to_end(&mut raw).context("inflate")?;
+                decode_raw(&raw)?
+            }
+            #[cfg(feature = "bench-zstd")]
+            Self::Zstd => {
+                let raw = zstd::bulk::decompress(data, CHUNK_VOL * 2).context("zstd")?;
+                decode_raw(&raw)?
+            }
+        };
+        if cells.len() != CHUNK_VOL {
+            bail!("{}: {} statt {CHUNK_VOL} Zellen", self.name(), cells.len());
+        }
+        Ok(cells)
+    }
+}
+
+/// Voxelinhalt eines Chunks in Speicherreihenfolge
+pub fn cells(ch: &Chunk<Block>) -> Vec<Cell> {
+    ch.blocks().iter().zip(ch.states()).map(|(b, s)| (*b as u8, *s)).collect()
+}
+
+fn encode_raw(cells: &[Cell]) -> Vec<u8> {
+    let mut out: Vec<u8> = cells.iter().map(|c| c.0).collect();
+    out.extend(cells.iter().map(|c| c.1));
+    out
+}
+
+fn decode_raw(data: &[u8]) -> Result<Vec<Cell>> {
+    if data.len() != CHUNK_VOL * 2 {
+        bail!("Rohdaten: {} Bytes statt {}", data.len(), CHUNK_VOL * 2);
+    }
+    let (ids, states) = data.split_at(CHUNK_VOL);
+    Ok(ids.iter().copied().zip(states.iter().copied()).collect())
+}
+
+/// Bits pro Index für `n` Einträge; ein einziger Eintrag braucht keine
+fn index_bits(n: usize) -> u32 {
+    usize::BITS - n.saturating_sub(1).leading_zeros()
+}
+
+fn encode_palette(cells: &[Cell]) -> Vec<u8> {
+    let mut palette: Vec<Cell> = Vec::new();
+    let indices: Vec<usize> = cells
+        .iter()
+        .map(|c| match palette.iter().position(|p| p == c) {
+            Some(i) => i,
+            None => {
+                palette.push(*c);
+                palette.len() - 1
+            }
+        })
+        .collect();
+
+    let mut w = ByteWriter::new();
+    w.u16(palette.len() as u16);
+    for (id, state) in &palette {
+        w.u8(*id);
+        w.u8(*state);
+    }
+    // Bitstrom, niedrigste Bits zuerst
+    let bits = index_bits(palette.len());
+    let (mut acc, mut filled) = (0u64, 0u32);
+    for i in indices {
+        acc |= (i as u64) << filled;
+        filled += bits;
+        while filled >= 8 {
+            w.u8(acc as u8);
+            acc >>= 8;
+            filled -= 8;
+        }
+    }
+    if filled > 0 {
+        w.u8(acc as u8);
+    }
+    w.into_bytes()
+}
+
+fn decode_palette(data: &[u8]) -> Result<Vec<Cell>> {
+    let mut r = ByteReader::new(data);
+    let n = r.u16()? as usize;
+    if n == 0 {
+        bail!("leere Palette");
+    }
+    let mut palette = Vec::with_capacity(n);
+    for _ in 0..n {
+        palette.push((r.u8()?, r.u8()?));
+    }
+    let bits = index_bits(n);
+    let mask = (1u64 << bits) - 1;
+    let (mut acc, mut filled) = (0u64, 0u32);
+    let mut cells = Vec::with_capacity(CHUNK_VOL);
+    for _ in 0..CHUNK_VOL {
+        while filled < bits {
+            acc |= (r.u8()? as u64) << filled;
+            filled += 8;
+        }
+        let i = (acc & mask) as usize;
+        acc >>= bits;
+        filled -= bits;
+        cells.push(*palette.get(i).with_context(|| format!("Palettenindex {i} außerhalb"))?);
+    }
+    Ok(cells)
+}
+
+fn encode_rle(cells: &[Cell]) -> Vec<u8> {
+    let mut w = ByteWriter::new();
+    let mut rest = cells;
+    while let Some(&first) = rest.first() {
+        let run = rest.iter().take(u16::MAX as usize).take_while(|c| **c == first).count();
+        w.u16(run as u16);
+        w.u8(first.0);
+        w.u8(first.1);
+        rest = &rest[run..];
+    }
+    w.into_bytes()
+}
+
+fn decode_rle(data: &[u8]) -> Result<Vec<Cell>> {
+    let mut r = ByteReader::new(data);
+    let mut cells = Vec::with_capacity(CHUNK_VOL);
+    while !r.is_empty() {
+        let run = r.u16()? as usize;
+        let cell = (r.u8()?, r.u8()?);
+        if cells.len() + run > CHUNK_VOL {
+            bail!("RLE: Lauf über das Chunkende");
+        }
+        cells.extend(std::iter::repeat_n(cell, run));
+    }
+    Ok(cells)
+}
+
+/// Ergebnis eines Formats über alle Chunks
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    pub encoding: Encoding,
+    pub bytes: usize,
+    /// Je Durchlauf über alle Chunks
+    pub encode: Duration,
+    pub decode: Duration,
+}
+
+/// Jedes Format `rounds` Mal über alle Chunks; Fehler, wenn eines nicht verlustfrei ist
+pub fn measure(chunks: &[Vec<Cell>], rounds: u32) -> Result<Vec<Measurement>> {
+    let rounds = rounds.max(1);
+    let mut results = Vec::new();
+    for &encoding in Encoding::ALL {
+        let encoded: Vec<Vec<u8>> = chunks.iter().map(|c| encoding.encode(c)).collect();
+        for (data, cells) in encoded.iter().zip(chunks) {
+            if encoding.decode(data)? != *cells {
+                bail!("{}: Chunk kommt verändert zurück", encoding.name());
+            }
+        }
+
+        let start = Instant::now();
+        for _ in 0..rounds {
+            for cells in chunks {
+                black_box(encoding.encode(black_box(cells)));
+            }
+        }
+        let encode = start.elapsed() / rounds;
+        let start = Instant::now();
+        for _ in 0..rounds {
+            for data in &encoded {
+                black_box(encoding.decode(black_box(data))?);
+            }
+        }
+        let decode = start.elapsed() / rounds;
+
+        let bytes = encoded.iter().map(Vec::len).sum();
+        results.push(Measurement { encoding, bytes, encode, decode });
+    }
+    Ok(results)
+}
+
+/// Die `count` Chunks um den Ursprung, wie eine neue Welt mit `seed` sie erzeugt
+pub fn generated_chunks(seed: u64, count: usize) -> Vec<Chunk<Block>> {
+    let mut world = World::generated(seed, GENERATOR_NOISE);
+    let r = (1..).find(|r: &i32| ((2 * r + 1).pow(3)) as usize >= count).unwrap_or(1);
+    let mut positions: Vec<(i32, i32, i32)> = Vec::new();
+    for cx in -r..=r {
+        for cy in -r..=r {
+            for cz in -r..=r {
+                positions.push((cx, cy, cz));
+            }
+        }
+    }
+    positions.sort_by_key(|&(x, y, z)| (x * x + y * y + z * z, x, y, z));
+    positions.truncate(count);
+    positions
+        .into_iter()
+        .filter_map(|(x, y, z)| {
+            let pos = ChunkPos::new(x, y, z);
+            world.ensure_chunk(pos);
+            world.chunk(pos).cloned()
+        })
+        .collect()
+}
+
+/// Bis zu `count` gespeicherte Chunks eines Spielstands
+pub fn saved_chunks(dir: &Path, count: usize) -> Result<Vec<Chunk<Block>>> {
+    let storage = WorldStorage::open(dir);
+    let mut positions = storage.saved_chunks();
+    if positions.is_empty() {
+        bail!("keine gespeicherten Chunks in {}", dir.display());
+    }
+    positions.sort_by_key(|p| (p.cx, p.cy, p.cz));
+    positions.truncate(count);
+    Ok(positions.into_iter().filter_map(|p| storage.load_chunk(p)).collect())
+}
+
+/// Verschiedene Zellen (ID + State) eines Chunks
+fn distinct(cells: &[Cell]) -> usize {
+    let mut seen = cells.to_vec();
+    seen.sort_unstable();
+    seen.dedup();
+    seen.len()
+}
+
+/// Messen und als Tabelle ausgeben; `source` beschreibt die Herkunft der Chunks
+pub fn run(chunks: &[Chunk<Block>], source: &str) -> Result<()> {
+    if chunks.is_empty() {
+        bail!("keine Chunks zum Messen");
+    }
+    let cells: Vec<Vec<Cell>> = chunks.iter().map(cells).collect();
+    let distinct: Vec<usize> = cells.iter().map(|c| distinct(c)).collect();
+    let uniform = distinct.iter().filter(|n| **n == 1).count();
+    let results = measure(&cells, ROUNDS)?;
+
+    let n = chunks.len();
+    let average = distinct.iter().sum::<usize>() as f64 / n as f64;
+    println!("{n} chunks ({CHUNK_SIZE}³) from {source}");
+    println!("{uniform} uniform, {average:.1} distinct cells per chunk on average");
+    println!(
+        "{:<10}{:>14}{:>9}{:>18}{:>18}",
+        "encoding", "bytes/chunk", "ratio", "encode µs/chunk", "decode µs/chunk"
+    );
+    let raw = results[0].bytes as f64;
+    for m in &results {
+        let per_chunk = |d: Duration| d.as_secs_f64() * 1e6 / n as f64;
+        println!(
+            "{:<10}{:>14.1}{:>8.1}x{:>18.2}{:>18.2}",
+            m.encoding.name(),
+            m.bytes as f64 / n as f64,
+            raw / m.bytes.max(1) as f64,
+            per_chunk(m.encode),
+            per_chunk(m.decode),
+        );
+    }
+    Ok(())
+}
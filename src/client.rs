@@ -0,0 +1,684 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+use glam::Vec3;
+
+use crate::camera::Camera;
+use crate::chunk::{ChunkPos, CHUNK_SIZE};
+use crate::console::Console;
+use crate::input::InputState;
+use crate::memory::MemoryReport;
+use crate::mesh::Vertex;
+use crate::mesh_cache::{MeshCache, DEFAULT_BUDGET_MB};
+use crate::movement::{Body, EYE_HEIGHT};
+use crate::player::{look_dir, PITCH_LIMIT};
+use crate::player_model::{Pose, Stride};
+use crate::protocol::{ClientMsg, PlayerState, PlayerView, ServerMsg};
+use crate::remote_player::{RemotePlayer, INTERP_DELAY};
+use crate::replay::ReplayFrame;
+use crate::settings::DEFAULT_FOV;
+use crate::sound::SoundEvent;
+use crate::voxel_mesher::mesh_chunk;
+use crate::world::World;
+
+/// Reserve hinter dem letzten Chunk der Sichtweite bis zur Fernebene
+const FAR_MARGIN: f32 = CHUNK_SIZE as f32;
+/// Außenansicht: Kamera so weit hinter dem Auge (näher, wenn ein Block im Weg ist)
+const THIRD_PERSON_DISTANCE: f32 = 4.0;
+const NEAR_WALL: f32 = 0.2;
+/// Draufsicht: Kamera so hoch über dem Auge, Bildhöhe in Blöcken
+const TOP_DOWN_HEIGHT: f32 = 48.0;
+const TOP_DOWN_SPAN: f32 = 48.0;
+/// Neu geladene Chunks steigen so lange aus `CHUNK_RISE` Blöcken Tiefe auf
+const CHUNK_FADE: Duration = Duration::from_millis(300);
+const CHUNK_RISE: f32 = 4.0;
+/// Hochgeladene Mesh-Daten pro Frame; was darüber hinausgeht, wartet auf den nächsten
+const MESH_UPLOAD_BUDGET: usize = 2 << 20;
+/// Unbestätigte Eingaben, die höchstens vorgehalten werden (Server hängt)
+const MAX_PENDING_INPUTS: usize = 200;
+/// Anteil des Vorhersagefehlers, der pro Tick sichtbar bleibt (weiches Nachziehen)
+const CORRECTION_DECAY: f32 = 0.6;
+/// Größere Korrekturen (Teleport, Respawn) springen sofort
+const CORRECTION_SNAP: f32 = 2.0;
+
+/// Darstellungsseite: Spiegel der Serverwelt, Meshes, Kamera und Ton.
+/// Schickt pro Tick die Eingaben, alles andere kommt als `ServerMsg` zurück.
+pub struct Client {
+    to_server: Sender<ClientMsg>,
+    from_server: Receiver<ServerMsg>,
+    world: World,
+    chunk_mesh_cache: MeshCache,
+    /// Chunks, die beim letzten Meshen im Bild waren
+    visible: HashSet<ChunkPos>,
+    /// Gerade erst aufgetauchte Chunks mit Startzeit der Animation
+    rising: HashMap<ChunkPos, Instant>,
+    /// Chunks, deren Mesh auf der GPU liegt
+    uploaded: HashSet<ChunkPos>,
+    /// Debug: Lichtwerte statt Blockfarben rendern (F7)
+    light_debug: bool,
+    /// Sichtfeld fürs Chunk-Culling (Radiant, wie in Gfx)
+    fov_y: f32,
+    /// Orthografische Draufsicht statt Ego-Perspektive
+    top_down: bool,
+    /// Kamera hinter dem Spieler, die eigene Figur ist zu sehen
+    third_person: bool,
+    /// Schrittzähler fürs eigene Modell
+    stride: Stride,
+    view_distance: i32,
+    player: PlayerView,
+    /// Übers Netz: eigene Bewegung sofort lokal vorhersagen
+    predict: bool,
+    predicted: Body,
+    /// Gesendet, vom Server noch nicht bestätigt: (Nummer, Eingabe, Blick)
+    unacked: VecDeque<(u32, InputState, (f32, f32))>,
+    /// Zuletzt vergebene Eingabenummer
+    seq: u32,
+    /// Sichtbarer Rest einer Korrektur, klingt pro Tick ab
+    correction: (f32, f32, f32),
+    /// Eigener Blick: Maus wirkt sofort, nicht erst nach dem nächsten Tick
+    yaw: f32,
+    pitch: f32,
+    /// Blick vom Server übernehmen (beim Start und während einer Wiedergabe)
+    follow_server_look: bool,
+    daylight: f32,
+    /// Niederschlag 0..1, Schnee statt Regen
+    weather: (f32, bool),
+    /// Tageszeit 0..1 und vergangene Tage
+    clock: (f32, u64),
+    overlay: Vec<Vertex>,
+    sound_events: Vec<SoundEvent>,
+    /// Chatverlauf und Eingabe (übers Netz; lokal tippt man in die Konsole des Spiels)
+    console: Console,
+    /// Namen aller anderen Spieler nach Server-ID
+    names: HashMap<u32, String>,
+    /// Andere Spieler in Reichweite
+    others: HashMap<u32, RemotePlayer>,
+    /// Grund, falls der Server die Verbindung beendet hat
+    disconnected: Option<String>,
+    screen_size: (f32, f32),
+}
+
+impl Client {
+    /// Server im selben Thread, vor dem Lesen gepollt: antwortet sofort, Vorhersage unnötig
+    pub fn new(to_server: Sender<ClientMsg>, from_server: Receiver<ServerMsg>, view: i32) -> Self {
+        Self::with_prediction(to_server, from_server, view, false)
+    }
+
+    /// Über `net::connect`: eigene Bewegung wird vorhergesagt und mit dem Server abgeglichen
+    pub fn networked(
+        to_server: Sender<ClientMsg>,
+        from_server: Receiver<ServerMsg>,
+        view: i32,
+    ) -> Self {
+        Self::with_prediction(to_server, from_server, view, true)
+    }
+
+    fn with_prediction(
+        to_server: Sender<ClientMsg>,
+        from_server: Receiver<ServerMsg>,
+        view: i32,
+        predict: bool,
+    ) -> Self {
+        let mut client = Self {
+            to_server,
+            from_server,
+            world: World::remote(),
+            chunk_mesh_cache: MeshCache::new((DEFAULT_BUDGET_MB as usize) << 20),
+            rising: HashMap::new(),
+            uploaded: HashSet::new(),
+            visible: HashSet::new(),
+            light_debug: false,
+            fov_y: DEFAULT_FOV.to_radians(),
+            top_down: false,
+            third_person: false,
+            stride: Stride::default(),
+            view_distance: view,
+            player: PlayerView {
+                body: Body::default(),
+                yaw: 0.0,
+                pitch: 0.0,
+                ack: 0,
+                can_sprint: true,
+            },
+            predict,
+            predicted: Body::default(),
+            unacked: VecDeque::new(),
+            seq: 0,
+            correction: (0.0, 0.0, 0.0),
+            yaw: 0.0,
+            pitch: 0.0,
+            follow_server_look: true,
+            daylight: 1.0,
+            weather: (0.0, false),
+            clock: (0.0, 0),
+            overlay: Vec::new(),
+            sound_events: Vec::new(),
+            console: Console::default(),
+            names: HashMap::new(),
+            others: HashMap::new(),
+            disconnected: None,
+            screen_size: (1.0, 1.0),
+        };
+        client.poll();
+        client
+    }
+
+    fn send(&self, msg: ClientMsg) {
+        if self.to_server.send(msg).is_err() {
+            log::warn!("Server nicht erreichbar");
+        }
+    }
+
+    /// Nachrichten des Servers übernehmen
+    pub fn poll(&mut self) {
+        let mut got_player = false;
+        loop {
+            let msg = match self.from_server.try_recv() {
+                Ok(msg) => msg,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.disconnected.get_or_insert_with(|| "Connection lost".to_string());
+                    break;
+                }
+            };
+            match msg {
+                ServerMsg::Welcome(_) => log::debug!("Welcome nach dem Handshake ignoriert"),
+                ServerMsg::Disconnect(reason) => {
+                    log::warn!("Vom Server getrennt: {reason}");
+                    self.disconnected = Some(reason);
+                }
+                ServerMsg::Chunk(ch) => self.world.put_chunk(*ch),
+                ServerMsg::BlockChange(pos, b, state) => {
+                    self.world.apply_remote_block(pos, b, state)
+                }
+                ServerMsg::ChunkLight(cp, light) => self.world.put_chunk_light(cp, &light),
+                ServerMsg::UnloadChunk(cp) => {
+                    self.world.drop_chunk(cp);
+                    self.chunk_mesh_cache.remove(cp);
+                }
+                ServerMsg::Player(view) => {
+                    if self.follow_server_look {
+                        (self.yaw, self.pitch) = (view.yaw, view.pitch);
+                    }
+                    self.player = view;
+                    got_player = true;
+                }
+                ServerMsg::Daylight(d) => self.daylight = d,
+                ServerMsg::Weather { rain, snow } => self.weather = (rain, snow),
+                ServerMsg::Clock { time_of_day, day } => self.clock = (time_of_day, day),
+                ServerMsg::Sound(ev) => self.sound_events.push(ev),
+                ServerMsg::Overlay(lines) => self.overlay = lines,
+                ServerMsg::Chat(line) => self.console.print(line, self.ticks()),
+                ServerMsg::PlayerJoined(id, name) => {
+                    self.names.insert(id, name);
+                }
+                ServerMsg::PlayerLeft(id) => {
+                    self.names.remove(&id);
+                    self.others.remove(&id);
+                }
+                ServerMsg::PlayerSpawn(id, state) => {
+                    let now = Instant::now();
+                    match self.others.get_mut(&id) {
+                        Some(other) => other.reset(now, state),
+                        None => {
+                            let name = self.names.get(&id).cloned().unwrap_or_default();
+                            self.others.insert(id, RemotePlayer::new(id, name, now, state));
+                        }
+                    }
+                }
+                ServerMsg::PlayerDespawn(id) => {
+                    self.others.remove(&id);
+                }
+                ServerMsg::PlayerMoves(moves) => {
+                    for delta in &moves {
+                        if let Some(other) = self.others.get_mut(&delta.id) {
+                            other.apply(delta);
+                        }
+                    }
+                    let now = Instant::now();
+                    for other in self.others.values_mut() {
+                        other.snapshot(now);
+                    }
+                }
+            }
+        }
+        if got_player && self.predict {
+            self.reconcile();
+        }
+        let (eye, _) = self.camera_pos_dir();
+        self.stride.step(Instant::now(), eye);
+    }
+
+    /// Vom letzten Serverstand aus alle unbestätigten Eingaben erneut anwenden
+    fn reconcile(&mut self) {
+        let ack = self.player.ack;
+        while self.unacked.front().is_some_and(|(seq, ..)| *seq <= ack) {
+            self.unacked.pop_front();
+        }
+        let before = self.predicted;
+        self.predicted = self.player.body;
+        for i in 0..self.unacked.len() {
+            let (_, input, look) = self.unacked[i];
+            self.predict_step(input, look);
+        }
+
+        // Abweichung nicht springen lassen, sondern über ein paar Ticks nachziehen
+        let (dx, dy, dz) = (
+            before.x - self.predicted.x,
+            before.y - self.predicted.y,
+            before.z - self.predicted.z,
+        );
+        let (cx, cy, cz) = self.correction;
+        self.correction = (cx + dx, cy + dy, cz + dz);
+        let (cx, cy, cz) = self.correction;
+        if cx * cx + cy * cy + cz * cz > CORRECTION_SNAP * CORRECTION_SNAP {
+            self.correction = (0.0, 0.0, 0.0);
+        }
+    }
+
+    /// Dieselbe Bewegung wie auf dem Server, nur gegen den Weltspiegel
+    fn predict_step(&mut self, input: InputState, look: (f32, f32)) {
+        let sprint = input.sprint && input.move_fwd && self.player.can_sprint;
+        self.predicted.walk(&self.world, input, look, sprint);
+        self.predicted.vertical(&self.world, input);
+    }
+
+    /// Eingaben eines Ticks an den Server
+    pub fn send_tick(&mut self, input: InputState) {
+        if input.toggle_light_debug {
+            self.light_debug = !self.light_debug;
+            // alle Chunks neu meshen
+            self.chunk_mesh_cache.clear();
+        }
+        self.follow_server_look = false;
+        let frame = ReplayFrame {
+            input,
+            yaw: self.yaw,
+            pitch: self.pitch,
+            screen: self.screen_size,
+            lines: Vec::new(),
+        };
+        self.seq += 1;
+        self.send(ClientMsg::Tick(self.seq, frame));
+
+        if self.predict {
+            let look = (self.yaw, self.pitch);
+            self.predict_step(input, look);
+            if self.unacked.len() == MAX_PENDING_INPUTS {
+                self.unacked.pop_front();
+            }
+            self.unacked.push_back((self.seq, input, look));
+            let (cx, cy, cz) = self.correction;
+            let k = CORRECTION_DECAY;
+            self.correction = (cx * k, cy * k, cz * k);
+        }
+    }
+
+    /// Wiedergabe: aufgezeichneten Frame statt eigener Eingaben schicken
+    pub fn send_frame(&mut self, frame: ReplayFrame) {
+        self.follow_server_look = true;
+        self.seq += 1;
+        self.send(ClientMsg::Tick(self.seq, frame));
+    }
+
+    /// Chatzeile oder Befehl; der Server prüft die Rechte und führt sie im nächsten Tick aus
+    pub fn send_chat(&mut self, line: String) {
+        self.send(ClientMsg::Chat(line));
+    }
+
+    pub fn set_view_distance(&mut self, d: i32) {
+        if d != self.view_distance {
+            self.view_distance = d;
+            self.send(ClientMsg::ViewDistance(d));
+        }
+    }
+
+    /// UI-Größe in Pixeln (Klicks in Screens hängen vom Layout ab)
+    pub fn set_screen_size(&mut self, w: f32, h: f32) {
+        self.screen_size = (w, h);
+    }
+
+    pub fn look_delta(&mut self, dx: f32, dy: f32) {
+        // bereits skaliert; Invertierung erledigt MouseSettings::look
+        self.yaw += dx;
+        self.pitch = (self.pitch + dy).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    pub fn look_angles(&self) -> (f32, f32) {
+        (self.yaw, self.pitch)
+    }
+
+    pub fn camera_pos_dir(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
+        let eye = if self.predict {
+            let (x, y, z) = self.predicted.eye_pos();
+            let (cx, cy, cz) = self.correction;
+            (x + cx, y + cy, z + cz)
+        } else {
+            self.player.body.eye_pos()
+        };
+        (eye, look_dir(self.yaw, self.pitch))
+    }
+
+    pub fn daylight(&self) -> f32 {
+        self.daylight
+    }
+
+    /// Niederschlag 0..1 und ob er als Schnee fällt
+    pub fn weather(&self) -> (f32, bool) {
+        self.weather
+    }
+
+    /// Tageszeit 0..1 (0 = Mittag) und vergangene Tage
+    pub fn clock(&self) -> (f32, u64) {
+        self.clock
+    }
+
+    /// Weltspiegel (übers Netz nur die Chunks um den Spieler)
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Himmelslicht im Weltspiegel an einer Position (Kamera), für die Klangkulisse
+    pub fn sky_light_at(&self, (x, y, z): (f32, f32, f32)) -> u8 {
+        self.world.sky_light(x.floor() as i32, y.floor() as i32, z.floor() as i32)
+    }
+
+    pub fn overlay_lines(&self) -> &[Vertex] {
+        &self.overlay
+    }
+
+    pub fn drain_sound_events(&mut self) -> Vec<SoundEvent> {
+        std::mem::take(&mut self.sound_events)
+    }
+
+    pub fn console(&self) -> &Console {
+        &self.console
+    }
+
+    pub fn console_mut(&mut self) -> &mut Console {
+        &mut self.console
+    }
+
+    /// Tab: wie `Game::complete_console`, Treffer nur im eigenen Verlauf
+    pub fn complete_console(&mut self) {
+        let options = self.console.complete();
+        if !options.is_empty() {
+            self.console.print(options.join(" "), self.ticks());
+        }
+    }
+
+    /// Für das Ausblenden alter Chatzeilen (wie `Game::ticks`)
+    pub fn ticks(&self) -> u64 {
+        self.seq as u64
+    }
+
+    /// Andere Spieler, um `INTERP_DELAY` verzögert zwischen den Momentaufnahmen interpoliert
+    pub fn remote_players(&self) -> Vec<(&str, PlayerState)> {
+        let now = Instant::now();
+        let at = now.checked_sub(INTERP_DELAY).unwrap_or(now);
+        self.others
+            .values()
+            .filter_map(|p| Some((p.name.as_str(), p.sample(at)?)))
+            .collect()
+    }
+
+    pub fn disconnected(&self) -> Option<&str> {
+        self.disconnected.as_deref()
+    }
+
+    /// Fernebene der aktuellen Sichtweite
+    pub fn far_plane(&self) -> f32 {
+        far_plane(self.view_distance)
+    }
+
+    /// Kamera am Auge des Spielers mit Sichtfeld und Fernebene (für Gfx und das Culling)
+    pub fn camera(&self) -> Camera {
+        let (eye, dir) = self.camera_pos_dir();
+        self.camera_at(eye, dir)
+    }
+
+    /// Kamera für einen beliebigen Standpunkt (interpoliertes Auge, freie Kamera); in der
+    /// Draufsicht senkrecht von oben, die Blickrichtung zählt dann nicht
+    pub fn camera_at(&self, eye: (f32, f32, f32), dir: (f32, f32, f32)) -> Camera {
+        if self.top_down {
+            let far = self.far_plane() + TOP_DOWN_HEIGHT;
+            let above = (eye.0, eye.1 + TOP_DOWN_HEIGHT, eye.2);
+            Camera::ortho(TOP_DOWN_SPAN * 0.5, far).looking(above, (0.0, -1.0, 0.0))
+        } else if self.third_person {
+            let back = (-dir.0, -dir.1, -dir.2);
+            let dist = match self.world.raycast(eye, back, THIRD_PERSON_DISTANCE) {
+                Some(hit) => (hit.distance - NEAR_WALL).max(0.0),
+                None => THIRD_PERSON_DISTANCE,
+            };
+            let behind = (eye.0 + back.0 * dist, eye.1 + back.1 * dist, eye.2 + back.2 * dist);
+            Camera::new(self.fov_y, self.far_plane()).looking(behind, dir)
+        } else {
+            Camera::new(self.fov_y, self.far_plane()).looking(eye, dir)
+        }
+    }
+
+    pub fn third_person(&self) -> bool {
+        self.third_person
+    }
+
+    pub fn toggle_third_person(&mut self) {
+        self.third_person = !self.third_person;
+        log::info!("Außenansicht {}", if self.third_person { "an" } else { "aus" });
+    }
+
+    /// Haltungen der anderen Spieler (verzögert wie `remote_players`), auf Wunsch mit der
+    /// eigenen Figur (Außenansicht, Draufsicht, freie Kamera)
+    pub fn player_poses(&self, with_self: bool) -> Vec<Pose> {
+        let now = Instant::now();
+        let at = now.checked_sub(INTERP_DELAY).unwrap_or(now);
+        let mut poses: Vec<Pose> = self.others.values().filter_map(|p| p.pose(at)).collect();
+        if with_self {
+            let ((x, y, z), _) = self.camera_pos_dir();
+            poses.push(Pose {
+                feet: Vec3::new(x, y - EYE_HEIGHT, z),
+                yaw: self.yaw,
+                pitch: self.pitch,
+                stride: self.stride,
+            });
+        }
+        poses
+    }
+
+    pub fn top_down(&self) -> bool {
+        self.top_down
+    }
+
+    pub fn toggle_top_down(&mut self) {
+        self.top_down = !self.top_down;
+        log::info!("Draufsicht {}", if self.top_down { "an" } else { "aus" });
+    }
+
+    /// Sichtfeld in Grad
+    pub fn set_fov(&mut self, degrees: f32) {
+        let fov_y = degrees.to_radians();
+        if fov_y != self.fov_y {
+            // Culling hängt am FOV; `update_meshes` merkt den neuen Ausschnitt selbst
+            self.fov_y = fov_y;
+        }
+    }
+
+    /// Speicherbudget der Chunk-Meshes in MiB
+    pub fn set_mesh_budget(&mut self, mb: u32) {
+        self.chunk_mesh_cache.set_budget((mb as usize) << 20);
+    }
+
+    /// Belegter Speicher der Chunk-Meshes in Bytes
+    pub fn mesh_cache_bytes(&self) -> usize {
+        self.chunk_mesh_cache.bytes()
+    }
+
+    /// Weltspiegel und Mesh-Cache in den Bericht eintragen
+    pub fn add_memory(&self, report: &mut MemoryReport) {
+        let mirror = self.world.memory_report();
+        report.client_chunks = mirror.chunks;
+        report.client_chunk_bytes = mirror.chunk_bytes + mirror.entity_bytes;
+        report.mesh_bytes = self.chunk_mesh_cache.bytes();
+    }
+
+    /// Geladene Chunks, die noch (oder nach Verdrängung wieder) auf ihr Mesh warten
+    pub fn mesh_backlog(&self) -> usize {
+        let cps = self.world.chunk_positions();
+        cps.into_iter().filter(|&cp| !self.chunk_mesh_cache.contains(cp)).count()
+    }
+
+    /// Geänderte Chunks neu meshen, bis `MESH_UPLOAD_BUDGET` voll ist; geliefert wird nur,
+    /// was die GPU-Seite hochladen bzw. freigeben muss
+    pub fn update_meshes(&mut self, screen_width: u32, screen_height: u32) -> MeshUpdate {
+        let _span = tracing::trace_span!("mesh").entered();
+        let cps = self.world.chunk_positions();
+        let aspect = (screen_width.max(1) as f32) / (screen_height.max(1) as f32);
+        let camera = self.camera();
+        let visible: HashSet<ChunkPos> = cps
+            .iter()
+            .copied()
+            .filter(|&cp| {
+                let (center, radius) = chunk_sphere(cp);
+                camera.sees_sphere(center, radius, aspect)
+            })
+            .collect();
+        let now = Instant::now();
+        let mut update = MeshUpdate::default();
+
+        // 1) Dirty Chunks neu meshen (oder wenn noch nicht im Cache). Sichtbare und nahe
+        // zuerst, und nur bis zum Budget: nach dem Laden oder einem Teleport verteilt sich
+        // die Arbeit so auf mehrere Frames, statt einen einzelnen lang zu machen
+        let mut order = cps;
+        order.sort_by_key(|cp| {
+            let (center, _) = chunk_sphere(*cp);
+            (!visible.contains(cp), center.distance_squared(camera.pos) as u32)
+        });
+        let mut uploaded_bytes = 0;
+        for cp in order {
+            let was_dirty = self.world.take_chunk_dirty(cp);
+            let missing = !self.chunk_mesh_cache.contains(cp);
+            let evicted = self.chunk_mesh_cache.is_evicted(cp);
+
+            // Verdrängte Meshes erst wieder, wenn der Chunk ins Bild kommt
+            if (was_dirty || missing) && (!evicted || visible.contains(&cp)) {
+                if uploaded_bytes >= MESH_UPLOAD_BUDGET {
+                    if was_dirty {
+                        self.world.mark_dirty(cp);
+                    }
+                    continue;
+                }
+                if missing && !evicted {
+                    // neuer Chunk -> Nachbarn neu meshen lassen, damit Grenz-Faces verschwinden
+                    const NEIGHBORS: [(i32, i32, i32); 6] = [
+                        (1, 0, 0),
+                        (-1, 0, 0),
+                        (0, 1, 0),
+                        (0, -1, 0),
+                        (0, 0, 1),
+                        (0, 0, -1),
+                    ];
+                    for (dx, dy, dz) in NEIGHBORS {
+                        self.world.mark_dirty(ChunkPos {
+                            cx: cp.cx + dx,
+                            cy: cp.cy + dy,
+                            cz: cp.cz + dz,
+                        });
+                    }
+                    // Nur wirklich neue Chunks (nicht nach F7 oder aus dem Bild zurück)
+                    if !self.visible.contains(&cp) {
+                        self.rising.insert(cp, now);
+                    }
+                }
+
+                let (v, i) = mesh_chunk(&self.world, cp, self.light_debug);
+                uploaded_bytes += std::mem::size_of_val(&v[..]) + std::mem::size_of_val(&i[..]);
+                self.chunk_mesh_cache.insert(cp, v.clone(), i.clone());
+                self.uploaded.insert(cp);
+                update.uploads.push((cp, v, i));
+            }
+        }
+
+        // Cache aufraeumen: Meshes zu entladenen Chunks entfernen, dann aufs Budget kürzen
+        self.chunk_mesh_cache.retain(|cp| self.world.has_chunk(cp));
+        self.chunk_mesh_cache.next_frame();
+        for &cp in &visible {
+            self.chunk_mesh_cache.mark_visible(cp);
+        }
+        let evicted = self.chunk_mesh_cache.evict();
+        if evicted > 0 {
+            log::debug!(
+                "{evicted} Chunk-Meshes verdrängt, {} MiB im Cache",
+                self.chunk_mesh_cache.bytes() >> 20
+            );
+        }
+        // Was nicht mehr im Cache liegt, gibt auch die GPU frei
+        let cache = &self.chunk_mesh_cache;
+        update.removed = self.uploaded.iter().copied().filter(|&cp| !cache.contains(cp)).collect();
+        for cp in &update.removed {
+            self.uploaded.remove(cp);
+        }
+
+        self.rising.retain(|cp, _| visible.contains(cp));
+        self.visible = visible;
+        update
+    }
+
+    /// Sichtbare Chunks mit Mesh, je einer ein Draw; jeden Frame abholen, solange Chunks
+    /// aufsteigen
+    pub fn chunk_draws(&mut self) -> Vec<ChunkDraw> {
+        let now = Instant::now();
+        self.rising.retain(|_, start| now - *start < CHUNK_FADE);
+        let cache = &self.chunk_mesh_cache;
+        let meshed = |cp: &&ChunkPos| cache.get(**cp).is_some_and(|m| !m.inds.is_empty());
+        self.visible
+            .iter()
+            .filter(meshed)
+            .map(|&pos| ChunkDraw {
+                pos,
+                sink: self.rising.get(&pos).map_or(0.0, |start| rise_offset(now - *start)),
+            })
+            .collect()
+    }
+}
+
+/// Änderungen an den Chunk-Meshes eines Frames für die GPU-Seite
+#[derive(Default)]
+pub struct MeshUpdate {
+    /// Neu gebaute Meshes; zusammen höchstens etwa `MESH_UPLOAD_BUDGET` Bytes
+    pub uploads: Vec<(ChunkPos, Vec<Vertex>, Vec<u32>)>,
+    /// Entladene oder verdrängte Chunks, deren Buffer frei werden
+    pub removed: Vec<ChunkPos>,
+}
+
+/// Ein sichtbarer Chunk und wie weit er noch unter seiner Höhe liegt (Aufsteigen neu
+/// geladener Chunks, im Shader pro Draw verschoben)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChunkDraw {
+    pub pos: ChunkPos,
+    pub sink: f32,
+}
+
+/// Wie weit ein Chunk `elapsed` nach dem Auftauchen noch unter seiner Höhe liegt
+/// (bremst zum Ende hin ab)
+fn rise_offset(elapsed: Duration) -> f32 {
+    let t = (elapsed.as_secs_f32() / CHUNK_FADE.as_secs_f32()).min(1.0);
+    (1.0 - t).powi(3) * CHUNK_RISE
+}
+
+/// Sichtweite in Chunks (Radius um den Spieler) plus Reserve
+pub fn far_plane(view_distance: i32) -> f32 {
+    (view_distance * CHUNK_SIZE) as f32 + FAR_MARGIN
+}
+
+/// Umkugel eines Chunks (Mittelpunkt, Radius) fürs Culling
+fn chunk_sphere(cp: ChunkPos) -> (Vec3, f32) {
+    let base = Vec3::new(
+        (cp.cx * CHUNK_SIZE) as f32,
+        (cp.cy * CHUNK_SIZE) as f32,
+        (cp.cz * CHUNK_SIZE) as f32,
+    );
+    let size = Vec3::splat(CHUNK_SIZE as f32);
+    let center = base + size * 0.5;
+    let radius = (size * 0.5).length() * 1.02; // kleine Reserve gegen harte Schnitte
+    (center, radius)
+}
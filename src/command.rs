@@ -1,7 +1,12 @@
 use crate::block::Block;
+use crate::item::Item;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Command {
     Break { x: i32, y: i32, z: i32 },
-    Place { x: i32, y: i32, z: i32, block: Block },
+    Place { x: i32, y: i32, z: i32, block: Block, state: u8 },
+    /// Rechtsklick auf einen benutzbaren Block (Tür)
+    Interact { x: i32, y: i32, z: i32 },
+    /// Verbrauchsgegenstand auf einen Block anwenden (kein Platzieren)
+    UseItem { x: i32, y: i32, z: i32, item: Item },
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+
+mod commands;
+
+#[cfg(feature = "scripting")]
+pub use commands::is_builtin;
+pub use commands::{needs_operator, parse, ConsoleCommand, Coord, ExportFormat, USAGE};
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// So viele Zeilen hält der Verlauf
+const SCROLLBACK: usize = 200;
+/// Maximale Länge einer Eingabezeile (Bytes); der Server kürzt Chat von Clients ebenso
+pub const MAX_INPUT: usize = 120;
+/// So viele eingegebene Zeilen merkt sich die Historie
+const HISTORY: usize = 100;
+/// Solange bleiben neue Meldungen bei geschlossener Konsole sichtbar (10 s)
+pub const RECENT_TICKS: u64 = 200;
+
+/// Chat-/Befehlskonsole: eigene Texteingabe plus Verlauf
+#[derive(Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    /// Zeile und Tick, an dem sie kam (älteste zuerst)
+    lines: VecDeque<(String, u64)>,
+    /// Wie viele Zeilen vom Ende weggescrollt
+    scroll: usize,
+    /// Frühere Eingaben (älteste zuerst), über Sitzungen hinweg gespeichert
+    history: Vec<String>,
+    /// Position beim Blättern mit Pfeil hoch/runter (None = neue Eingabe)
+    history_pos: Option<usize>,
+    /// Zähler für `lines_since`
+    printed: u64,
+}
+
+impl Console {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Öffnen; `prefix` landet schon in der Eingabe (z.B. "/")
+    pub fn open(&mut self, prefix: &str) {
+        self.open = true;
+        self.input.clear();
+        self.input.push_str(prefix);
+        self.scroll = 0;
+        self.history_pos = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.input.clear();
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Tippen: nur druckbares ASCII (der UI-Font kann nicht mehr)
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if (c == ' ' || c.is_ascii_graphic()) && self.input.len() < MAX_INPUT {
+                self.input.push(c);
+            }
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// Enter: Konsole schließt, nicht-leere Zeile wird zurückgegeben
+    pub fn submit(&mut self) -> Option<String> {
+        let line = self.input.trim().to_string();
+        self.close();
+        if !line.is_empty() && self.history.last() != Some(&line) {
+            if self.history.len() == HISTORY {
+                self.history.remove(0);
+            }
+            self.history.push(line.clone());
+        }
+        (!line.is_empty()).then_some(line)
+    }
+
+    /// Pfeil hoch: ältere Eingabe holen
+    pub fn history_prev(&mut self) {
+        let pos = match self.history_pos {
+            None => self.history.len().checked_sub(1),
+            Some(p) => Some(p.saturating_sub(1)),
+        };
+        if let Some(p) = pos {
+            self.history_pos = Some(p);
+            self.input = self.history[p].clone();
+        }
+    }
+
+    /// Pfeil runter: neuere Eingabe, hinter der neuesten wieder leer
+    pub fn history_next(&mut self) {
+        let Some(p) = self.history_pos else {
+            return;
+        };
+        if p + 1 < self.history.len() {
+            self.history_pos = Some(p + 1);
+            self.input = self.history[p + 1].clone();
+        } else {
+            self.history_pos = None;
+            self.input.clear();
+        }
+    }
+
+    /// Tab: letztes Wort eines Befehls ergänzen. Eindeutig -> ganz plus Leerzeichen,
+    /// sonst gemeinsamer Anfang; die Kandidaten werden zurückgegeben (zum Anzeigen)
+    pub fn complete(&mut self) -> Vec<String> {
+        let Some(line) = self.input.strip_prefix('/') else {
+            return Vec::new();
+        };
+        let options = commands::completions(line);
+        let word_start = self.input.rfind(' ').map_or(1, |i| i + 1);
+        match options.as_slice() {
+            [] => {}
+            [only] => {
+                self.input.truncate(word_start);
+                self.input.push_str(only);
+                self.input.push(' ');
+            }
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |n, o| {
+                    first.bytes().zip(o.bytes()).take(n).take_while(|(a, b)| a == b).count()
+                });
+                self.input.truncate(word_start);
+                self.input.push_str(&first[..common]);
+            }
+        }
+        if options.len() > 1 { options } else { Vec::new() }
+    }
+
+    /// Historie laden (eine Zeile pro Eingabe); fehlende Datei = leer
+    pub fn load_history(&mut self, path: &Path) {
+        if let Ok(text) = fs::read_to_string(path) {
+            self.history = text.lines().map(str::to_string).collect();
+            let skip = self.history.len().saturating_sub(HISTORY);
+            self.history.drain(..skip);
+        }
+    }
+
+    pub fn save_history(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+        }
+        let mut text = self.history.join("\n");
+        text.push('\n');
+        fs::write(path, text).with_context(|| format!("write {}", path.display()))
+    }
+
+    pub fn print(&mut self, line: impl Into<String>, tick: u64) {
+        if self.lines.len() == SCROLLBACK {
+            self.lines.pop_front();
+        }
+        self.lines.push_back((line.into(), tick));
+        self.printed += 1;
+    }
+
+    /// Anzahl aller je ausgegebenen Zeilen (auch der aus dem Verlauf gefallenen)
+    pub fn printed(&self) -> u64 {
+        self.printed
+    }
+
+    /// Zeilen, die nach dem Stand `printed` dazugekommen und noch im Verlauf sind
+    pub fn lines_since(&self, printed: u64) -> impl Iterator<Item = &str> {
+        let new = (self.printed - printed).min(self.lines.len() as u64) as usize;
+        self.lines.iter().skip(self.lines.len() - new).map(|(line, _)| line.as_str())
+    }
+
+    /// Positiv = ältere Zeilen zeigen
+    pub fn scroll_by(&mut self, lines: i32) {
+        let max = self.lines.len().saturating_sub(1) as i32;
+        self.scroll = (self.scroll as i32 + lines).clamp(0, max) as usize;
+    }
+
+    /// Bis zu `n` sichtbare Zeilen (älteste zuerst) unter Berücksichtigung des Scrollens
+    pub fn visible(&self, n: usize) -> impl Iterator<Item = &(String, u64)> {
+        let end = self.lines.len() - self.scroll.min(self.lines.len());
+        self.lines.range(end.saturating_sub(n)..end)
+    }
+}
@@ -0,0 +1,281 @@
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::block::Block;
+use crate::gamemode::GameMode;
+use crate::item::Item;
+use crate::weather::Weather;
+
+/// Koordinate aus einem Befehl; `~` bzw. `~n` ist relativ zur Spielerposition
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coord {
+    pub value: f32,
+    pub relative: bool,
+}
+
+impl Coord {
+    pub fn resolve(self, origin: f32) -> f32 {
+        if self.relative { origin + self.value } else { self.value }
+    }
+}
+
+/// Geparster Konsolenbefehl (ohne führendes "/")
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Help,
+    Tp([Coord; 3]),
+    Give { item: Item, count: u32 },
+    /// Tageszeit in Ticks (0 = Mittag)
+    TimeSet(u64),
+    Fill { from: [Coord; 3], to: [Coord; 3], block: Block },
+    Seed,
+    GameMode(GameMode),
+    /// Auswahl komplett mit einem Block füllen
+    Set(Block),
+    /// In der Auswahl einen Block durch einen anderen ersetzen
+    Replace { from: Block, to: Block },
+    /// Hülle der Auswahl mit einem Block, Inneres mit Luft
+    Hollow(Block),
+    /// Auswahl relativ zum Spieler in die Zwischenablage
+    Copy,
+    /// Zwischenablage relativ zum Spieler einsetzen (erst spiegeln, dann drehen)
+    Paste { quarter_turns: u8, mirror: bool },
+    /// Zwischenablage als Schematic speichern bzw. laden
+    SchemSave(String),
+    SchemLoad(String),
+    /// Auswahl (ohne Auswahl: geladene Welt) als Datei exportieren
+    Export { name: String, format: ExportFormat },
+    /// Makrodatei ausführen; Variablen aus `name=wert`-Argumenten
+    Run { name: String, vars: Vec<(String, String)> },
+    /// Messwert-Verlauf als CSV speichern
+    Metrics(String),
+    /// Wegmarke an der Spielerposition (ohne Namen: nächster freier `wp<n>`)
+    WaypointAdd(Option<String>),
+    WaypointRemove(String),
+    WaypointList,
+    /// Statistik der Welt
+    Stats,
+    /// Wetter setzen, Dauer wird gewürfelt
+    Weather(Weather),
+}
+
+/// Dateiformat von /export
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// MagicaVoxel (Blöcke als Voxel)
+    Vox,
+    /// Wavefront OBJ (gemeshte Oberflächen mit Vertexfarben)
+    Obj,
+    /// PNG-Karte von oben
+    Map,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Vox => "vox",
+            ExportFormat::Obj => "obj",
+            ExportFormat::Map => "png",
+        }
+    }
+}
+
+/// Für /help und die Fehlermeldung bei unbekannten Befehlen
+pub const USAGE: &[&str] = &[
+    "/tp <x> <y> <z>",
+    "/give <item> [count]",
+    "/time set <day|noon|night|midnight|ticks>",
+    "/fill <x1> <y1> <z1> <x2> <y2> <z2> <block>",
+    "/seed",
+    "/gamemode <survival|creative>",
+    "/set <block>",
+    "/replace <from> <to>",
+    "/hollow <block>",
+    "/copy",
+    "/paste [90|180|270] [mirror]",
+    "/schem <save|load> <name>",
+    "/run <macro> [name=value ...]",
+    "/export <name> [vox|obj|map]",
+    "/metrics <name>",
+    "/waypoint <add|remove|list> [name]",
+    "/stats",
+    "/weather <clear|rain|thunder>",
+];
+
+pub fn parse(line: &str, day_length: u64) -> Result<ConsoleCommand> {
+    let mut args = line.split_whitespace();
+    let name = args.next().ok_or_else(|| anyhow!("empty command"))?;
+    let args: Vec<&str> = args.collect();
+
+    let cmd = match (name, args.as_slice()) {
+        ("help", []) => ConsoleCommand::Help,
+        ("tp", [x, y, z]) => ConsoleCommand::Tp([coord(x)?, coord(y)?, coord(z)?]),
+        ("give", [item, rest @ ..]) if rest.len() <= 1 => {
+            let count = match rest.first() {
+                Some(n) => n.parse().with_context(|| format!("invalid count '{n}'"))?,
+                None => 1,
+            };
+            ConsoleCommand::Give {
+                item: item_by_name(item)?,
+                count,
+            }
+        }
+        ("time", ["set", t]) => ConsoleCommand::TimeSet(time_of_day(t, day_length)?),
+        ("fill", [x1, y1, z1, x2, y2, z2, block]) => ConsoleCommand::Fill {
+            from: [coord(x1)?, coord(y1)?, coord(z1)?],
+            to: [coord(x2)?, coord(y2)?, coord(z2)?],
+            block: block_by_name(block)?,
+        },
+        ("seed", []) => ConsoleCommand::Seed,
+        ("gamemode", [mode]) => ConsoleCommand::GameMode(
+            GameMode::parse(mode).ok_or_else(|| anyhow!("unknown game mode '{mode}'"))?,
+        ),
+        ("set", [block]) => ConsoleCommand::Set(block_by_name(block)?),
+        ("replace", [from, to]) => ConsoleCommand::Replace {
+            from: block_by_name(from)?,
+            to: block_by_name(to)?,
+        },
+        ("hollow", [block]) => ConsoleCommand::Hollow(block_by_name(block)?),
+        ("copy", []) => ConsoleCommand::Copy,
+        ("paste", opts) if opts.len() <= 2 => {
+            let (mut quarter_turns, mut mirror) = (0, false);
+            for opt in opts {
+                match *opt {
+                    "mirror" => mirror = true,
+                    "0" | "90" | "180" | "270" => {
+                        quarter_turns = (opt.parse::<u16>().expect("Winkel") / 90) as u8
+                    }
+                    _ => bail!("invalid paste option '{opt}' (90, 180, 270 or mirror)"),
+                }
+            }
+            ConsoleCommand::Paste { quarter_turns, mirror }
+        }
+        ("schem", ["save", name]) => ConsoleCommand::SchemSave(file_name("schematic", name)?),
+        ("schem", ["load", name]) => ConsoleCommand::SchemLoad(file_name("schematic", name)?),
+        ("export", [name, rest @ ..]) if rest.len() <= 1 => ConsoleCommand::Export {
+            name: file_name("export", name)?,
+            format: match rest.first().copied() {
+                None | Some("vox") => ExportFormat::Vox,
+                Some("obj") => ExportFormat::Obj,
+                Some("map") => ExportFormat::Map,
+                Some(f) => bail!("unknown export format '{f}' (vox, obj or map)"),
+            },
+        },
+        ("run", [name, vars @ ..]) => ConsoleCommand::Run {
+            name: file_name("macro", name)?,
+            vars: vars
+                .iter()
+                .map(|v| match v.split_once('=') {
+                    Some((k, val)) if !k.is_empty() => Ok((k.to_string(), val.to_string())),
+                    _ => Err(anyhow!("invalid variable '{v}' (use name=value)")),
+                })
+                .collect::<Result<_>>()?,
+        },
+        ("metrics", [name]) => ConsoleCommand::Metrics(file_name("metrics", name)?),
+        ("waypoint", ["add"]) => ConsoleCommand::WaypointAdd(None),
+        ("waypoint", ["add", name]) => {
+            ConsoleCommand::WaypointAdd(Some(file_name("waypoint", name)?))
+        }
+        ("waypoint", ["remove", name]) => ConsoleCommand::WaypointRemove(name.to_string()),
+        ("waypoint", ["list"]) => ConsoleCommand::WaypointList,
+        ("stats", []) => ConsoleCommand::Stats,
+        ("weather", [kind]) => ConsoleCommand::Weather(
+            Weather::by_name(kind).ok_or_else(|| anyhow!("unknown weather '{kind}'"))?,
+        ),
+        (n, _) if COMMAND_NAMES.contains(&n) => {
+            let usage = USAGE.iter().find(|u| u[1..].starts_with(name)).unwrap_or(&"");
+            bail!("usage: {usage}")
+        }
+        _ => bail!("unknown command /{name} (try /help)"),
+    };
+    Ok(cmd)
+}
+
+fn coord(s: &str) -> Result<Coord> {
+    let (rest, relative) = match s.strip_prefix('~') {
+        Some(rest) => (rest, true),
+        None => (s, false),
+    };
+    let value = if relative && rest.is_empty() {
+        0.0
+    } else {
+        rest.parse().with_context(|| format!("invalid coordinate '{s}'"))?
+    };
+    Ok(Coord { value, relative })
+}
+
+/// Nur einfache Dateinamen (keine Pfade)
+fn file_name(kind: &str, name: &str) -> Result<String> {
+    let ok = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !ok {
+        bail!("invalid {kind} name '{name}' (letters, digits, _ and - only)");
+    }
+    Ok(name.to_string())
+}
+
+fn item_by_name(name: &str) -> Result<Item> {
+    Item::by_name(name).ok_or_else(|| anyhow!("unknown item '{name}'"))
+}
+
+fn block_by_name(name: &str) -> Result<Block> {
+    Block::by_name(name).ok_or_else(|| anyhow!("unknown block '{name}'"))
+}
+
+/// Benannte Tageszeiten relativ zur Tageslänge (0 = Mittag, 0.75 = Sonnenaufgang)
+fn time_of_day(s: &str, day_length: u64) -> Result<u64> {
+    let frac = match s {
+        "day" => 0.8,
+        "noon" => 0.0,
+        "night" => 0.3,
+        "midnight" => 0.5,
+        _ => return s.parse().with_context(|| format!("invalid time '{s}'")),
+    };
+    Ok((frac * day_length as f64) as u64)
+}
+
+/// Befehle ohne Folgen für andere Spieler; alle übrigen (auch Skriptbefehle) verändern
+/// die Welt, schreiben Dateien auf dem Server oder sind Cheats wie `give` und `gamemode`
+const PLAYER_COMMANDS: &[&str] = &["help", "tp", "seed", "copy", "waypoint", "stats"];
+
+/// Im Mehrspieler nur für Operatoren (`line` ohne '/')
+pub fn needs_operator(line: &str) -> bool {
+    let name = line.split_whitespace().next().unwrap_or("");
+    !PLAYER_COMMANDS.contains(&name)
+}
+
+/// Eingebauter Befehl (Skripte dürfen diese Namen nicht belegen)
+pub fn is_builtin(name: &str) -> bool {
+    COMMAND_NAMES.contains(&name)
+}
+
+const COMMAND_NAMES: &[&str] = &[
+    "help", "tp", "give", "time", "fill", "seed", "gamemode", "set", "replace", "hollow", "copy",
+    "paste", "schem", "run", "export", "metrics", "waypoint", "stats", "weather",
+];
+
+/// Kandidaten für das letzte (unvollständige) Wort einer Befehlszeile ohne "/"
+pub fn completions(line: &str) -> Vec<String> {
+    let words: Vec<&str> = line.split(' ').collect();
+    let (current, before) = words.split_last().expect("split liefert mindestens ein Wort");
+    let arg = before.len();
+
+    let options: Vec<String> = match (before.first().copied(), arg) {
+        (_, 0) => COMMAND_NAMES.iter().map(|s| s.to_string()).collect(),
+        (Some("give"), 1) => Item::all().map(|i| i.name().to_string()).collect(),
+        (Some("fill"), 7) | (Some("set" | "hollow"), 1) | (Some("replace"), 1..=2) => {
+            Block::ALL.iter().map(|b| b.name().to_string()).collect()
+        }
+        (Some("tp"), 1..=3) | (Some("fill"), 1..=6) => vec!["~".to_string()],
+        (Some("time"), 1) => vec!["set".to_string()],
+        (Some("export"), 2) => ["vox", "obj", "map"].map(String::from).to_vec(),
+        (Some("schem"), 1) => vec!["save".to_string(), "load".to_string()],
+        (Some("waypoint"), 1) => ["add", "remove", "list"].map(String::from).to_vec(),
+        (Some("paste"), 1..=2) => ["90", "180", "270", "mirror"].map(String::from).to_vec(),
+        (Some("time"), 2) => ["day", "noon", "night", "midnight"].map(String::from).to_vec(),
+        (Some("weather"), 1) => Weather::ALL.map(|w| w.name().to_string()).to_vec(),
+        (Some("gamemode"), 1) => {
+            [GameMode::Survival, GameMode::Creative].map(|m| m.name().to_string()).to_vec()
+        }
+        _ => Vec::new(),
+    };
+    options.into_iter().filter(|o| o.starts_with(current)).collect()
+}
@@ -0,0 +1,215 @@
+use crate::block::Block;
+use crate::inventory::{Inventory, ItemStack};
+use crate::item::Item;
+
+/// Rezeptform: Muster mit Zeichen-Schlüssel oder nur die Zutatenliste
+pub enum Pattern {
+    /// Zeilen des Musters; Leerzeichen = leeres Feld. Passt überall im Raster und gespiegelt.
+    Shaped {
+        rows: &'static [&'static str],
+        key: &'static [(char, Item)],
+    },
+    /// Zutaten in beliebiger Anordnung (je Eintrag genau ein Feld)
+    Shapeless(&'static [Item]),
+}
+
+pub struct Recipe {
+    pub pattern: Pattern,
+    pub output: Item,
+    pub count: u16,
+}
+
+const LOG: Item = Item::Block(Block::Log);
+const PLANKS: Item = Item::Block(Block::Planks);
+const STONE: Item = Item::Block(Block::Stone);
+const STICK: Item = Item::Stick;
+const WHEAT: Item = Item::Wheat;
+
+/// Rezept-Registry; das erste passende Rezept gewinnt
+pub const RECIPES: &[Recipe] = &[
+    Recipe {
+        pattern: Pattern::Shapeless(&[LOG]),
+        output: PLANKS,
+        count: 4,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["P", "P"],
+            key: &[('P', PLANKS)],
+        },
+        output: Item::Stick,
+        count: 4,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["PP", "PP"],
+            key: &[('P', PLANKS)],
+        },
+        output: Item::Block(Block::CraftingTable),
+        count: 1,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["PPP", "P P", "PPP"],
+            key: &[('P', PLANKS)],
+        },
+        output: Item::Block(Block::Chest),
+        count: 1,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["PP", "PP", "PP"],
+            key: &[('P', PLANKS)],
+        },
+        output: Item::Block(Block::Door),
+        count: 3,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["SSS"],
+            key: &[('S', STONE)],
+        },
+        output: Item::Block(Block::StoneSlab),
+        count: 6,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["WWW"],
+            key: &[('W', WHEAT)],
+        },
+        output: Item::Bread,
+        count: 1,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["MMM", " S ", " S "],
+            key: &[('M', PLANKS), ('S', STICK)],
+        },
+        output: Item::WoodenPickaxe,
+        count: 1,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["MMM", " S ", " S "],
+            key: &[('M', STONE), ('S', STICK)],
+        },
+        output: Item::StonePickaxe,
+        count: 1,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["M", "S", "S"],
+            key: &[('M', PLANKS), ('S', STICK)],
+        },
+        output: Item::WoodenShovel,
+        count: 1,
+    },
+    Recipe {
+        pattern: Pattern::Shaped {
+            rows: &["M", "S", "S"],
+            key: &[('M', STONE), ('S', STICK)],
+        },
+        output: Item::StoneShovel,
+        count: 1,
+    },
+];
+
+/// Quadratisches Crafting-Raster (2×2 im Inventar, 3×3 an der Werkbank)
+#[derive(Clone, Debug)]
+pub struct CraftGrid {
+    size: usize,
+    pub slots: Inventory,
+}
+
+impl CraftGrid {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            slots: Inventory::new(size * size),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn item_at(&self, col: usize, row: usize) -> Option<Item> {
+        self.slots.get(row * self.size + col).map(|s| s.item)
+    }
+
+    /// Ergebnis des ersten passenden Rezepts
+    pub fn result(&self) -> Option<ItemStack> {
+        RECIPES
+            .iter()
+            .find(|r| self.matches(&r.pattern))
+            .map(|r| ItemStack::new(r.output, r.count))
+    }
+
+    /// Verbraucht je belegtem Feld ein Item (nach dem Entnehmen des Ergebnisses)
+    pub fn consume(&mut self) {
+        for i in 0..self.slots.len() {
+            self.slots.remove(i, 1);
+        }
+    }
+
+    fn matches(&self, pattern: &Pattern) -> bool {
+        match pattern {
+            Pattern::Shapeless(items) => {
+                let mut want: Vec<Item> = items.to_vec();
+                for st in self.slots.iter().flatten() {
+                    match want.iter().position(|i| *i == st.item) {
+                        Some(p) => {
+                            want.swap_remove(p);
+                        }
+                        None => return false,
+                    }
+                }
+                want.is_empty()
+            }
+            Pattern::Shaped { rows, key } => {
+                let h = rows.len();
+                let w = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+                if w > self.size || h > self.size {
+                    return false;
+                }
+                let cell = |col: usize, row: usize| -> Option<Item> {
+                    let c = rows[row].as_bytes().get(col).copied().unwrap_or(b' ') as char;
+                    key.iter().find(|(k, _)| *k == c).map(|(_, i)| *i)
+                };
+                // jede Verschiebung im Raster, normal und gespiegelt
+                (0..=self.size - h).any(|oy| {
+                    (0..=self.size - w).any(|ox| {
+                        [false, true].into_iter().any(|mirror| {
+                            self.matches_at(ox, oy, w, h, |col, row| {
+                                cell(if mirror { w - 1 - col } else { col }, row)
+                            })
+                        })
+                    })
+                })
+            }
+        }
+    }
+
+    /// Muster (w×h, ab ox/oy) gegen das ganze Raster; außerhalb muss alles leer sein
+    fn matches_at(
+        &self,
+        ox: usize,
+        oy: usize,
+        w: usize,
+        h: usize,
+        cell: impl Fn(usize, usize) -> Option<Item>,
+    ) -> bool {
+        (0..self.size).all(|row| {
+            (0..self.size).all(|col| {
+                let inside = (ox..ox + w).contains(&col) && (oy..oy + h).contains(&row);
+                let want = if inside { cell(col - ox, row - oy) } else { None };
+                self.item_at(col, row) == want
+            })
+        })
+    }
+
+    /// Alle Stacks herausnehmen (Screen schließen)
+    pub fn drain(&mut self) -> Vec<ItemStack> {
+        (0..self.slots.len()).filter_map(|i| self.slots.take(i)).collect()
+    }
+}
@@ -0,0 +1,184 @@
+//! Ereignisse der Simulation. Systeme wie Audio, Partikel, Netzwerk oder Achievements
+//! abonnieren die Typen, die sie brauchen, statt dass die Spiellogik sie einzeln bedient.
+//! Jedes Abo liest mit eigenem Zeiger; ein Ereignis bleibt zwei Ticks lang lesbar.
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+
+use crate::block::Block;
+use crate::chunk::ChunkPos;
+use crate::inventory::ItemStack;
+use crate::world::BlockPos;
+
+/// Block gesetzt, abgebaut oder im State geändert
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockChanged {
+    pub pos: BlockPos,
+    pub old: Block,
+    pub block: Block,
+    pub state: u8,
+}
+
+/// Chunk neu im Speicher (vom Spielstand geladen oder angelegt)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChunkLoaded(pub ChunkPos);
+
+/// Ein Spieler hat Leben verloren (Fall, Hunger)
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerDamaged {
+    pub player: String,
+    pub amount: u8,
+    /// Leben danach
+    pub health: u8,
+}
+
+/// Items ins Inventar aufgenommen (nur der Teil, der Platz hatte)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemPickedUp {
+    pub player: String,
+    pub stack: ItemStack,
+}
+
+/// Ein Spieler hat einen Block abgebaut
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockBroken {
+    pub player: String,
+    pub pos: BlockPos,
+    pub block: Block,
+}
+
+/// Ein Spieler hat einen Block platziert
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockPlaced {
+    pub player: String,
+    pub pos: BlockPos,
+    pub block: Block,
+}
+
+/// Strecke, die ein Spieler in einem Tick am Boden gelaufen ist
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerWalked {
+    pub player: String,
+    pub distance: f32,
+}
+
+/// Ein Spieler ist abgesprungen
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerJumped {
+    pub player: String,
+}
+
+/// Ein Spieler ist gestorben (und schon wiederbelebt)
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerDied {
+    pub player: String,
+}
+
+/// Lesezeiger eines Abonnenten auf einen Ereignistyp
+pub struct Subscription<E> {
+    /// Laufende Nummer des nächsten ungelesenen Ereignisses
+    next: u64,
+    _event: PhantomData<fn() -> E>,
+}
+
+struct Channel<E> {
+    events: VecDeque<E>,
+    /// Laufende Nummer von `events[0]`
+    first: u64,
+    /// Ab dieser Nummer beim nächsten `end_tick` behalten (Ereignisse des laufenden Ticks)
+    tick_start: u64,
+}
+
+impl<E> Channel<E> {
+    fn end(&self) -> u64 {
+        self.first + self.events.len() as u64
+    }
+}
+
+/// Kanal ohne bekannten Ereignistyp, damit `end_tick` alle aufräumen kann
+trait AnyChannel: Send {
+    fn end_tick(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<E: Send + 'static> AnyChannel for Channel<E> {
+    fn end_tick(&mut self) {
+        while self.first < self.tick_start && self.events.pop_front().is_some() {
+            self.first += 1;
+        }
+        self.tick_start = self.end();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct EventBus {
+    channels: HashMap<TypeId, Box<dyn AnyChannel>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn channel<E: Send + 'static>(&self) -> Option<&Channel<E>> {
+        self.channels.get(&TypeId::of::<E>()).and_then(|c| c.as_any().downcast_ref())
+    }
+
+    fn channel_mut<E: Send + 'static>(&mut self) -> &mut Channel<E> {
+        self.channels
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| {
+                Box::new(Channel::<E> {
+                    events: VecDeque::new(),
+                    first: 0,
+                    tick_start: 0,
+                })
+            })
+            .as_any_mut()
+            .downcast_mut()
+            .expect("Kanal passt zum Typ")
+    }
+
+    pub fn publish<E: Send + 'static>(&mut self, event: E) {
+        self.channel_mut().events.push_back(event);
+    }
+
+    /// Neues Abo; sieht nur, was ab jetzt veröffentlicht wird
+    pub fn subscribe<E: Send + 'static>(&self) -> Subscription<E> {
+        Subscription {
+            next: self.channel::<E>().map_or(0, Channel::end),
+            _event: PhantomData,
+        }
+    }
+
+    /// Alles seit dem letzten Lesen dieses Abos. Wer länger als einen Tick nicht liest,
+    /// verpasst die ältesten Ereignisse.
+    pub fn read<E: Send + 'static>(&self, sub: &mut Subscription<E>) -> impl Iterator<Item = &E> {
+        let (events, skip) = match self.channel::<E>() {
+            Some(ch) => {
+                let skip = sub.next.saturating_sub(ch.first) as usize;
+                sub.next = ch.end();
+                (Some(&ch.events), skip)
+            }
+            None => (None, 0),
+        };
+        events.into_iter().flat_map(move |ev| ev.iter().skip(skip))
+    }
+
+    /// Einmal pro Tick: Ereignisse von vor dem vorigen `end_tick` verwerfen
+    pub fn end_tick(&mut self) {
+        for channel in self.channels.values_mut() {
+            channel.end_tick();
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! 5x7-Bitmapfont für das UI (nur ASCII, Kleinbuchstaben werden groß dargestellt).
+//! Die Glyphen stehen in `assets/font.txt` und werden beim ersten Text geladen.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use anyhow::{bail, Context, Result};
+
+use crate::assets::{self, Asset};
+
+pub const GLYPH_W: i32 = 5;
+pub const GLYPH_H: i32 = 7;
+
+pub const FONT_ASSET: &str = "font.txt";
+
+/// Unbekannte Zeichen: volles Kästchen
+const MISSING: [u8; 7] = [0x1F, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1F];
+
+static FONT: LazyLock<Font> = LazyLock::new(|| {
+    assets::load_now(FONT_ASSET).expect("eingebauter Font ist gültig")
+});
+
+pub struct Font {
+    glyphs: HashMap<char, [u8; 7]>,
+}
+
+impl Font {
+    /// Zeilen von oben nach unten, Bit 4 = linkes Pixel
+    pub fn glyph(&self, c: char) -> [u8; 7] {
+        self.glyphs.get(&c.to_ascii_uppercase()).copied().unwrap_or(MISSING)
+    }
+}
+
+impl Asset for Font {
+    /// `[c]` je Zeichen, darunter 7 Zeilen aus `#` und `.`; `;` leitet Kommentare ein
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(bytes).context("font is not UTF-8")?;
+        let mut glyphs = HashMap::new();
+        let mut lines = text.lines().enumerate().filter(|(_, l)| {
+            let l = l.trim_end();
+            !l.is_empty() && !l.starts_with(';')
+        });
+        while let Some((n, header)) = lines.next() {
+            let mut chars = header.trim_end().chars();
+            let (Some('['), Some(c), Some(']'), None) =
+                (chars.next(), chars.next(), chars.next(), chars.next())
+            else {
+                bail!("line {}: expected [c], got {header:?}", n + 1);
+            };
+            let mut rows = [0u8; 7];
+            for row in &mut rows {
+                let Some((n, line)) = lines.next() else {
+                    bail!("glyph {c:?}: needs {GLYPH_H} rows");
+                };
+                let line = line.trim_end();
+                if line.len() != GLYPH_W as usize {
+                    bail!("line {}: expected {GLYPH_W} pixels, got {line:?}", n + 1);
+                }
+                for px in line.chars() {
+                    *row = *row << 1 | (px == '#') as u8;
+                }
+            }
+            glyphs.insert(c, rows);
+        }
+        Ok(Self { glyphs })
+    }
+}
+
+/// Zeilen von oben nach unten, Bit 4 = linkes Pixel
+pub fn glyph(c: char) -> [u8; 7] {
+    FONT.glyph(c)
+}
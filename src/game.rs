@@ -1,264 +1,1099 @@
 use crate::block::Block;
-use crate::chunk::{chunk_coord, ChunkPos, CHUNK_SIZE};
+use crate::block_buffer::{BlockBuffer, SCHEMATIC_MAGIC};
+use crate::block_entity::BlockEntity;
+use crate::chunk::{chunk_coord, ChunkPos};
 use crate::command::Command;
+use crate::console::{self, Console, ConsoleCommand, Coord, ExportFormat};
+use crate::crafting::CraftGrid;
+use crate::gamemode::GameMode;
 use crate::input::InputState;
-use crate::mesh::Vertex;
-use crate::player::Player;
+use crate::inventory::{click_slot, Inventory, ItemStack};
+use crate::item::Tool;
+use crate::event::{PlayerJumped, PlayerWalked};
+use crate::level::{self, LevelInfo, GENERATOR_NOISE};
+use crate::macros;
+use crate::memory::MemoryReport;
+use crate::map_image;
+use crate::mc_schematic;
+use crate::mesh::{box_lines, Vertex};
+use crate::metrics;
+use crate::movement::Body;
+use crate::obj;
+use crate::player::{Player, DEFAULT_NAME, SPAWN};
+use crate::replay::{Replay, ReplayFrame};
+use crate::save::{self, WorldStorage};
+#[cfg(feature = "scripting")]
+use crate::script::{Answer, Request, ScriptHost, SCRIPT_DIR};
+use crate::screens::{
+    draw_block_picker, draw_break_progress, draw_container_screen, draw_crafting_screen,
+    draw_console, draw_crosshair, draw_hotbar, draw_vitals, picker_layout, picker_stack, ContainerLayout, SlotRef,
+};
+use crate::selection::{on_shell, region_volume, Selection};
+use crate::sound::SoundEvent;
+use crate::stats::{Stats, StatsRecorder};
+use crate::terrain::Terrain;
+use crate::ui::UiBatch;
+use crate::vox;
 use crate::voxel_mesher::mesh_chunk;
-use crate::world::World;
+use crate::world::{BlockPos, RaycastHit, World, DAY_LENGTH_TICKS};
+use anyhow::Context;
 use glam::Vec3;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+mod actions;
+mod interaction;
+mod motion;
+mod vitals;
+
+use interaction::{Breaking, Outcome};
+
+pub const SAVE_DIR: &str = "saves/world";
+/// Name neuer Welten (level.toml kann ihn ändern)
+const WORLD_NAME: &str = "New World";
+/// Obergrenze für Bereichsbefehle (/fill, /set, ...)
+const MAX_FILL: i64 = 32_768;
+const NO_SELECTION: &str = "No selection (press the wand key, then click two corners)";
+/// Schematics gelten für alle Welten
+const SCHEMATIC_DIR: &str = "saves/schematics";
+/// Ziel von /export
+const EXPORT_DIR: &str = "saves/exports";
+/// Ziel von /metrics
+const METRICS_DIR: &str = "saves/metrics";
+/// Makros, die sich (gegenseitig) aufrufen, brechen hier ab
+const MAX_MACRO_DEPTH: u8 = 8;
+/// Konsolen-Historie gilt für alle Welten
+const CONSOLE_HISTORY: &str = "saves/console_history.txt";
+
+/// Offener UI-Screen (schluckt Spiel-Eingaben)
+enum Screen {
+    /// Container-Block an dieser Position
+    Container((i32, i32, i32)),
+    /// Creative-Block-Picker
+    BlockPicker,
+    /// Inventar (2×2) oder Werkbank (3×3)
+    Crafting(CraftGrid),
+}
+
+/// Spielerbezogener Zustand eines weiteren Spielers im Mehrspieler. Der Host-Spieler lebt
+/// direkt im Game; `Game::with_seat` tauscht einen Seat für die Dauer eines Aufrufs ein.
+pub struct Seat {
+    player: Player,
+    player_name: String,
+    commands: Vec<Command>,
+    breaking: Breaking,
+    last_jump_tick: Option<u64>,
+    screen: Option<Screen>,
+    cursor_stack: Option<ItemStack>,
+    selection: Selection,
+    clipboard: Option<BlockBuffer>,
+    screen_size: (f32, f32),
+}
 
-const CAMERA_FOV_Y: f32 = 45.0_f32.to_radians();
-const CAMERA_FAR: f32 = 200.0;
+impl Seat {
+    /// Tick ohne neue Eingaben (Client hängt hinterher)
+    pub fn idle_frame(&self) -> ReplayFrame {
+        ReplayFrame {
+            yaw: self.player.yaw,
+            pitch: self.player.pitch,
+            screen: self.screen_size,
+            ..ReplayFrame::default()
+        }
+    }
+
+    fn swap(&mut self, game: &mut Game) {
+        std::mem::swap(&mut self.player, &mut game.player);
+        std::mem::swap(&mut self.player_name, &mut game.player_name);
+        std::mem::swap(&mut self.commands, &mut game.commands);
+        std::mem::swap(&mut self.breaking, &mut game.breaking);
+        std::mem::swap(&mut self.last_jump_tick, &mut game.last_jump_tick);
+        std::mem::swap(&mut self.screen, &mut game.screen);
+        std::mem::swap(&mut self.cursor_stack, &mut game.cursor_stack);
+        std::mem::swap(&mut self.selection, &mut game.selection);
+        std::mem::swap(&mut self.clipboard, &mut game.clipboard);
+        std::mem::swap(&mut self.screen_size, &mut game.screen_size);
+    }
+}
 
 pub struct Game {
     tick: u64,
     world: World,
     player: Player,
+    /// Name im Chat
+    player_name: String,
     commands: Vec<Command>,
-    chunk_mesh_cache: HashMap<ChunkPos, (Vec<Vertex>, Vec<u32>)>,
+    /// Survival-Abbau: anvisierter Block und bisherige Ticks
+    breaking: Breaking,
+    /// Tick des letzten Sprungs (Doppeltipp schaltet Fliegen)
+    last_jump_tick: Option<u64>,
+
+    screen: Option<Screen>,
+    /// Stack, der im Screen gerade am Mauszeiger hängt
+    cursor_stack: Option<ItemStack>,
+    /// Chat/Befehle (T bzw. /)
+    console: Console,
+    /// Bereich für /set, /replace, /hollow
+    selection: Selection,
+    /// Inhalt von /copy (Bezugspunkt = Spielerposition beim Kopieren)
+    clipboard: Option<BlockBuffer>,
+    /// Abgeschickte Konsolenzeilen; laufen zu Beginn des nächsten Ticks (gleich im Replay)
+    pending_lines: Vec<String>,
+    /// Konsolenzeilen (Stand von `Console::printed`), die Befehle ausgegeben haben;
+    /// nur bis zum nächsten `tick` gültig
+    command_output: Vec<Range<u64>>,
+    /// Verschachtelungstiefe laufender /run-Makros
+    macro_depth: u8,
+    /// Weltinfo (None = Welt ohne Spielstand)
+    level: Option<LevelInfo>,
+    /// Statistik der Welt (alle Spieler zusammen), gezählt aus den Ereignissen
+    stats: Stats,
+    stats_recorder: StatsRecorder,
+    /// Laufende Aufzeichnung (nur in deterministischen Welten)
+    recording: Option<Replay>,
+    /// Zuletzt gezeichnete UI-Größe (für Hit-Tests der Screens)
+    screen_size: (f32, f32),
+    /// Rhai-Skripte (None, solange ein Skript läuft)
+    #[cfg(feature = "scripting")]
+    scripts: Option<ScriptHost>,
+
+    /// Geräusche dieses Ticks (vom Audio-System abgeholt)
+    sound_events: Vec<SoundEvent>,
 }
 
-impl Game {
-    pub fn new() -> Self {
-        Self {
-            tick: 0,
-            world: World::new(),
-            player: Player::new(),
-            commands: Vec::new(),
-            chunk_mesh_cache: HashMap::new(),
-        }
-    }
+fn save_schematic(name: &str, buf: &BlockBuffer) -> anyhow::Result<()> {
+    let path = save::named_file(SCHEMATIC_DIR, name, "schem")?;
+    fs::create_dir_all(SCHEMATIC_DIR).with_context(|| format!("create {SCHEMATIC_DIR}"))?;
+    save::write_atomic(&path, &buf.encode())
+}
 
-    pub fn look_delta(&mut self, dx: f32, dy: f32) {
-        // native Mausbewegung (kein invert)
-        self.player.add_look(dx, dy);
+/// Eigene Schematics oder Minecraft-Dateien (<name>.schem bzw. <name>.schematic)
+fn load_schematic(name: &str) -> anyhow::Result<BlockBuffer> {
+    let mut path = save::named_file(SCHEMATIC_DIR, name, "schem")?;
+    if !path.exists() {
+        path.set_extension("schematic");
     }
+    let data = fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+    if data.starts_with(SCHEMATIC_MAGIC) {
+        BlockBuffer::decode(&data)
+    } else {
+        mc_schematic::decode(&data).context("Minecraft schematic")
+    }
+}
 
-    pub fn apply_movement(&mut self, input: InputState) {
-        // 20 TPS => dt = 0.05s
-        let dt = 0.05_f32;
-        let speed = 4.0_f32; // Blöcke pro Sekunde (gefühlvoll, anpassbar)
-        let step = speed * dt;
+/// Chunks, die bei Sichtweite `radius` um `center` geladen bleiben (Kreis in der
+/// Chunk-Ebene des Spielers und je eine Ebene darüber und darunter)
+pub fn chunk_window_around(center: ChunkPos, radius: i32) -> HashSet<ChunkPos> {
+    let mut window = HashSet::new();
+    for dx in -radius..=radius {
+        for dz in -radius..=radius {
+            if dx * dx + dz * dz <= radius * radius {
+                for dy in -1..=1 {
+                    window.insert(ChunkPos::new(center.cx + dx, center.cy + dy, center.cz + dz));
+                }
+            }
+        }
+    }
+    window
+}
 
-        // Vorwärtsrichtung nur in XZ (ohne hoch/runter)
-        let (dx, _dy, dz) = self.player.dir();
+/// /metrics: Messwert-Verlauf nach saves/metrics/<name>.csv
+fn save_metrics(name: &str) -> String {
+    let (csv, rows) = metrics::to_csv();
+    let result = save::named_file(METRICS_DIR, name, "csv").and_then(|path| {
+        fs::create_dir_all(METRICS_DIR).with_context(|| format!("create {METRICS_DIR}"))?;
+        fs::write(&path, csv).with_context(|| format!("write {}", path.display()))?;
+        Ok(path)
+    });
+    match result {
+        Ok(path) => format!("Saved {rows} metric samples to {}", path.display()),
+        Err(e) => format!("Could not save metrics '{name}': {e:#}"),
+    }
+}
 
-        // Normalisieren in XZ
-        let mut fwd_x = dx;
-        let mut fwd_z = dz;
-        let len = (fwd_x * fwd_x + fwd_z * fwd_z).sqrt();
-        if len > 0.0001 {
-            fwd_x /= len;
-            fwd_z /= len;
+impl Game {
+    /// Welt aus `dir` laden oder anlegen; `seed` gilt nur für neue Welten
+    pub fn new(dir: &Path, seed: Option<u64>) -> Self {
+        let storage = WorldStorage::open(dir);
+        let mut defaults = LevelInfo::new(WORLD_NAME);
+        if let Some(seed) = seed {
+            defaults.seed = seed;
+        }
+        // Nur Welten ohne gespeicherte Chunks bekommen Gelände; bestehende behalten den
+        // Generator aus level.toml (ohne Eintrag dort bleiben sie flach)
+        if !storage.has_data() {
+            defaults.generator = GENERATOR_NOISE.to_string();
+            let (x, _, z) = defaults.spawn;
+            let ground = Terrain::new(defaults.seed).surface(x.floor() as i32, z.floor() as i32);
+            defaults.spawn.1 = ground as f32 + 1.0;
         }
+        let level = storage.load_level(defaults.clone()).unwrap_or(defaults);
+        let mut world = World::with_storage(storage, level.seed, &level.generator);
+        world.set_rules(level.rules.clone());
+        world.set_day_ticks(level.day_ticks);
+        world.set_day(level.day);
+        if level.weather_ticks > 0 {
+            world.restore_weather(level.weather, level.weather_ticks);
+        }
+        let player = world.storage().and_then(|s| s.load_player()).unwrap_or_else(|| {
+            let mut p = Player::new();
+            (p.x, p.y, p.z) = level.spawn;
+            p
+        });
+        let stats = world.storage().and_then(|s| s.load_stats()).unwrap_or_default();
+        let mut game = Self::with_world(world, player);
+        game.level = Some(level);
+        game.stats = stats;
+        game
+    }
 
-        // Rechtsvektor (90° gedreht)
-        let right_x = fwd_z;
-        let right_z = -fwd_x;
+    /// Frische Welt ohne Spielstand für Aufzeichnung und Wiedergabe
+    pub fn new_deterministic(seed: u64) -> Self {
+        Self::with_world(World::deterministic(seed), Player::new())
+    }
 
-        let mut mx = 0.0_f32;
-        let mut mz = 0.0_f32;
+    fn with_world(world: World, player: Player) -> Self {
+        let mut console = Console::default();
+        console.load_history(Path::new(CONSOLE_HISTORY));
+        let stats_recorder = StatsRecorder::new(world.events());
+        let mut game = Self {
+            tick: 0,
+            world,
+            player,
+            player_name: DEFAULT_NAME.to_string(),
+            commands: Vec::new(),
+            breaking: None,
+            last_jump_tick: None,
+            screen: None,
+            cursor_stack: None,
+            console,
+            selection: Selection::default(),
+            clipboard: None,
+            pending_lines: Vec::new(),
+            command_output: Vec::new(),
+            macro_depth: 0,
+            level: None,
+            stats: Stats::default(),
+            stats_recorder,
+            recording: None,
+            screen_size: (1.0, 1.0),
+            #[cfg(feature = "scripting")]
+            scripts: Some(ScriptHost::spawn()),
+            sound_events: Vec::new(),
+        };
+        game.load_scripts();
+        game
+    }
 
-        if input.move_fwd {
-            mx += fwd_x;
-            mz += fwd_z;
+    pub fn save(&mut self) {
+        self.world.save_all();
+        if let Some(storage) = self.world.storage()
+            && let Err(e) = storage.save_player(&self.player)
+        {
+            log::warn!("Spieler nicht gespeichert: {e:#}");
         }
-        if input.move_back {
-            mx -= fwd_x;
-            mz -= fwd_z;
+        if let (Some(storage), Some(level)) = (self.world.storage(), self.level_info())
+            && let Err(e) = storage.save_level(&level)
+        {
+            log::warn!("Weltinfo nicht gespeichert: {e:#}");
         }
-        if input.move_right {
-            mx += right_x;
-            mz += right_z;
+        self.stats_recorder.record(self.world.events(), &mut self.stats);
+        if let Some(storage) = self.world.storage()
+            && let Err(e) = storage.save_stats(&self.stats)
+        {
+            log::warn!("Statistik nicht gespeichert: {e:#}");
         }
-        if input.move_left {
-            mx -= right_x;
-            mz -= right_z;
+        if let Err(e) = self.console.save_history(Path::new(CONSOLE_HISTORY)) {
+            log::warn!("Konsolen-Historie nicht gespeichert: {e:#}");
         }
+    }
 
-        // Diagonal nicht schneller
-        let mlen = (mx * mx + mz * mz).sqrt();
-        if mlen > 0.0001 {
-            mx /= mlen;
-            mz /= mlen;
+    /// Aktuelle Weltinfo (Spielzeit inklusive dieser Sitzung)
+    pub fn level_info(&self) -> Option<LevelInfo> {
+        let mut level = self.level.clone()?;
+        level.seed = self.world.seed();
+        level.play_ticks += self.tick;
+        level.day_ticks = self.world.day_ticks();
+        level.day = self.world.day();
+        level.weather = self.world.weather();
+        level.weather_ticks = self.world.weather_ticks_left();
+        level.rules = self.world.rules().clone();
+        Some(level)
+    }
 
-            let target_x = self.player.x + mx * step;
-            let target_z = self.player.z + mz * step;
+    fn spawn_point(&self) -> (f32, f32, f32) {
+        self.level.as_ref().map_or(SPAWN, |l| l.spawn)
+    }
 
-            // erst X bewegen
-            if !self.collides_at(target_x, self.player.y, self.player.z) {
-                self.player.x = target_x;
-            } else {
-                // Step-up versuchen (nur wenn wir grundsätzlich "laufen")
-                let _ = self.try_step_up(target_x, self.player.z);
-            }
+    /// Neuer Mitspieler am Spawnpunkt
+    pub fn new_seat(&self, name: String) -> Seat {
+        let mut player = Player::new();
+        (player.x, player.y, player.z) = self.spawn_point();
+        Seat {
+            player,
+            player_name: name,
+            commands: Vec::new(),
+            breaking: None,
+            last_jump_tick: None,
+            screen: None,
+            cursor_stack: None,
+            selection: Selection::default(),
+            clipboard: None,
+            screen_size: (1.0, 1.0),
+        }
+    }
+
+    /// `f` sieht den Spieler aus `seat` als eigenen (Ticks, Blick, Auswahl ...)
+    pub fn with_seat<R>(&mut self, seat: &mut Seat, f: impl FnOnce(&mut Game) -> R) -> R {
+        seat.swap(self);
+        let out = f(self);
+        seat.swap(self);
+        out
+    }
 
-            // dann Z bewegen
-            if !self.collides_at(self.player.x, self.player.y, target_z) {
-                self.player.z = target_z;
-            } else {
-                let _ = self.try_step_up(self.player.x, target_z);
+    /// Tick eines Mitspielers; die Welt selbst tickt nur mit dem Host-Spieler
+    pub fn seat_tick(&mut self, seat: &mut Seat, frame: &ReplayFrame) {
+        self.with_seat(seat, |game| {
+            game.player.yaw = frame.yaw;
+            game.player.pitch = frame.pitch;
+            game.screen_size = frame.screen;
+            for line in &frame.lines {
+                game.run_line(line);
             }
+            game.tick_player(frame.input);
+        });
+    }
+
+    pub fn set_player_name(&mut self, name: String) {
+        self.player_name = name;
+    }
+
+    pub fn screen_open(&self) -> bool {
+        self.screen.is_some() || self.console.is_open()
+    }
+
+    pub fn console_open(&self) -> bool {
+        self.console.is_open()
+    }
+
+    pub fn open_console(&mut self, prefix: &str) {
+        self.console.open(prefix);
+    }
+
+    pub fn console_mut(&mut self) -> &mut Console {
+        &mut self.console
+    }
+
+    /// Meldung in den Konsolenverlauf
+    pub fn message(&mut self, line: impl Into<String>) {
+        self.console.print(line, self.tick);
+    }
+
+    /// Tab in der Konsole: ergänzen, bei mehreren Treffern die Auswahl anzeigen
+    pub fn complete_console(&mut self) {
+        let options = self.console.complete();
+        if !options.is_empty() {
+            self.message(options.join(" "));
         }
     }
 
-    pub fn apply_vertical_physics(&mut self, input: InputState) {
-        let dt = 0.05_f32; // 20 TPS
-        let gravity = 18.0_f32; // Blöcke/s^2
-        let jump_v = 7.0_f32; // Sprungimpuls
+    pub fn console(&self) -> &Console {
+        &self.console
+    }
+
+    /// Zeile wie aus der Konsole, ausgeführt im nächsten Tick
+    pub fn queue_line(&mut self, line: String) {
+        self.pending_lines.push(line);
+    }
 
-        // Jump (one-shot)
-        if input.jump && self.player.on_ground {
-            self.player.vy = jump_v;
-            self.player.on_ground = false;
+    fn run_line(&mut self, line: &str) {
+        let Some(cmd) = line.strip_prefix('/') else {
+            self.message(format!("<{}> {line}", self.player_name));
+            return;
+        };
+        let start = self.console.printed();
+        if !self.run_script_command(cmd) {
+            self.run_command(cmd);
         }
+        self.command_output.push(start..self.console.printed());
+    }
 
-        // Gravity
-        self.player.vy -= gravity * dt;
+    /// Seit dem letzten `tick` von Befehlen ausgegebene Konsolenzeilen (der Server
+    /// schickt sie nur an den Spieler, der den Befehl eingegeben hat)
+    pub fn take_command_output(&mut self) -> Vec<Range<u64>> {
+        std::mem::take(&mut self.command_output)
+    }
 
-        // Y-Bewegung
-        let new_y = self.player.y + self.player.vy * dt;
+    /// Ab jetzt jeden Tick mitschreiben (Welt sollte `new_deterministic` sein)
+    pub fn start_recording(&mut self, seed: u64) {
+        self.recording = Some(Replay::new(seed));
+    }
 
-        // Kollision nur auf Y testen
-        if !self.collides_at(self.player.x, new_y, self.player.z) {
-            self.player.y = new_y;
-            self.player.on_ground = false;
-        } else {
-            // Wenn wir nach unten fallen und kollidieren -> auf Boden stehen
-            if self.player.vy < 0.0 {
-                self.player.on_ground = true;
-            }
-            // Stop vertikale Bewegung bei Kollision
-            self.player.vy = 0.0;
+    pub fn take_recording(&mut self) -> Option<Replay> {
+        self.recording.take()
+    }
+
+    /// Wiedergabe: Blickrichtung und Konsolenzeilen aus dem Frame, dann normal ticken
+    pub fn replay_tick(&mut self, frame: &ReplayFrame) {
+        self.player.yaw = frame.yaw;
+        self.player.pitch = frame.pitch;
+        self.screen_size = frame.screen;
+        self.pending_lines.extend(frame.lines.iter().cloned());
+        self.tick(frame.input);
+    }
 
-            // Mini-Fix gegen Einsinken durch Rundung
-            let mut y_fix = self.player.y;
-            for _ in 0..5 {
-                if !self.collides_at(self.player.x, y_fix, self.player.z) {
-                    break;
+    pub fn look_angles(&self) -> (f32, f32) {
+        (self.player.yaw, self.player.pitch)
+    }
+
+    /// Konsolenbefehl parsen und ausführen; Ergebnis/Fehler landen in der Konsole
+    fn run_command(&mut self, line: &str) {
+        let cmd = match console::parse(line, DAY_LENGTH_TICKS) {
+            Ok(cmd) => cmd,
+            Err(e) => {
+                self.message(format!("{e:#}"));
+                return;
+            }
+        };
+        let reply = match cmd {
+            ConsoleCommand::Help => self.help(),
+            ConsoleCommand::Tp([x, y, z]) => {
+                let p = &self.player;
+                self.teleport(x.resolve(p.x), y.resolve(p.y), z.resolve(p.z));
+                let p = &self.player;
+                format!("Teleported to {:.1} {:.1} {:.1}", p.x, p.y, p.z)
+            }
+            ConsoleCommand::Give { item, count } => {
+                let mut left = count;
+                while left > 0 {
+                    let n = left.min(item.max_stack() as u32);
+                    left -= n;
+                    if let Some(lost) = self.player.inventory.add(ItemStack::new(item, n as u16)) {
+                        left += lost.count as u32;
+                        break;
+                    }
                 }
-                y_fix += 0.01;
+                format!("Gave {} {}", count - left, item.name())
             }
-            self.player.y = y_fix;
-        }
+            ConsoleCommand::TimeSet(ticks) => {
+                self.world.set_day_ticks(ticks);
+                format!("Time set to {}", ticks % DAY_LENGTH_TICKS)
+            }
+            ConsoleCommand::Fill { from, to, block } => self.fill(from, to, block),
+            ConsoleCommand::Seed => format!("Seed: {}", self.world.seed()),
+            ConsoleCommand::GameMode(mode) => {
+                self.player.set_game_mode(mode);
+                format!("Game mode: {}", mode.name())
+            }
+            ConsoleCommand::Set(block) => self.edit_selection(|_, _| Some(block)),
+            ConsoleCommand::Replace { from, to } => {
+                self.edit_selection(|_, old| (old == from).then_some(to))
+            }
+            ConsoleCommand::Hollow(block) => match self.selection.bounds() {
+                Some((min, max)) => self.edit_selection(|p, _| {
+                    Some(if on_shell(p, min, max) { block } else { Block::Air })
+                }),
+                None => NO_SELECTION.to_string(),
+            },
+            ConsoleCommand::Copy => self.copy_selection(),
+            ConsoleCommand::Paste { quarter_turns, mirror } => self.paste(quarter_turns, mirror),
+            ConsoleCommand::SchemSave(name) => match &self.clipboard {
+                None => "Clipboard is empty (use /copy)".to_string(),
+                Some(buf) => match save_schematic(&name, buf) {
+                    Ok(()) => format!("Saved schematic '{name}'"),
+                    Err(e) => format!("Could not save '{name}': {e:#}"),
+                },
+            },
+            ConsoleCommand::SchemLoad(name) => match load_schematic(&name) {
+                Ok(buf) => {
+                    let (w, h, d) = buf.size;
+                    self.clipboard = Some(buf);
+                    format!("Loaded schematic '{name}' ({w}x{h}x{d}) into the clipboard")
+                }
+                Err(e) => format!("Could not load '{name}': {e:#}"),
+            },
+            ConsoleCommand::Run { name, vars } => self.run_macro(&name, &vars),
+            ConsoleCommand::Export { name, format } => self.export(&name, format),
+            ConsoleCommand::Metrics(name) => save_metrics(&name),
+            ConsoleCommand::WaypointAdd(name) => self.add_waypoint(name),
+            ConsoleCommand::WaypointRemove(name) => {
+                if self.level.as_mut().is_some_and(|l| l.remove_waypoint(&name)) {
+                    format!("Removed waypoint '{name}'")
+                } else {
+                    format!("No waypoint '{name}'")
+                }
+            }
+            ConsoleCommand::Stats => {
+                let ticks = self.level_info().map_or(self.tick, |l| l.play_ticks);
+                let mut lines = self.stats.summary(&level::play_time_label(ticks));
+                let last = lines.pop().unwrap_or_default();
+                for line in lines {
+                    self.message(line);
+                }
+                last
+            }
+            ConsoleCommand::Weather(kind) => {
+                self.world.set_weather(kind, None);
+                format!("Weather set to {}", kind.name())
+            }
+            ConsoleCommand::WaypointList => match &self.level {
+                Some(level) if !level.waypoints.is_empty() => {
+                    let p = &self.player;
+                    let entries: Vec<String> = level
+                        .waypoints
+                        .iter()
+                        .map(|w| {
+                            let (x, y, z) = w.pos;
+                            let dist = ((x - p.x).powi(2) + (z - p.z).powi(2)).sqrt();
+                            format!("{}: {x:.0} {y:.0} {z:.0} ({dist:.0}m)", w.name)
+                        })
+                        .collect();
+                    entries.join("  ")
+                }
+                _ => "No waypoints".to_string(),
+            },
+        };
+        self.message(reply);
     }
 
-    fn collides_at(&self, px: f32, py: f32, pz: f32) -> bool {
-        // Player-Hitbox (Minecraft-ish)
-        let half_w = 0.3_f32; // Breite ~0.6
-        let height = 1.8_f32; // Höhe ~1.8
-
-        let min_x = px - half_w;
-        let max_x = px + half_w;
-        let min_y = py;
-        let max_y = py + height;
-        let min_z = pz - half_w;
-        let max_z = pz + half_w;
+    /// /waypoint add bzw. Taste: Wegmarke an den Füßen des Spielers (gibt es den Namen
+    /// schon, wird sie versetzt)
+    fn add_waypoint(&mut self, name: Option<String>) -> String {
+        let p = &self.player;
+        let pos = (p.x, p.y, p.z);
+        let Some(level) = &mut self.level else {
+            return "Waypoints need a saved world".to_string();
+        };
+        let name = name.unwrap_or_else(|| level.next_waypoint_name());
+        level.set_waypoint(&name, pos);
+        format!("Waypoint '{name}' at {:.0} {:.0} {:.0}", pos.0, pos.1, pos.2)
+    }
 
-        let x0 = min_x.floor() as i32;
-        let x1 = max_x.floor() as i32;
-        let y0 = min_y.floor() as i32;
-        let y1 = max_y.floor() as i32;
-        let z0 = min_z.floor() as i32;
-        let z1 = max_z.floor() as i32;
+    /// /export: Auswahl bzw. alle geladenen Blöcke nach saves/exports/<name>.<format>
+    fn export(&mut self, name: &str, format: ExportFormat) -> String {
+        let Some((min, max)) = self.selection.bounds().or_else(|| self.world.loaded_bounds())
+        else {
+            return "Nothing to export".to_string();
+        };
+        let path = match save::named_file(EXPORT_DIR, name, format.extension()) {
+            Ok(path) => path,
+            Err(e) => return format!("Could not export '{name}': {e:#}"),
+        };
+        let size = (max.0 - min.0 + 1, max.1 - min.1 + 1, max.2 - min.2 + 1);
+        let data = match format {
+            ExportFormat::Vox => vox::check_size(size)
+                .context("select a smaller region")
+                .and_then(|()| vox::encode(&BlockBuffer::copy_from(&self.world, min, max, min))),
+            ExportFormat::Obj => {
+                let (verts, inds) = self.region_mesh(min, max);
+                let origin = [min.0 as f32, min.1 as f32, min.2 as f32];
+                Ok(obj::encode(&verts, &inds, origin).into_bytes())
+            }
+            ExportFormat::Map => map_image::render(&self.world, min, max)
+                .context("select a smaller region")
+                .and_then(|(w, h, rgba)| map_image::encode_png(w, h, &rgba)),
+        };
+        let result = data.and_then(|data| {
+            fs::create_dir_all(EXPORT_DIR).with_context(|| format!("create {EXPORT_DIR}"))?;
+            fs::write(&path, data).with_context(|| format!("write {}", path.display()))
+        });
+        match result {
+            Ok(()) => {
+                let (w, h, d) = size;
+                format!("Exported {w}x{h}x{d} blocks to {}", path.display())
+            }
+            Err(e) => format!("Could not export '{name}': {e:#}"),
+        }
+    }
 
-        for y in y0..=y1 {
-            for z in z0..=z1 {
-                for x in x0..=x1 {
-                    if self.world.is_solid(x, y, z) {
-                        return true;
+    /// Mesh der geladenen Chunks im Quader min..=max. Dreiecke gehören zu dem Block,
+    /// in dem ihr Mittelpunkt knapp hinter der Face liegt; fremde fallen weg.
+    fn region_mesh(&self, min: BlockPos, max: BlockPos) -> (Vec<Vertex>, Vec<u32>) {
+        let lo = Vec3::new(min.0 as f32, min.1 as f32, min.2 as f32);
+        let hi = Vec3::new(max.0 as f32, max.1 as f32, max.2 as f32) + Vec3::ONE;
+        let inside = |p: Vec3| p.cmpge(lo).all() && p.cmplt(hi).all();
+        let (mut verts, mut inds) = (Vec::new(), Vec::new());
+        for cx in chunk_coord(min.0)..=chunk_coord(max.0) {
+            for cy in chunk_coord(min.1)..=chunk_coord(max.1) {
+                for cz in chunk_coord(min.2)..=chunk_coord(max.2) {
+                    let cp = ChunkPos::new(cx, cy, cz);
+                    if !self.world.has_chunk(cp) {
+                        continue;
+                    }
+                    let (chunk_verts, chunk_inds) = mesh_chunk(&self.world, cp, false);
+                    // Chunk-Index -> Index im Ergebnis (nur benutzte Vertices übernehmen)
+                    let mut remap = HashMap::new();
+                    for tri in chunk_inds.chunks_exact(3) {
+                        let [a, b, c] = [0, 1, 2].map(|k| {
+                            Vec3::from(chunk_verts[tri[k] as usize].pos)
+                        });
+                        // CCW = außen (wie im Backface-Culling)
+                        let normal = (b - a).cross(c - a).normalize_or_zero();
+                        if !inside((a + b + c) / 3.0 - normal * 0.01) {
+                            continue;
+                        }
+                        for &i in tri {
+                            let out = *remap.entry(i).or_insert_with(|| {
+                                verts.push(chunk_verts[i as usize]);
+                                verts.len() as u32 - 1
+                            });
+                            inds.push(out);
+                        }
                     }
                 }
             }
         }
-        false
+        (verts, inds)
     }
 
-    fn try_step_up(&mut self, new_x: f32, new_z: f32) -> bool {
-        // Wie hoch darf "hochgesteppt" werden?
-        let step_height = 0.51_f32;
+    /// /run: Zeilen der Makrodatei nacheinander als Befehle ausführen
+    fn run_macro(&mut self, name: &str, vars: &[(String, String)]) -> String {
+        if self.macro_depth >= MAX_MACRO_DEPTH {
+            return format!("Macros nested too deeply (max {MAX_MACRO_DEPTH})");
+        }
+        let lines = match macros::load(name).and_then(|text| macros::expand(&text, vars)) {
+            Ok(lines) => lines,
+            Err(e) => return format!("Could not run '{name}': {e:#}"),
+        };
+        self.macro_depth += 1;
+        for line in &lines {
+            self.run_line(&format!("/{line}"));
+        }
+        self.macro_depth -= 1;
+        format!("Ran {} commands from '{name}'", lines.len())
+    }
 
-        // Versuch: erst +step_height hoch, dann die Bewegung durchführen
-        let y_up = self.player.y + step_height;
+    /// Eingebaute Befehle plus die von Skripten
+    fn help(&self) -> String {
+        let mut usage = console::USAGE.join("  ");
+        for name in self.script_commands() {
+            usage.push_str(&format!("  /{name}"));
+        }
+        usage
+    }
 
-        // 1) Platz über uns frei?
-        if self.collides_at(self.player.x, y_up, self.player.z) {
-            return false;
+    fn teleport(&mut self, x: f32, y: f32, z: f32) {
+        let p = &mut self.player;
+        (p.x, p.y, p.z) = (x, y, z);
+        p.vy = 0.0;
+        p.fall_start_y = p.y;
+    }
+
+    /// /fill: Quader (inklusive Ecken) mit einem Block füllen
+    fn fill(&mut self, from: [Coord; 3], to: [Coord; 3], block: Block) -> String {
+        let p = [self.player.x, self.player.y, self.player.z];
+        let [a, b] = [from, to].map(|c| {
+            let v: [i32; 3] = std::array::from_fn(|i| c[i].resolve(p[i]).floor() as i32);
+            (v[0], v[1], v[2])
+        });
+        let min = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        let max = (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2));
+        match self.fill_region(min, max, |_, _| Some((block, 0))) {
+            Ok(changed) => format!("Filled {changed} blocks with {}", block.name()),
+            Err(e) => e,
         }
+    }
 
-        // 2) Zielposition in der Luft frei?
-        if self.collides_at(new_x, y_up, new_z) {
-            return false;
+    /// Bereichsbefehl auf der aktuellen Auswahl
+    fn edit_selection(&mut self, f: impl FnMut(BlockPos, Block) -> Option<Block>) -> String {
+        let Some((min, max)) = self.selection.bounds() else {
+            return NO_SELECTION.to_string();
+        };
+        let mut f = f;
+        match self.fill_region(min, max, |p, old| f(p, old).map(|b| (b, 0))) {
+            Ok(changed) => format!("{changed} blocks changed"),
+            Err(e) => e,
         }
+    }
 
-        // 3) Erfolg: hochsetzen + bewegen
-        self.player.y = y_up;
-        self.player.x = new_x;
-        self.player.z = new_z;
-        true
+    /// Block unter den Füßen: Bezugspunkt für /copy und /paste
+    fn player_block(&self) -> BlockPos {
+        let p = &self.player;
+        (p.x.floor() as i32, p.y.floor() as i32, p.z.floor() as i32)
     }
 
-    pub fn apply_input(&mut self, input: InputState) {
-        // 1) Raycast, um Ziel zu bestimmen
-        let (sx, sy, sz) = self.player.eye_pos();
-        let (dx, dy, dz) = self.player.dir();
-        let hit = self.world.raycast_first_solid(sx, sy, sz, dx, dy, dz, 20.0);
-        let Some((x, y, z, block, (nx, ny, nz))) = hit else {
-            if input.break_block || input.place_block {
-                println!("INPUT: no target");
-            }
-            return;
+    fn copy_selection(&mut self) -> String {
+        let Some((min, max)) = self.selection.bounds() else {
+            return NO_SELECTION.to_string();
         };
+        if region_volume(min, max) > MAX_FILL {
+            return format!("Too many blocks ({} > {MAX_FILL})", region_volume(min, max));
+        }
+        let buf = BlockBuffer::copy_from(&self.world, min, max, self.player_block());
+        let msg = format!("Copied {} blocks", buf.volume());
+        self.clipboard = Some(buf);
+        msg
+    }
 
-        // 2) Commands erzeugen
-        if input.break_block {
-            self.commands.push(Command::Break { x, y, z });
-            println!("INPUT: break {:?} at ({},{},{})", block, x, y, z);
+    fn paste(&mut self, quarter_turns: u8, mirror: bool) -> String {
+        let Some(mut buf) = self.clipboard.clone() else {
+            return "Clipboard is empty (use /copy)".to_string();
+        };
+        if mirror {
+            buf = buf.mirrored_x();
         }
+        for _ in 0..quarter_turns {
+            buf = buf.rotated_cw();
+        }
+        let anchor = self.player_block();
+        let (lo, hi) = buf.relative_bounds();
+        let min = (anchor.0 + lo.0, anchor.1 + lo.1, anchor.2 + lo.2);
+        let max = (anchor.0 + hi.0, anchor.1 + hi.1, anchor.2 + hi.2);
+        let rel = |p: BlockPos| (p.0 - anchor.0, p.1 - anchor.1, p.2 - anchor.2);
+        match self.fill_region(min, max, |p, _| buf.at_relative(rel(p))) {
+            Ok(changed) => format!("Pasted {} blocks ({changed} changed)", buf.volume()),
+            Err(e) => e,
+        }
+    }
 
-        if input.place_block {
-            self.commands.push(Command::Place {
-                x: x + nx,
-                y: y + ny,
-                z: z + nz,
-                block: Block::Stone,
-            });
-            println!("INPUT: place Stone at ({},{},{})", x + nx, y + ny, z + nz);
+    /// Bulk-Fill mit Größenlimit; Err = Meldung für die Konsole
+    fn fill_region(
+        &mut self,
+        min: BlockPos,
+        max: BlockPos,
+        f: impl FnMut(BlockPos, Block) -> Option<(Block, u8)>,
+    ) -> Result<usize, String> {
+        let volume = region_volume(min, max);
+        if volume > MAX_FILL {
+            return Err(format!("Too many blocks ({volume} > {MAX_FILL})"));
         }
+        Ok(self.world.fill_region(min, max, f))
+    }
+
+    /// Auswahlbox (bzw. erste Ecke) als Linien fürs Rendering
+    pub fn overlay_lines(&self) -> Vec<Vertex> {
+        let bounds = self.selection.bounds().or_else(|| {
+            let corner = self.selection.corners.iter().flatten().next()?;
+            Some((*corner, *corner))
+        });
+        let Some((min, max)) = bounds else {
+            return Vec::new();
+        };
+        // leicht vergrößert, damit die Linien nicht mit den Blockflächen flimmern
+        let e = 0.005;
+        let lo = [min.0 as f32 - e, min.1 as f32 - e, min.2 as f32 - e];
+        let hi = [max.0 as f32 + 1.0 + e, max.1 as f32 + 1.0 + e, max.2 as f32 + 1.0 + e];
+        box_lines(lo, hi, [1.0, 0.85, 0.2])
+    }
+
+    pub fn look_delta(&mut self, dx: f32, dy: f32) {
+        // bereits skaliert; Invertierung erledigt MouseSettings::look
+        self.player.add_look(dx, dy);
+    }
+
+    /// Genug Hunger zum Sprinten (der Client braucht das für die Vorhersage)
+    pub fn can_sprint(&self) -> bool {
+        motion::can_sprint(&self.player)
+    }
+
+    pub fn player_body(&self) -> Body {
+        self.player.body()
+    }
+
+    pub fn inventory(&self) -> &Inventory {
+        &self.player.inventory
+    }
+
+    pub fn drain_sound_events(&mut self) -> Vec<SoundEvent> {
+        std::mem::take(&mut self.sound_events)
     }
 
     pub fn tick(&mut self, input: InputState) {
+        let _span = tracing::trace_span!("tick", tick = self.tick).entered();
+        if let Some(rec) = &mut self.recording {
+            rec.frames.push(ReplayFrame {
+                input,
+                yaw: self.player.yaw,
+                pitch: self.player.pitch,
+                screen: self.screen_size,
+                lines: self.pending_lines.clone(),
+            });
+        }
+        self.command_output.clear();
+        for line in std::mem::take(&mut self.pending_lines) {
+            self.run_line(&line);
+        }
+
+        // Vor `world.tick`: die Ereignisse aller Spieler des vorigen Ticks sind noch da
+        self.stats_recorder.record(self.world.events(), &mut self.stats);
+        actions::spill_containers(&mut self.world);
         self.tick += 1;
         self.world.tick();
-        // Movement pro Tick anwenden (halten)
-        self.apply_movement(input);
-        self.apply_vertical_physics(input);
+        self.tick_player(input);
+    }
+
+    /// Eingaben, Bewegung und Befehle des aktuellen Spielers
+    fn tick_player(&mut self, mut input: InputState) {
+        if input.toggle_wand {
+            self.selection.active = !self.selection.active;
+            let state = if self.selection.active { "on" } else { "off" };
+            self.message(format!("Selection mode {state}"));
+        }
+
+        if input.mark_waypoint {
+            let reply = self.add_waypoint(None);
+            self.message(reply);
+        }
+
+        if input.toggle_game_mode {
+            let mode = self.player.game_mode.toggled();
+            self.player.set_game_mode(mode);
+            self.message(format!("Game mode: {}", mode.name()));
+            if self.screen.is_some() {
+                self.close_screen();
+            }
+        }
+
+        // E: Creative-Picker bzw. Inventar mit 2×2-Crafting
+        if input.open_inventory && !self.screen_open() {
+            self.screen = Some(match self.player.game_mode {
+                GameMode::Creative => Screen::BlockPicker,
+                GameMode::Survival => Screen::Crafting(CraftGrid::new(2)),
+            });
+        }
+
+        // Über einem offenen Screen bleibt die Hotbar, wie sie ist
+        if !self.screen_open() {
+            if let Some(slot) = input.hotbar_select {
+                self.player.select_slot(slot);
+            }
+            if input.hotbar_scroll != 0 {
+                self.player.scroll_hotbar(input.hotbar_scroll);
+            }
+        }
 
-        // Debug: alle 20 Ticks Raycast-Ergebnis und Position ausgeben
-        if self.tick % 20 == 0 {
-            println!(
+        // Offener Screen schluckt Spiel-Eingaben
+        if self.screen_open() {
+            self.handle_screen_input(input);
+            input = InputState {
+                cursor: input.cursor,
+                ..InputState::default()
+            };
+        }
+
+        // Systeme in fester Reihenfolge; jedes bekommt nur den Zustand, den es braucht
+        let health = self.player.health;
+        let walked =
+            motion::walk(&mut self.player, &self.world, input, self.tick, &mut self.sound_events);
+        let jumped = motion::vertical(
+            &mut self.player,
+            &self.world,
+            input,
+            self.tick,
+            &mut self.last_jump_tick,
+            &mut self.sound_events,
+        );
+        let events = self.world.events_mut();
+        if walked > 0.0 {
+            let player = self.player_name.clone();
+            events.publish(PlayerWalked { player, distance: walked });
+        }
+        if jumped {
+            events.publish(PlayerJumped { player: self.player_name.clone() });
+        }
+        vitals::publish_damage(&self.player, &self.player_name, health, self.world.events_mut());
+
+        let spawn = self.spawn_point();
+        if vitals::tick(&mut self.player, &self.player_name, spawn, self.world.events_mut()) {
+            self.message("You died");
+        }
+
+        // Debug: alle 20 Ticks Position loggen (nicht auf stdout, dort liest der Server)
+        if self.tick.is_multiple_of(20) {
+            log::debug!(
                 "POS x={:.2} y={:.2} z={:.2} vy={:.2} ground={}",
                 self.player.x, self.player.y, self.player.z, self.player.vy, self.player.on_ground
             );
         }
 
-        self.apply_input(input);
+        let outcome = interaction::run(
+            &mut self.player,
+            &self.world,
+            &mut self.selection,
+            &mut self.breaking,
+            &mut self.commands,
+            input,
+        );
+        match outcome {
+            Some(Outcome::OpenScreen(screen)) => self.screen = Some(screen),
+            Some(Outcome::Message(line)) => self.message(line),
+            None => {}
+        }
+
+        let commands = std::mem::take(&mut self.commands);
+        let name = &self.player_name;
+        actions::execute(commands, &mut self.player, name, &mut self.world, &mut self.sound_events);
+        actions::collect_drops(&mut self.player, name, &mut self.world);
+    }
+
+    fn handle_screen_input(&mut self, input: InputState) {
+        if input.close_screen {
+            self.close_screen();
+            return;
+        }
+        if !input.ui_click {
+            return;
+        }
+        match self.screen {
+            Some(Screen::Container(pos)) => self.handle_container_click(pos, input.cursor),
+            Some(Screen::BlockPicker) => self.handle_picker_click(input.cursor),
+            Some(Screen::Crafting(_)) => self.handle_crafting_click(input.cursor),
+            None => {}
+        }
+    }
+
+    fn handle_container_click(&mut self, (x, y, z): (i32, i32, i32), cursor: (f32, f32)) {
+        let Some(BlockEntity::Container(chest)) = self.world.block_entity(x, y, z) else {
+            // Block ist weg -> Screen schließen
+            self.close_screen();
+            return;
+        };
+
+        // Layout muss zum gezeichneten Screen passen (gleiche Bildschirmgröße)
+        let (w, h) = self.screen_size;
+        let layout = ContainerLayout::new(w, h, chest.len(), self.player.inventory.len());
+        let Some(slot) = layout.slot_at(cursor.0, cursor.1) else {
+            return;
+        };
+
+        let cursor = &mut self.cursor_stack;
+        match slot {
+            SlotRef::Player(i) => {
+                if let Some(s) = self.player.inventory.slot_mut(i) {
+                    click_slot(s, cursor);
+                }
+            }
+            SlotRef::Container(i) => {
+                if let Some(BlockEntity::Container(chest)) = self.world.block_entity_mut(x, y, z)
+                    && let Some(s) = chest.slot_mut(i)
+                {
+                    click_slot(s, cursor);
+                }
+            }
+        }
+    }
+
+    /// Picker: Block anklicken füllt den aktiven Hotbar-Slot, Hotbar-Klick wählt den Slot
+    fn handle_picker_click(&mut self, cursor: (f32, f32)) {
+        let (w, h) = self.screen_size;
+        match picker_layout(w, h).slot_at(cursor.0, cursor.1) {
+            Some(SlotRef::Container(i)) => {
+                let slot = self.player.selected_slot;
+                self.player.inventory.set(slot, picker_stack(i));
+            }
+            Some(SlotRef::Player(i)) => self.player.select_slot(i),
+            None => {}
+        }
+    }
+
+    /// Crafting: Raster wie ein Container, Ergebnis-Slot (letzter) nur entnehmen
+    fn handle_crafting_click(&mut self, cursor: (f32, f32)) {
+        let Some(Screen::Crafting(grid)) = &mut self.screen else {
+            return;
+        };
+        let (w, h) = self.screen_size;
+        let layout = ContainerLayout::crafting(w, h, grid.size(), self.player.inventory.len());
+        let Some(slot) = layout.slot_at(cursor.0, cursor.1) else {
+            return;
+        };
+
+        let output = grid.size() * grid.size();
+        match slot {
+            SlotRef::Player(i) => {
+                if let Some(s) = self.player.inventory.slot_mut(i) {
+                    click_slot(s, &mut self.cursor_stack);
+                }
+            }
+            SlotRef::Container(i) if i == output => {
+                let Some(result) = grid.result() else {
+                    return;
+                };
+                // nur auf leeren Cursor oder passenden, nicht vollen Stack
+                let fits = self.cursor_stack.is_none_or(|c| {
+                    c.item == result.item && c.count + result.count <= c.item.max_stack()
+                });
+                if !fits {
+                    return;
+                }
+                match &mut self.cursor_stack {
+                    Some(c) => c.count += result.count,
+                    None => self.cursor_stack = Some(result),
+                }
+                grid.consume();
+            }
+            SlotRef::Container(i) => {
+                if let Some(s) = grid.slots.slot_mut(i) {
+                    click_slot(s, &mut self.cursor_stack);
+                }
+            }
+        }
+    }
+
+    fn close_screen(&mut self) {
+        // Reste im Crafting-Raster und gehaltenen Stack zurück ins Inventar
+        let mut back: Vec<ItemStack> = match self.screen.take() {
+            Some(Screen::Crafting(mut grid)) => grid.drain(),
+            _ => Vec::new(),
+        };
+        back.extend(self.cursor_stack.take());
+        for st in back {
+            if let Some(lost) = self.player.inventory.add(st) {
+                tracing::debug!(?lost, "Inventar voll, verworfen");
+            }
+        }
+    }
+
+    /// Werkzeug im aktiven Hotbar-Slot
+    fn held_tool(&self) -> Option<Tool> {
+        self.player.selected_stack().and_then(|s| s.item.tool())
+    }
 
-        // --- Commands ausführen ---
-        for cmd in self.commands.drain(..) {
-            match cmd {
-                Command::Break { x, y, z } => {
-                    let ok = self.world.break_block(x, y, z);
-                    println!("CMD Break ({},{},{}) -> {}", x, y, z, ok);
+    /// Baut das UI-Overlay für den aktuellen Zustand
+    pub fn build_ui(&mut self, batch: &mut UiBatch, cursor: (f32, f32)) {
+        self.screen_size = (batch.width(), batch.height());
+
+        let inv = &self.player.inventory;
+        match &self.screen {
+            None => {
+                let interaction = self.target().and_then(|hit| hit.block.interaction());
+                draw_crosshair(batch, interaction);
+                if let Some(((x, y, z), ticks)) = self.breaking {
+                    let tool = self.held_tool();
+                    let total = self.world.get_block(x, y, z).break_ticks(tool).max(1);
+                    draw_break_progress(batch, ticks as f32 / total as f32);
                 }
-                Command::Place { x, y, z, block } => {
-                    let ok = self.world.place_block(x, y, z, block);
-                    println!("CMD Place {:?} ({},{},{}) -> {}", block, x, y, z, ok);
+                draw_hotbar(batch, inv, self.player.selected_slot);
+                if self.player.game_mode.has_vitals() {
+                    draw_vitals(batch, self.player.health, self.player.hunger);
+                }
+            }
+            Some(Screen::BlockPicker) => {
+                draw_block_picker(batch, inv, self.player.selected_slot, cursor);
+            }
+            Some(Screen::Crafting(grid)) => {
+                draw_crafting_screen(batch, grid, inv, self.cursor_stack, cursor);
+            }
+            Some(Screen::Container((x, y, z))) => {
+                if let Some(BlockEntity::Container(chest)) = self.world.block_entity(*x, *y, *z) {
+                    draw_container_screen(batch, "Chest", chest, inv, self.cursor_stack, cursor);
                 }
             }
         }
+        draw_console(batch, &self.console, self.tick);
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Seit dem Start dieser Sitzung simulierte Ticks
+    pub fn ticks(&self) -> u64 {
+        self.tick
     }
 
     pub fn world_size(&self) -> i32 {
@@ -267,14 +1102,7 @@ impl Game {
 
     pub fn highest_solid_in_column(&self, x: i32, z: i32) -> Option<Block> {
         let size = self.world.size();
-        for y in (0..size).rev() {
-            if let Some(b) = self.world.get_block_opt(x, y, z) {
-                if b != Block::Air {
-                    return Some(b);
-                }
-            }
-        }
-        None
+        self.world.top_block(x, z, 0, size - 1).map(|(_, b)| b)
     }
 
     pub fn player_xz(&self) -> (f32, f32) {
@@ -286,132 +1114,53 @@ impl Game {
         (dx, dz)
     }
 
+    /// Anvisierter Block in Reichweite samt Seite, Entfernung und Schnittpunkt
+    pub fn target(&self) -> Option<RaycastHit> {
+        let reach = self.player.game_mode.reach();
+        self.world.raycast(self.player.eye_pos(), self.player.dir(), reach)
+    }
+
     pub fn target_block(&self) -> Option<(i32, i32, i32)> {
-        let (sx, sy, sz) = self.player.eye_pos();
-        let (dx, dy, dz) = self.player.dir();
-        self.world
-            .raycast_first_solid(sx, sy, sz, dx, dy, dz, 20.0)
-            .map(|(x, y, z, _b, _n)| (x, y, z))
+        self.target().map(|hit| hit.pos)
     }
 
     pub fn unload_chunk(&mut self, pos: ChunkPos) -> bool {
-        let removed = self.world.unload_chunk(pos);
-        if removed {
-            self.chunk_mesh_cache.remove(&pos);
-        }
-        removed
+        self.world.unload_chunk(pos)
     }
 
-    pub fn maintain_chunk_window(&mut self, radius: i32) {
-        // Spieler-Chunk
-        let player_chunk = ChunkPos {
+    /// Chunks im Kreis um den Spieler (XZ-Entfernung, Y-Ebene des Spielers ±1)
+    pub fn chunk_window(&self, radius: i32) -> HashSet<ChunkPos> {
+        let center = ChunkPos {
             cx: chunk_coord(self.player.x.floor() as i32),
             cy: chunk_coord(self.player.y.floor() as i32),
             cz: chunk_coord(self.player.z.floor() as i32),
         };
-
-        // 1) Alle Chunks im Radius (nur XZ) sicherstellen, Y-Ebene des Spielers
-        for dx in -radius..=radius {
-            for dz in -radius..=radius {
-                let cp = ChunkPos {
-                    cx: player_chunk.cx + dx,
-                    cy: player_chunk.cy,
-                    cz: player_chunk.cz + dz,
-                };
-                self.world.ensure_chunk(cp);
-            }
-        }
-
-        // 2) Außerhalb entladen (nur XZ-Entfernung)
-        let keep_sq = radius * radius;
-        let to_unload: Vec<ChunkPos> = self
-            .world
-            .chunk_positions()
-            .into_iter()
-            .filter(|cp| {
-                let dx = cp.cx - player_chunk.cx;
-                let dz = cp.cz - player_chunk.cz;
-                dx * dx + dz * dz > keep_sq || cp.cy != player_chunk.cy
-            })
-            .collect();
-
-        for cp in to_unload {
-            self.unload_chunk(cp);
-        }
+        chunk_window_around(center, radius)
     }
 
-    pub fn mesh_loaded_chunks_if_dirty(
-        &mut self,
-        screen_width: u32,
-        screen_height: u32,
-    ) -> Option<(Vec<Vertex>, Vec<u32>)> {
-        let cps = self.world.chunk_positions();
-
-        // 1) Dirty Chunks neu meshen (oder wenn noch nicht im Cache)
-        let mut any_changed = false;
-
-        for &cp in &cps {
-            let was_dirty = self.world.take_chunk_dirty(cp);
-            let missing = !self.chunk_mesh_cache.contains_key(&cp);
-
-            if was_dirty || missing {
-                if missing {
-                    // neuer Chunk -> Nachbarn neu meshen lassen, damit Grenz-Faces verschwinden
-                    const NEIGHBORS: [(i32, i32, i32); 6] = [
-                        (1, 0, 0),
-                        (-1, 0, 0),
-                        (0, 1, 0),
-                        (0, -1, 0),
-                        (0, 0, 1),
-                        (0, 0, -1),
-                    ];
-                    for (dx, dy, dz) in NEIGHBORS {
-                        self.world.mark_dirty(ChunkPos {
-                            cx: cp.cx + dx,
-                            cy: cp.cy + dy,
-                            cz: cp.cz + dz,
-                        });
-                    }
-                }
-
-                let (v, i) = mesh_chunk(&self.world, cp);
-                self.chunk_mesh_cache.insert(cp, (v, i));
-                any_changed = true;
-            }
-        }
-
-        // Cache aufraeumen: Meshes zu entladenen Chunks entfernen
-        self.chunk_mesh_cache
-            .retain(|cp, _| self.world.has_chunk(*cp));
+    /// Speicher der Simulation: Welt, Spieler und Zwischenablage
+    pub fn memory_report(&self) -> MemoryReport {
+        let mut report = self.world.memory_report();
+        report.entity_bytes += self.player.heap_bytes();
+        report.entity_bytes += self.clipboard.as_ref().map_or(0, BlockBuffer::heap_bytes);
+        report
+    }
 
-        if !any_changed {
-            return None;
+    /// Genau diese Chunks geladen halten. Die Tickets aller Spieler kommen zusammen herein,
+    /// damit überlappende Fenster jeden Chunk nur einmal laden.
+    pub fn retain_chunks(&mut self, tickets: &HashSet<ChunkPos>) {
+        for &cp in tickets {
+            self.world.ensure_chunk(cp);
         }
-
-        // 2) Aus Cache ein Gesamtmesh bauen (Chunk-FOV-Culling)
-        let aspect = (screen_width.max(1) as f32) / (screen_height.max(1) as f32);
-        let cam_pos = vec3_from(self.player.eye_pos());
-        let cam_dir = vec3_from(self.player.dir()).normalize_or_zero();
-
-        let mut verts: Vec<Vertex> = Vec::new();
-        let mut inds: Vec<u32> = Vec::new();
-
-        for cp in cps {
-            if !chunk_in_frustum(cp, cam_pos, cam_dir, aspect) {
-                continue;
-            }
-            if let Some((v, i)) = self.chunk_mesh_cache.get(&cp) {
-                let base = verts.len() as u32;
-                verts.extend_from_slice(v);
-                inds.extend(i.iter().map(|idx| idx + base));
+        for cp in self.world.chunk_positions() {
+            if !tickets.contains(&cp) {
+                self.unload_chunk(cp);
             }
         }
+    }
 
-        if inds.is_empty() || verts.is_empty() {
-            return Some((Vec::new(), Vec::new())); // signalisiert leeres Mesh zum Zurücksetzen
-        }
-
-        Some((verts, inds))
+    pub fn daylight(&self) -> f32 {
+        self.world.daylight()
     }
 
     pub fn camera_pos_dir(&self) -> ((f32, f32, f32), (f32, f32, f32)) {
@@ -419,75 +1168,74 @@ impl Game {
     }
 }
 
-#[inline]
-fn vec3_from(t: (f32, f32, f32)) -> Vec3 {
-    Vec3::new(t.0, t.1, t.2)
-}
-
-fn chunk_bounds(cp: ChunkPos) -> (Vec3, Vec3, Vec3, f32) {
-    let base = Vec3::new(
-        (cp.cx * CHUNK_SIZE) as f32,
-        (cp.cy * CHUNK_SIZE) as f32,
-        (cp.cz * CHUNK_SIZE) as f32,
-    );
-    let size = Vec3::splat(CHUNK_SIZE as f32);
-    let center = base + size * 0.5;
-    let radius = (size * 0.5).length() * 1.02; // kleine Reserve gegen harte Schnitte
-    (base, base + size, center, radius)
-}
-
-fn chunk_in_frustum(cp: ChunkPos, cam_pos: Vec3, cam_dir: Vec3, aspect: f32) -> bool {
-    let (_min, _max, center, radius) = chunk_bounds(cp);
-
-    // Distanz-Cull gegen Far-Plane (Gfx nutzt 200.0)
-    let to_center = center - cam_pos;
-    let dist = to_center.length();
-    if dist - radius > CAMERA_FAR {
-        return false;
+#[cfg(feature = "scripting")]
+impl Game {
+    /// Skripte aus saves/scripts (neu) laden; Fehler landen in der Konsole
+    fn load_scripts(&mut self) {
+        let Some(mut host) = self.scripts.take() else {
+            return;
+        };
+        if let Err(e) = host.load(Path::new(SCRIPT_DIR), |req| self.serve_script(req)) {
+            self.message(format!("Script error: {e:#}"));
+        }
+        self.scripts = Some(host);
     }
 
-    // Wenn Kamera im Chunk oder sehr nah: immer sichtbar
-    if dist < radius {
-        return true;
+    /// false = kein Skriptbefehl (dann normale Befehle)
+    fn run_script_command(&mut self, line: &str) -> bool {
+        let Some(mut host) = self.scripts.take() else {
+            return false;
+        };
+        let result = host.command(line, |req| self.serve_script(req));
+        self.scripts = Some(host);
+        match result {
+            Some(Err(e)) => self.message(format!("Script error: {e:#}")),
+            Some(Ok(())) => {}
+            None => return false,
+        }
+        true
     }
 
-    let dir_to = to_center / dist.max(1e-6);
-
-    // FOV-Halbwinkel
-    let half_v = 0.5 * CAMERA_FOV_Y;
-    let half_h = (aspect * half_v.tan()).atan(); // tan(h/2) = aspect * tan(v/2)
-
-    // Basisachsen
-    let up = Vec3::Y;
-    let mut right = cam_dir.cross(up);
-    if right.length_squared() < 1e-5 {
-        right = Vec3::new(1.0, 0.0, 0.0); // Fallback wenn Blick senkrecht nach oben/unten
+    fn script_commands(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.scripts.iter().flat_map(|h| h.command_names()).collect();
+        names.sort();
+        names
     }
-    let right = right.normalize();
-
-    let ang_allow = (radius / dist).atan(); // erlaubt etwas Spielraum fuer Chunk-Groesse
 
-    // Horizontal (XZ)
-    let cam_forward_h = (cam_dir - up * cam_dir.dot(up)).normalize_or_zero();
-    let dir_h = (dir_to - up * dir_to.dot(up)).normalize_or_zero();
-    if cam_forward_h.length_squared() > 0.0 && dir_h.length_squared() > 0.0 {
-        let cos_h = cam_forward_h.dot(dir_h).clamp(-1.0, 1.0);
-        let ang_h = cos_h.acos();
-        if ang_h > half_h + ang_allow {
-            return false;
+    /// Anfrage eines laufenden Skripts beantworten
+    fn serve_script(&mut self, req: Request) -> Answer {
+        match req {
+            Request::GetBlock((x, y, z)) => Answer::Block(self.world.get_block(x, y, z)),
+            Request::SetBlock((x, y, z), block) => {
+                let changed = self.world.get_block(x, y, z) != block;
+                if changed {
+                    self.world.set_block(x, y, z, block);
+                }
+                Answer::Changed(changed)
+            }
+            Request::PlayerPos => Answer::Pos(self.player.x, self.player.y, self.player.z),
+            Request::Teleport(x, y, z) => {
+                self.teleport(x, y, z);
+                Answer::None
+            }
+            Request::Message(text) => {
+                self.message(text);
+                Answer::None
+            }
         }
     }
+}
 
-    // Vertikal (Pitch)
-    let cam_forward_v = (cam_dir - right * cam_dir.dot(right)).normalize_or_zero();
-    let dir_v = (dir_to - right * dir_to.dot(right)).normalize_or_zero();
-    if cam_forward_v.length_squared() > 0.0 && dir_v.length_squared() > 0.0 {
-        let cos_v = cam_forward_v.dot(dir_v).clamp(-1.0, 1.0);
-        let ang_v = cos_v.acos();
-        if ang_v > half_v + ang_allow {
-            return false;
-        }
+/// Ohne Skript-Feature: keine Skriptbefehle
+#[cfg(not(feature = "scripting"))]
+impl Game {
+    fn load_scripts(&mut self) {}
+
+    fn run_script_command(&mut self, _line: &str) -> bool {
+        false
     }
 
-    true
+    fn script_commands(&self) -> Vec<&str> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,251 @@
+//! Aktionssystem: führt die Befehle eines Ticks (Abbauen, Platzieren, Benutzen) gegen
+//! Welt und Inventar aus und sammelt Abgefallenes ein.
+
+use crate::block_entity::BlockEntity;
+use crate::command::Command;
+use crate::event::{BlockBroken, BlockPlaced, EventBus, ItemPickedUp};
+use crate::inventory::ItemStack;
+use crate::item::Item;
+use crate::player::Player;
+use crate::sound::{SoundEvent, SoundKind};
+use crate::world::World;
+
+pub(super) fn execute(
+    commands: Vec<Command>,
+    player: &mut Player,
+    name: &str,
+    world: &mut World,
+    sounds: &mut Vec<SoundEvent>,
+) {
+    let tool = player.selected_stack().and_then(|s| s.item.tool());
+    for cmd in commands {
+        match cmd {
+            Command::Break { x, y, z } => {
+                let b = world.get_block(x, y, z);
+                // Creative baut ohne Drops ab
+                let harvest = player.game_mode.consumes_items() && b.harvestable_with(tool);
+                let ok = world.break_block(x, y, z, harvest);
+                tracing::debug!(x, y, z, ok, "Befehl Break");
+                if ok {
+                    sounds.push(SoundEvent::at_block(SoundKind::Break, b.sound(), x, y, z));
+                    world.events_mut().publish(BlockBroken {
+                        player: name.to_string(),
+                        pos: (x, y, z),
+                        block: b,
+                    });
+                }
+            }
+            Command::Place {
+                x,
+                y,
+                z,
+                block,
+                state,
+            } => {
+                // nur platzieren, was der Spieler auch hat
+                let slot = player.selected_slot;
+                let has_item = player
+                    .inventory
+                    .get(slot)
+                    .is_some_and(|st| st.item.placed_block() == Some(block));
+                let ok = has_item && world.place_block(x, y, z, block, state);
+                tracing::debug!(?block, x, y, z, ok, "Befehl Place");
+                if ok {
+                    if player.game_mode.consumes_items() {
+                        player.inventory.remove(slot, 1);
+                    }
+                    sounds.push(SoundEvent::at_block(SoundKind::Place, block.sound(), x, y, z));
+                    world.events_mut().publish(BlockPlaced {
+                        player: name.to_string(),
+                        pos: (x, y, z),
+                        block,
+                    });
+                }
+            }
+            Command::UseItem { x, y, z, item } => {
+                let slot = player.selected_slot;
+                let has_item = player.inventory.get(slot).is_some_and(|st| st.item == item);
+                let ok = has_item
+                    && match item {
+                        Item::Bonemeal => world.apply_bonemeal(x, y, z),
+                        _ => false,
+                    };
+                tracing::debug!(?item, x, y, z, ok, "Befehl Use");
+                if ok && player.game_mode.consumes_items() {
+                    player.inventory.remove(slot, 1);
+                }
+            }
+            Command::Interact { x, y, z } => {
+                let ok = world.interact(x, y, z);
+                tracing::debug!(x, y, z, ok, "Befehl Interact");
+            }
+        }
+    }
+}
+
+/// Inhalt von Containern, die seit dem letzten Tick verschwunden sind (egal wodurch),
+/// fällt an ihrer Stelle heraus; einmal pro Tick
+pub(super) fn spill_containers(world: &mut World) {
+    for (pos, be) in world.take_removed_block_entities() {
+        if let BlockEntity::Container(inv) = be {
+            for st in inv.iter().flatten() {
+                world.drop_item(pos, *st);
+            }
+        }
+    }
+}
+
+/// Abgefallenes direkt einsammeln (noch keine Item-Entities); was nicht ins Inventar
+/// passt, bleibt liegen. Creative sammelt nichts.
+pub(super) fn collect_drops(player: &mut Player, name: &str, world: &mut World) {
+    if !player.game_mode.consumes_items() {
+        return;
+    }
+    let mut drops = world.take_drops();
+    drops.retain_mut(|drop| match pick_up(player, name, world.events_mut(), drop.stack) {
+        Some(rest) => {
+            drop.stack = rest;
+            true
+        }
+        None => false,
+    });
+    world.restore_drops(drops);
+}
+
+/// Ins Inventar, soweit Platz ist; gibt zurück, was nicht mehr passt
+fn pick_up(
+    player: &mut Player,
+    name: &str,
+    events: &mut EventBus,
+    st: ItemStack,
+) -> Option<ItemStack> {
+    let rest = player.inventory.add(st);
+    let count = st.count - rest.map_or(0, |l| l.count);
+    if count > 0 {
+        events.publish(ItemPickedUp {
+            player: name.to_string(),
+            stack: ItemStack::new(st.item, count),
+        });
+    }
+    rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::gamemode::GameMode;
+
+    fn count(player: &Player, item: Item) -> u32 {
+        player.inventory.iter().flatten().filter(|s| s.item == item).map(|s| s.count as u32).sum()
+    }
+
+    #[test]
+    fn breaking_drops_into_the_inventory() {
+        let mut world = World::remote();
+        world.set_block(0, 0, 0, Block::Dirt);
+        let mut sub = world.events().subscribe::<ItemPickedUp>();
+        let mut p = Player::new();
+        let before = count(&p, Item::from(Block::Dirt));
+        let mut sounds = Vec::new();
+
+        execute(vec![Command::Break { x: 0, y: 0, z: 0 }], &mut p, "A", &mut world, &mut sounds);
+        collect_drops(&mut p, "A", &mut world);
+
+        assert_eq!(world.get_block(0, 0, 0), Block::Air);
+        assert_eq!(sounds.len(), 1);
+        assert_eq!(count(&p, Item::from(Block::Dirt)), before + 1);
+        let picked: Vec<_> = world.events().read(&mut sub).cloned().collect();
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].stack, ItemStack::new(Block::Dirt, 1));
+        assert_eq!(picked[0].player, "A");
+    }
+
+    #[test]
+    fn placing_needs_and_uses_the_held_item() {
+        let mut world = World::remote();
+        let mut p = Player::new();
+        p.select_slot(0);
+        let held = p.selected_stack().unwrap();
+        let mut sounds = Vec::new();
+
+        // Falscher Block im Slot: nichts passiert
+        let glass = Command::Place {
+            x: 0,
+            y: 1,
+            z: 0,
+            block: Block::Glass,
+            state: 0,
+        };
+        execute(vec![glass], &mut p, "A", &mut world, &mut sounds);
+        assert_eq!(world.get_block(0, 1, 0), Block::Air);
+
+        let own = Command::Place {
+            x: 0,
+            y: 1,
+            z: 0,
+            block: held.item.placed_block().unwrap(),
+            state: 0,
+        };
+        execute(vec![own], &mut p, "A", &mut world, &mut sounds);
+        assert_eq!(Some(world.get_block(0, 1, 0)), held.item.placed_block());
+        assert_eq!(p.selected_stack().unwrap().count, held.count - 1);
+        assert_eq!(sounds.len(), 1);
+    }
+
+    #[test]
+    fn creative_collects_nothing() {
+        let mut world = World::remote();
+        world.set_block(0, 0, 0, Block::Dirt);
+        let mut p = Player::new();
+        p.set_game_mode(GameMode::Creative);
+        let before = count(&p, Item::from(Block::Dirt));
+        let mut sounds = Vec::new();
+        execute(vec![Command::Break { x: 0, y: 0, z: 0 }], &mut p, "A", &mut world, &mut sounds);
+        collect_drops(&mut p, "A", &mut world);
+        assert_eq!(count(&p, Item::from(Block::Dirt)), before);
+    }
+
+    fn chest_with_stone(world: &mut World) {
+        world.set_block(0, 0, 0, Block::Chest);
+        let Some(BlockEntity::Container(inv)) = world.block_entity_mut(0, 0, 0) else {
+            panic!("Kiste ohne Inventar");
+        };
+        inv.set(0, Some(ItemStack::new(Block::Stone, 5)));
+    }
+
+    #[test]
+    fn removed_chest_spills_its_contents() {
+        // Ohne Break-Befehl entfernt (wie /fill): der Inhalt fällt trotzdem heraus
+        let mut world = World::remote();
+        chest_with_stone(&mut world);
+        world.set_block(0, 0, 0, Block::Air);
+        spill_containers(&mut world);
+
+        let mut p = Player::new();
+        let before = count(&p, Item::from(Block::Stone));
+        collect_drops(&mut p, "A", &mut world);
+        assert_eq!(count(&p, Item::from(Block::Stone)), before + 5);
+        assert!(world.take_drops().is_empty());
+    }
+
+    #[test]
+    fn drops_stay_when_the_inventory_is_full() {
+        let mut world = World::remote();
+        chest_with_stone(&mut world);
+        world.set_block(0, 0, 0, Block::Air);
+        spill_containers(&mut world);
+
+        let mut p = Player::new();
+        for slot in 0..p.inventory.len() {
+            p.inventory.set(slot, Some(ItemStack::new(Block::Glass, 1)));
+        }
+        collect_drops(&mut p, "A", &mut world);
+        assert_eq!(count(&p, Item::from(Block::Stone)), 0);
+
+        // Mit Platz kommt der Stapel doch noch an
+        p.inventory.set(3, None);
+        collect_drops(&mut p, "A", &mut world);
+        assert_eq!(count(&p, Item::from(Block::Stone)), 5);
+    }
+}
@@ -0,0 +1,239 @@
+//! Zielsystem: was der Spieler anvisiert und was seine Klicks daraus machen (Befehle,
+//! Abbaufortschritt, Auswahlecken, Screens). Ausgeführt wird erst im Aktionssystem.
+
+use crate::block::{Block, Interaction};
+use crate::block_entity::BlockEntity;
+use crate::command::Command;
+use crate::crafting::CraftGrid;
+use crate::input::InputState;
+use crate::item::Item;
+use crate::player::Player;
+use crate::selection::Selection;
+use crate::world::{BlockPos, Face, World};
+
+use super::Screen;
+
+/// Survival-Abbau: anvisierter Block und bisherige Ticks
+pub(super) type Breaking = Option<(BlockPos, u32)>;
+
+/// Was das Game außerhalb der Welt tun soll
+pub(super) enum Outcome {
+    OpenScreen(Screen),
+    Message(String),
+}
+
+pub(super) fn run(
+    player: &mut Player,
+    world: &World,
+    selection: &mut Selection,
+    breaking: &mut Breaking,
+    commands: &mut Vec<Command>,
+    input: InputState,
+) -> Option<Outcome> {
+    // 1) Raycast, um Ziel zu bestimmen
+    let look = player.dir();
+    // Rechtsklick mit Essen in der Hand isst (solange man nicht satt ist)
+    let slot = player.selected_slot;
+    if input.place_block
+        && !selection.active
+        && let Some(food) = player.selected_stack().map(|s| s.item.food())
+        && player.eat(food)
+    {
+        player.inventory.remove(slot, 1);
+        return None;
+    }
+
+    let reach = player.game_mode.reach();
+    let Some(hit) = world.raycast(player.eye_pos(), look, reach) else {
+        if input.break_block || input.place_block {
+            tracing::debug!("kein Zielblock");
+        }
+        *breaking = None;
+        return None;
+    };
+    let ((x, y, z), block) = (hit.pos, hit.block);
+
+    // Auswahlmodus: Links = Ecke 1, Rechts = Ecke 2 (nichts wird abgebaut/gebaut)
+    if selection.active && (input.break_block || input.place_block || input.break_held) {
+        *breaking = None;
+        if !(input.break_block || input.place_block) {
+            return None;
+        }
+        let corner = input.place_block as usize;
+        selection.set_corner(corner, (x, y, z));
+        let size = selection.volume().map(|v| format!(" ({v} blocks)"));
+        return Some(Outcome::Message(format!(
+            "Corner {} set to {x} {y} {z}{}",
+            corner + 1,
+            size.unwrap_or_default()
+        )));
+    }
+
+    // Mittelklick: Zielblock in die Hand nehmen
+    if input.pick_block {
+        player.pick_block(Item::for_block(block));
+        return None;
+    }
+
+    // Rechtsklick auf Container öffnet den Screen statt zu platzieren
+    if input.place_block
+        && let Some(BlockEntity::Container(_)) = world.block_entity(x, y, z)
+    {
+        return Some(Outcome::OpenScreen(Screen::Container((x, y, z))));
+    }
+
+    // Werkbank öffnet das 3×3-Raster
+    if input.place_block && block == Block::CraftingTable {
+        return Some(Outcome::OpenScreen(Screen::Crafting(CraftGrid::new(3))));
+    }
+
+    // Knochenmehl auf wachsende Pflanzen statt Platzieren
+    let held = player.selected_stack().map(|s| s.item);
+    if input.place_block
+        && held == Some(Item::Bonemeal)
+        && matches!(block, Block::Wheat | Block::Sapling)
+    {
+        commands.push(Command::UseItem {
+            x,
+            y,
+            z,
+            item: Item::Bonemeal,
+        });
+        return None;
+    }
+
+    // Umschaltbare Blöcke (Türen) werden benutzt statt bebaut
+    if input.place_block && block.interaction() == Some(Interaction::Toggle) {
+        commands.push(Command::Interact { x, y, z });
+        return None;
+    }
+
+    // 2) Commands erzeugen
+    if player.game_mode.instant_break() {
+        if input.break_block {
+            commands.push(Command::Break { x, y, z });
+            tracing::debug!(?block, x, y, z, "abgebaut");
+        }
+    } else if input.break_block || input.break_held {
+        // Abbauzeit: Fortschritt nur, solange derselbe Block anvisiert bleibt
+        let ticks = match *breaking {
+            Some((pos, t)) if pos == (x, y, z) => t + 1,
+            _ => 1,
+        };
+        let tool = player.selected_stack().and_then(|s| s.item.tool());
+        if ticks > block.break_ticks(tool) {
+            commands.push(Command::Break { x, y, z });
+            tracing::debug!(?block, x, y, z, "abgebaut");
+            *breaking = None;
+        } else {
+            *breaking = Some(((x, y, z), ticks));
+        }
+    } else {
+        *breaking = None;
+    }
+
+    if input.place_block {
+        // Block aus dem aktiven Hotbar-Slot; leerer Slot oder Nicht-Block platziert nichts
+        let block = held.and_then(Item::placed_block)?;
+        let (px, py, pz) = hit.adjacent();
+        let normal = hit.face.map_or((0, 0, 0), Face::normal);
+        commands.push(Command::Place {
+            x: px,
+            y: py,
+            z: pz,
+            block,
+            state: block.placement_state(normal, look),
+        });
+        tracing::debug!(?block, x = px, y = py, z = pz, "gesetzt");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamemode::GameMode;
+    use crate::player::PITCH_LIMIT;
+
+    /// Spieler auf einem Steinblock, Blick senkrecht nach unten
+    fn setup() -> (Player, World) {
+        let mut world = World::remote();
+        world.set_block(0, 0, 0, Block::Stone);
+        let mut p = Player::new();
+        (p.x, p.y, p.z) = (0.5, 1.0, 0.5);
+        p.pitch = -PITCH_LIMIT;
+        (p, world)
+    }
+
+    fn click(
+        input: InputState,
+        p: &mut Player,
+        world: &World,
+        breaking: &mut Breaking,
+    ) -> Vec<Command> {
+        let mut commands = Vec::new();
+        let mut selection = Selection::default();
+        run(p, world, &mut selection, breaking, &mut commands, input);
+        commands
+    }
+
+    #[test]
+    fn survival_breaking_takes_time() {
+        let (mut p, world) = setup();
+        let held = InputState {
+            break_held: true,
+            ..InputState::default()
+        };
+        let needed = Block::Stone.break_ticks(p.selected_stack().and_then(|s| s.item.tool()));
+        let mut breaking = None;
+        for _ in 0..needed {
+            assert!(click(held, &mut p, &world, &mut breaking).is_empty());
+        }
+        assert_eq!(breaking, Some(((0, 0, 0), needed)));
+        let done = click(held, &mut p, &world, &mut breaking);
+        assert!(matches!(done[..], [Command::Break { x: 0, y: 0, z: 0 }]));
+        assert_eq!(breaking, None);
+    }
+
+    #[test]
+    fn creative_breaks_instantly() {
+        let (mut p, world) = setup();
+        p.set_game_mode(GameMode::Creative);
+        let input = InputState {
+            break_block: true,
+            ..InputState::default()
+        };
+        let done = click(input, &mut p, &world, &mut None);
+        assert!(matches!(done[..], [Command::Break { x: 0, y: 0, z: 0 }]));
+    }
+
+    #[test]
+    fn placing_targets_the_face_neighbour() {
+        let (mut p, world) = setup();
+        p.select_slot(0);
+        let input = InputState {
+            place_block: true,
+            ..InputState::default()
+        };
+        let done = click(input, &mut p, &world, &mut None);
+        assert!(matches!(done[..], [Command::Place { x: 0, y: 1, z: 0, .. }]));
+    }
+
+    #[test]
+    fn selection_mode_sets_corners_instead() {
+        let (mut p, world) = setup();
+        let mut selection = Selection {
+            active: true,
+            ..Selection::default()
+        };
+        let input = InputState {
+            place_block: true,
+            ..InputState::default()
+        };
+        let mut commands = Vec::new();
+        let out = run(&mut p, &world, &mut selection, &mut None, &mut commands, input);
+        assert!(matches!(out, Some(Outcome::Message(_))));
+        assert!(commands.is_empty());
+        assert_eq!(selection.corners[1], Some((0, 0, 0)));
+    }
+}
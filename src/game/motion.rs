@@ -0,0 +1,224 @@
+//! Bewegungssystem: Laufen, Springen, Fliegen und Fallhöhe eines Spielers. Die Physik
+//! selbst steckt in `Body`; hier kommen Hunger, Fallschaden und Schrittgeräusche dazu.
+
+use crate::input::InputState;
+use crate::player::{Player, SPRINT_MIN_HUNGER};
+use crate::rng::Rng;
+use crate::sound::{SoundEvent, SoundKind};
+use crate::world::World;
+
+/// Erschöpfung pro gesprintetem Block
+const SPRINT_EXHAUSTION: f32 = 0.1;
+/// Zwei Sprünge innerhalb dieser Ticks = Fliegen an/aus
+const FLY_DOUBLE_TAP_TICKS: u64 = 7;
+/// Am Boden gelaufene Blöcke pro Schrittgeräusch
+const STEP_DISTANCE: f32 = 1.6;
+/// Schritte klingen zufällig bis zu so viel höher oder tiefer
+const STEP_PITCH_SPREAD: f32 = 0.08;
+
+/// Genug Hunger zum Sprinten (der Client braucht das für die Vorhersage)
+pub(super) fn can_sprint(player: &Player) -> bool {
+    !player.game_mode.has_vitals() || player.hunger >= SPRINT_MIN_HUNGER
+}
+
+/// Sprinten: nur vorwärts, am Boden oder in der Luft, mit genug Hunger
+fn is_sprinting(player: &Player, input: InputState) -> bool {
+    input.sprint && input.move_fwd && can_sprint(player)
+}
+
+/// Waagerecht laufen; Sprinten kostet Hunger, am Boden gibt es Schrittgeräusche.
+/// Liefert die am Boden gelaufene Strecke (Statistik).
+pub(super) fn walk(
+    player: &mut Player,
+    world: &World,
+    input: InputState,
+    tick: u64,
+    sounds: &mut Vec<SoundEvent>,
+) -> f32 {
+    let sprinting = is_sprinting(player, input);
+    let look = (player.yaw, player.pitch);
+    let mut body = player.body();
+    let dist = body.walk(world, input, look, sprinting);
+    let moved = (body.x - player.x).hypot(body.z - player.z);
+    player.set_body(body);
+    if sprinting && dist > 0.0 {
+        player.exhaust(SPRINT_EXHAUSTION * dist);
+    }
+
+    // nur am Boden; gegen eine Wand laufen bewegt nicht und zählt daher nicht
+    if !player.on_ground || player.flying {
+        return 0.0;
+    }
+    player.step_distance += moved;
+    if player.step_distance >= STEP_DISTANCE {
+        player.step_distance -= STEP_DISTANCE;
+        let mut rng = Rng::new(tick);
+        let pitch = 1.0 + (rng.below(201) as f32 / 100.0 - 1.0) * STEP_PITCH_SPREAD;
+        sounds.push(feet_sound(SoundKind::Step, player, world).with_pitch(pitch));
+    }
+    moved
+}
+
+/// Springen, Fallen und Fliegen. `last_jump` ist der Tick des letzten Sprungs
+/// (Doppeltipp schaltet Fliegen). Sprung und Landung machen ein Geräusch, Landungen prüfen
+/// Fallschaden. Liefert true bei einem Absprung.
+pub(super) fn vertical(
+    player: &mut Player,
+    world: &World,
+    input: InputState,
+    tick: u64,
+    last_jump: &mut Option<u64>,
+    sounds: &mut Vec<SoundEvent>,
+) -> bool {
+    if input.jump && player.game_mode.can_fly() {
+        match *last_jump {
+            Some(t) if tick - t <= FLY_DOUBLE_TAP_TICKS => {
+                player.flying = !player.flying;
+                player.vy = 0.0;
+                *last_jump = None;
+            }
+            _ => *last_jump = Some(tick),
+        }
+    }
+
+    // Untergrund vor dem Absprung merken, danach steht der Spieler nicht mehr darauf
+    let takeoff = feet_sound(SoundKind::Jump, player, world);
+    let was_flying = player.flying;
+    let mut body = player.body();
+    let v = body.vertical(world, input);
+    player.set_body(body);
+
+    if v.jumped {
+        let cost = if is_sprinting(player, input) { 0.2 } else { 0.05 };
+        player.exhaust(cost);
+        sounds.push(takeoff);
+    }
+    if was_flying {
+        player.fall_start_y = player.y;
+    } else if v.landed {
+        sounds.push(feet_sound(SoundKind::Land, player, world));
+        player.land();
+    } else if !player.on_ground {
+        player.fall_start_y = player.fall_start_y.max(player.y);
+    }
+    v.jumped
+}
+
+/// Geräusch des Blocks unter den Füßen (Schritt, Sprung, Landung)
+fn feet_sound(kind: SoundKind, player: &Player, world: &World) -> SoundEvent {
+    let (x, y, z) = (
+        player.x.floor() as i32,
+        (player.y - 0.05).floor() as i32,
+        player.z.floor() as i32,
+    );
+    let group = world.get_block(x, y, z).sound();
+    SoundEvent::at_block(kind, group, x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::Block;
+    use crate::gamemode::GameMode;
+    use crate::player::MAX_HEALTH;
+
+    /// Steinboden auf y = 0 um den Ursprung
+    fn floor() -> World {
+        let mut world = World::remote();
+        for x in -4..4 {
+            for z in -4..4 {
+                world.set_block(x, 0, z, Block::Stone);
+            }
+        }
+        world
+    }
+
+    fn player_at(y: f32) -> Player {
+        let mut p = Player::new();
+        (p.x, p.y, p.z) = (0.5, y, 0.5);
+        p.fall_start_y = y;
+        p
+    }
+
+    #[test]
+    fn walking_makes_step_sounds() {
+        let world = floor();
+        let mut p = player_at(1.0);
+        p.on_ground = true;
+        let fwd = InputState {
+            move_fwd: true,
+            ..InputState::default()
+        };
+        let mut sounds = Vec::new();
+        for tick in 0..20 {
+            walk(&mut p, &world, fwd, tick, &mut sounds);
+        }
+        assert!(!sounds.is_empty());
+        for s in &sounds {
+            assert_eq!((s.kind, s.group), (SoundKind::Step, Block::Stone.sound()));
+            assert!((s.pitch - 1.0).abs() <= STEP_PITCH_SPREAD);
+        }
+    }
+
+    fn fall(player: &mut Player, world: &World) -> Vec<SoundEvent> {
+        let mut sounds = Vec::new();
+        for tick in 0..100 {
+            vertical(player, world, InputState::default(), tick, &mut None, &mut sounds);
+        }
+        sounds
+    }
+
+    #[test]
+    fn landing_makes_a_sound() {
+        let world = floor();
+        let mut p = player_at(2.0);
+        let sounds = fall(&mut p, &world);
+        assert!(p.on_ground);
+        let last = sounds.last().expect("Landegeräusch");
+        assert_eq!((last.kind, last.group), (SoundKind::Land, Block::Stone.sound()));
+        assert_eq!(p.health, MAX_HEALTH);
+    }
+
+    #[test]
+    fn long_fall_hurts() {
+        let world = floor();
+        let mut p = player_at(10.0);
+        fall(&mut p, &world);
+        assert!(p.on_ground);
+        assert!(p.health < MAX_HEALTH);
+    }
+
+    #[test]
+    fn double_jump_toggles_flying_in_creative() {
+        let world = floor();
+        let mut p = player_at(1.0);
+        p.set_game_mode(GameMode::Creative);
+        let jump = InputState {
+            jump: true,
+            ..InputState::default()
+        };
+        let (mut last, mut sounds) = (None, Vec::new());
+        vertical(&mut p, &world, jump, 10, &mut last, &mut sounds);
+        assert!(!p.flying);
+        vertical(&mut p, &world, jump, 10 + FLY_DOUBLE_TAP_TICKS, &mut last, &mut sounds);
+        assert!(p.flying);
+    }
+
+    #[test]
+    fn sprinting_needs_food_and_costs_hunger() {
+        let world = floor();
+        let mut p = player_at(1.0);
+        let sprint = InputState {
+            move_fwd: true,
+            sprint: true,
+            ..InputState::default()
+        };
+        walk(&mut p, &world, sprint, 0, &mut Vec::new());
+        assert!(p.exhaustion > 0.0);
+
+        p.hunger = SPRINT_MIN_HUNGER - 1;
+        assert!(!can_sprint(&p));
+        p.set_game_mode(GameMode::Creative);
+        assert!(can_sprint(&p));
+    }
+}
@@ -0,0 +1,83 @@
+//! Lebenssystem: Heilen und Verhungern, Schaden melden, Tod und Respawn
+
+use crate::event::{EventBus, PlayerDamaged, PlayerDied};
+use crate::player::Player;
+
+/// Leben gegenüber `before` gesunken: für Audio, Anzeige usw. veröffentlichen
+pub(super) fn publish_damage(player: &Player, name: &str, before: u8, events: &mut EventBus) {
+    if player.health < before {
+        events.publish(PlayerDamaged {
+            player: name.to_string(),
+            amount: before - player.health,
+            health: player.health,
+        });
+    }
+}
+
+/// Ein Tick Hunger und Heilen; true = gestorben und schon am `spawn` wiederbelebt
+pub(super) fn tick(
+    player: &mut Player,
+    name: &str,
+    spawn: (f32, f32, f32),
+    events: &mut EventBus,
+) -> bool {
+    let health = player.health;
+    player.tick_vitals();
+    publish_damage(player, name, health, events);
+    if !player.is_dead() {
+        return false;
+    }
+    player.respawn(spawn);
+    events.publish(PlayerDied {
+        player: name.to_string(),
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Subscription;
+    use crate::player::{MAX_HEALTH, SPAWN};
+
+    fn damage(events: &EventBus, sub: &mut Subscription<PlayerDamaged>) -> Vec<u8> {
+        events.read(sub).map(|d| d.amount).collect()
+    }
+
+    #[test]
+    fn starving_is_reported_as_damage() {
+        let mut events = EventBus::new();
+        let mut sub = events.subscribe();
+        let mut p = Player::new();
+        p.hunger = 0;
+        let mut hits = Vec::new();
+        for _ in 0..400 {
+            assert!(!tick(&mut p, "A", SPAWN, &mut events));
+            hits.extend(damage(&events, &mut sub));
+        }
+        // Verhungern lässt immer ein Leben übrig
+        assert_eq!(hits.iter().map(|&a| a as u32).sum::<u32>(), (MAX_HEALTH - p.health) as u32);
+        assert!(!hits.is_empty() && hits.iter().all(|&a| a == 1));
+    }
+
+    #[test]
+    fn death_respawns_at_spawn() {
+        let mut events = EventBus::new();
+        let mut sub = events.subscribe();
+        let mut p = Player::new();
+        p.x = 100.0;
+        p.damage(MAX_HEALTH);
+        publish_damage(&p, "A", MAX_HEALTH, &mut events);
+        assert!(tick(&mut p, "A", (1.0, 2.0, 3.0), &mut events));
+        assert_eq!((p.x, p.y, p.z, p.health), (1.0, 2.0, 3.0, MAX_HEALTH));
+        let hit = events.read(&mut sub).next().cloned();
+        assert_eq!(
+            hit,
+            Some(PlayerDamaged {
+                player: "A".into(),
+                amount: MAX_HEALTH,
+                health: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,71 @@
+/// Spielmodus: bestimmt Verbrauch, Abbauzeit, Fliegen und Reichweite
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameMode {
+    #[default]
+    Survival,
+    Creative,
+}
+
+impl GameMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Survival => "survival",
+            GameMode::Creative => "creative",
+        }
+    }
+
+    /// Für Befehle: Name oder Kurzform ("s", "c", "0", "1")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "survival" | "s" | "0" => Some(GameMode::Survival),
+            "creative" | "c" | "1" => Some(GameMode::Creative),
+            _ => None,
+        }
+    }
+
+    pub fn id(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(GameMode::Survival),
+            1 => Some(GameMode::Creative),
+            _ => None,
+        }
+    }
+
+    /// Platzieren verbraucht Items aus dem Inventar
+    pub fn consumes_items(self) -> bool {
+        self == GameMode::Survival
+    }
+
+    /// Abbauen ohne Abbauzeit (und ohne Drops)
+    pub fn instant_break(self) -> bool {
+        self == GameMode::Creative
+    }
+
+    /// Schaden, Hunger und Tod gibt es nur hier
+    pub fn has_vitals(self) -> bool {
+        self == GameMode::Survival
+    }
+
+    pub fn can_fly(self) -> bool {
+        self == GameMode::Creative
+    }
+
+    /// Maximale Entfernung zum Zielblock
+    pub fn reach(self) -> f32 {
+        match self {
+            GameMode::Survival => 5.0,
+            GameMode::Creative => 8.0,
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            GameMode::Survival => GameMode::Creative,
+            GameMode::Creative => GameMode::Survival,
+        }
+    }
+}
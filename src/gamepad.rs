@@ -0,0 +1,139 @@
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+use rust_game::input::InputState;
+use rust_game::keybinds::Action;
+use rust_game::settings::GamepadSettings;
+
+/// Knöpfe mit fester Aktion (gleiche Schicht wie die Tastatur)
+const BUTTONS: &[(Button, Action)] = &[
+    (Button::South, Action::Jump),
+    (Button::East, Action::Sneak),
+    (Button::North, Action::Inventory),
+    (Button::LeftThumb, Action::Sprint),
+    (Button::Select, Action::Chat),
+];
+
+/// Ab dieser Auslenkung zählt der linke Stick als gedrückte Richtungstaste
+const MOVE_THRESHOLD: f32 = 0.35;
+/// Ab diesem Wert zählt ein analoger Trigger als gedrückt
+const TRIGGER_THRESHOLD: f32 = 0.5;
+
+/// Was ein Poll außer den direkt gesetzten Maus-Ersatzfeldern liefert
+#[derive(Default)]
+pub struct PadFrame {
+    /// Aktionen wie von der Tastatur (gedrückt/losgelassen)
+    pub actions: Vec<(Action, bool)>,
+    /// Blickänderung (Yaw, Pitch) seit dem letzten Poll
+    pub look: (f32, f32),
+    /// Start: wie Escape
+    pub menu: bool,
+}
+
+/// Controller-Eingabe über gilrs (alle verbundenen Pads wirken gleich)
+pub struct Gamepad {
+    gilrs: Gilrs,
+    left: (f32, f32),
+    right: (f32, f32),
+    /// Richtungsaktionen, die der linke Stick gerade hält
+    moving: [bool; 4],
+    triggers: [bool; 2],
+}
+
+impl Gamepad {
+    /// None, wenn das Backend nicht startet (Spiel läuft dann ohne Controller)
+    pub fn new() -> Option<Self> {
+        match Gilrs::new() {
+            Ok(gilrs) => {
+                for (_, pad) in gilrs.gamepads() {
+                    log::info!("Controller: {}", pad.name());
+                }
+                Some(Self {
+                    gilrs,
+                    left: (0.0, 0.0),
+                    right: (0.0, 0.0),
+                    moving: [false; 4],
+                    triggers: [false; 2],
+                })
+            }
+            Err(e) => {
+                log::warn!("Controller-Unterstützung nicht verfügbar: {e}");
+                None
+            }
+        }
+    }
+
+    /// Einmal pro Tick: Events abholen, Trigger/Schultertasten direkt in `input`
+    pub fn poll(
+        &mut self,
+        input: &mut InputState,
+        settings: &GamepadSettings,
+        dt: f32,
+    ) -> PadFrame {
+        let mut frame = PadFrame::default();
+        while let Some(ev) = self.gilrs.next_event() {
+            match ev.event {
+                EventType::ButtonPressed(Button::Start, _) => frame.menu = true,
+                EventType::ButtonPressed(Button::LeftTrigger, _) => input.hotbar_scroll -= 1,
+                EventType::ButtonPressed(Button::RightTrigger, _) => input.hotbar_scroll += 1,
+                EventType::ButtonPressed(button, _) | EventType::ButtonReleased(button, _) => {
+                    let down = matches!(ev.event, EventType::ButtonPressed(..));
+                    if let Some((_, action)) = BUTTONS.iter().find(|(b, _)| *b == button) {
+                        frame.actions.push((*action, down));
+                    }
+                }
+                EventType::ButtonChanged(
+                    button @ (Button::LeftTrigger2 | Button::RightTrigger2),
+                    v,
+                    _,
+                ) => {
+                    let i = (button == Button::RightTrigger2) as usize;
+                    let down = v > TRIGGER_THRESHOLD;
+                    if down != self.triggers[i] {
+                        self.triggers[i] = down;
+                        if i == 1 {
+                            // rechts = abbauen (wie linke Maustaste)
+                            input.break_block |= down;
+                            input.break_held = down;
+                        } else {
+                            input.place_block |= down;
+                            input.place_held = down;
+                        }
+                    }
+                }
+                EventType::AxisChanged(axis, v, _) => match axis {
+                    Axis::LeftStickX => self.left.0 = v,
+                    Axis::LeftStickY => self.left.1 = v,
+                    Axis::RightStickX => self.right.0 = v,
+                    Axis::RightStickY => self.right.1 = v,
+                    _ => {}
+                },
+                EventType::Disconnected => {
+                    self.left = (0.0, 0.0);
+                    self.right = (0.0, 0.0);
+                }
+                _ => {}
+            }
+        }
+
+        // Linker Stick -> Richtungsaktionen, nur bei Änderung (sonst überstimmt er die Tastatur)
+        let (x, y) = self.left;
+        let want = [
+            y > MOVE_THRESHOLD,
+            y < -MOVE_THRESHOLD,
+            x < -MOVE_THRESHOLD,
+            x > MOVE_THRESHOLD,
+        ];
+        let dirs = [Action::MoveForward, Action::MoveBack, Action::MoveLeft, Action::MoveRight];
+        for ((moving, want), dir) in self.moving.iter_mut().zip(want).zip(dirs) {
+            if want != *moving {
+                *moving = want;
+                frame.actions.push((dir, want));
+            }
+        }
+
+        // Rechter Stick: Stick-Y nach oben ist positiv, Pitch nach oben negativ
+        let (x, y) = self.right;
+        frame.look = (settings.look_rate(x) * dt, -settings.look_rate(y) * dt);
+        frame
+    }
+}
@@ -1,95 +1,81 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::mesh::Vertex;
+use rust_game::assets::{Assets, Handle, Shader};
+use rust_game::camera::Camera;
+use rust_game::chunk::ChunkPos;
+use rust_game::client::{ChunkDraw, MeshUpdate};
+use rust_game::mesh::Vertex;
+use rust_game::settings::{DEFAULT_FOV, RENDER_SCALE_MIN};
+use rust_game::ui::{UiBatch, UiVertex};
 use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 use wgpu::util::DeviceExt;
 use winit::dpi::PhysicalSize;
 use winit::window::Window;
 
-fn cube_mesh() -> (Vec<Vertex>, Vec<u32>) {
-    let v = vec![
-        Vertex {
-            pos: [-1.0, -1.0, 1.0],
-            color: [1.0, 0.2, 0.2],
-        }, // 0
-        Vertex {
-            pos: [1.0, -1.0, 1.0],
-            color: [0.2, 1.0, 0.2],
-        }, // 1
-        Vertex {
-            pos: [1.0, 1.0, 1.0],
-            color: [0.2, 0.2, 1.0],
-        }, // 2
-        Vertex {
-            pos: [-1.0, 1.0, 1.0],
-            color: [1.0, 1.0, 0.2],
-        }, // 3
-        Vertex {
-            pos: [-1.0, -1.0, -1.0],
-            color: [0.2, 1.0, 1.0],
-        }, // 4
-        Vertex {
-            pos: [1.0, -1.0, -1.0],
-            color: [1.0, 0.2, 1.0],
-        }, // 5
-        Vertex {
-            pos: [1.0, 1.0, -1.0],
-            color: [0.9, 0.9, 0.9],
-        }, // 6
-        Vertex {
-            pos: [-1.0, 1.0, -1.0],
-            color: [0.3, 0.3, 0.3],
-        }, // 7
-    ];
-
-    let i: Vec<u32> = vec![
-        0, 1, 2, 0, 2, 3, // front
-        1, 5, 6, 1, 6, 2, // right
-        5, 4, 7, 5, 7, 6, // back
-        4, 0, 3, 4, 3, 7, // left
-        3, 2, 6, 3, 6, 7, // top
-        4, 5, 1, 4, 1, 0, // bottom
-    ];
-
-    (v, i)
-}
+const WORLD_SHADER: &str = "shaders/cube.wgsl";
+const UI_SHADER: &str = "shaders/ui.wgsl";
+const POST_SHADER: &str = "shaders/post.wgsl";
+
+/// Versatz eines Chunk-Draws (`vs_chunk`), ein Eintrag pro Instanz
+const CHUNK_OFFSET_LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+    array_stride: size_of::<[f32; 3]>() as wgpu::BufferAddress,
+    step_mode: wgpu::VertexStepMode::Instance,
+    attributes: &wgpu::vertex_attr_array![4 => Float32x3],
+};
 
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 struct CameraUniform {
     view_proj: [[f32; 4]; 4],
+    /// x = Faktor fürs Himmelslicht (Tageszeit), Rest Padding
+    daylight: [f32; 4],
+    /// xyz = Kameraposition, w = Beginn des Nebels
+    eye: [f32; 4],
+    /// rgb = Nebelfarbe (Himmel), w = Ende des Nebels
+    fog: [f32; 4],
 }
 
 impl CameraUniform {
-    fn new() -> Self {
+    fn new(camera: &Camera, aspect: f32, daylight: f32) -> Self {
+        let [r, g, b] = sky_color(daylight).map(|c| c as f32);
         Self {
-            view_proj: Mat4::IDENTITY.to_cols_array_2d(),
+            view_proj: camera.view_proj(aspect).to_cols_array_2d(),
+            daylight: [daylight, 0.0, 0.0, 0.0],
+            eye: camera.pos.extend(camera.far * FOG_START).to_array(),
+            fog: [r, g, b, camera.far * FOG_END],
         }
     }
 }
 
-fn build_view_proj_from(pos: Vec3, dir: Vec3, aspect: f32) -> Mat4 {
-    let eye = pos;
-    let target = pos + dir;
-    let up = Vec3::Y;
+/// Himmelsfarbe bei Tag und Nacht (Clear-Color, nach Tageszeit gemischt)
+const SKY_DAY: [f64; 3] = [0.48, 0.68, 0.95];
+const SKY_NIGHT: [f64; 3] = [0.1, 0.0, 0.2];
+/// Fernebene, bis der Client die Sichtweite meldet
+const DEFAULT_FAR: f32 = 80.0;
+/// Nebel als Anteil der Fernebene: ab hier beginnt er, dort ist nur noch Himmel
+const FOG_START: f32 = 0.6;
+const FOG_END: f32 = 0.95;
 
-    let view = Mat4::look_at_rh(eye, target, up);
-    let proj = Mat4::perspective_rh(45f32.to_radians(), aspect, 0.1, 200.0);
-    proj * view
+fn sky_color(daylight: f32) -> [f64; 3] {
+    let t = daylight as f64;
+    std::array::from_fn(|i| SKY_NIGHT[i] + (SKY_DAY[i] - SKY_NIGHT[i]) * t)
 }
 
+
 struct Depth {
     view: wgpu::TextureView,
     format: wgpu::TextureFormat,
 }
 
 impl Depth {
-    fn create(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> Self {
+    /// In Szenengröße (`Gfx::scene_size`), nicht zwingend Fenstergröße
+    fn create(device: &wgpu::Device, (width, height): (u32, u32)) -> Self {
         let format = wgpu::TextureFormat::Depth32Float;
         let size = wgpu::Extent3d {
-            width: config.width.max(1),
-            height: config.height.max(1),
+            width: width.max(1),
+            height: height.max(1),
             depth_or_array_layers: 1,
         };
 
@@ -109,6 +95,390 @@ impl Depth {
     }
 }
 
+/// Offscreen-Ziel der 3D-Szene bei verringerter Renderauflösung; der Post-Pass zieht es
+/// aufs Fenster
+struct SceneTarget {
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+}
+
+impl SceneTarget {
+    fn create(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        format: wgpu::TextureFormat,
+        (width, height): (u32, u32),
+    ) -> Self {
+        let tex = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("scene"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = tex.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("scene bg"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+        Self { view, bind_group }
+    }
+}
+
+/// Shader aus dem Asset-Verzeichnis; `Gfx::reload_shaders` baut bei neuen Fassungen die
+/// Pipelines neu
+pub struct Shaders {
+    pub world: Handle<Shader>,
+    pub ui: Handle<Shader>,
+    pub post: Handle<Shader>,
+}
+
+impl Shaders {
+    pub fn load(assets: &mut Assets) -> anyhow::Result<Self> {
+        Ok(Self {
+            world: assets.load(WORLD_SHADER)?,
+            ui: assets.load(UI_SHADER)?,
+            post: assets.load(POST_SHADER)?,
+        })
+    }
+
+    fn versions(&self) -> (u32, u32, u32) {
+        (self.world.version(), self.ui.version(), self.post.version())
+    }
+}
+
+/// GPU-Buffer eines Chunk-Meshes
+struct ChunkMesh {
+    vertices: wgpu::Buffer,
+    indices: wgpu::Buffer,
+    index_count: u32,
+}
+
+impl ChunkMesh {
+    /// None bei leerem Mesh (nur Luft oder ganz verdeckt)
+    fn upload(device: &wgpu::Device, vertices: &[Vertex], indices: &[u32]) -> Option<Self> {
+        if vertices.is_empty() || indices.is_empty() {
+            return None;
+        }
+        let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("chunk vertex buffer"),
+            contents: bytemuck::cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_count = indices.len() as u32;
+        let indices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("chunk index buffer"),
+            contents: bytemuck::cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        Some(Self { vertices, indices, index_count })
+    }
+
+    fn bytes(&self) -> u64 {
+        self.vertices.size() + self.indices.size()
+    }
+}
+
+/// Versatz-Buffer für `count` Chunk-Draws (auf Zweierpotenzen aufgerundet)
+fn chunk_offset_buffer(device: &wgpu::Device, count: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("chunk offset buffer"),
+        size: (count.max(1).next_power_of_two() * size_of::<[f32; 3]>()) as u64,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Chunks, Modelle, Linien, Nachthimmel und Schattenflecken aus dem Welt-Shader
+fn world_pipelines(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    depth_format: wgpu::TextureFormat,
+    source: &str,
+) -> [wgpu::RenderPipeline; 5] {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("cube shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+
+    // Chunks mit Versatz pro Draw, Modelle ohne; sonst gleich
+    let opaque = |label, entry_point, buffers: &[wgpu::VertexBufferLayout]| {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(layout),
+
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some(entry_point),
+                buffers,
+                compilation_options: Default::default(),
+            },
+
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: Some(wgpu::Face::Back),
+                front_face: wgpu::FrontFace::Ccw,
+                ..Default::default()
+            },
+
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: depth_format,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        })
+    };
+    let chunks = opaque("chunk pipeline", "vs_chunk", &[Vertex::layout(), CHUNK_OFFSET_LAYOUT]);
+    let pipeline = opaque("cube pipeline", "vs_main", &[Vertex::layout()]);
+
+    // Linien in der Welt (Auswahlbox): gleicher Shader, ohne Depth-Write
+    let lines = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("line pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[Vertex::layout()],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::LineList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: depth_format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: Default::default(),
+            bias: Default::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    });
+
+    // Sterne und Mond: additiv auf die Himmelsfarbe, vor der Welt und ohne Depth
+    let additive = wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let sky = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("sky pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[Vertex::layout()],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_sky"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState {
+                    color: additive,
+                    alpha: additive,
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: depth_format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: Default::default(),
+            bias: Default::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    });
+
+    // Schattenflecken: multipliziert die Farbe darunter, nach der Welt, ohne Depth-Write
+    let multiply = wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::Src,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let shadow = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("shadow pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[Vertex::layout()],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_shadow"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState {
+                    color: multiply,
+                    alpha: multiply,
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: depth_format,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: Default::default(),
+            bias: Default::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    });
+
+    [chunks, pipeline, lines, sky, shadow]
+}
+
+/// UI-Overlay: eigener Pass ohne Depth, mit Alpha
+fn ui_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    source: &str,
+) -> wgpu::RenderPipeline {
+    let ui_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("ui shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("ui pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &ui_shader,
+            entry_point: Some("vs_main"),
+            buffers: &[UiVertex::layout()],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &ui_shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// Post-Pass: Szene aus der Offscreen-Textur aufs Fenster skalieren
+fn post_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    source: &str,
+) -> wgpu::RenderPipeline {
+    let post_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("post shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("post pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &post_shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &post_shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
 pub struct Gfx {
     window: Arc<Window>,
     pub size: PhysicalSize<u32>,
@@ -118,20 +488,72 @@ pub struct Gfx {
     queue: wgpu::Queue,
     config: wgpu::SurfaceConfiguration,
 
+    pipeline_layout: wgpu::PipelineLayout,
     pipeline: wgpu::RenderPipeline,
 
-    vertex_buf: Option<wgpu::Buffer>,
-    index_buf: Option<wgpu::Buffer>,
-    index_count: u32,
+    // Eigene Buffer je Chunk, je Chunk ein Draw mit eigenem Versatz (Instanz im Offset-Buffer)
+    chunk_pipeline: wgpu::RenderPipeline,
+    chunk_meshes: HashMap<ChunkPos, ChunkMesh>,
+    chunk_draws: Vec<ChunkPos>,
+    chunk_offset_buf: wgpu::Buffer,
+
+    // Spielermodelle, jeden Frame neu (gleiche Pipeline wie die Chunks)
+    entity_vertex_buf: Option<wgpu::Buffer>,
+    entity_index_buf: Option<wgpu::Buffer>,
+    entity_index_count: u32,
 
     camera_buf: wgpu::Buffer,
     camera_bg: wgpu::BindGroup,
+    /// Zuletzt gesetzte Kamera (für `project`)
+    view_proj: Mat4,
 
     depth: Depth,
+
+    // Linien in der Welt (Auswahlbox), gleicher Shader, ohne Depth-Write
+    line_pipeline: wgpu::RenderPipeline,
+    line_buf: Option<wgpu::Buffer>,
+    line_count: u32,
+
+    // Sterne und Mond, jeden Frame neu, vor allem anderen gezeichnet
+    sky_pipeline: wgpu::RenderPipeline,
+    sky_vertex_buf: Option<wgpu::Buffer>,
+    sky_index_buf: Option<wgpu::Buffer>,
+    sky_index_count: u32,
+
+    // Schattenflecken unter den Spielern, jeden Frame neu, nach der Welt
+    shadow_pipeline: wgpu::RenderPipeline,
+    shadow_vertex_buf: Option<wgpu::Buffer>,
+    shadow_index_buf: Option<wgpu::Buffer>,
+    shadow_index_count: u32,
+
+    /// Auflösung der 3D-Szene relativ zum Fenster (0..1]; die UI bleibt nativ
+    render_scale: f32,
+    /// Nur bei `render_scale` < 1; sonst geht die Szene direkt ins Fenster
+    scene: Option<SceneTarget>,
+    post_bgl: wgpu::BindGroupLayout,
+    post_sampler: wgpu::Sampler,
+    post_layout: wgpu::PipelineLayout,
+    post_pipeline: wgpu::RenderPipeline,
+
+    // UI-Overlay (eigener Pass ohne Depth)
+    ui_layout: wgpu::PipelineLayout,
+    ui_pipeline: wgpu::RenderPipeline,
+    ui_vertex_buf: Option<wgpu::Buffer>,
+    ui_index_buf: Option<wgpu::Buffer>,
+    ui_index_count: u32,
+
+    /// Himmelslicht-Faktor nach Tageszeit (0..1)
+    daylight: f32,
+    /// Zuletzt gesetzte Kamera; der Nebel endet kurz vor ihrer Fernebene
+    camera: Camera,
+
+    shaders: Shaders,
+    /// Fassungen, aus denen die Pipelines gebaut sind
+    shader_versions: (u32, u32, u32),
 }
 
 impl Gfx {
-    pub async fn new(window: Arc<Window>) -> Self {
+    pub async fn new(window: Arc<Window>, shaders: Shaders) -> Self {
         let size = window.inner_size();
 
         let instance = wgpu::Instance::default();
@@ -182,32 +604,14 @@ impl Gfx {
 
         surface.configure(&device, &config);
 
-        // ----- Mesh -----
-        let (verts, inds) = cube_mesh();
-
-        let vertex_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("vertex buffer"),
-            contents: bytemuck::cast_slice(&verts),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("index buffer"),
-            contents: bytemuck::cast_slice(&inds),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-
-        let index_count = inds.len() as u32;
+        // ----- Chunks -----
+        let chunk_offset_buf = chunk_offset_buffer(&device, 1);
 
         // ----- Camera uniform -----
-        let mut cam_u = CameraUniform::new();
+        let camera = Camera::new(DEFAULT_FOV.to_radians(), DEFAULT_FAR)
+            .looking((3.0, 2.0, 5.0), (-0.5, -0.2, -1.0));
         let aspect = config.width as f32 / config.height as f32;
-        cam_u.view_proj = build_view_proj_from(
-            Vec3::new(3.0, 2.0, 5.0),
-            Vec3::new(-0.5, -0.2, -1.0),
-            aspect,
-        )
-        .to_cols_array_2d();
+        let cam_u = CameraUniform::new(&camera, aspect, 1.0);
 
         let camera_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("camera buffer"),
@@ -219,7 +623,7 @@ impl Gfx {
             label: Some("camera bgl"),
             entries: &[wgpu::BindGroupLayoutEntry {
                 binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
+                visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
                 ty: wgpu::BindingType::Buffer {
                     ty: wgpu::BufferBindingType::Uniform,
                     has_dynamic_offset: false,
@@ -238,61 +642,66 @@ impl Gfx {
             }],
         });
 
-        // ----- Pipeline -----
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("cube shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/cube.wgsl").into()),
-        });
-
+        // ----- Pipelines -----
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("pipeline layout"),
             bind_group_layouts: &[&camera_bgl],
             immediate_size: 0,
         });
 
-        let depth = Depth::create(&device, &config);
-
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("cube pipeline"),
-            layout: Some(&pipeline_layout),
+        let depth = Depth::create(&device, (config.width, config.height));
 
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[Vertex::layout()],
-                compilation_options: Default::default(),
-            },
-
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                cull_mode: Some(wgpu::Face::Back),
-                front_face: wgpu::FrontFace::Ccw,
-                ..Default::default()
-            },
+        let ui_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("ui pipeline layout"),
+            bind_group_layouts: &[],
+            immediate_size: 0,
+        });
 
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: depth.format,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: Default::default(),
-                bias: Default::default(),
-            }),
+        let [chunk_pipeline, pipeline, line_pipeline, sky_pipeline, shadow_pipeline] =
+            world_pipelines(
+                &device,
+                &pipeline_layout,
+                config.format,
+                depth.format,
+                &shaders.world.get().0,
+            );
+        let ui_pipeline = ui_pipeline(&device, &ui_layout, config.format, &shaders.ui.get().0);
 
-            multisample: wgpu::MultisampleState::default(),
-            multiview_mask: None,
-            cache: None,
+        let post_bgl = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("post bgl"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
         });
+        let post_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("post sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let post_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("post pipeline layout"),
+            bind_group_layouts: &[&post_bgl],
+            immediate_size: 0,
+        });
+        let post_pipeline =
+            post_pipeline(&device, &post_layout, config.format, &shaders.post.get().0);
+        let shader_versions = shaders.versions();
 
         Self {
             window,
@@ -301,14 +710,89 @@ impl Gfx {
             device,
             queue,
             config,
+            pipeline_layout,
             pipeline,
-            vertex_buf: Some(vertex_buf),
-            index_buf: Some(index_buf),
-            index_count,
+            chunk_pipeline,
+            chunk_meshes: HashMap::new(),
+            chunk_draws: Vec::new(),
+            chunk_offset_buf,
+            entity_vertex_buf: None,
+            entity_index_buf: None,
+            entity_index_count: 0,
             camera_buf,
             camera_bg,
+            view_proj: Mat4::IDENTITY,
             depth,
+            line_pipeline,
+            line_buf: None,
+            line_count: 0,
+            sky_pipeline,
+            sky_vertex_buf: None,
+            sky_index_buf: None,
+            sky_index_count: 0,
+            shadow_pipeline,
+            shadow_vertex_buf: None,
+            shadow_index_buf: None,
+            shadow_index_count: 0,
+            render_scale: 1.0,
+            scene: None,
+            post_bgl,
+            post_sampler,
+            post_layout,
+            post_pipeline,
+            ui_layout,
+            ui_pipeline,
+            ui_vertex_buf: None,
+            ui_index_buf: None,
+            ui_index_count: 0,
+            daylight: 1.0,
+            camera,
+            shaders,
+            shader_versions,
+        }
+    }
+
+    /// Nach einem Asset-Reload: Pipelines aus geänderten Shadern neu bauen. Kompiliert ein
+    /// Shader nicht, bleibt die alte Pipeline und der Fehler landet im Log.
+    pub fn reload_shaders(&mut self) {
+        let versions = self.shaders.versions();
+        if versions == self.shader_versions {
+            return;
+        }
+        self.shader_versions = versions;
+        let scope = self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let [chunk_pipeline, pipeline, line_pipeline, sky_pipeline, shadow_pipeline] =
+            world_pipelines(
+                &self.device,
+                &self.pipeline_layout,
+                self.config.format,
+                self.depth.format,
+                &self.shaders.world.get().0,
+            );
+        let ui_pipeline = ui_pipeline(
+            &self.device,
+            &self.ui_layout,
+            self.config.format,
+            &self.shaders.ui.get().0,
+        );
+        let post_pipeline = post_pipeline(
+            &self.device,
+            &self.post_layout,
+            self.config.format,
+            &self.shaders.post.get().0,
+        );
+        if let Some(e) = pollster::block_on(scope.pop()) {
+            log::warn!("Shader nicht übernommen: {e}");
+            return;
         }
+        self.chunk_pipeline = chunk_pipeline;
+        self.pipeline = pipeline;
+        self.line_pipeline = line_pipeline;
+        self.sky_pipeline = sky_pipeline;
+        self.shadow_pipeline = shadow_pipeline;
+        self.ui_pipeline = ui_pipeline;
+        self.post_pipeline = post_pipeline;
+        log::info!("Shader neu geladen");
     }
 
     pub fn resize(&mut self, new_size: PhysicalSize<u32>) {
@@ -319,75 +803,228 @@ impl Gfx {
         self.config.width = new_size.width;
         self.config.height = new_size.height;
         self.surface.configure(&self.device, &self.config);
-
-        self.depth = Depth::create(&self.device, &self.config);
+        self.create_scene_targets();
 
         // Kamera-Aspect aktualisieren
-        let mut cam_u = CameraUniform::new();
-        let aspect = self.config.width as f32 / self.config.height as f32;
-        cam_u.view_proj = build_view_proj_from(
-            Vec3::new(3.0, 2.0, 5.0),
-            Vec3::new(-0.5, -0.2, -1.0),
-            aspect,
-        )
-        .to_cols_array_2d();
+        self.set_camera(self.camera);
+    }
+
+    /// Renderauflösung der 3D-Szene als Anteil der Fenstergröße; baut die Ziele nur bei
+    /// Änderung neu
+    pub fn set_render_scale(&mut self, scale: f32) {
+        let scale = scale.clamp(RENDER_SCALE_MIN, 1.0);
+        if scale != self.render_scale {
+            self.render_scale = scale;
+            self.create_scene_targets();
+            log::info!("Renderauflösung {:?} ({:.0}%)", self.scene_size(), scale * 100.0);
+        }
+    }
+
+    /// Pixel der 3D-Szene
+    fn scene_size(&self) -> (u32, u32) {
+        let scaled = |n: u32| ((n as f32 * self.render_scale).round() as u32).max(1);
+        (scaled(self.config.width), scaled(self.config.height))
+    }
 
+    fn create_scene_targets(&mut self) {
+        let size = self.scene_size();
+        self.depth = Depth::create(&self.device, size);
+        self.scene = (self.render_scale < 1.0).then(|| {
+            let (layout, sampler) = (&self.post_bgl, &self.post_sampler);
+            SceneTarget::create(&self.device, layout, sampler, self.config.format, size)
+        });
+    }
+
+    /// Sichtfeld und Fernebene kommen mit der Kamera (`Client::camera`)
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+        let aspect = self.config.width as f32 / self.config.height as f32;
+        self.view_proj = camera.view_proj(aspect);
+        let cam_u = CameraUniform::new(&camera, aspect, self.daylight);
         self.queue
             .write_buffer(&self.camera_buf, 0, bytemuck::bytes_of(&cam_u));
     }
 
-    pub fn set_camera(&mut self, pos: (f32, f32, f32), dir: (f32, f32, f32)) {
-        let pos = Vec3::new(pos.0, pos.1, pos.2);
-        let mut dir = Vec3::new(dir.0, dir.1, dir.2);
+    /// Weltpunkt in Pixelkoordinaten der UI; None = hinter der Kamera oder außerhalb
+    pub fn project(&self, pos: (f32, f32, f32)) -> Option<(f32, f32)> {
+        let clip = self.view_proj * Vec3::new(pos.0, pos.1, pos.2).extend(1.0);
+        if clip.w <= 0.0 {
+            return None;
+        }
+        let (x, y) = (clip.x / clip.w, clip.y / clip.w);
+        if x.abs() > 1.0 || y.abs() > 1.0 {
+            return None;
+        }
+        let (w, h) = (self.config.width as f32, self.config.height as f32);
+        Some(((x + 1.0) * 0.5 * w, (1.0 - y) * 0.5 * h))
+    }
 
-        // Schutz: nie Nullrichtung
-        if dir.length_squared() < 1e-6 {
-            dir = Vec3::new(0.0, 0.0, -1.0);
-        } else {
-            dir = dir.normalize();
+    /// Wirkt ab dem nächsten `set_camera`
+    pub fn set_daylight(&mut self, daylight: f32) {
+        self.daylight = daylight.clamp(0.0, 1.0);
+    }
+
+    /// Belegter Speicher aller Vertex-, Index- und Uniform-Puffer
+    pub fn buffer_bytes(&self) -> u64 {
+        let optional = [
+            &self.entity_vertex_buf,
+            &self.entity_index_buf,
+            &self.line_buf,
+            &self.sky_vertex_buf,
+            &self.sky_index_buf,
+            &self.shadow_vertex_buf,
+            &self.shadow_index_buf,
+            &self.ui_vertex_buf,
+            &self.ui_index_buf,
+        ];
+        let sum: u64 = optional.iter().filter_map(|b| b.as_ref()).map(wgpu::Buffer::size).sum();
+        let chunks: u64 = self.chunk_meshes.values().map(ChunkMesh::bytes).sum();
+        sum + chunks + self.camera_buf.size() + self.chunk_offset_buf.size()
+    }
+
+    /// Neu gebaute Chunk-Meshes hochladen (je Chunk eigene Buffer), entfernte freigeben
+    pub fn update_chunk_meshes(&mut self, update: MeshUpdate) {
+        for cp in update.removed {
+            self.chunk_meshes.remove(&cp);
+        }
+        for (cp, vertices, indices) in update.uploads {
+            match ChunkMesh::upload(&self.device, &vertices, &indices) {
+                Some(mesh) => self.chunk_meshes.insert(cp, mesh),
+                None => self.chunk_meshes.remove(&cp),
+            };
         }
+    }
 
-        let aspect = self.config.width as f32 / self.config.height as f32;
+    /// Zu zeichnende Chunks samt Versatz; schreibt nur die Versätze, die Meshes bleiben
+    pub fn set_chunk_draws(&mut self, draws: &[ChunkDraw]) {
+        self.chunk_draws = draws.iter().map(|d| d.pos).collect();
+        let offsets: Vec<[f32; 3]> = draws.iter().map(|d| [0.0, -d.sink, 0.0]).collect();
+        let bytes = size_of_val(&offsets[..]) as u64;
+        if bytes > self.chunk_offset_buf.size() {
+            self.chunk_offset_buf = chunk_offset_buffer(&self.device, offsets.len());
+        }
+        self.queue.write_buffer(&self.chunk_offset_buf, 0, bytemuck::cast_slice(&offsets));
+    }
 
-        let mut cam_u = CameraUniform::new();
-        cam_u.view_proj = build_view_proj_from(pos, dir, aspect).to_cols_array_2d();
+    /// Dynamische Modelle (andere Spieler); leer = keine
+    pub fn set_entities(&mut self, vertices: &[Vertex], indices: &[u32]) {
+        self.entity_index_count = indices.len() as u32;
+        if vertices.is_empty() || indices.is_empty() {
+            self.entity_vertex_buf = None;
+            self.entity_index_buf = None;
+            self.entity_index_count = 0;
+            return;
+        }
+        self.entity_vertex_buf = Some(self.device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("entity vertex buffer"),
+                contents: bytemuck::cast_slice(vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            },
+        ));
+        self.entity_index_buf = Some(self.device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("entity index buffer"),
+                contents: bytemuck::cast_slice(indices),
+                usage: wgpu::BufferUsages::INDEX,
+            },
+        ));
+    }
 
-        self.queue
-            .write_buffer(&self.camera_buf, 0, bytemuck::bytes_of(&cam_u));
+    /// Sterne und Mond (siehe `sky::sky_mesh`); leer = keine
+    pub fn set_sky(&mut self, vertices: &[Vertex], indices: &[u32]) {
+        self.sky_index_count = indices.len() as u32;
+        if vertices.is_empty() || indices.is_empty() {
+            self.sky_vertex_buf = None;
+            self.sky_index_buf = None;
+            self.sky_index_count = 0;
+            return;
+        }
+        self.sky_vertex_buf = Some(self.device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("sky vertex buffer"),
+                contents: bytemuck::cast_slice(vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            },
+        ));
+        self.sky_index_buf = Some(self.device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("sky index buffer"),
+                contents: bytemuck::cast_slice(indices),
+                usage: wgpu::BufferUsages::INDEX,
+            },
+        ));
     }
 
-    pub fn set_mesh(&mut self, vertices: &[Vertex], indices: &[u32]) {
-        // Schutz: leeres Mesh -> Buffer entfernen, nichts zeichnen
+    /// Schattenflecken (siehe `player_model::shadow_mesh`); leer = keine
+    pub fn set_shadows(&mut self, vertices: &[Vertex], indices: &[u32]) {
+        self.shadow_index_count = indices.len() as u32;
         if vertices.is_empty() || indices.is_empty() {
-            self.vertex_buf = None;
-            self.index_buf = None;
-            self.index_count = 0;
+            self.shadow_vertex_buf = None;
+            self.shadow_index_buf = None;
+            self.shadow_index_count = 0;
+            return;
+        }
+        self.shadow_vertex_buf = Some(self.device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("shadow vertex buffer"),
+                contents: bytemuck::cast_slice(vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            },
+        ));
+        self.shadow_index_buf = Some(self.device.create_buffer_init(
+            &wgpu::util::BufferInitDescriptor {
+                label: Some("shadow index buffer"),
+                contents: bytemuck::cast_slice(indices),
+                usage: wgpu::BufferUsages::INDEX,
+            },
+        ));
+    }
+
+    /// Linienpaare (LineList) für Overlays in der Welt; leer = keine
+    pub fn set_lines(&mut self, vertices: &[Vertex]) {
+        self.line_count = vertices.len() as u32;
+        self.line_buf = (!vertices.is_empty()).then(|| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("line vertex buffer"),
+                    contents: bytemuck::cast_slice(vertices),
+                    usage: wgpu::BufferUsages::VERTEX,
+                })
+        });
+    }
+
+    pub fn set_ui(&mut self, batch: &UiBatch) {
+        if batch.is_empty() {
+            self.ui_vertex_buf = None;
+            self.ui_index_buf = None;
+            self.ui_index_count = 0;
             return;
         }
 
         let vb = self
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("dynamic vertex buffer"),
-                contents: bytemuck::cast_slice(vertices),
+                label: Some("ui vertex buffer"),
+                contents: bytemuck::cast_slice(&batch.verts),
                 usage: wgpu::BufferUsages::VERTEX,
             });
 
         let ib = self
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("dynamic index buffer"),
-                contents: bytemuck::cast_slice(indices),
+                label: Some("ui index buffer"),
+                contents: bytemuck::cast_slice(&batch.inds),
                 usage: wgpu::BufferUsages::INDEX,
             });
 
-        self.vertex_buf = Some(vb);
-        self.index_buf = Some(ib);
-        self.index_count = indices.len() as u32;
+        self.ui_vertex_buf = Some(vb);
+        self.ui_index_buf = Some(ib);
+        self.ui_index_count = batch.inds.len() as u32;
     }
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        eprintln!("RENDER");
+        let _span = tracing::trace_span!("render").entered();
 
         let frame = self.surface.get_current_texture()?;
         let view = frame
@@ -400,17 +1037,20 @@ impl Gfx {
                 label: Some("render encoder"),
             });
 
+        let sky = sky_color(self.daylight);
+        let scene_view = self.scene.as_ref().map_or(&view, |scene| &scene.view);
+
         {
             let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("render pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
+                    view: scene_view,
                     resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.0,
-                            b: 0.2,
+                            r: sky[0],
+                            g: sky[1],
+                            b: sky[2],
                             a: 1.0,
                         }),
                         store: wgpu::StoreOp::Store,
@@ -430,19 +1070,98 @@ impl Gfx {
                 multiview_mask: None,
             });
 
-            rp.set_pipeline(&self.pipeline);
             rp.set_bind_group(0, &self.camera_bg, &[]);
-            if self.index_count > 0 {
-                if let (Some(vb), Some(ib)) = (&self.vertex_buf, &self.index_buf) {
+            if let (Some(vb), Some(ib)) = (&self.sky_vertex_buf, &self.sky_index_buf) {
+                rp.set_pipeline(&self.sky_pipeline);
+                rp.set_vertex_buffer(0, vb.slice(..));
+                rp.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                rp.draw_indexed(0..self.sky_index_count, 0, 0..1);
+            }
+
+            rp.set_pipeline(&self.chunk_pipeline);
+            rp.set_vertex_buffer(1, self.chunk_offset_buf.slice(..));
+            for (i, cp) in (0..).zip(&self.chunk_draws) {
+                if let Some(mesh) = self.chunk_meshes.get(cp) {
+                    rp.set_vertex_buffer(0, mesh.vertices.slice(..));
+                    rp.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
+                    rp.draw_indexed(0..mesh.index_count, 0, i..i + 1);
+                }
+            }
+            rp.set_pipeline(&self.pipeline);
+            if let (Some(vb), Some(ib)) = (&self.entity_vertex_buf, &self.entity_index_buf) {
                 rp.set_vertex_buffer(0, vb.slice(..));
                 rp.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
-                rp.draw_indexed(0..self.index_count, 0, 0..1);
+                rp.draw_indexed(0..self.entity_index_count, 0, 0..1);
             }
+            if let (Some(vb), Some(ib)) = (&self.shadow_vertex_buf, &self.shadow_index_buf) {
+                rp.set_pipeline(&self.shadow_pipeline);
+                rp.set_vertex_buffer(0, vb.slice(..));
+                rp.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                rp.draw_indexed(0..self.shadow_index_count, 0, 0..1);
+            }
+
+            if let Some(lb) = &self.line_buf {
+                rp.set_pipeline(&self.line_pipeline);
+                rp.set_vertex_buffer(0, lb.slice(..));
+                rp.draw(0..self.line_count, 0..1);
             }
         }
 
+        // Verkleinerte Szene aufs Fenster strecken
+        if let Some(scene) = &self.scene {
+            let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("post pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None,
+            });
+
+            rp.set_pipeline(&self.post_pipeline);
+            rp.set_bind_group(0, &scene.bind_group, &[]);
+            rp.draw(0..3, 0..1);
+        }
+
+        // UI-Overlay über die 3D-Szene
+        if self.ui_index_count > 0
+            && let (Some(vb), Some(ib)) = (&self.ui_vertex_buf, &self.ui_index_buf)
+        {
+            let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("ui pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None,
+            });
+
+            rp.set_pipeline(&self.ui_pipeline);
+            rp.set_vertex_buffer(0, vb.slice(..));
+            rp.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+            rp.draw_indexed(0..self.ui_index_count, 0, 0..1);
+        }
+
         self.queue.submit(Some(encoder.finish()));
         frame.present();
+        #[cfg(feature = "profiler")]
+        tracy_client::frame_mark();
         Ok(())
     }
 }
@@ -0,0 +1,167 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::game::Game;
+use crate::lan::{Announcer, Beacon};
+use crate::metrics;
+use crate::net;
+use crate::protocol::PROTOCOL_VERSION;
+use crate::server::Server;
+use crate::settings::{Settings, SETTINGS_FILE};
+
+/// Ab diesem Rückstand meldet der Server, dass er nicht mitkommt
+const LAG_WARN: Duration = Duration::from_secs(2);
+/// Operatoren, ein Name pro Zeile (im Weltordner)
+const OPS_FILE: &str = "ops.txt";
+
+/// Dedizierter Server ohne Fenster und GPU (--headless). Jede Zeile auf stdin ist ein
+/// Konsolenbefehl (der '/' ist optional); dazu `status`, `op`/`deop <name>`, und `stop`
+/// speichert und beendet.
+/// Mit `port` können Netzwerk-Clients mitspielen (der erste steuert den Host-Spieler), und
+/// der Server ruft sich im LAN aus. `metrics_port` stellt die Messwerte per HTTP bereit.
+pub fn run(
+    dir: &Path,
+    seed: Option<u64>,
+    tick_dt: Duration,
+    port: Option<u16>,
+    metrics_port: Option<u16>,
+) {
+    let settings = Settings::load(Path::new(SETTINGS_FILE));
+    let mut server = Server::detached(Game::new(dir, seed), settings.graphics.view_distance);
+    let ops_path = dir.join(OPS_FILE);
+    for name in fs::read_to_string(&ops_path).unwrap_or_default().lines() {
+        if !name.trim().is_empty() {
+            server.set_operator(name.trim(), true);
+        }
+    }
+    log::info!("Headless: Welt {} mit {:.0} TPS", dir.display(), 1.0 / tick_dt.as_secs_f64());
+    let incoming = port.and_then(|port| match net::listen(port, server.world_params()) {
+        Ok(rx) => {
+            log::info!("Headless: warte auf Spieler an Port {port}");
+            Some(rx)
+        }
+        Err(e) => {
+            log::error!("Headless: kein Netzwerk: {e:#}");
+            None
+        }
+    });
+    if let Some(port) = metrics_port {
+        match metrics::serve(port) {
+            Ok(()) => log::info!("Headless: Messwerte unter http://0.0.0.0:{port}/metrics"),
+            Err(e) => log::error!("Headless: keine Messwerte: {e:#}"),
+        }
+    }
+    let world_name = server.world_params().name;
+    let mut announcer = incoming.as_ref().and_then(|_| match Announcer::new() {
+        Ok(a) => Some(a),
+        Err(e) => {
+            log::warn!("Headless: keine LAN-Suche: {e:#}");
+            None
+        }
+    });
+
+    // stdin blockiert, daher eigener Thread; bei EOF läuft die Welt einfach weiter
+    let (tx, rx) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut printed = server.game().console().printed();
+    let mut last_autosave = Instant::now();
+    let mut next_tick = Instant::now();
+    // Gemessene TPS über die letzte volle Sekunde
+    let (mut window_start, mut window_ticks, mut tps) = (Instant::now(), 0u32, 0.0f64);
+    loop {
+        while let Some(conn) = incoming.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            if let Err(e) = server.attach(conn.hello.name, conn.to_client, conn.from_client) {
+                log::warn!("Headless: Spieler abgelehnt: {e:#}");
+            }
+        }
+
+        let mut stop = false;
+        while let Ok(line) = rx.try_recv() {
+            let line = line.trim();
+            if let Some((cmd @ ("op" | "deop"), name)) = line.split_once(' ') {
+                server.set_operator(name.trim(), cmd == "op");
+                let mut ops: Vec<&str> = server.operators().collect();
+                ops.sort();
+                if let Err(e) = fs::write(&ops_path, ops.join("\n") + "\n") {
+                    log::warn!("{} nicht gespeichert: {e}", ops_path.display());
+                }
+                println!("Operators: {}", ops.join(", "));
+                continue;
+            }
+            let game = server.game_mut();
+            match line {
+                "" => {}
+                // Erst die schon eingereihten Befehle ausführen
+                "stop" | "/stop" => stop = true,
+                "status" | "/status" => println!(
+                    "tick {}, {} chunks loaded, {tps:.1} TPS (target {:.0})",
+                    game.ticks(),
+                    game.world().chunk_positions().len(),
+                    1.0 / tick_dt.as_secs_f64()
+                ),
+                _ if line.starts_with('/') => game.queue_line(line.to_string()),
+                _ => game.queue_line(format!("/{line}")),
+            }
+        }
+
+        server.tick();
+        // Ohne Fenster ist ein Tick ein Frame im Profiler
+        #[cfg(feature = "profiler")]
+        tracy_client::frame_mark();
+        if let (Some(announcer), Some(port)) = (&mut announcer, port) {
+            announcer.tick(&Beacon {
+                name: world_name.clone(),
+                players: server.player_count() as u16,
+                version: PROTOCOL_VERSION,
+                port,
+            });
+        }
+        let game = server.game_mut();
+        for line in game.console().lines_since(printed) {
+            println!("{line}");
+        }
+        printed = game.console().printed();
+        if stop {
+            game.save();
+            log::info!("Headless: gespeichert, beende");
+            return;
+        }
+
+        let minutes = settings.gameplay.autosave_minutes;
+        if minutes > 0 && last_autosave.elapsed() >= Duration::from_secs(60 * minutes as u64) {
+            last_autosave = Instant::now();
+            game.save();
+        }
+
+        window_ticks += 1;
+        if window_start.elapsed() >= Duration::from_secs(1) {
+            tps = window_ticks as f64 / window_start.elapsed().as_secs_f64();
+            (window_start, window_ticks) = (Instant::now(), 0);
+        }
+
+        next_tick += tick_dt;
+        let now = Instant::now();
+        if next_tick > now {
+            thread::sleep(next_tick - now);
+        } else {
+            let behind = now - next_tick;
+            if behind >= LAG_WARN {
+                log::warn!("Server kommt nicht mit: {} ms im Rückstand", behind.as_millis());
+            }
+            // Hinterher: nicht alle verpassten Ticks nachholen
+            next_tick = now;
+        }
+    }
+}
@@ -1,24 +1,284 @@
+use anyhow::Result;
+use winit::event::MouseButton;
+
+use crate::keybinds::Action;
+use crate::menu::AppState;
+use crate::serial::{ByteReader, ByteWriter};
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct InputState {
     // --- One-shot actions (werden nach Tick zurückgesetzt) ---
     pub break_block: bool,
     pub place_block: bool,
     pub jump: bool,
-    pub toggle_mouse_lock: bool,
+    /// Linksklick in einem offenen Screen (statt Abbauen)
+    pub ui_click: bool,
+    pub close_screen: bool,
+    /// Mittelklick: angezielten Block in die Hotbar holen
+    pub pick_block: bool,
+    /// E ohne offenen Screen (Creative: Block-Picker)
+    pub open_inventory: bool,
+    /// Debug-Ansicht der Lichtwerte umschalten
+    pub toggle_light_debug: bool,
+    /// Zifferntaste 1..9 -> Hotbar-Slot 0..8
+    pub hotbar_select: Option<usize>,
+    /// Mausrad-Schritte seit dem letzten Tick (positiv = nach rechts)
+    pub hotbar_scroll: i32,
+    /// Survival <-> Creative (bis es eine Konsole gibt)
+    pub toggle_game_mode: bool,
+    /// Auswahlmodus (Zauberstab) an/aus
+    pub toggle_wand: bool,
+    /// Wegmarke an der aktuellen Position setzen
+    pub mark_waypoint: bool,
 
     // --- Held keys (bleiben true solange gedrückt) ---
     pub move_fwd: bool,
     pub move_back: bool,
     pub move_left: bool,
     pub move_right: bool,
+    /// Linke Maustaste gehalten (Abbauen mit Abbauzeit)
+    pub break_held: bool,
+    /// Rechte Maustaste gehalten (Bauen wiederholt, siehe `HoldRepeat`)
+    pub place_held: bool,
+    /// Leertaste gehalten (Fliegen: steigen)
+    pub jump_held: bool,
+    /// Shift gehalten (Fliegen: sinken)
+    pub sneak: bool,
+    /// Strg gehalten: Sprinten (kostet Hunger)
+    pub sprint: bool,
+
+    /// Mausposition in Fensterpixeln (für Screens)
+    pub cursor: (f32, f32),
 }
 
 impl InputState {
+    /// Taste einer Aktion gedrückt/losgelassen (Aktionen mit Spielzustand erledigt der Aufrufer)
+    pub fn apply(&mut self, action: Action, down: bool) {
+        match action {
+            Action::MoveForward => self.move_fwd = down,
+            Action::MoveBack => self.move_back = down,
+            Action::MoveLeft => self.move_left = down,
+            Action::MoveRight => self.move_right = down,
+            Action::Jump => {
+                self.jump |= down && !self.jump_held;
+                self.jump_held = down;
+            }
+            Action::Sneak => self.sneak = down,
+            Action::Sprint => self.sprint = down,
+            Action::ToggleGameMode => self.toggle_game_mode |= down,
+            Action::LightDebug => self.toggle_light_debug |= down,
+            Action::Wand => self.toggle_wand |= down,
+            Action::Waypoint => self.mark_waypoint |= down,
+            _ => {
+                if down && let Some(slot) = action.hotbar_slot() {
+                    self.hotbar_select = Some(slot);
+                }
+            }
+        }
+    }
+
+    /// Wie `apply`, mit Blick auf offene Screens: die Inventartaste schließt einen offenen
+    /// Screen, statt das Inventar zu öffnen (so verteilt das Fenster die Tasten)
+    pub fn apply_with_screen(&mut self, action: Action, down: bool, screen_open: bool) {
+        match action {
+            Action::Inventory if down && screen_open => self.close_screen = true,
+            Action::Inventory if down => self.open_inventory = true,
+            _ => self.apply(action, down),
+        }
+    }
+
+    /// Escape (bzw. Start am Controller): offenen Screen schließen oder eine Menüebene
+    /// wechseln. Gleiche Regel für das Fenster und den `InputDriver`.
+    pub fn escape(&mut self, state: AppState, screen_open: bool) -> AppState {
+        if state.ticking() && screen_open {
+            self.close_screen = true;
+            state
+        } else {
+            state.on_escape()
+        }
+    }
+
+    /// Aktion einer Taste für Fenster und `InputDriver`: Chat und Befehl öffnen im laufenden
+    /// Spiel die Konsole und liefern deren Präfix, alles andere geht an `apply_with_screen`
+    pub fn route_action(
+        &mut self,
+        action: Action,
+        down: bool,
+        state: AppState,
+        screen_open: bool,
+    ) -> Option<&'static str> {
+        match action {
+            Action::Chat | Action::Command if down && state.ticking() && !screen_open => {
+                self.release_held();
+                Some(if action == Action::Command { "/" } else { "" })
+            }
+            _ => {
+                self.apply_with_screen(action, down, screen_open);
+                None
+            }
+        }
+    }
+
+    /// Maustaste nach Fokus: über der UI zählt nur der Linksklick, in der Welt bauen und
+    /// abbauen die Tasten
+    pub fn mouse_button(&mut self, focus: Focus, button: MouseButton, down: bool) {
+        match (focus, button, down) {
+            (_, MouseButton::Left, false) => self.break_held = false,
+            (_, MouseButton::Right, false) => self.place_held = false,
+            (Focus::Ui, MouseButton::Left, true) => self.ui_click = true,
+            (Focus::World, MouseButton::Left, true) => {
+                self.break_block = true;
+                self.break_held = true;
+            }
+            (Focus::World, MouseButton::Right, true) => {
+                self.place_block = true;
+                self.place_held = true;
+            }
+            (Focus::World, MouseButton::Middle, true) => self.pick_block = true,
+            _ => {}
+        }
+    }
+
+    /// Mausrad (positiv = nach oben); über Menüs und Screens blättert es nicht in der Hotbar
+    pub fn scroll(&mut self, focus: Focus, y: f32) {
+        if focus == Focus::World {
+            // Rad nach unten = nächster Slot
+            self.hotbar_scroll += (y < 0.0) as i32 - (y > 0.0) as i32;
+        }
+    }
+
+    /// Nur was nicht den Spieler steuert (freie Kamera: der Spieler bleibt stehen)
+    pub fn detached(&self) -> Self {
+        Self {
+            toggle_light_debug: self.toggle_light_debug,
+            cursor: self.cursor,
+            ..Self::default()
+        }
+    }
+
+    /// Gehaltene Tasten loslassen (Texteingabe übernimmt die Tastatur)
+    pub fn release_held(&mut self) {
+        self.move_fwd = false;
+        self.move_back = false;
+        self.move_left = false;
+        self.move_right = false;
+        self.break_held = false;
+        self.place_held = false;
+        self.jump_held = false;
+        self.sneak = false;
+        self.sprint = false;
+    }
+
+    /// Alle Bool-Felder in fester Reihenfolge (Replay-Format: Bitmaske)
+    fn flags_mut(&mut self) -> [&mut bool; 20] {
+        [
+            &mut self.break_block,
+            &mut self.place_block,
+            &mut self.jump,
+            &mut self.ui_click,
+            &mut self.close_screen,
+            &mut self.pick_block,
+            &mut self.open_inventory,
+            &mut self.toggle_light_debug,
+            &mut self.toggle_game_mode,
+            &mut self.toggle_wand,
+            &mut self.move_fwd,
+            &mut self.move_back,
+            &mut self.move_left,
+            &mut self.move_right,
+            &mut self.break_held,
+            &mut self.jump_held,
+            &mut self.sneak,
+            &mut self.sprint,
+            // Neue Flags hinten anhängen, sonst passen alte Replays nicht mehr
+            &mut self.mark_waypoint,
+            &mut self.place_held,
+        ]
+    }
+
+    pub fn write(&self, w: &mut ByteWriter) {
+        // flags_mut braucht &mut; auf einer Kopie lesen
+        let mut copy = *self;
+        let bits = copy
+            .flags_mut()
+            .into_iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, f)| acc | ((*f as u32) << i));
+        w.u32(bits);
+        w.u8(self.hotbar_select.map_or(u8::MAX, |s| s as u8));
+        w.i32(self.hotbar_scroll);
+        w.f32(self.cursor.0);
+        w.f32(self.cursor.1);
+    }
+
+    pub fn read(r: &mut ByteReader) -> Result<Self> {
+        let mut input = Self::default();
+        let bits = r.u32()?;
+        for (i, f) in input.flags_mut().into_iter().enumerate() {
+            *f = bits & (1 << i) != 0;
+        }
+        input.hotbar_select = match r.u8()? {
+            u8::MAX => None,
+            s => Some(s as usize),
+        };
+        input.hotbar_scroll = r.i32()?;
+        input.cursor = (r.f32()?, r.f32()?);
+        Ok(input)
+    }
+
     /// Nach jedem Tick aufrufen: setzt nur One-shot Aktionen zurück.
     pub fn clear_one_shots(&mut self) {
         self.break_block = false;
         self.place_block = false;
         self.jump = false;
-        self.toggle_mouse_lock = false;
+        self.ui_click = false;
+        self.close_screen = false;
+        self.open_inventory = false;
+        self.pick_block = false;
+        self.toggle_light_debug = false;
+        self.hotbar_select = None;
+        self.hotbar_scroll = 0;
+        self.toggle_game_mode = false;
+        self.toggle_wand = false;
+        self.mark_waypoint = false;
+    }
+}
+
+/// Wohin Klicks und das Mausrad gehen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Focus {
+    /// Maus gefangen: Abbauen, Bauen, Hotbar
+    World,
+    /// Menü, Screen oder Konsole offen: nur Klicks auf die UI, der Rest verfällt
+    Ui,
+}
+
+/// Wiederholt gehaltene Maustasten als neue Klicks: Bauen, im Creative auch Abbauen (der
+/// Survival-Abbau läuft über `break_held` und nimmt nach jedem Block den nächsten). Läuft im
+/// Frontend mit den Ticks, der Server sieht nur einzelne Klicks.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HoldRepeat {
+    place: u32,
+    brk: u32,
+}
+
+impl HoldRepeat {
+    /// Einmal pro Tick vor dem Senden; `interval` Ticks zwischen zwei Klicks (0 = aus)
+    pub fn tick(&mut self, input: &mut InputState, interval: u32) {
+        self.place = repeat(self.place, input.place_held, &mut input.place_block, interval);
+        self.brk = repeat(self.brk, input.break_held, &mut input.break_block, interval);
+    }
+}
+
+/// Ticks seit dem letzten Klick; der erste kommt vom Drücken selbst
+fn repeat(ticks: u32, held: bool, click: &mut bool, interval: u32) -> u32 {
+    if !held || interval == 0 || *click {
+        return 0;
+    }
+    let ticks = ticks + 1;
+    if ticks < interval {
+        return ticks;
     }
+    *click = true;
+    0
 }
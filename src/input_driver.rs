@@ -0,0 +1,148 @@
+//! Eingaben wie aus dem Fenster, aber ohne Fenster: Tasten laufen über dieselbe Belegung
+//! (`KeyBindings`) und dieselben Regeln wie im Frontend, Mausklicks je nach Fokus an die UI
+//! oder in die Welt. So lassen sich ganze Abläufe (Truhe öffnen, Item verschieben, Block
+//! setzen) headless gegen ein `Game` testen.
+
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use crate::game::Game;
+use crate::input::{Focus, HoldRepeat, InputState};
+use crate::keybinds::KeyBindings;
+use crate::menu::AppState;
+use crate::replay::ReplayFrame;
+use crate::settings::GameplaySettings;
+
+/// Fenstergröße, für die Screens ihr Layout rechnen
+pub const DEFAULT_SCREEN: (f32, f32) = (1280.0, 720.0);
+
+pub struct InputDriver {
+    game: Game,
+    bindings: KeyBindings,
+    input: InputState,
+    repeat: HoldRepeat,
+    click_repeat_ticks: u32,
+    screen: (f32, f32),
+    /// Konsolenzeilen für den nächsten Tick
+    lines: Vec<String>,
+}
+
+impl InputDriver {
+    pub fn new(game: Game) -> Self {
+        Self {
+            game,
+            bindings: KeyBindings::default(),
+            input: InputState::default(),
+            repeat: HoldRepeat::default(),
+            click_repeat_ticks: GameplaySettings::default().click_repeat_ticks,
+            screen: DEFAULT_SCREEN,
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn with_bindings(self, bindings: KeyBindings) -> Self {
+        Self { bindings, ..self }
+    }
+
+    pub fn with_screen(self, width: f32, height: f32) -> Self {
+        Self {
+            screen: (width, height),
+            ..self
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    pub fn screen(&self) -> (f32, f32) {
+        self.screen
+    }
+
+    /// Gleiche Regel wie im Fenster: offener Screen oder Konsole bekommt den Zeiger
+    pub fn focus(&self) -> Focus {
+        if self.game.screen_open() { Focus::Ui } else { Focus::World }
+    }
+
+    /// Taste gedrückt/losgelassen, verteilt wie im Fenster (`InputState::route_action`);
+    /// nicht belegte Tasten und reine Frontend-Aktionen (Kameras, Minikarte, Debug-Anzeige)
+    /// ändern am Spiel nichts. Menüs gibt es headless nicht, das Spiel läuft immer.
+    pub fn key(&mut self, code: KeyCode, down: bool) {
+        let (state, screen_open) = (AppState::Playing, self.game.screen_open());
+        if code == KeyCode::Escape {
+            if down {
+                self.input.escape(state, screen_open);
+            }
+            return;
+        }
+        let Some(action) = self.bindings.action(code) else {
+            return;
+        };
+        if let Some(prefix) = self.input.route_action(action, down, state, screen_open) {
+            self.game.open_console(prefix);
+        }
+    }
+
+    /// Drücken und gleich wieder loslassen (wirkt im nächsten Tick)
+    pub fn tap(&mut self, code: KeyCode) {
+        self.key(code, true);
+        self.key(code, false);
+    }
+
+    pub fn mouse(&mut self, button: MouseButton, down: bool) {
+        let focus = self.focus();
+        self.input.mouse_button(focus, button, down);
+    }
+
+    pub fn click(&mut self, button: MouseButton) {
+        self.mouse(button, true);
+        self.mouse(button, false);
+    }
+
+    /// Linksklick an eine Stelle im Fenster (Screens)
+    pub fn click_at(&mut self, x: f32, y: f32) {
+        self.input.cursor = (x, y);
+        self.click(MouseButton::Left);
+    }
+
+    /// Mausrad-Rasten (positiv = nach oben)
+    pub fn scroll(&mut self, y: f32) {
+        let focus = self.focus();
+        self.input.scroll(focus, y);
+    }
+
+    /// Blick drehen (Radiant), wie die Mausbewegung im Fenster
+    pub fn look(&mut self, yaw: f32, pitch: f32) {
+        self.game.look_delta(yaw, pitch);
+    }
+
+    /// Zeile wie aus der Konsole abgeschickt (Chat oder `/befehl`)
+    pub fn line(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    /// Einen Tick mit den bisher gesammelten Eingaben rechnen
+    pub fn tick(&mut self) {
+        self.repeat.tick(&mut self.input, self.click_repeat_ticks);
+        let (yaw, pitch) = self.game.look_angles();
+        let frame = ReplayFrame {
+            input: self.input,
+            yaw,
+            pitch,
+            screen: self.screen,
+            lines: std::mem::take(&mut self.lines),
+        };
+        self.game.replay_tick(&frame);
+        self.input.clear_one_shots();
+    }
+
+    pub fn ticks(&mut self, n: usize) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+}
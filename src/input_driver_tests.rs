@@ -0,0 +1,89 @@
+//! Abläufe über die Eingabeschicht des Fensters, ohne Fenster: Block setzen, Truhe öffnen,
+//! Item verschieben, Screen wieder schließen
+
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+use crate::block::Block;
+use crate::block_entity::BlockEntity;
+use crate::game::Game;
+use crate::input::Focus;
+use crate::input_driver::InputDriver;
+use crate::inventory::{Inventory, ItemStack};
+use crate::screens::ContainerLayout;
+use crate::ui::Rect;
+
+const SEED: u64 = 7;
+
+/// Spieler blickt schräg nach unten auf den Boden ein paar Blöcke vor sich
+fn driver() -> InputDriver {
+    let mut d = InputDriver::new(Game::new_deterministic(SEED));
+    d.look(0.0, -0.45);
+    d
+}
+
+fn chest(d: &InputDriver, (x, y, z): (i32, i32, i32)) -> &Inventory {
+    match d.game().world().block_entity(x, y, z) {
+        Some(BlockEntity::Container(inv)) => inv,
+        other => panic!("keine Truhe: {other:?}"),
+    }
+}
+
+fn click_slot(d: &mut InputDriver, slot: Rect) {
+    d.click_at(slot.x + slot.w * 0.5, slot.y + slot.h * 0.5);
+    d.tick();
+}
+
+#[test]
+fn right_click_places_the_selected_block() {
+    let mut d = driver();
+    let hit = d.game().target().expect("Boden im Blick");
+    let (x, y, z) = hit.adjacent();
+    d.tap(KeyCode::Digit1);
+    d.click(MouseButton::Right);
+    d.tick();
+    assert_eq!(d.game().world().get_block(x, y, z), Block::Stone);
+    assert_eq!(d.game().inventory().get(0).map(|s| s.count), Some(63));
+}
+
+#[test]
+fn chest_opens_takes_an_item_and_closes() {
+    let mut d = driver();
+    let pos = d.game().target().expect("Boden im Blick").pos;
+    d.game_mut().world_mut().set_block(pos.0, pos.1, pos.2, Block::Chest);
+
+    d.click(MouseButton::Right);
+    d.tick();
+    assert_eq!(d.focus(), Focus::Ui);
+
+    // Stapel aus der Hotbar aufnehmen und in den ersten Truhenslot legen
+    let stone = d.game().inventory().get(0);
+    let (w, h) = d.screen();
+    let layout = ContainerLayout::new(w, h, chest(&d, pos).len(), d.game().inventory().len());
+    click_slot(&mut d, layout.player[0]);
+    click_slot(&mut d, layout.container[0]);
+    assert_eq!(d.game().inventory().get(0), None);
+    assert_eq!(chest(&d, pos).get(0), stone);
+    let placed = stone.and_then(|ItemStack { item, .. }| item.placed_block());
+    assert_eq!(placed, Some(Block::Stone));
+
+    // Über dem Screen baut ein Rechtsklick nichts, E schließt
+    let above = d.game().world().get_block(pos.0, pos.1 + 1, pos.2);
+    d.click(MouseButton::Right);
+    d.tap(KeyCode::KeyE);
+    d.tick();
+    assert_eq!(d.focus(), Focus::World);
+    assert_eq!(d.game().world().get_block(pos.0, pos.1 + 1, pos.2), above);
+}
+
+#[test]
+fn command_key_opens_the_console_like_the_window() {
+    let mut d = driver();
+    d.tap(KeyCode::Slash);
+    assert!(d.game().console_open());
+    assert_eq!(d.game().console().input(), "/");
+    // In der offenen Konsole beginnt T keine neue Zeile
+    d.tap(KeyCode::KeyT);
+    assert_eq!(d.game().console().input(), "/");
+    assert_eq!(d.focus(), Focus::Ui);
+}
@@ -0,0 +1,191 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::block::Block;
+use crate::item::Item;
+use crate::serial::{ByteReader, ByteWriter};
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: Item,
+    pub count: u16,
+}
+
+impl ItemStack {
+    pub fn new(item: impl Into<Item>, count: u16) -> Self {
+        Self {
+            item: item.into(),
+            count,
+        }
+    }
+
+    /// Voller Stack (bis zur Stapelgrenze des Items)
+    pub fn full(item: impl Into<Item>) -> Self {
+        let item = item.into();
+        Self::new(item, item.max_stack())
+    }
+}
+
+/// Feste Anzahl Slots, jeder leer oder mit einem Stack
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl Inventory {
+    pub fn new(size: usize) -> Self {
+        Self {
+            slots: vec![None; size],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        self.slots.capacity() * size_of::<Option<ItemStack>>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn get(&self, slot: usize) -> Option<ItemStack> {
+        self.slots.get(slot).copied().flatten()
+    }
+
+    pub fn set(&mut self, slot: usize, stack: Option<ItemStack>) {
+        if let Some(s) = self.slots.get_mut(slot) {
+            *s = stack;
+        }
+    }
+
+    pub fn slot_mut(&mut self, slot: usize) -> Option<&mut Option<ItemStack>> {
+        self.slots.get_mut(slot)
+    }
+
+    /// Füllt zuerst angefangene Stacks desselben Items (bis zur Stapelgrenze), dann freie Slots.
+    /// Gibt den Rest zurück, der nicht mehr hineinpasst.
+    pub fn add(&mut self, stack: ItemStack) -> Option<ItemStack> {
+        let mut left = stack.count;
+        let max = stack.item.max_stack();
+
+        for slot in self.slots.iter_mut().flatten() {
+            if left == 0 {
+                break;
+            }
+            if slot.item == stack.item {
+                let moved = left.min(max.saturating_sub(slot.count));
+                slot.count += moved;
+                left -= moved;
+            }
+        }
+
+        for slot in self.slots.iter_mut().filter(|s| s.is_none()) {
+            if left == 0 {
+                break;
+            }
+            let moved = left.min(max);
+            *slot = Some(ItemStack::new(stack.item, moved));
+            left -= moved;
+        }
+
+        (left > 0).then(|| ItemStack::new(stack.item, left))
+    }
+
+    /// Nimmt bis zu `n` Items aus dem Slot; ein leerer Stack verschwindet
+    pub fn remove(&mut self, slot: usize, n: u16) -> Option<ItemStack> {
+        let s = self.slots.get_mut(slot)?;
+        let st = s.as_mut()?;
+        let taken = n.min(st.count);
+        st.count -= taken;
+        let item = st.item;
+        if st.count == 0 {
+            *s = None;
+        }
+        (taken > 0).then(|| ItemStack::new(item, taken))
+    }
+
+    /// Gesamtanzahl eines Items über alle Slots
+    pub fn count_of(&self, item: Item) -> u32 {
+        self.slots
+            .iter()
+            .flatten()
+            .filter(|s| s.item == item)
+            .map(|s| s.count as u32)
+            .sum()
+    }
+
+    /// Nimmt den Stack aus dem Slot heraus
+    pub fn take(&mut self, slot: usize) -> Option<ItemStack> {
+        self.slots.get_mut(slot).and_then(|s| s.take())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Option<ItemStack>> {
+        self.slots.iter()
+    }
+
+    pub fn write(&self, w: &mut ByteWriter) {
+        w.u16(self.slots.len() as u16);
+        for slot in &self.slots {
+            match slot {
+                // 1 = Block-Item (u8-ID, altes Format), 2 = sonstiges Item (u16-ID)
+                Some(st) => {
+                    match st.item {
+                        Item::Block(b) => {
+                            w.u8(1);
+                            w.u8(b as u8);
+                        }
+                        item => {
+                            w.u8(2);
+                            w.u16(item.id());
+                        }
+                    }
+                    w.u16(st.count);
+                }
+                None => w.u8(0),
+            }
+        }
+    }
+
+    pub fn read(r: &mut ByteReader) -> Result<Self> {
+        let n = r.u16()? as usize;
+        let mut inv = Inventory::new(n);
+        for i in 0..n {
+            let item = match r.u8()? {
+                0 => continue,
+                1 => {
+                    let id = r.u8()?;
+                    Block::from_id(id)
+                        .map(Item::Block)
+                        .ok_or_else(|| anyhow!("unbekannte Block-ID {id}"))?
+                }
+                2 => {
+                    let id = r.u16()?;
+                    Item::from_id(id).ok_or_else(|| anyhow!("unbekannte Item-ID {id}"))?
+                }
+                tag => bail!("ungültiger Slot-Typ {tag}"),
+            };
+            let count = r.u16()?;
+            inv.set(i, Some(ItemStack::new(item, count)));
+        }
+        Ok(inv)
+    }
+}
+
+/// Linksklick auf einen Slot mit (evtl.) gehaltenem Stack am Mauszeiger:
+/// aufnehmen, ablegen, gleiche Items zusammenlegen oder tauschen.
+pub fn click_slot(slot: &mut Option<ItemStack>, cursor: &mut Option<ItemStack>) {
+    match (slot.as_mut(), cursor.as_mut()) {
+        (Some(s), Some(c)) if s.item == c.item => {
+            let moved = c.count.min(s.item.max_stack().saturating_sub(s.count));
+            s.count += moved;
+            c.count -= moved;
+            if c.count == 0 {
+                *cursor = None;
+            }
+        }
+        _ => std::mem::swap(slot, cursor),
+    }
+}
@@ -0,0 +1,268 @@
+use crate::block::Block;
+
+/// Alles, was in einem Inventar-Slot liegen kann: Block-Items und eigenständige Items
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Item {
+    /// Platzierbarer Block (1:1 aus der Block-Registry)
+    Block(Block),
+    /// Lässt Pflanzen sofort wachsen
+    Bonemeal,
+    /// Ernte von reifem Weizen
+    Wheat,
+    /// Pflanzt Weizen auf Ackerboden
+    Seeds,
+    /// Zwischenprodukt fürs Crafting
+    Stick,
+    WoodenPickaxe,
+    StonePickaxe,
+    WoodenShovel,
+    StoneShovel,
+    /// Essbar, aus Weizen
+    Bread,
+}
+
+/// Werkzeugart; Blöcke nennen in der Registry ihr effektives Werkzeug
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToolKind {
+    Pickaxe,
+    Shovel,
+}
+
+/// Werkzeug-Eigenschaften eines Items
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tool {
+    pub kind: ToolKind,
+    /// Material-Stufe (0 = Holz, 1 = Stein)
+    pub tier: u8,
+    /// Abbautempo-Faktor beim passenden Block
+    pub speed: f32,
+}
+
+/// Wie ein Item im UI gezeichnet wird
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Icon {
+    /// Voller Block in Blockfarbe
+    Block([f32; 3]),
+    /// Kleines Quadrat (Kleinkram wie Samen, Pulver)
+    Small([f32; 3]),
+    /// Schmaler, hoher Balken (Halme, Stäbe)
+    Bar([f32; 3]),
+    /// Stiel mit Kopf in Materialfarbe
+    Tool { head: [f32; 3], handle: [f32; 3] },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ItemDef {
+    pub name: &'static str,
+    pub icon: Icon,
+    pub max_stack: u16,
+    pub tool: Option<Tool>,
+    /// Sättigung beim Essen (0 = nicht essbar)
+    pub food: u8,
+}
+
+/// Registry der Nicht-Block-Items, indexiert nach `Item::EXTRA`
+const ITEM_DEFS: [ItemDef; Item::EXTRA.len()] = [
+    ItemDef {
+        name: "bonemeal",
+        icon: Icon::Small([0.92, 0.90, 0.84]),
+        max_stack: 64,
+        tool: None,
+        food: 0,
+    },
+    ItemDef {
+        name: "wheat",
+        icon: Icon::Bar([0.85, 0.74, 0.35]),
+        max_stack: 64,
+        tool: None,
+        food: 0,
+    },
+    ItemDef {
+        name: "seeds",
+        icon: Icon::Small([0.45, 0.62, 0.25]),
+        max_stack: 64,
+        tool: None,
+        food: 0,
+    },
+    ItemDef {
+        name: "stick",
+        icon: Icon::Bar([0.55, 0.40, 0.22]),
+        max_stack: 64,
+        tool: None,
+        food: 0,
+    },
+    ItemDef {
+        name: "wooden_pickaxe",
+        icon: Icon::Tool {
+            head: WOOD,
+            handle: HANDLE,
+        },
+        max_stack: 1,
+        tool: Some(Tool {
+            kind: ToolKind::Pickaxe,
+            tier: 0,
+            speed: 2.0,
+        }),
+        food: 0,
+    },
+    ItemDef {
+        name: "stone_pickaxe",
+        icon: Icon::Tool {
+            head: STONE,
+            handle: HANDLE,
+        },
+        max_stack: 1,
+        tool: Some(Tool {
+            kind: ToolKind::Pickaxe,
+            tier: 1,
+            speed: 4.0,
+        }),
+        food: 0,
+    },
+    ItemDef {
+        name: "wooden_shovel",
+        icon: Icon::Tool {
+            head: WOOD,
+            handle: HANDLE,
+        },
+        max_stack: 1,
+        tool: Some(Tool {
+            kind: ToolKind::Shovel,
+            tier: 0,
+            speed: 2.0,
+        }),
+        food: 0,
+    },
+    ItemDef {
+        name: "stone_shovel",
+        icon: Icon::Tool {
+            head: STONE,
+            handle: HANDLE,
+        },
+        max_stack: 1,
+        tool: Some(Tool {
+            kind: ToolKind::Shovel,
+            tier: 1,
+            speed: 4.0,
+        }),
+        food: 0,
+    },
+    ItemDef {
+        name: "bread",
+        icon: Icon::Small([0.78, 0.58, 0.30]),
+        max_stack: 64,
+        tool: None,
+        food: 5,
+    },
+];
+
+const WOOD: [f32; 3] = [0.72, 0.56, 0.34];
+const STONE: [f32; 3] = [0.60, 0.60, 0.62];
+const HANDLE: [f32; 3] = [0.55, 0.40, 0.22];
+
+/// IDs ab hier gehören Nicht-Block-Items (darunter: Block-ID)
+const EXTRA_ID_BASE: u16 = 256;
+
+impl Item {
+    /// Alle Items, die keine Blöcke sind (Reihenfolge = ID)
+    pub const EXTRA: [Item; 9] = [
+        Item::Bonemeal,
+        Item::Wheat,
+        Item::Seeds,
+        Item::Stick,
+        Item::WoodenPickaxe,
+        Item::StonePickaxe,
+        Item::WoodenShovel,
+        Item::StoneShovel,
+        Item::Bread,
+    ];
+
+    /// Alle Items: Blöcke (ohne Luft) zuerst, dann die eigenständigen
+    pub fn all() -> impl Iterator<Item = Item> {
+        Block::ALL
+            .into_iter()
+            .filter(|b| *b != Block::Air)
+            .map(Item::Block)
+            .chain(Item::EXTRA)
+    }
+
+    /// Registry-Name -> Item (für Befehle)
+    pub fn by_name(name: &str) -> Option<Item> {
+        Item::all().find(|i| i.name() == name)
+    }
+
+    pub fn def(self) -> ItemDef {
+        match self {
+            Item::Block(b) => ItemDef {
+                name: b.name(),
+                icon: Icon::Block(b.color()),
+                max_stack: 64,
+                tool: None,
+                food: 0,
+            },
+            _ => ITEM_DEFS[self.extra_index().expect("Nicht-Block-Item")],
+        }
+    }
+
+    fn extra_index(self) -> Option<usize> {
+        Item::EXTRA.iter().position(|i| *i == self)
+    }
+
+    pub fn name(self) -> &'static str {
+        self.def().name
+    }
+
+    pub fn icon(self) -> Icon {
+        self.def().icon
+    }
+
+    pub fn max_stack(self) -> u16 {
+        self.def().max_stack
+    }
+
+    pub fn tool(self) -> Option<Tool> {
+        self.def().tool
+    }
+
+    pub fn food(self) -> u8 {
+        self.def().food
+    }
+
+    /// Stabile ID für Spielstände
+    pub fn id(self) -> u16 {
+        match self {
+            Item::Block(b) => b as u16,
+            _ => EXTRA_ID_BASE + self.extra_index().expect("Nicht-Block-Item") as u16,
+        }
+    }
+
+    pub fn from_id(id: u16) -> Option<Item> {
+        match id.checked_sub(EXTRA_ID_BASE) {
+            None => Block::from_id(id as u8).map(Item::Block),
+            Some(i) => Item::EXTRA.get(i as usize).copied(),
+        }
+    }
+
+    /// Welcher Block beim Rechtsklick gesetzt wird (Samen pflanzen Weizen)
+    pub fn placed_block(self) -> Option<Block> {
+        match self {
+            Item::Block(b) => Some(b),
+            Item::Seeds => Some(Block::Wheat),
+            _ => None,
+        }
+    }
+
+    /// Item für Pick-Block: Pflanzen liefern ihr Saatgut
+    pub fn for_block(b: Block) -> Item {
+        match b {
+            Block::Wheat => Item::Seeds,
+            _ => Item::Block(b),
+        }
+    }
+}
+
+impl From<Block> for Item {
+    fn from(b: Block) -> Self {
+        Item::Block(b)
+    }
+}
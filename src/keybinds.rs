@@ -0,0 +1,346 @@
+use winit::keyboard::KeyCode;
+
+/// Spielaktion, die auf eine Taste gelegt werden kann
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Sneak,
+    Sprint,
+    Inventory,
+    Chat,
+    Command,
+    ToggleGameMode,
+    LightDebug,
+    /// Debug-Overlay mit Speicherbilanz
+    DebugOverlay,
+    /// Auswahlmodus für Bereichsbefehle
+    Wand,
+    /// Minikarte ein/aus und zoomen
+    Minimap,
+    MinimapZoomIn,
+    MinimapZoomOut,
+    /// Wegmarke an der eigenen Position
+    Waypoint,
+    /// Debug: Kamera vom Spieler lösen und frei fliegen
+    FreeCamera,
+    /// Orthografische Draufsicht an/aus
+    TopDownCamera,
+    /// Außenansicht hinter dem Spieler an/aus
+    ThirdPerson,
+    Hotbar1,
+    Hotbar2,
+    Hotbar3,
+    Hotbar4,
+    Hotbar5,
+    Hotbar6,
+    Hotbar7,
+    Hotbar8,
+    Hotbar9,
+}
+
+impl Action {
+    /// Alle Aktionen (Reihenfolge = Anzeige und Index in `KeyBindings`)
+    pub const ALL: [Action; 30] = [
+        Action::MoveForward,
+        Action::MoveBack,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Sneak,
+        Action::Sprint,
+        Action::Inventory,
+        Action::Chat,
+        Action::Command,
+        Action::ToggleGameMode,
+        Action::LightDebug,
+        Action::DebugOverlay,
+        Action::Wand,
+        Action::Minimap,
+        Action::MinimapZoomIn,
+        Action::MinimapZoomOut,
+        Action::Waypoint,
+        Action::FreeCamera,
+        Action::TopDownCamera,
+        Action::ThirdPerson,
+        Action::Hotbar1,
+        Action::Hotbar2,
+        Action::Hotbar3,
+        Action::Hotbar4,
+        Action::Hotbar5,
+        Action::Hotbar6,
+        Action::Hotbar7,
+        Action::Hotbar8,
+        Action::Hotbar9,
+    ];
+
+    fn index(self) -> usize {
+        Action::ALL.iter().position(|a| *a == self).expect("Aktion in ALL")
+    }
+
+    /// Schlüssel in der Einstellungsdatei
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveForward => "forward",
+            Action::MoveBack => "back",
+            Action::MoveLeft => "left",
+            Action::MoveRight => "right",
+            Action::Jump => "jump",
+            Action::Sneak => "sneak",
+            Action::Sprint => "sprint",
+            Action::Inventory => "inventory",
+            Action::Chat => "chat",
+            Action::Command => "command",
+            Action::ToggleGameMode => "toggle_game_mode",
+            Action::LightDebug => "light_debug",
+            Action::DebugOverlay => "debug_overlay",
+            Action::Wand => "wand",
+            Action::Minimap => "minimap",
+            Action::MinimapZoomIn => "minimap_zoom_in",
+            Action::MinimapZoomOut => "minimap_zoom_out",
+            Action::Waypoint => "waypoint",
+            Action::FreeCamera => "free_camera",
+            Action::TopDownCamera => "top_down_camera",
+            Action::ThirdPerson => "third_person",
+            Action::Hotbar1 => "hotbar_1",
+            Action::Hotbar2 => "hotbar_2",
+            Action::Hotbar3 => "hotbar_3",
+            Action::Hotbar4 => "hotbar_4",
+            Action::Hotbar5 => "hotbar_5",
+            Action::Hotbar6 => "hotbar_6",
+            Action::Hotbar7 => "hotbar_7",
+            Action::Hotbar8 => "hotbar_8",
+            Action::Hotbar9 => "hotbar_9",
+        }
+    }
+
+    /// Anzeigename im Steuerungsmenü
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveForward => "Forward",
+            Action::MoveBack => "Back",
+            Action::MoveLeft => "Left",
+            Action::MoveRight => "Right",
+            Action::Jump => "Jump",
+            Action::Sneak => "Sneak",
+            Action::Sprint => "Sprint",
+            Action::Inventory => "Inventory",
+            Action::Chat => "Chat",
+            Action::Command => "Command",
+            Action::ToggleGameMode => "Game Mode",
+            Action::LightDebug => "Light Debug",
+            Action::DebugOverlay => "Debug Overlay",
+            Action::Wand => "Selection Wand",
+            Action::Minimap => "Minimap",
+            Action::MinimapZoomIn => "Map Zoom In",
+            Action::MinimapZoomOut => "Map Zoom Out",
+            Action::Waypoint => "Set Waypoint",
+            Action::FreeCamera => "Free Camera",
+            Action::TopDownCamera => "Top-Down Camera",
+            Action::ThirdPerson => "Third Person",
+            Action::Hotbar1 => "Hotbar 1",
+            Action::Hotbar2 => "Hotbar 2",
+            Action::Hotbar3 => "Hotbar 3",
+            Action::Hotbar4 => "Hotbar 4",
+            Action::Hotbar5 => "Hotbar 5",
+            Action::Hotbar6 => "Hotbar 6",
+            Action::Hotbar7 => "Hotbar 7",
+            Action::Hotbar8 => "Hotbar 8",
+            Action::Hotbar9 => "Hotbar 9",
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    /// Hotbar-Aktionen -> Slot 0..8
+    pub fn hotbar_slot(self) -> Option<usize> {
+        Action::ALL[Action::Hotbar1.index()..]
+            .iter()
+            .position(|a| *a == self)
+    }
+
+    fn default_key(self) -> KeyCode {
+        match self {
+            Action::MoveForward => KeyCode::KeyW,
+            Action::MoveBack => KeyCode::KeyS,
+            Action::MoveLeft => KeyCode::KeyA,
+            Action::MoveRight => KeyCode::KeyD,
+            Action::Jump => KeyCode::Space,
+            Action::Sneak => KeyCode::ShiftLeft,
+            Action::Sprint => KeyCode::ControlLeft,
+            Action::Inventory => KeyCode::KeyE,
+            Action::Chat => KeyCode::KeyT,
+            Action::Command => KeyCode::Slash,
+            Action::ToggleGameMode => KeyCode::F4,
+            Action::LightDebug => KeyCode::F7,
+            Action::DebugOverlay => KeyCode::F3,
+            Action::Wand => KeyCode::KeyV,
+            Action::Minimap => KeyCode::KeyM,
+            Action::MinimapZoomIn => KeyCode::Equal,
+            Action::MinimapZoomOut => KeyCode::Minus,
+            Action::Waypoint => KeyCode::KeyB,
+            Action::FreeCamera => KeyCode::F6,
+            Action::TopDownCamera => KeyCode::F5,
+            Action::ThirdPerson => KeyCode::F8,
+            Action::Hotbar1 => KeyCode::Digit1,
+            Action::Hotbar2 => KeyCode::Digit2,
+            Action::Hotbar3 => KeyCode::Digit3,
+            Action::Hotbar4 => KeyCode::Digit4,
+            Action::Hotbar5 => KeyCode::Digit5,
+            Action::Hotbar6 => KeyCode::Digit6,
+            Action::Hotbar7 => KeyCode::Digit7,
+            Action::Hotbar8 => KeyCode::Digit8,
+            Action::Hotbar9 => KeyCode::Digit9,
+        }
+    }
+}
+
+/// Belegbare Tasten; Escape bleibt fest fürs Menü
+const BINDABLE: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::CapsLock,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::Enter,
+    KeyCode::Backspace,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Backquote,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Backslash,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::IntlBackslash,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+];
+
+/// Tastenname für Datei und Anzeige (winit-Debug-Name, z.B. "KeyW")
+pub fn key_name(code: KeyCode) -> String {
+    format!("{code:?}")
+}
+
+pub fn key_by_name(name: &str) -> Option<KeyCode> {
+    BINDABLE.iter().copied().find(|k| key_name(*k) == name)
+}
+
+/// Kurzform fürs UI: "KeyW" -> "W", "Digit1" -> "1"
+pub fn key_label(code: KeyCode) -> String {
+    let name = key_name(code);
+    match name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")) {
+        Some(short) => short.to_string(),
+        None => name,
+    }
+}
+
+pub fn is_bindable(code: KeyCode) -> bool {
+    BINDABLE.contains(&code)
+}
+
+/// Aktion -> physische Taste (Tastenposition, unabhängig vom Layout)
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    keys: [KeyCode; Action::ALL.len()],
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: Action::ALL.map(Action::default_key),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys[action.index()]
+    }
+
+    /// Aktion auf einer Taste (erste in `Action::ALL`-Reihenfolge)
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        self.keys.iter().position(|k| *k == code).map(|i| Action::ALL[i])
+    }
+
+    /// Neu belegen; eine andere Aktion auf derselben Taste bekommt die alte Taste
+    pub fn set(&mut self, action: Action, code: KeyCode) {
+        let old = self.key(action);
+        if let Some(other) = self.action(code) {
+            self.keys[other.index()] = old;
+        }
+        self.keys[action.index()] = code;
+    }
+}
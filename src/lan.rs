@@ -0,0 +1,141 @@
+//! LAN-Suche: Server rufen regelmäßig per UDP-Broadcast Namen, Spielerzahl und
+//! Protokollversion aus, das Mehrspieler-Menü sammelt die Rufe ein.
+
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::protocol::PROTOCOL_VERSION;
+use crate::serial::{ByteReader, ByteWriter};
+
+/// Hier lauschen Clients auf Rufe
+pub const DISCOVERY_PORT: u16 = 25581;
+/// So oft ruft ein Server
+const BEACON_INTERVAL: Duration = Duration::from_millis(1500);
+/// Ohne neuen Ruf verschwindet ein Server aus der Liste
+const SERVER_TIMEOUT: Duration = Duration::from_secs(5);
+const BEACON_MAGIC: &[u8; 4] = b"VXLB";
+/// Längere Weltnamen werden im Menü gekürzt (Buttonbreite)
+const LABEL_NAME_CHARS: usize = 12;
+
+/// Inhalt eines Rufs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Beacon {
+    pub name: String,
+    pub players: u16,
+    pub version: u16,
+    /// TCP-Port des Servers (Adresse = Absender des Rufs)
+    pub port: u16,
+}
+
+impl Beacon {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = ByteWriter::new();
+        w.bytes(BEACON_MAGIC);
+        w.u16(self.version);
+        w.u16(self.port);
+        w.u16(self.players);
+        w.str(&self.name);
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        if r.bytes(4)? != BEACON_MAGIC {
+            bail!("kein Ruf dieses Spiels");
+        }
+        Ok(Self {
+            version: r.u16()?,
+            port: r.u16()?,
+            players: r.u16()?,
+            name: r.str()?,
+        })
+    }
+}
+
+/// Server-Seite: ruft im eigenen Takt, egal wie oft `tick` kommt
+pub struct Announcer {
+    socket: UdpSocket,
+    last: Option<Instant>,
+}
+
+impl Announcer {
+    pub fn new() -> Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).context("UDP-Socket")?;
+        socket.set_broadcast(true)?;
+        Ok(Self { socket, last: None })
+    }
+
+    pub fn tick(&mut self, beacon: &Beacon) {
+        if self.last.is_some_and(|t| t.elapsed() < BEACON_INTERVAL) {
+            return;
+        }
+        self.last = Some(Instant::now());
+        let target = (Ipv4Addr::BROADCAST, DISCOVERY_PORT);
+        if let Err(e) = self.socket.send_to(&beacon.encode(), target) {
+            log::debug!("LAN-Ruf nicht gesendet: {e}");
+        }
+    }
+}
+
+/// Gefundener Server
+#[derive(Clone, Debug)]
+pub struct LanServer {
+    pub addr: SocketAddr,
+    pub beacon: Beacon,
+    seen: Instant,
+}
+
+impl LanServer {
+    /// Menüzeile: Name und Spielerzahl, fremde Protokollversionen markiert
+    pub fn label(&self) -> String {
+        let mut name: String = self.beacon.name.chars().take(LABEL_NAME_CHARS).collect();
+        if self.beacon.name.chars().count() > LABEL_NAME_CHARS {
+            name.push('.');
+        }
+        if self.beacon.version != PROTOCOL_VERSION {
+            return format!("{name} (v{})", self.beacon.version);
+        }
+        format!("{name} ({})", self.beacon.players)
+    }
+}
+
+/// Client-Seite: sammelt Rufe, solange das Mehrspieler-Menü offen ist
+pub struct Scanner {
+    socket: UdpSocket,
+    servers: Vec<LanServer>,
+}
+
+impl Scanner {
+    /// Schlägt fehl, wenn schon ein anderer Client auf diesem Rechner sucht
+    pub fn new() -> Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT))
+            .with_context(|| format!("UDP-Port {DISCOVERY_PORT}"))?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            servers: Vec::new(),
+        })
+    }
+
+    /// Neue Rufe einsammeln und verstummte Server vergessen
+    pub fn poll(&mut self) {
+        let mut buf = [0; 512];
+        while let Ok((len, from)) = self.socket.recv_from(&mut buf) {
+            let Ok(beacon) = Beacon::decode(&buf[..len]) else { continue };
+            let addr = SocketAddr::new(from.ip(), beacon.port);
+            let seen = Instant::now();
+            match self.servers.iter_mut().find(|s| s.addr == addr) {
+                Some(s) => (s.beacon, s.seen) = (beacon, seen),
+                None => self.servers.push(LanServer { addr, beacon, seen }),
+            }
+        }
+        self.servers.retain(|s| s.seen.elapsed() < SERVER_TIMEOUT);
+    }
+
+    /// In der Reihenfolge des ersten Rufs (Buttons springen nicht)
+    pub fn servers(&self) -> &[LanServer] {
+        &self.servers
+    }
+}
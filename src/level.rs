@@ -0,0 +1,214 @@
+use anyhow::{bail, Context, Result};
+
+use crate::player::SPAWN;
+use crate::settings::{parse_bool, parse_f32, parse_string};
+use crate::weather::Weather;
+use crate::world::{DEFAULT_SEED, RANDOM_TICKS_PER_CHUNK};
+
+/// Dateiname im Weltordner (TOML-kompatibles `key = value`)
+pub const LEVEL_FILE: &str = "level.toml";
+/// Flache Startwelt mit Testobjekten; darüber hinaus nur Luft
+pub const GENERATOR_FLAT: &str = "flat";
+/// Hügel aus Perlin-Noise (`terrain.rs`), Standard für neue Welten
+pub const GENERATOR_NOISE: &str = "noise";
+
+/// Spielregeln einer Welt
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRules {
+    /// Tageszeit läuft weiter
+    pub daylight_cycle: bool,
+    /// Zufällige Block-Ticks pro Chunk und Tick (0 = kein Wachstum/Verfall)
+    pub random_tick_speed: u32,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            daylight_cycle: true,
+            random_tick_speed: RANDOM_TICKS_PER_CHUNK,
+        }
+    }
+}
+
+/// Benannte Wegmarke; Name wie ein Dateiname (Buchstaben, Ziffern, _ und -)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Waypoint {
+    pub name: String,
+    pub pos: (f32, f32, f32),
+}
+
+/// Metadaten einer Welt: beim Speichern aktualisiert, vom Hauptmenü angezeigt
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelInfo {
+    pub name: String,
+    pub seed: u64,
+    pub generator: String,
+    pub spawn: (f32, f32, f32),
+    /// Gespielte Ticks über alle Sitzungen
+    pub play_ticks: u64,
+    /// Tageszeit und Wetter, damit die Welt so weiterläuft, wie sie gespeichert wurde
+    pub day_ticks: u64,
+    pub day: u64,
+    pub weather: Weather,
+    /// Ticks bis zum Wetterwechsel; 0 = noch nie gespeichert, Wetter frisch würfeln
+    pub weather_ticks: u64,
+    pub rules: GameRules,
+    /// In Anlagereihenfolge
+    pub waypoints: Vec<Waypoint>,
+}
+
+impl LevelInfo {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            seed: DEFAULT_SEED,
+            generator: GENERATOR_FLAT.to_string(),
+            spawn: SPAWN,
+            play_ticks: 0,
+            day_ticks: 0,
+            day: 0,
+            weather: Weather::Clear,
+            weather_ticks: 0,
+            rules: GameRules::default(),
+            waypoints: Vec::new(),
+        }
+    }
+
+    /// Fehlende oder kaputte Zeilen behalten die Werte aus `defaults`
+    pub fn parse(text: &str, defaults: LevelInfo) -> Self {
+        let mut info = defaults;
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Err(e) = info.apply_line(line) {
+                log::warn!("{LEVEL_FILE} Zeile {}: {e}", n + 1);
+            }
+        }
+        info
+    }
+
+    fn apply_line(&mut self, line: &str) -> Result<()> {
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("kein '=' in '{line}'"))?;
+        let (key, value) = (key.trim(), value.trim());
+        match key {
+            "name" => self.name = parse_string(value)?,
+            "seed" => self.seed = value.parse().with_context(|| format!("kein Seed: '{value}'"))?,
+            "generator" => self.generator = parse_string(value)?,
+            "spawn" => self.spawn = parse_position(key, value)?,
+            "play_ticks" => {
+                self.play_ticks = value.parse().with_context(|| format!("keine Zahl: '{value}'"))?
+            }
+            "day_ticks" => {
+                self.day_ticks = value.parse().with_context(|| format!("keine Zahl: '{value}'"))?
+            }
+            "day" => self.day = value.parse().with_context(|| format!("keine Zahl: '{value}'"))?,
+            "weather" => {
+                let name = parse_string(value)?;
+                self.weather =
+                    Weather::by_name(&name).with_context(|| format!("kein Wetter '{name}'"))?
+            }
+            "weather_ticks" => {
+                self.weather_ticks =
+                    value.parse().with_context(|| format!("keine Zahl: '{value}'"))?
+            }
+            "rules.daylight_cycle" => self.rules.daylight_cycle = parse_bool(value)?,
+            "rules.random_tick_speed" => {
+                self.rules.random_tick_speed =
+                    value.parse().with_context(|| format!("keine Zahl: '{value}'"))?
+            }
+            _ => match key.strip_prefix("waypoint.") {
+                Some(name) if valid_waypoint_name(name) => {
+                    let pos = parse_position(key, value)?;
+                    self.set_waypoint(name, pos);
+                }
+                _ => bail!("unbekannter Schlüssel '{key}'"),
+            },
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        let (x, y, z) = self.spawn;
+        let mut text = String::from("# Weltinfo\n");
+        text += &format!("name = {:?}\n", self.name);
+        text += &format!("seed = {}\n", self.seed);
+        text += &format!("generator = {:?}\n", self.generator);
+        text += &format!("spawn = [{x:.2}, {y:.2}, {z:.2}]\n");
+        text += &format!("play_ticks = {}\n", self.play_ticks);
+        text += &format!("day_ticks = {}\n", self.day_ticks);
+        text += &format!("day = {}\n", self.day);
+        text += &format!("weather = {:?}\n", self.weather.name());
+        text += &format!("weather_ticks = {}\n", self.weather_ticks);
+        text += &format!("rules.daylight_cycle = {}\n", self.rules.daylight_cycle);
+        text += &format!("rules.random_tick_speed = {}\n", self.rules.random_tick_speed);
+        for w in &self.waypoints {
+            let (x, y, z) = w.pos;
+            text += &format!("waypoint.{} = [{x:.2}, {y:.2}, {z:.2}]\n", w.name);
+        }
+        text
+    }
+
+    pub fn waypoint(&self, name: &str) -> Option<&Waypoint> {
+        self.waypoints.iter().find(|w| w.name == name)
+    }
+
+    /// Anlegen oder (gleicher Name) versetzen
+    pub fn set_waypoint(&mut self, name: &str, pos: (f32, f32, f32)) {
+        match self.waypoints.iter_mut().find(|w| w.name == name) {
+            Some(w) => w.pos = pos,
+            None => self.waypoints.push(Waypoint {
+                name: name.to_string(),
+                pos,
+            }),
+        }
+    }
+
+    /// false, wenn es die Wegmarke nicht gab
+    pub fn remove_waypoint(&mut self, name: &str) -> bool {
+        let len = self.waypoints.len();
+        self.waypoints.retain(|w| w.name != name);
+        self.waypoints.len() != len
+    }
+
+    /// Erster freier Name der Form `wp<n>` (Wegmarke per Taste)
+    pub fn next_waypoint_name(&self) -> String {
+        (1..)
+            .map(|n| format!("wp{n}"))
+            .find(|name| self.waypoint(name).is_none())
+            .expect("unendlich viele Namen")
+    }
+
+    /// Spielzeit fürs Menü, z.B. "2h 05m"
+    pub fn play_time_label(&self) -> String {
+        play_time_label(self.play_ticks)
+    }
+}
+
+/// Ticks als Spielzeit, z.B. "2h 05m"
+pub fn play_time_label(ticks: u64) -> String {
+    // 20 TPS
+    let minutes = ticks / 20 / 60;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+/// Nur Zeichen, die als TOML-Schlüssel ohne Anführungszeichen gehen
+pub fn valid_waypoint_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// `[x, y, z]`
+fn parse_position(key: &str, value: &str) -> Result<(f32, f32, f32)> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .with_context(|| format!("kein Array: '{value}'"))?;
+    let v: Vec<f32> = inner.split(',').map(|s| parse_f32(s.trim())).collect::<Result<_>>()?;
+    let [x, y, z] = v[..] else {
+        bail!("{key} braucht drei Werte");
+    };
+    Ok((x, y, z))
+}
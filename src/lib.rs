@@ -0,0 +1,84 @@
+//! Engine: Welt, Chunks, Spiellogik, Mesher, UI-Geometrie und Spielstände.
+//! Fenster, Eventloop und GPU-Zugriff liegen in der Binary (main.rs, gfx.rs), damit Tests,
+//! ein Server oder andere Frontends die Simulation direkt einbinden können.
+
+// Einige APIs (Debug-Renderer, Chunk-Infos) werden erst von kommenden Systemen genutzt
+#![allow(dead_code)]
+
+pub mod assets;
+pub mod biome;
+pub mod block;
+#[cfg(test)]
+mod block_tests;
+pub mod block_buffer;
+pub mod block_entity;
+pub mod camera;
+pub mod chunk;
+pub mod chunk_bench;
+pub mod client;
+pub mod command;
+pub mod console;
+pub mod crafting;
+pub mod event;
+pub mod font;
+pub mod game;
+pub mod gamemode;
+pub mod headless;
+pub mod input;
+pub mod input_driver;
+#[cfg(test)]
+mod input_driver_tests;
+pub mod inventory;
+pub mod item;
+pub mod keybinds;
+pub mod lan;
+pub mod level;
+pub mod macros;
+pub mod map_image;
+pub mod mc_schematic;
+#[cfg(test)]
+mod mc_schematic_tests;
+pub mod memory;
+pub mod menu;
+pub mod movement;
+pub mod mesh;
+pub mod mesh_cache;
+pub mod metrics;
+pub mod minimap;
+pub mod net;
+pub mod net_sim;
+#[cfg(test)]
+mod net_sim_tests;
+pub mod noise;
+pub mod obj;
+pub mod player;
+pub mod player_model;
+pub mod protocol;
+pub mod remote_player;
+pub mod replay;
+pub mod rng;
+pub mod save;
+#[cfg(test)]
+mod save_roundtrip;
+pub mod screens;
+#[cfg(feature = "scripting")]
+pub mod script;
+pub mod selection;
+pub mod serial;
+pub mod server;
+pub mod settings;
+pub mod sim_thread;
+#[cfg(test)]
+mod sim_thread_tests;
+pub mod sky;
+pub mod sound;
+pub mod soundscape;
+pub mod stats;
+pub mod terrain;
+pub mod ui;
+pub mod vox;
+pub mod voxel_mesher;
+pub mod weather;
+pub mod world;
+#[cfg(test)]
+mod world_math_tests;
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::save;
+
+/// Makros gelten für alle Welten
+pub const MACRO_DIR: &str = "saves/macros";
+
+pub fn macro_path(name: &str) -> Result<PathBuf> {
+    save::named_file(MACRO_DIR, name, "txt")
+}
+
+pub fn load(name: &str) -> Result<String> {
+    let path = macro_path(name)?;
+    fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))
+}
+
+/// Makrotext in ausführbare Befehlszeilen (ohne "/") umsetzen.
+///
+/// Pro Zeile ein Befehl, führendes "/" optional; `#` leitet Kommentare ein.
+/// `let name = wert` setzt eine Variable, sofern sie nicht schon als Argument
+/// kam; `$name` bzw. `${name}` setzt sie ein, `$$` ergibt ein `$`.
+pub fn expand(text: &str, args: &[(String, String)]) -> Result<Vec<String>> {
+    let mut vars: HashMap<String, String> = args.iter().cloned().collect();
+    let mut lines = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let raw = raw.trim();
+        if raw.is_empty() || raw.starts_with('#') {
+            continue;
+        }
+        let line = substitute(raw, &vars).with_context(|| format!("line {}", i + 1))?;
+        if let Some(def) = line.strip_prefix("let ") {
+            let (name, value) = def
+                .split_once('=')
+                .ok_or_else(|| anyhow!("line {}: expected 'let name = value'", i + 1))?;
+            vars.entry(name.trim().to_string()).or_insert_with(|| value.trim().to_string());
+            continue;
+        }
+        lines.push(line.strip_prefix('/').unwrap_or(&line).to_string());
+    }
+    Ok(lines)
+}
+
+fn substitute(line: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(i) = rest.find('$') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        let name = if let Some(r) = rest.strip_prefix('$') {
+            out.push('$');
+            rest = r;
+            continue;
+        } else if let Some(r) = rest.strip_prefix('{') {
+            let end = r.find('}').ok_or_else(|| anyhow!("missing '}}'"))?;
+            rest = &r[end + 1..];
+            &r[..end]
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let name = &rest[..end];
+            rest = &rest[end..];
+            name
+        };
+        if name.is_empty() {
+            bail!("'$' without variable name (use $$ for a literal $)");
+        }
+        let value = vars.get(name).ok_or_else(|| anyhow!("unknown variable ${name}"))?;
+        out.push_str(value);
+    }
+    out.push_str(rest);
+    Ok(out)
+}
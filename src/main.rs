@@ -1,138 +1,1055 @@
-mod block;
-mod chunk;
-mod command;
-mod game;
+// Fenster-Frontend: Eingabe, Rendering und Menüs um die Engine aus lib.rs
+// (Gfx hält das Fenster nur fest, damit die Surface es nicht überlebt)
+#![allow(dead_code)]
+
+#[cfg(feature = "audio")]
+mod audio;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod gfx;
-mod input;
-mod mesh;
-mod player;
-mod voxel_mesher;
-mod world;
+mod pacing;
+mod presentation;
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
+
+use clap::{Parser, Subcommand};
 
-use game::Game;
-use gfx::Gfx;
-use input::InputState;
+use gfx::{Gfx, Shaders};
+use pacing::{FramePacer, Mode, Smoothed};
+use presentation::{Presentation, Session};
+use rust_game::assets::{Assets, ASSET_DIR};
+use rust_game::client::Client;
+use rust_game::console::Console;
+use rust_game::game::{self, Game};
+use rust_game::input::{Focus, HoldRepeat, InputState};
+use rust_game::keybinds::Action;
+use rust_game::menu::{AppState, MenuAction};
+use rust_game::metrics::{self, Metric};
+use rust_game::minimap::Minimap;
+use rust_game::movement::EYE_HEIGHT;
+use rust_game::replay::{Playback, Replay, Spectator};
+use rust_game::settings::{self, Settings, SETTINGS_FILE};
+use rust_game::sim_thread::SimThread;
+use rust_game::ui::UiBatch;
+use rust_game::{
+    chunk_bench, headless, keybinds, lan, net, player_model, remote_player, screens, server, sky,
+    weather, world,
+};
 
-use winit::event::{DeviceEvent, ElementState, Event, MouseButton, WindowEvent};
-use winit::event_loop::{ControlFlow, EventLoop};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{DeviceEvent, ElementState, Event, MouseScrollDelta, WindowEvent};
+use winit::event_loop::EventLoop;
 use winit::keyboard::{KeyCode, PhysicalKey};
-use winit::window::{CursorGrabMode, WindowBuilder};
+use winit::window::{CursorGrabMode, Fullscreen, Window, WindowBuilder};
+
+/// So oft wird die Einstellungsdatei auf externe Änderungen geprüft
+const SETTINGS_POLL: Duration = Duration::from_secs(1);
+/// Namen anderer Spieler nur bis zu dieser Entfernung (Blöcke)
+const NAME_TAG_RANGE: f32 = 48.0;
+/// Namensschild über den Füßen, knapp über dem Kopf
+const NAME_TAG_HEIGHT: f32 = 2.0;
+/// Wegmarken speichern die Füße; angezeigt wird etwa in Augenhöhe darüber
+const WAYPOINT_HEIGHT: f32 = 1.5;
+/// Sterne und Mond als Anteil der Fernebene (dahinter würden sie abgeschnitten)
+const SKY_DISTANCE: f32 = 0.9;
+/// Mehr LAN-Server passen nicht zwischen Titel und Unterzeile
+const LAN_LIST_MAX: usize = 6;
+
+/// Startoptionen von der Kommandozeile
+#[derive(Parser, Debug)]
+#[command(about = "Voxel sandbox game")]
+struct LaunchOptions {
+    /// World directory to load or create
+    #[arg(long, value_name = "PATH", default_value = game::SAVE_DIR)]
+    world: PathBuf,
+    /// Seed for new worlds and for --record
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Initial window width in pixels
+    #[arg(long, requires = "height")]
+    width: Option<u32>,
+    /// Initial window height in pixels
+    #[arg(long, requires = "width")]
+    height: Option<u32>,
+    /// Start in borderless fullscreen
+    #[arg(long)]
+    fullscreen: bool,
+    /// Simulation ticks per second
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=200))]
+    tps: u32,
+    /// Run the world without a window; commands are read from stdin
+    #[arg(long, conflicts_with_all = ["replay", "record"])]
+    headless: bool,
+    /// With --headless: accept a network player on this port
+    #[arg(long, requires = "headless")]
+    port: Option<u16>,
+    /// With --headless: serve Prometheus metrics at http://0.0.0.0:PORT/metrics
+    #[arg(long, value_name = "PORT", requires = "headless")]
+    metrics_port: Option<u16>,
+    /// Join a multiplayer server at HOST[:PORT]
+    #[arg(long, value_name = "ADDR", conflicts_with_all = ["headless", "replay", "record"])]
+    connect: Option<String>,
+    /// Player name shown on the server
+    #[arg(long, default_value = "Player")]
+    name: String,
+    /// Deterministic world; every tick is written to FILE
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+    /// Play back a recording
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+    /// Free camera instead of the player view during --replay
+    #[arg(long, requires = "replay")]
+    spectator: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Developer tools
+    #[command(subcommand)]
+    Dev(DevCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum DevCommand {
+    /// Compare chunk storage encodings (size, encode and decode time)
+    ChunkBench {
+        /// Number of chunks to measure
+        #[arg(long, default_value_t = 256)]
+        chunks: usize,
+        /// Seed for the generated chunks
+        #[arg(long, default_value_t = world::DEFAULT_SEED)]
+        seed: u64,
+        /// Measure the saved chunks of this world instead of generating new ones
+        #[arg(long, value_name = "PATH")]
+        saved: Option<PathBuf>,
+    },
+}
+
+impl LaunchOptions {
+    fn tick_dt(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.tps as f64)
+    }
+}
+
+/// Logs über `tracing` (auch die `log`-Aufrufe). `RUST_LOG` filtert je Modul, z.B.
+/// `rust_game::net=debug`; mit `trace` kommen die Zeiten der Spans tick/mesh/render dazu.
+fn init_logging(headless: bool) {
+    // Server: Log gehört zur normalen Ausgabe, standardmäßig ab Info
+    let default = if headless { "info" } else { "error" };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default));
+    let logs = tracing_subscriber::fmt::layer().with_span_events(FmtSpan::CLOSE);
+    let logs = if headless {
+        logs.with_writer(BoxMakeWriter::new(std::io::stdout))
+    } else {
+        logs.with_writer(BoxMakeWriter::new(std::io::stderr))
+    };
+    let registry = tracing_subscriber::registry().with(logs.with_filter(filter));
+    // Der Profiler bekommt alle Spans, unabhängig von RUST_LOG
+    #[cfg(feature = "profiler")]
+    let registry = registry.with(tracing_tracy::TracyLayer::default());
+    registry.init();
+}
+
+/// Aufzeichnung (falls aktiv) in die Datei schreiben
+fn finish_recording(game: &mut Game, path: Option<&Path>) {
+    if let (Some(path), Some(rec)) = (path, game.take_recording()) {
+        match rec.save(path) {
+            Ok(()) => {
+                let ticks = rec.frames.len();
+                log::info!("Replay gespeichert: {} ({ticks} Ticks)", path.display());
+            }
+            Err(e) => log::warn!("Replay speichern: {e:#}"),
+        }
+    }
+}
+
+/// Simulation anhalten, speichern und eine laufende Aufzeichnung abschließen
+fn shut_down(sim: SimThread, record: Option<&Path>) {
+    let mut server = sim.stop();
+    let game = server.game_mut();
+    game.save();
+    finish_recording(game, record);
+}
+
+/// Mausbewegung: beim Abspielen oder mit freier Kamera nur an die Zuschauerkamera
+fn look(
+    client: &mut Client,
+    playback: &mut Option<Playback>,
+    free_camera: &mut Option<Spectator>,
+    yaw: f32,
+    pitch: f32,
+) {
+    match playback {
+        Some(pb) => {
+            if let Some(spec) = &mut pb.spectator {
+                spec.add_look(yaw, pitch);
+            }
+        }
+        None => match free_camera {
+            Some(spec) => spec.add_look(yaw, pitch),
+            None => client.look_delta(yaw, pitch),
+        },
+    }
+}
+
+/// Kamera, die nicht am Spieler hängt (Zuschauer beim Abspielen oder freie Kamera)
+fn detached_camera(
+    playback: &Option<Playback>,
+    free_camera: Option<Spectator>,
+) -> Option<Spectator> {
+    match playback {
+        Some(pb) => pb.spectator,
+        None => free_camera,
+    }
+}
+
+/// Maus fangen; ohne Rohdaten wird nur eingesperrt und per Cursorposition gemessen
+fn set_mouse_lock(window: &Window, locked: bool, raw: bool) {
+    let mode = match (locked, raw) {
+        (false, _) => CursorGrabMode::None,
+        (true, true) => CursorGrabMode::Locked,
+        (true, false) => CursorGrabMode::Confined,
+    };
+    if window.set_cursor_grab(mode).is_err() && locked {
+        // nicht jede Plattform kann beides
+        let fallback = if raw { CursorGrabMode::Confined } else { CursorGrabMode::Locked };
+        let _ = window.set_cursor_grab(fallback);
+    }
+    window.set_cursor_visible(!locked);
+}
+
+/// Sobald etwas Modales offen ist, gehört der Zeiger der UI
+fn focus(state: AppState, sim: &mut Option<SimThread>, client: &Client) -> Focus {
+    if state.ticking() && !screen_open(sim, client) { Focus::World } else { Focus::Ui }
+}
+
+/// Mausfang dem Fokus anpassen; beim Freigeben gehaltene Maustasten loslassen
+fn sync_mouse_lock(
+    window: &Window,
+    focus: Focus,
+    locked: &mut bool,
+    raw: bool,
+    input: &mut InputState,
+) {
+    let want = focus == Focus::World;
+    if want != *locked {
+        *locked = want;
+        set_mouse_lock(window, want, raw);
+        input.break_held = false;
+        input.place_held = false;
+    }
+}
+
+/// Escape (bzw. Start am Controller): offenen Screen schließen oder eine Menüebene wechseln
+fn escape(state: AppState, sim: Option<&mut SimThread>, input: &mut InputState) -> AppState {
+    let screen_open = sim.is_some_and(|sim| sim.snapshot().screen_open);
+    input.escape(state, screen_open)
+}
+
+/// Gemeinsamer Weg für Tastatur und Controller; Aktionen mit Spielzustand hier, Rest in `input`.
+/// Übers Netz (`sim` = None) gibt es noch keine Screens, nur die Chat-Konsole des Clients.
+fn handle_action(
+    action: Action,
+    down: bool,
+    state: AppState,
+    sim: Option<&mut SimThread>,
+    client: &mut Client,
+    input: &mut InputState,
+) {
+    let Some(sim) = sim else {
+        match action {
+            Action::Chat | Action::Command
+                if down && state.ticking() && !client.console().is_open() =>
+            {
+                client.console_mut().open(if action == Action::Command { "/" } else { "" });
+                input.release_held();
+            }
+            _ => input.apply(action, down),
+        }
+        return;
+    };
+    let screen_open = sim.snapshot().screen_open;
+    if let Some(prefix) = input.route_action(action, down, state, screen_open) {
+        sim.run(move |server| server.game_mut().open_console(prefix));
+        let snapshot = sim.snapshot_mut();
+        (snapshot.screen_open, snapshot.console_open) = (true, true);
+    }
+}
+
+/// Offener Screen oder Konsole (übers Netz nur die Chat-Konsole)
+fn screen_open(sim: &mut Option<SimThread>, client: &Client) -> bool {
+    sim.as_mut().map_or(client.console().is_open(), |sim| sim.snapshot().screen_open)
+}
+
+/// Konsole, in die gerade getippt wird
+fn console_open(sim: &mut Option<SimThread>, client: &Client) -> bool {
+    sim.as_mut().map_or(client.console().is_open(), |sim| sim.snapshot().console_open)
+}
+
+/// Taste in der offenen Konsole (außer Tab); Enter liefert die abgeschickte Zeile
+fn console_key(console: &mut Console, key: KeyCode, text: Option<&str>) -> Option<String> {
+    match key {
+        KeyCode::Escape => console.close(),
+        KeyCode::Enter | KeyCode::NumpadEnter => return console.submit(),
+        KeyCode::Backspace => console.backspace(),
+        KeyCode::ArrowUp => console.history_prev(),
+        KeyCode::ArrowDown => console.history_next(),
+        KeyCode::PageUp => console.scroll_by(5),
+        KeyCode::PageDown => console.scroll_by(-5),
+        _ => {
+            if let Some(text) = text {
+                console.type_text(text);
+            }
+        }
+    }
+    None
+}
+
+/// Speichert und liefert die neue Änderungszeit, damit das eigene Schreiben
+/// nicht als externe Änderung neu geladen wird
+fn save_settings(settings: &Settings) -> Option<SystemTime> {
+    if let Err(e) = settings.save(Path::new(SETTINGS_FILE)) {
+        log::warn!("Einstellungen speichern: {e:#}");
+    }
+    settings::modified(Path::new(SETTINGS_FILE))
+}
+
+/// Entwicklerwerkzeuge ohne Fenster
+fn run_dev(command: &DevCommand) -> anyhow::Result<()> {
+    match command {
+        DevCommand::ChunkBench { chunks, seed, saved: None } => {
+            let source = format!("a new world with seed {seed}");
+            chunk_bench::run(&chunk_bench::generated_chunks(*seed, *chunks), &source)
+        }
+        DevCommand::ChunkBench { chunks, saved: Some(dir), .. } => {
+            let source = format!("saved world {}", dir.display());
+            chunk_bench::run(&chunk_bench::saved_chunks(dir, *chunks)?, &source)
+        }
+    }
+}
 
 fn main() {
-    env_logger::init();
+    let opts = LaunchOptions::parse();
+    init_logging(opts.headless);
+    if let Some(Command::Dev(command)) = &opts.command {
+        if let Err(e) = run_dev(command) {
+            log::error!("{e:#}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    if opts.headless {
+        headless::run(&opts.world, opts.seed, opts.tick_dt(), opts.port, opts.metrics_port);
+        return;
+    }
+    // Vor dem Fenster verbinden: ohne Server gibt es nichts zu zeigen
+    let remote = opts.connect.as_deref().map(|addr| match net::connect(addr, &opts.name) {
+        Ok((params, to_server, from_server)) => {
+            log::info!("Verbunden mit {addr}: Welt {:?}, Seed {}", params.name, params.seed);
+            (params.name, to_server, from_server)
+        }
+        Err(e) => {
+            log::error!("--connect {addr}: {e:#}");
+            std::process::exit(2);
+        }
+    });
 
     let event_loop = EventLoop::new().expect("create event loop");
 
-    let window = Arc::new(
-        WindowBuilder::new()
-            .with_title("Voxel Engine – wgpu")
-            .build(&event_loop)
-            .expect("create window"),
-    );
+    let mut builder = WindowBuilder::new()
+        .with_title(presentation::APP_NAME)
+        .with_window_icon(presentation::icon());
+    if let (Some(w), Some(h)) = (opts.width, opts.height) {
+        builder = builder.with_inner_size(PhysicalSize::new(w, h));
+    }
+    if opts.fullscreen {
+        builder = builder.with_fullscreen(Some(Fullscreen::Borderless(None)));
+    }
+    let window = Arc::new(builder.build(&event_loop).expect("create window"));
 
-    let mut gfx = pollster::block_on(Gfx::new(window.clone()));
-    let mut game = Game::new();
+    let mut assets = Assets::new(ASSET_DIR);
+    let shaders = match Shaders::load(&mut assets) {
+        Ok(shaders) => shaders,
+        Err(e) => {
+            log::error!("Shader laden: {e:#}");
+            std::process::exit(1);
+        }
+    };
+    let mut gfx = pollster::block_on(Gfx::new(window.clone(), shaders));
+    let mut playback = opts.replay.as_deref().and_then(|path| match Replay::load(path) {
+        Ok(replay) => Some(Playback::new(replay)),
+        Err(e) => {
+            log::error!("Replay nicht lesbar: {e:#}");
+            None
+        }
+    });
+    let mut settings = Settings::load(Path::new(SETTINGS_FILE));
+    let view_distance = settings.graphics.view_distance;
+    let (mut sim, mut client, mut presentation) = match remote {
+        Some((world, to_server, from_server)) => {
+            let addr = opts.connect.clone().unwrap_or_default();
+            let presentation = Presentation::new(world, Session::Multiplayer { addr });
+            (None, Client::networked(to_server, from_server, view_distance), presentation)
+        }
+        None => {
+            let game = match &playback {
+                Some(pb) => Game::new_deterministic(pb.seed()),
+                None if opts.record.is_some() => {
+                    let seed = opts.seed.unwrap_or(world::DEFAULT_SEED);
+                    let mut game = Game::new_deterministic(seed);
+                    game.start_recording(seed);
+                    game
+                }
+                None => Game::new(&opts.world, opts.seed),
+            };
+            let session = match (&playback, &opts.record) {
+                (Some(_), _) => Session::Replay,
+                (None, Some(_)) => Session::Recording,
+                (None, None) => Session::Singleplayer,
+            };
+            let world = game.level_info().map(|level| level.name).unwrap_or_default();
+            let presentation = Presentation::new(world, session);
+            let (server, client) = server::integrated(game, view_distance);
+            let sim = SimThread::spawn(server, opts.tick_dt(), playback.is_some());
+            (Some(sim), client, presentation)
+        }
+    };
+    if let Some(pb) = &mut playback
+        && opts.spectator
+    {
+        let (pos, _) = client.camera_pos_dir();
+        let (yaw, pitch) = client.look_angles();
+        pb.spectator = Some(Spectator { pos, yaw, pitch });
+    }
     let mut input = InputState::default();
+    let mut hold_repeat = HoldRepeat::default();
     let mut mouse_locked = false;
+    // Wiedergabe startet direkt im Spiel
+    let mut state = if playback.is_some() { AppState::Playing } else { AppState::MainMenu };
+    let mut settings_mtime = settings::modified(Path::new(SETTINGS_FILE));
+    let mut settings_checked = Instant::now();
+    let mut last_autosave = Instant::now();
+    // Steuerungsmenü: Aktion, die auf ihre neue Taste wartet
+    let mut rebinding: Option<Action> = None;
+    let mut debug_overlay = false;
+    // Debug: vom Spieler gelöste Kamera, die Simulation läuft weiter
+    let mut free_camera: Option<Spectator> = None;
+    let mut minimap = Minimap::default();
+    // Mehrspieler-Menü: LAN-Suche und letzter Fehler (Suche oder Verbindung)
+    let mut lan: Option<lan::Scanner> = None;
+    let mut lan_error: Option<String> = None;
+    #[cfg(feature = "gamepad")]
+    let mut gamepad = gamepad::Gamepad::new();
+    #[cfg(feature = "audio")]
+    let mut audio = audio::Audio::new(&settings.audio);
 
-    let tick_dt = Duration::from_millis(50); // 20 TPS
-    let mut next_tick = Instant::now() + tick_dt;
+    let tick_dt = opts.tick_dt();
+    let mut pacer = FramePacer::new(tick_dt);
+    // Augenposition der letzten beiden Ticks, gezeichnet wird dazwischen
+    let mut eye = Smoothed::default();
+    let mut last_frame = Instant::now();
 
     event_loop
         .run(move |event, elwt| {
-            elwt.set_control_flow(ControlFlow::WaitUntil(next_tick));
+            elwt.set_control_flow(pacer.control_flow());
 
             match event {
                 Event::WindowEvent { event, .. } => match event {
-                    WindowEvent::CloseRequested => elwt.exit(),
+                    WindowEvent::CloseRequested => {
+                        if let Some(sim) = sim.take() {
+                            shut_down(sim, opts.record.as_deref());
+                        }
+                        elwt.exit();
+                    }
 
                     WindowEvent::Resized(size) => {
+                        pacer.resized(size);
                         gfx.resize(size);
                         window.request_redraw();
                     }
 
-                    WindowEvent::RedrawRequested => match gfx.render() {
-                        Ok(_) => {}
-                        Err(wgpu::SurfaceError::Lost) => gfx.resize(gfx.size),
-                        Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
-                        Err(_) => {}
-                    },
+                    WindowEvent::Focused(focused) => pacer.set_focused(focused),
+                    WindowEvent::Occluded(occluded) => pacer.set_occluded(occluded),
+
+                    WindowEvent::RedrawRequested => {
+                        pacer.rendered();
+                        presentation.frame();
+                        let now = Instant::now();
+                        metrics::set_duration(Metric::FrameMs, now - last_frame);
+                        last_frame = now;
+                        // Blickrichtung immer die neueste, nur die Position wird interpoliert
+                        let dir = match detached_camera(&playback, free_camera) {
+                            Some(spec) => spec.dir(),
+                            None => client.camera_pos_dir().1,
+                        };
+                        gfx.set_camera(client.camera_at(eye.at(pacer.alpha(now)), dir));
+                        match gfx.render() {
+                            Ok(_) => {}
+                            Err(wgpu::SurfaceError::Lost) => gfx.resize(gfx.size),
+                            Err(wgpu::SurfaceError::OutOfMemory) => elwt.exit(),
+                            Err(_) => {}
+                        }
+                    }
+
+                    // Offene Konsole bekommt alle Tasten als Texteingabe
+                    WindowEvent::KeyboardInput { event, .. } if console_open(&mut sim, &client) => {
+                        let PhysicalKey::Code(key) = event.physical_key else { return };
+                        if event.state != ElementState::Pressed {
+                            return;
+                        }
+                        let text = event.text.as_ref().map(|t| t.to_string());
+                        // Befehle laufen auf dem Server mit dem nächsten Tick
+                        match &mut sim {
+                            // Die Konsole des Spiels lebt auf dem Simulations-Thread
+                            Some(sim) => {
+                                if matches!(
+                                    key,
+                                    KeyCode::Escape | KeyCode::Enter | KeyCode::NumpadEnter
+                                ) {
+                                    let snapshot = sim.snapshot_mut();
+                                    (snapshot.screen_open, snapshot.console_open) = (false, false);
+                                }
+                                sim.run(move |server| {
+                                    let game = server.game_mut();
+                                    if key == KeyCode::Tab {
+                                        game.complete_console();
+                                    } else if let Some(line) =
+                                        console_key(game.console_mut(), key, text.as_deref())
+                                    {
+                                        server.host_chat(line);
+                                    }
+                                });
+                            }
+                            None if key == KeyCode::Tab => client.complete_console(),
+                            None => {
+                                let console = client.console_mut();
+                                if let Some(line) = console_key(console, key, text.as_deref()) {
+                                    client.send_chat(line);
+                                }
+                            }
+                        }
+                    }
 
                     WindowEvent::KeyboardInput { event, .. } => {
                         let down = event.state == ElementState::Pressed;
                         match event.physical_key {
+                            PhysicalKey::Code(code) if down && rebinding.is_some() => {
+                                // Escape bricht ab, nicht belegbare Tasten werden ignoriert
+                                if code == KeyCode::Escape {
+                                    rebinding = None;
+                                } else if keybinds::is_bindable(code) {
+                                    let action = rebinding.take().expect("geprüft");
+                                    settings.bindings.set(action, code);
+                                    settings_mtime = save_settings(&settings);
+                                }
+                            }
                             PhysicalKey::Code(KeyCode::Escape) if down => {
-                                input.toggle_mouse_lock = true
+                                state = escape(state, sim.as_mut(), &mut input);
+                            }
+                            PhysicalKey::Code(code) => {
+                                let action = settings.bindings.action(code);
+                                match action {
+                                    Some(Action::DebugOverlay) if down => {
+                                        debug_overlay = !debug_overlay
+                                    }
+                                    Some(Action::FreeCamera) if down && playback.is_none() => {
+                                        free_camera = match free_camera {
+                                            Some(_) => None,
+                                            None => {
+                                                let (pos, _) = client.camera_pos_dir();
+                                                let (yaw, pitch) = client.look_angles();
+                                                Some(Spectator { pos, yaw, pitch })
+                                            }
+                                        };
+                                        log::info!(
+                                            "Freie Kamera {}",
+                                            if free_camera.is_some() { "an" } else { "aus" }
+                                        );
+                                    }
+                                    Some(Action::FreeCamera) => {}
+                                    Some(Action::TopDownCamera) if down => {
+                                        client.toggle_top_down()
+                                    }
+                                    Some(Action::ThirdPerson) if down => {
+                                        client.toggle_third_person()
+                                    }
+                                    Some(Action::Minimap) if down => minimap.toggle(),
+                                    Some(Action::MinimapZoomIn) if down => minimap.zoom(1),
+                                    Some(Action::MinimapZoomOut) if down => minimap.zoom(-1),
+                                    Some(action) => {
+                                        let (sim, client) = (sim.as_mut(), &mut client);
+                                        handle_action(action, down, state, sim, client, &mut input);
+                                    }
+                                    None => {}
+                                }
                             }
-                            PhysicalKey::Code(KeyCode::Space) if down => input.jump = true,
-
-                            PhysicalKey::Code(KeyCode::KeyW) => input.move_fwd = down,
-                            PhysicalKey::Code(KeyCode::KeyS) => input.move_back = down,
-                            PhysicalKey::Code(KeyCode::KeyA) => input.move_left = down,
-                            PhysicalKey::Code(KeyCode::KeyD) => input.move_right = down,
                             _ => {}
                         }
+                        // Pausenmenü oder Konsole geben die Maus sofort frei, nicht erst im Tick
+                        let focus = focus(state, &mut sim, &client);
+                        let raw = settings.mouse.raw_input;
+                        sync_mouse_lock(&window, focus, &mut mouse_locked, raw, &mut input);
                     }
 
-                    WindowEvent::MouseInput { state, button, .. } => {
-                        if state == ElementState::Pressed {
-                            match button {
-                                MouseButton::Left => input.break_block = true,
-                                MouseButton::Right => input.place_block = true,
-                                _ => {}
-                            }
+                    WindowEvent::MouseInput {
+                        state: button_state,
+                        button,
+                        ..
+                    } => {
+                        let down = button_state == ElementState::Pressed;
+                        input.mouse_button(focus(state, &mut sim, &client), button, down);
+                    }
+
+                    WindowEvent::MouseWheel { delta, .. } => {
+                        let y = match delta {
+                            MouseScrollDelta::LineDelta(_, y) => y,
+                            MouseScrollDelta::PixelDelta(p) => p.y as f32,
+                        };
+                        input.scroll(focus(state, &mut sim, &client), y);
+                    }
+
+                    // Ohne Rohdaten: Abstand zur Fenstermitte, danach Cursor zurücksetzen
+                    WindowEvent::CursorMoved { position, .. }
+                        if mouse_locked && !settings.mouse.raw_input =>
+                    {
+                        let cx = (gfx.size.width / 2) as f64;
+                        let cy = (gfx.size.height / 2) as f64;
+                        let (dx, dy) = (position.x - cx, position.y - cy);
+                        if dx != 0.0 || dy != 0.0 {
+                            let (yaw, pitch) = settings.mouse.look(dx, dy);
+                            look(&mut client, &mut playback, &mut free_camera, yaw, pitch);
+                            let _ = window.set_cursor_position(PhysicalPosition::new(cx, cy));
                         }
                     }
 
+                    WindowEvent::CursorMoved { position, .. } => {
+                        input.cursor = (position.x as f32, position.y as f32);
+                    }
+
                     _ => {}
                 },
 
                 Event::DeviceEvent {
                     event: DeviceEvent::MouseMotion { delta },
                     ..
-                } => {
-                    if mouse_locked {
-                        let (dx, dy) = delta;
-                        let sens = 0.002_f32;
-                        game.look_delta((dx as f32) * sens, (dy as f32) * sens);
-                    }
+                } if mouse_locked && settings.mouse.raw_input => {
+                    let (yaw, pitch) = settings.mouse.look(delta.0, delta.1);
+                    look(&mut client, &mut playback, &mut free_camera, yaw, pitch);
                 }
 
                 Event::AboutToWait => {
-                    // Mouse lock toggle
-                    if input.toggle_mouse_lock {
-                        mouse_locked = !mouse_locked;
-                        let _ = window.set_cursor_grab(if mouse_locked {
-                            CursorGrabMode::Locked
-                        } else {
-                            CursorGrabMode::None
-                        });
-                        window.set_cursor_visible(!mouse_locked);
-                    }
-
                     let now = Instant::now();
-                    if now >= next_tick {
-                        game.apply_input(input);
-                        game.tick(input);
+                    if pacer.tick_due(now) {
+                        // Externe Änderungen an der Einstellungsdatei übernehmen
+                        if now - settings_checked >= SETTINGS_POLL {
+                            settings_checked = now;
+                            let mtime = settings::modified(Path::new(SETTINGS_FILE));
+                            if mtime.is_some() && mtime != settings_mtime {
+                                settings_mtime = mtime;
+                                settings.apply_live(&Settings::load(Path::new(SETTINGS_FILE)));
+                                log::info!("Einstellungen neu geladen");
+                            }
+                            // Assets nur im Debug-Build live nachladen
+                            if cfg!(debug_assertions) {
+                                for path in assets.reload_changed() {
+                                    log::info!("Asset neu geladen: {path}");
+                                }
+                                gfx.reload_shaders();
+                            }
+                        }
+
+                        #[cfg(feature = "gamepad")]
+                        if let Some(pad) = &mut gamepad {
+                            let dt = tick_dt.as_secs_f32();
+                            let frame = pad.poll(&mut input, &settings.gamepad, dt);
+                            for (action, down) in frame.actions {
+                                let sim = sim.as_mut();
+                                handle_action(action, down, state, sim, &mut client, &mut input);
+                            }
+                            if frame.menu {
+                                state = escape(state, sim.as_mut(), &mut input);
+                            }
+                            if mouse_locked {
+                                let (yaw, pitch) = frame.look;
+                                look(&mut client, &mut playback, &mut free_camera, yaw, pitch);
+                            }
+                        }
+
+                        // LAN-Suche nur, solange die Serverliste offen ist
+                        if state == AppState::Multiplayer {
+                            if lan.is_none() && lan_error.is_none() {
+                                match lan::Scanner::new() {
+                                    Ok(scanner) => lan = Some(scanner),
+                                    Err(e) => {
+                                        log::warn!("LAN-Suche nicht möglich: {e:#}");
+                                        lan_error = Some(format!("LAN search unavailable: {e:#}"));
+                                    }
+                                }
+                            }
+                            if let Some(scanner) = &mut lan {
+                                scanner.poll();
+                            }
+                        } else if lan.is_some() || lan_error.is_some() {
+                            (lan, lan_error) = (None, None);
+                        }
 
+                        if state.ticking() {
+                            hold_repeat.tick(&mut input, settings.gameplay.click_repeat_ticks);
+                            match &mut playback {
+                                Some(pb) => {
+                                    if let Some(frame) = pb.next_frame() {
+                                        client.send_frame(frame.clone());
+                                        if pb.finished()
+                                            && let Some(sim) = &sim
+                                        {
+                                            sim.run(|s| s.game_mut().message("Replay finished"));
+                                        }
+                                    }
+                                    if let Some(spec) = &mut pb.spectator {
+                                        spec.fly(&input, tick_dt.as_secs_f32());
+                                    }
+                                }
+                                None => match &mut free_camera {
+                                    Some(spec) => {
+                                        spec.fly(&input, tick_dt.as_secs_f32());
+                                        client.send_tick(input.detached());
+                                    }
+                                    None => client.send_tick(input),
+                                },
+                            }
+
+                            let minutes = settings.gameplay.autosave_minutes;
+                            if minutes > 0
+                                && playback.is_none()
+                                && now - last_autosave >= Duration::from_secs(60 * minutes as u64)
+                                && let Some(sim) = &sim
+                            {
+                                last_autosave = now;
+                                sim.run(|server| server.game_mut().save());
+                            }
+                        } else if input.ui_click && state == AppState::Controls {
+                            let (w, h) = (gfx.size.width as f32, gfx.size.height as f32);
+                            let rects = screens::controls_layout(w, h);
+                            let hit = rects
+                                .iter()
+                                .position(|r| r.contains(input.cursor.0, input.cursor.1));
+                            match hit {
+                                Some(i) if i < Action::ALL.len() => {
+                                    rebinding = Some(Action::ALL[i])
+                                }
+                                Some(_) => {
+                                    rebinding = None;
+                                    state = state.on_action(MenuAction::Back);
+                                }
+                                None => rebinding = None,
+                            }
+                        } else if input.ui_click && state == AppState::Mouse {
+                            let (w, h) = (gfx.size.width as f32, gfx.size.height as f32);
+                            let rects = screens::menu_buttons(w, h, 4);
+                            let hit = rects
+                                .iter()
+                                .position(|r| r.contains(input.cursor.0, input.cursor.1));
+                            let mouse = &mut settings.mouse;
+                            match hit {
+                                Some(0) => {
+                                    let value = screens::slider_value(rects[0], input.cursor.0);
+                                    mouse.sensitivity = value;
+                                }
+                                Some(1) => mouse.invert_y = !mouse.invert_y,
+                                Some(2) => mouse.raw_input = !mouse.raw_input,
+                                Some(_) => state = state.on_action(MenuAction::Back),
+                                None => {}
+                            }
+                            if matches!(hit, Some(0..=2)) {
+                                settings_mtime = save_settings(&settings);
+                            }
+                        } else if input.ui_click && state == AppState::Audio {
+                            let (w, h) = (gfx.size.width as f32, gfx.size.height as f32);
+                            let rects = screens::menu_buttons(w, h, 4);
+                            let hit = rects
+                                .iter()
+                                .position(|r| r.contains(input.cursor.0, input.cursor.1));
+                            let audio = &mut settings.audio;
+                            match hit {
+                                Some(i @ 0..=1) => {
+                                    let value = screens::slider_at(rects[i], input.cursor.0);
+                                    if i == 0 {
+                                        audio.volume = value;
+                                    } else {
+                                        audio.music_volume = value;
+                                    }
+                                }
+                                Some(2) => audio.shuffle = !audio.shuffle,
+                                Some(_) => state = state.on_action(MenuAction::Back),
+                                None => {}
+                            }
+                            if matches!(hit, Some(0..=2)) {
+                                settings_mtime = save_settings(&settings);
+                            }
+                        } else if input.ui_click && state == AppState::Multiplayer {
+                            let (w, h) = (gfx.size.width as f32, gfx.size.height as f32);
+                            let found = lan.as_ref().map_or(&[][..], lan::Scanner::servers);
+                            let found = &found[..found.len().min(LAN_LIST_MAX)];
+                            let hit = screens::menu_buttons(w, h, found.len() + 1)
+                                .iter()
+                                .position(|r| r.contains(input.cursor.0, input.cursor.1));
+                            match hit.map(|i| found.get(i)) {
+                                Some(Some(found)) => {
+                                    let addr = found.addr.to_string();
+                                    match net::connect(&addr, &opts.name) {
+                                        Ok((params, to_server, from_server)) => {
+                                            log::info!(
+                                                "Verbunden mit {addr}: Welt {:?}",
+                                                params.name
+                                            );
+                                            if let Some(sim) = sim.take() {
+                                                shut_down(sim, opts.record.as_deref());
+                                            }
+                                            let view_distance = settings.graphics.view_distance;
+                                            client = Client::networked(
+                                                to_server,
+                                                from_server,
+                                                view_distance,
+                                            );
+                                            presentation.world = params.name;
+                                            presentation.session = Session::Multiplayer { addr };
+                                            state = AppState::Playing;
+                                        }
+                                        Err(e) => {
+                                            log::warn!("{addr}: {e:#}");
+                                            lan_error = Some(format!("Connection failed: {e:#}"));
+                                        }
+                                    }
+                                }
+                                Some(None) => state = state.on_action(MenuAction::Back),
+                                None => {}
+                            }
+                        } else if input.ui_click {
+                            // Menüs: Klick auf einen Button
+                            let (w, h) = (gfx.size.width as f32, gfx.size.height as f32);
+                            let buttons = state.buttons();
+                            let hit = screens::menu_buttons(w, h, buttons.len())
+                                .iter()
+                                .position(|r| r.contains(input.cursor.0, input.cursor.1));
+                            if let Some(i) = hit {
+                                let action = buttons[i].1;
+                                match action {
+                                    MenuAction::SaveAndQuit => {
+                                        if let Some(sim) = &sim {
+                                            sim.run(|server| server.game_mut().save());
+                                        }
+                                    }
+                                    MenuAction::Quit => {
+                                        if let Some(sim) = sim.take() {
+                                            shut_down(sim, opts.record.as_deref());
+                                        }
+                                        elwt.exit();
+                                    }
+                                    _ => {}
+                                }
+                                state = state.on_action(action);
+                            }
+                        }
+
+                        // Menü-Eingaben dürfen nicht ins Spiel durchsickern
                         input.clear_one_shots();
-                        next_tick += tick_dt;
+                        if !state.ticking() {
+                            input.break_held = false;
+                            input.place_held = false;
+                        }
+                        let frame = pacer.ticked(now);
+
+                        // Integrierter Server: tickt im eigenen Takt auf seinem Thread und
+                        // bekommt von hier nur Eingaben; Ergebnisse kommen als Nachrichten
+                        client.set_view_distance(settings.graphics.view_distance);
+                        client.set_screen_size(gfx.size.width as f32, gfx.size.height as f32);
+                        if let Some(sim) = &sim {
+                            sim.set_view(input.cursor, (gfx.size.width, gfx.size.height));
+                            sim.set_paused(!state.ticking());
+                        }
+                        client.poll();
+                        if let Some(reason) = client.disconnected() {
+                            log::error!("Verbindung zum Server verloren: {reason}");
+                            elwt.exit();
+                        }
+                        presentation.update(&window, now);
+
+                        // Maus nur im Spiel ohne offenen Screen gefangen; nach dem Poll, damit
+                        // ein eben geöffneter Screen die Maus sofort freigibt
+                        let focus = focus(state, &mut sim, &client);
+                        let raw = settings.mouse.raw_input;
+                        sync_mouse_lock(&window, focus, &mut mouse_locked, raw, &mut input);
+
+                        let (pos, dir) = match detached_camera(&playback, free_camera) {
+                            Some(spec) => (spec.pos, spec.dir()),
+                            None => client.camera_pos_dir(),
+                        };
+
+                        // Ohne `audio`-Feature werden die Ereignisse nur geloggt
+                        for ev in client.drain_sound_events() {
+                            log::debug!("sound {:?} {:?} at {:?}", ev.kind, ev.group, ev.pos);
+                            #[cfg(feature = "audio")]
+                            if let Some(audio) = &mut audio {
+                                audio.push(ev);
+                            }
+                        }
+                        #[cfg(feature = "audio")]
+                        if let Some(audio) = &mut audio {
+                            audio.play(pos, settings.audio.volume);
+                            let sky_light = client.sky_light_at(pos);
+                            let dt = tick_dt.as_secs_f32();
+                            audio.update(client.daylight(), sky_light, &settings.audio, dt);
+                        }
+                        gfx.set_daylight(client.daylight());
+                        client.set_fov(settings.graphics.fov);
+                        gfx.set_render_scale(settings.graphics.render_scale);
+                        client.set_mesh_budget(settings.graphics.mesh_cache_mb);
+                        eye.push(pos);
+                        gfx.set_camera(client.camera_at(pos, dir));
 
-                        let (pos, dir) = game.camera_pos_dir();
-                        gfx.set_camera(pos, dir);
+                        let meshes = client.update_meshes(gfx.size.width, gfx.size.height);
+                        gfx.update_chunk_meshes(meshes);
+                        gfx.set_chunk_draws(&client.chunk_draws());
+                        metrics::set(Metric::MeshQueue, client.mesh_backlog() as f64);
+                        let mesh_mib = client.mesh_cache_bytes() as f64 / (1 << 20) as f64;
+                        metrics::set(Metric::MeshMib, mesh_mib);
 
-                        // Chunk-Streaming: einfacher Radius um den Spieler
-                        game.maintain_chunk_window(4);
+                        // Sterne und Mond; Regenwolken verdecken sie
+                        let (time_of_day, day) = client.clock();
+                        let night = sky::night(time_of_day) * (1.0 - client.weather().0);
+                        let radius = client.far_plane() * SKY_DISTANCE;
+                        let (verts, inds) = sky::sky_mesh(pos, radius, time_of_day, day, night);
+                        gfx.set_sky(&verts, &inds);
 
-                        if let Some((verts, inds)) =
-                            game.mesh_loaded_chunks_if_dirty(gfx.size.width, gfx.size.height)
-                        {
-                            gfx.set_mesh(&verts, &inds);
+                        // Andere Spieler samt dem Block, den sie anvisieren; die eigene Figur,
+                        // sobald die Kamera nicht im Kopf sitzt
+                        let others = client.remote_players();
+                        let states: Vec<_> = others.iter().map(|(_, st)| *st).collect();
+                        let outside = client.third_person()
+                            || client.top_down()
+                            || detached_camera(&playback, free_camera).is_some();
+                        let poses = client.player_poses(outside);
+                        let (verts, inds) = player_model::model_mesh(&poses);
+                        gfx.set_entities(&verts, &inds);
+                        let (verts, inds) = player_model::shadow_mesh(client.world(), &poses);
+                        gfx.set_shadows(&verts, &inds);
+                        let mut lines = client.overlay_lines().to_vec();
+                        lines.extend(remote_player::target_lines(&states));
+                        let (rain, snow) = client.weather();
+                        if rain > 0.0 {
+                            let time = client.ticks() as f32 / 20.0;
+                            let world = client.world();
+                            lines.extend(weather::precipitation_lines(
+                                world, pos, rain, snow, time,
+                            ));
                         }
+                        gfx.set_lines(&lines);
 
+                        let mut ui = UiBatch::new(gfx.size.width, gfx.size.height);
+                        for (name, st) in &others {
+                            let (x, y, z) = st.pos;
+                            let (dx, dy, dz) = (x - pos.0, y - pos.1, z - pos.2);
+                            if dx * dx + dy * dy + dz * dz > NAME_TAG_RANGE * NAME_TAG_RANGE {
+                                continue;
+                            }
+                            if let Some(at) = gfx.project((x, y + NAME_TAG_HEIGHT, z)) {
+                                screens::draw_name_tag(&mut ui, at, name);
+                            }
+                        }
+                        // Übers Netz noch ohne HUD: nur Welt, Chat und Menüs
+                        let snapshot = sim.as_mut().map(SimThread::snapshot);
+                        if state.ticking() {
+                            let waypoints = snapshot
+                                .and_then(|s| s.level.as_ref())
+                                .map_or(&[][..], |l| &l.waypoints[..]);
+                            for w in waypoints {
+                                let (x, y, z) = w.pos;
+                                let dist = ((x - pos.0).powi(2) + (z - pos.2).powi(2)).sqrt();
+                                if let Some(at) = gfx.project((x, y + WAYPOINT_HEIGHT, z)) {
+                                    screens::draw_waypoint_marker(&mut ui, at, &w.name, dist);
+                                }
+                            }
+                            let rotate = settings.graphics.minimap_rotate;
+                            minimap.update(client.world(), pos, rotate);
+                            let yaw = dir.0.atan2(dir.2);
+                            screens::draw_compass(&mut ui, (pos.0, pos.2), yaw, waypoints);
+                            if settings.graphics.show_coordinates {
+                                let feet = (pos.0, pos.1 - EYE_HEIGHT, pos.2);
+                                let target = snapshot.and_then(|s| s.target);
+                                screens::draw_coordinates(&mut ui, feet, yaw, target);
+                            }
+                            minimap.draw(&mut ui, (pos.0, pos.2), yaw, rotate, waypoints);
+                        }
+                        match &snapshot {
+                            Some(snapshot) => ui.append(&snapshot.ui),
+                            None => {
+                                screens::draw_console(&mut ui, client.console(), client.ticks())
+                            }
+                        }
+                        if debug_overlay && state.ticking() {
+                            let mut memory = snapshot.map(|s| s.memory).unwrap_or_default();
+                            client.add_memory(&mut memory);
+                            memory.gpu_bytes = gfx.buffer_bytes() as usize;
+                            let lines = memory.lines(settings.graphics.view_distance);
+                            screens::draw_debug_lines(&mut ui, &lines);
+                        } else if free_camera.is_some() && state.ticking() {
+                            let key = settings.bindings.key(Action::FreeCamera);
+                            let line = format!("Free camera ({})", keybinds::key_label(key));
+                            screens::draw_debug_lines(&mut ui, &[line]);
+                        }
+                        if state == AppState::Controls {
+                            let bindings = &settings.bindings;
+                            screens::draw_controls(&mut ui, bindings, rebinding, input.cursor);
+                        } else if state == AppState::Mouse {
+                            screens::draw_mouse_settings(&mut ui, &settings.mouse, input.cursor);
+                        } else if state == AppState::Audio {
+                            screens::draw_audio_settings(&mut ui, &settings.audio, input.cursor);
+                        } else if state == AppState::Multiplayer {
+                            let found = lan.as_ref().map_or(&[][..], lan::Scanner::servers);
+                            let mut labels: Vec<String> =
+                                found.iter().take(LAN_LIST_MAX).map(|s| s.label()).collect();
+                            labels.push("Back".into());
+                            let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+                            screens::draw_menu(&mut ui, state.title(), &labels, input.cursor);
+                            let caption = match &lan_error {
+                                Some(e) => e.as_str(),
+                                None if found.is_empty() => "Searching for LAN servers...",
+                                None => "Click a server to join",
+                            };
+                            screens::draw_caption(&mut ui, caption);
+                        } else if !state.ticking() {
+                            let labels: Vec<&str> =
+                                state.buttons().iter().map(|(label, _)| *label).collect();
+                            screens::draw_menu(&mut ui, state.title(), &labels, input.cursor);
+                            if state == AppState::MainMenu
+                                && let Some(level) = snapshot.and_then(|s| s.level.as_ref())
+                            {
+                                let caption = format!(
+                                    "{} - seed {} - {} played",
+                                    level.name,
+                                    level.seed,
+                                    level.play_time_label()
+                                );
+                                screens::draw_caption(&mut ui, &caption);
+                            }
+                        }
+                        gfx.set_ui(&ui);
+
+                        if frame {
+                            window.request_redraw();
+                        }
+                    }
+                    // Mit Fokus läuft jede Runde ein Bild, dazwischen interpoliert
+                    if pacer.mode() == Mode::Focused {
                         window.request_redraw();
                     }
                 }
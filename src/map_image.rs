@@ -0,0 +1,57 @@
+use anyhow::{bail, Context, Result};
+
+use crate::block::Block;
+use crate::world::{BlockPos, World};
+
+/// Größere Karten werden unhandlich (1 Pixel pro Block)
+pub const MAX_MAP_SIZE: i32 = 4096;
+
+/// Draufsicht wie im DebugRenderer, aber offline: oberster Block jeder Säule in min..=max,
+/// heller je höher, dazu Relief gegenüber der nördlichen Nachbarsäule. Norden ist oben.
+/// Liefert Breite, Höhe und RGBA-Pixel; leere Säulen bleiben transparent.
+pub fn render(world: &World, min: BlockPos, max: BlockPos) -> Result<(u32, u32, Vec<u8>)> {
+    let (w, d) = (max.0 - min.0 + 1, max.2 - min.2 + 1);
+    if w > MAX_MAP_SIZE || d > MAX_MAP_SIZE {
+        bail!("{w}x{d} is too large for a map (max {MAX_MAP_SIZE} per axis)");
+    }
+    let mut pixels = vec![0u8; (w * d * 4) as usize];
+    // Höhen der vorigen (nördlichen) Zeile fürs Relief
+    let mut north: Vec<Option<i32>> = vec![None; w as usize];
+    for z in 0..d {
+        for x in 0..w {
+            let Some((y, b)) = world.top_block(min.0 + x, min.2 + z, min.1, max.1) else {
+                north[x as usize] = None;
+                continue;
+            };
+            let color = shade(b, y, (min.1, max.1), north[x as usize]);
+            north[x as usize] = Some(y);
+            let [r, g, bl] = color.map(|c| (c * 255.0).clamp(0.0, 255.0) as u8);
+            let i = ((z * w + x) * 4) as usize;
+            pixels[i..i + 4].copy_from_slice(&[r, g, bl, 255]);
+        }
+    }
+    Ok((w as u32, d as u32, pixels))
+}
+
+/// Farbe einer Säule: heller je höher in `range`, Relief gegenüber der nördlichen Nachbarsäule
+pub fn shade(b: Block, y: i32, (min_y, max_y): (i32, i32), north: Option<i32>) -> [f32; 3] {
+    let span = (max_y - min_y).max(1) as f32;
+    let mut shade = 0.7 + 0.3 * (y - min_y) as f32 / span;
+    match north {
+        Some(ny) if y > ny => shade *= 1.12,
+        Some(ny) if y < ny => shade *= 0.85,
+        _ => {}
+    }
+    b.color().map(|c| c * shade)
+}
+
+pub fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().context("PNG header")?;
+    writer.write_image_data(rgba).context("PNG data")?;
+    writer.finish().context("PNG finish")?;
+    Ok(out)
+}
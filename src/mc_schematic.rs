@@ -0,0 +1,386 @@
+use std::io::Read;
+
+use anyhow::{anyhow, bail, Context, Result};
+use flate2::read::GzDecoder;
+
+use crate::block::{state, Block};
+use crate::block_buffer::BlockBuffer;
+
+/// Größere Importe würden nur den Speicher fluten (Einfügen ist ohnehin begrenzt)
+const MAX_IMPORT_VOLUME: i64 = 256 * 256 * 256;
+
+/// Minecraft-Schematic (MCEdit `.schematic` oder Sponge `.schem` v1–v3, meist gzip)
+/// in einen Blockquader. Unbekannte Blöcke werden Stein bzw. bei Deko Luft.
+pub fn decode(data: &[u8]) -> Result<BlockBuffer> {
+    let mut raw = Vec::new();
+    let data = if data.starts_with(&[0x1f, 0x8b]) {
+        GzDecoder::new(data).read_to_end(&mut raw).context("gzip")?;
+        &raw[..]
+    } else {
+        data
+    };
+    let root = Nbt::parse(data)?;
+    // Sponge v3 schachtelt alles unter "Schematic"
+    let root = root.get("Schematic").unwrap_or(&root);
+
+    let dim = |key: &str| -> Result<i32> {
+        match root.get(key) {
+            // Shorts sind in Schematics vorzeichenlos
+            Some(Nbt::Short(v)) => Ok(*v as u16 as i32),
+            _ => bail!("missing {key}"),
+        }
+    };
+    let size = (dim("Width")?, dim("Height")?, dim("Length")?);
+    let volume = size.0 as i64 * size.1 as i64 * size.2 as i64;
+    if volume == 0 || volume > MAX_IMPORT_VOLUME {
+        bail!("unsupported size {size:?}");
+    }
+
+    let cells = if let Some(Nbt::ByteArray(ids)) = root.get("Blocks") {
+        legacy_cells(root, ids, (size.0 * size.2) as usize)
+    } else {
+        sponge_cells(root)?
+    };
+    if (cells.len() as i64) < volume {
+        bail!("block data too short ({} < {volume})", cells.len());
+    }
+
+    // Schematic-Reihenfolge: (y * Länge + z) * Breite + x
+    let (w, h, d) = size;
+    let mut buf = BlockBuffer::new(size, (0, 0, 0));
+    for y in 0..h {
+        for z in 0..d {
+            for x in 0..w {
+                buf.set((x, y, z), cells[((y * d + z) * w + x) as usize]);
+            }
+        }
+    }
+    Ok(buf)
+}
+
+/// Sponge-Format: Palette aus Blockstate-Strings, Daten als VarInts
+fn sponge_cells(root: &Nbt) -> Result<Vec<(Block, u8)>> {
+    let blocks = root.get("Blocks").unwrap_or(root);
+    // v3: Blocks.Data, v1/v2: BlockData
+    let data = blocks.get("Data").or(root.get("BlockData"));
+    let (palette, data) = match (blocks.get("Palette"), data) {
+        (Some(Nbt::Compound(p)), Some(Nbt::ByteArray(d))) => (p, d),
+        _ => bail!("neither MCEdit nor Sponge schematic"),
+    };
+    // Indizes liegen unter PaletteMax (v1/v2) und nie über der Zahl der Einträge;
+    // sonst könnte eine Datei mit einem einzigen Eintrag riesige Tabellen anlegen
+    let limit = match root.get("PaletteMax") {
+        Some(Nbt::Int(max)) => usize::try_from(*max).unwrap_or(0).min(palette.len()),
+        _ => palette.len(),
+    };
+    let mut by_index = Vec::new();
+    for (name, tag) in palette {
+        let Nbt::Int(i) = tag else {
+            bail!("invalid palette entry {name}");
+        };
+        let i = usize::try_from(*i).map_err(|_| anyhow!("invalid palette index {i}"))?;
+        if i >= limit {
+            bail!("palette index {i} of {name} out of range (max {limit})");
+        }
+        if by_index.len() <= i {
+            by_index.resize(i + 1, (Block::Air, 0));
+        }
+        by_index[i] = map_block_state(name);
+    }
+
+    let mut cells = Vec::with_capacity(data.len());
+    let mut bytes = data.iter().map(|b| *b as u8);
+    while let Some(first) = bytes.next() {
+        let (mut value, mut shift, mut byte) = (0usize, 0, first);
+        loop {
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            if shift > 28 {
+                bail!("invalid varint");
+            }
+            byte = bytes.next().ok_or_else(|| anyhow!("truncated varint"))?;
+        }
+        let cell = by_index.get(value).ok_or_else(|| anyhow!("palette index {value} missing"))?;
+        cells.push(*cell);
+    }
+    Ok(cells)
+}
+
+/// MCEdit-Format: numerische IDs (vor 1.13) plus 4 Bit Daten; `layer` = Zellen pro Ebene
+fn legacy_cells(root: &Nbt, ids: &[i8], layer: usize) -> Vec<(Block, u8)> {
+    let data = match root.get("Data") {
+        Some(Nbt::ByteArray(d)) => d.as_slice(),
+        _ => &[],
+    };
+    let mut cells: Vec<(Block, u8)> = ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| map_legacy(*id as u8, data.get(i).map_or(0, |d| *d as u8 & 0xf)))
+        .collect();
+    // Obere Türhälfte kennt ihre Richtung nicht: von der unteren übernehmen
+    for i in layer..cells.len() {
+        if let ((Block::Door, upper), (Block::Door, lower)) = (cells[i], cells[i - layer])
+            && upper & state::UPPER_HALF != 0
+        {
+            cells[i].1 = state::UPPER_HALF | (lower & !state::UPPER_HALF);
+        }
+    }
+    cells
+}
+
+fn map_legacy(id: u8, data: u8) -> (Block, u8) {
+    let torch = |d| match d {
+        1 => state::TORCH_WALL_PX,
+        2 => state::TORCH_WALL_NX,
+        3 => state::TORCH_WALL_PZ,
+        4 => state::TORCH_WALL_NZ,
+        _ => state::TORCH_FLOOR,
+    };
+    match id {
+        0 | 37 | 38 | 39 | 40 | 78 | 175 => (Block::Air, 0),
+        2 | 110 => (Block::Grass, 0),
+        3 => (Block::Dirt, 0),
+        5 => (Block::Planks, 0),
+        6 => (Block::Sapling, 0),
+        8 | 9 => (Block::Water, 0),
+        17 | 162 => {
+            let axis = match data >> 2 {
+                1 => state::AXIS_X,
+                2 => state::AXIS_Z,
+                _ => state::AXIS_Y,
+            };
+            (Block::Log, axis)
+        }
+        18 | 161 => (Block::Leaves, state::LEAVES_PERSISTENT),
+        20 | 95 | 102 | 160 => (Block::Glass, 0),
+        31 => (Block::TallGrass, 0),
+        44 | 126 => {
+            let half = if data & 0x8 != 0 { state::SLAB_TOP } else { state::SLAB_BOTTOM };
+            (Block::StoneSlab, half)
+        }
+        50 => (Block::Torch, torch(data)),
+        54 | 146 => (Block::Chest, 0),
+        58 => (Block::CraftingTable, 0),
+        59 => (Block::Wheat, data.min(state::CROP_MAX_STAGE)),
+        60 => (Block::Farmland, 0),
+        64 | 193..=197 => {
+            if data & 0x8 != 0 {
+                (Block::Door, state::UPPER_HALF)
+            } else {
+                // alt: 0 = Osten, 1 = Süden, 2 = Westen, 3 = Norden
+                let facing = [1, 2, 3, 0][(data & 0x3) as usize];
+                let open = if data & 0x4 != 0 { state::DOOR_OPEN } else { 0 };
+                (Block::Door, facing | open)
+            }
+        }
+        _ => (Block::Stone, 0),
+    }
+}
+
+/// Blockstate-String wie "minecraft:oak_log[axis=x]" auf Registry-Block und State
+fn map_block_state(full: &str) -> (Block, u8) {
+    let (name, props) = match full.split_once('[') {
+        Some((n, p)) => (n, p.trim_end_matches(']')),
+        None => (full, ""),
+    };
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let prop = |key: &str| {
+        props
+            .split(',')
+            .filter_map(|kv| kv.split_once('='))
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    };
+    let facing = || match prop("facing") {
+        Some("east") => 1,
+        Some("south") => 2,
+        Some("west") => 3,
+        _ => 0,
+    };
+
+    match name {
+        "air" | "cave_air" | "void_air" => (Block::Air, 0),
+        "dirt" | "coarse_dirt" | "rooted_dirt" | "podzol" | "mud" => (Block::Dirt, 0),
+        "grass_block" | "mycelium" => (Block::Grass, 0),
+        "farmland" => (Block::Farmland, 0),
+        "water" => (Block::Water, 0),
+        "chest" | "trapped_chest" => (Block::Chest, 0),
+        "crafting_table" => (Block::CraftingTable, 0),
+        "torch" => (Block::Torch, state::TORCH_FLOOR),
+        "wall_torch" => {
+            let st = match prop("facing") {
+                Some("east") => state::TORCH_WALL_PX,
+                Some("west") => state::TORCH_WALL_NX,
+                Some("south") => state::TORCH_WALL_PZ,
+                _ => state::TORCH_WALL_NZ,
+            };
+            (Block::Torch, st)
+        }
+        "wheat" => {
+            let age = prop("age").and_then(|a| a.parse().ok()).unwrap_or(0);
+            (Block::Wheat, u8::min(age, state::CROP_MAX_STAGE))
+        }
+        // vor 1.20.3 hieß kurzes Gras "grass"
+        "grass" | "short_grass" | "tall_grass" | "fern" | "large_fern" => (Block::TallGrass, 0),
+        n if n.ends_with("_log") || n.ends_with("_wood") || n.ends_with("_stem") => {
+            let axis = match prop("axis") {
+                Some("x") => state::AXIS_X,
+                Some("z") => state::AXIS_Z,
+                _ => state::AXIS_Y,
+            };
+            (Block::Log, axis)
+        }
+        n if n.ends_with("_leaves") => (Block::Leaves, state::LEAVES_PERSISTENT),
+        n if n == "glass" || n.ends_with("_glass") || n.ends_with("glass_pane") => {
+            (Block::Glass, 0)
+        }
+        n if n.ends_with("_slab") => match prop("type") {
+            Some("top") => (Block::StoneSlab, state::SLAB_TOP),
+            Some("double") => (Block::Stone, 0),
+            _ => (Block::StoneSlab, state::SLAB_BOTTOM),
+        },
+        n if n.ends_with("_planks") => (Block::Planks, 0),
+        n if n.ends_with("_sapling") => (Block::Sapling, 0),
+        n if n.ends_with("_door") => {
+            let upper = if prop("half") == Some("upper") { state::UPPER_HALF } else { 0 };
+            let open = if prop("open") == Some("true") { state::DOOR_OPEN } else { 0 };
+            (Block::Door, facing() | upper | open)
+        }
+        n if is_decoration(n) => (Block::Air, 0),
+        _ => (Block::Stone, 0),
+    }
+}
+
+/// Kleinkram ohne Gegenstück, der als Steinblock stören würde
+fn is_decoration(name: &str) -> bool {
+    const PARTS: &[&str] = &[
+        "flower", "tulip", "orchid", "dandelion", "poppy", "allium", "bluet", "daisy", "rose",
+        "lilac", "peony", "mushroom", "button", "pressure_plate", "sign", "banner", "carpet",
+        "rail", "vine", "lever", "redstone_wire", "snow", "lantern", "candle", "pot", "head",
+        "skull", "bush", "sugar_cane", "kelp", "seagrass", "trapdoor", "ladder", "fence",
+    ];
+    PARTS.iter().any(|p| name.contains(p))
+}
+
+/// Minimaler NBT-Leser (Big Endian, nur was Schematics brauchen)
+#[derive(Debug)]
+enum Nbt {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Nbt>),
+    Compound(Vec<(String, Nbt)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Nbt {
+    fn parse(data: &[u8]) -> Result<Nbt> {
+        let mut r = NbtReader { data, pos: 0 };
+        if r.u8()? != 10 {
+            bail!("NBT root is not a compound");
+        }
+        r.string()?;
+        r.payload(10, 0)
+    }
+
+    fn get(&self, key: &str) -> Option<&Nbt> {
+        match self {
+            Nbt::Compound(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// Schutz vor bösartig tief verschachtelten Dateien
+const MAX_NBT_DEPTH: usize = 64;
+
+struct NbtReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl NbtReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self.data.get(self.pos..self.pos + N).ok_or_else(|| anyhow!("truncated NBT"))?;
+        self.pos += N;
+        Ok(bytes.try_into().expect("Länge passt"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn len(&mut self) -> Result<usize> {
+        let n = i32::from_be_bytes(self.take()?);
+        let n = usize::try_from(n).map_err(|_| anyhow!("negative NBT length"))?;
+        // jedes Element braucht mindestens ein Byte
+        if n > self.data.len() - self.pos {
+            bail!("NBT length {n} exceeds file");
+        }
+        Ok(n)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let n = u16::from_be_bytes(self.take()?) as usize;
+        let bytes = self.data.get(self.pos..self.pos + n).ok_or_else(|| anyhow!("truncated NBT"))?;
+        self.pos += n;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn payload(&mut self, tag: u8, depth: usize) -> Result<Nbt> {
+        if depth > MAX_NBT_DEPTH {
+            bail!("NBT nested too deeply");
+        }
+        Ok(match tag {
+            1 => Nbt::Byte(i8::from_be_bytes(self.take()?)),
+            2 => Nbt::Short(i16::from_be_bytes(self.take()?)),
+            3 => Nbt::Int(i32::from_be_bytes(self.take()?)),
+            4 => Nbt::Long(i64::from_be_bytes(self.take()?)),
+            5 => Nbt::Float(f32::from_be_bytes(self.take()?)),
+            6 => Nbt::Double(f64::from_be_bytes(self.take()?)),
+            7 => {
+                let n = self.len()?;
+                Nbt::ByteArray((0..n).map(|_| Ok(self.u8()? as i8)).collect::<Result<_>>()?)
+            }
+            8 => Nbt::String(self.string()?),
+            9 => {
+                let inner = self.u8()?;
+                let n = self.len()?;
+                let items = (0..n).map(|_| self.payload(inner, depth + 1));
+                Nbt::List(items.collect::<Result<_>>()?)
+            }
+            10 => {
+                let mut entries = Vec::new();
+                loop {
+                    let tag = self.u8()?;
+                    if tag == 0 {
+                        break;
+                    }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(tag, depth + 1)?));
+                }
+                Nbt::Compound(entries)
+            }
+            11 => {
+                let n = self.len()?;
+                let items = (0..n).map(|_| Ok(i32::from_be_bytes(self.take()?)));
+                Nbt::IntArray(items.collect::<Result<_>>()?)
+            }
+            12 => {
+                let n = self.len()?;
+                let items = (0..n).map(|_| Ok(i64::from_be_bytes(self.take()?)));
+                Nbt::LongArray(items.collect::<Result<_>>()?)
+            }
+            _ => bail!("unknown NBT tag {tag}"),
+        })
+    }
+}
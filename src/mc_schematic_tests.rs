@@ -0,0 +1,58 @@
+//! Sponge-Schematics aus handgebautem NBT, darunter feindselige Paletten
+
+use crate::block::Block;
+use crate::mc_schematic::decode;
+
+/// Benannter NBT-Tag (Typ, Name, Nutzdaten)
+fn tag(out: &mut Vec<u8>, kind: u8, name: &str, payload: &[u8]) {
+    out.push(kind);
+    out.extend((name.len() as u16).to_be_bytes());
+    out.extend(name.as_bytes());
+    out.extend(payload);
+}
+
+fn int(v: i32) -> Vec<u8> {
+    v.to_be_bytes().to_vec()
+}
+
+/// 1×1×2 Sponge v2 mit den Paletteneinträgen `palette` und den Daten `data` (VarInts)
+fn sponge(palette: &[(&str, i32)], palette_max: i32, data: &[u8]) -> Vec<u8> {
+    let mut entries = Vec::new();
+    for (name, i) in palette {
+        tag(&mut entries, 3, name, &int(*i));
+    }
+    entries.push(0);
+
+    let mut body = Vec::new();
+    tag(&mut body, 2, "Width", &1i16.to_be_bytes());
+    tag(&mut body, 2, "Height", &2i16.to_be_bytes());
+    tag(&mut body, 2, "Length", &1i16.to_be_bytes());
+    tag(&mut body, 3, "PaletteMax", &int(palette_max));
+    tag(&mut body, 10, "Palette", &entries);
+    let mut bytes = int(data.len() as i32);
+    bytes.extend(data);
+    tag(&mut body, 7, "BlockData", &bytes);
+    body.push(0);
+
+    let mut out = Vec::new();
+    tag(&mut out, 10, "Schematic", &body);
+    out
+}
+
+#[test]
+fn sponge_palette_maps_blocks() {
+    let data = sponge(&[("minecraft:air", 0), ("minecraft:stone", 1)], 2, &[1, 0]);
+    let buf = decode(&data).unwrap();
+    assert_eq!(buf.get((0, 0, 0)).0, Block::Stone);
+    assert_eq!(buf.get((0, 1, 0)).0, Block::Air);
+}
+
+#[test]
+fn hostile_palette_index_is_rejected() {
+    // Ein Eintrag mit Index nahe i32::MAX würde sonst Gigabytes an Tabelle anlegen
+    for (index, max) in [(i32::MAX, i32::MAX), (5, 2), (1, 1)] {
+        let data = sponge(&[("minecraft:stone", index)], max, &[0, 0]);
+        let err = decode(&data).unwrap_err().to_string();
+        assert!(err.contains("out of range"), "{index}/{max}: {err}");
+    }
+}
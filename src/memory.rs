@@ -0,0 +1,68 @@
+//! Grobe Speicherbilanz: Chunks, Entities, Meshes und GPU-Puffer. Die Simulation füllt
+//! ihren Teil (`Game::memory_report`), Client und Renderer ergänzen den Rest. Daraus
+//! lässt sich abschätzen, wie viel eine größere Sichtweite kosten würde.
+
+use crate::chunk::ChunkPos;
+use crate::game::chunk_window_around;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// Geladene Chunks der Simulation
+    pub chunks: usize,
+    /// Blöcke, Zustände und Licht dieser Chunks
+    pub chunk_bytes: usize,
+    /// Block-Entities, Drops, Spieler und Zwischenablage
+    pub entity_bytes: usize,
+    /// Weltspiegel des Clients (übers Netz der einzige Chunkspeicher)
+    pub client_chunks: usize,
+    pub client_chunk_bytes: usize,
+    pub mesh_bytes: usize,
+    pub gpu_bytes: usize,
+}
+
+impl MemoryReport {
+    pub fn total(&self) -> usize {
+        self.chunk_bytes
+            + self.entity_bytes
+            + self.client_chunk_bytes
+            + self.mesh_bytes
+            + self.gpu_bytes
+    }
+
+    /// Durchschnitt pro geladenem Chunk (alles zusammen, None ohne Chunks)
+    pub fn per_chunk(&self) -> Option<usize> {
+        let chunks = self.chunks.max(self.client_chunks);
+        (chunks > 0).then(|| self.total() / chunks)
+    }
+
+    /// Hochgerechneter Gesamtbedarf bei Sichtweite `radius`
+    pub fn estimate(&self, radius: i32) -> Option<usize> {
+        let window = chunk_window_around(ChunkPos::new(0, 0, 0), radius);
+        self.per_chunk().map(|b| b * window.len())
+    }
+
+    /// Zeilen fürs Debug-Overlay
+    pub fn lines(&self, view_distance: i32) -> Vec<String> {
+        let mut lines = vec![
+            format!("Memory: {} total", mib(self.total())),
+            format!("Chunks: {} in {}", self.chunks, mib(self.chunk_bytes)),
+            format!("Entities: {}", mib(self.entity_bytes)),
+            format!("Client chunks: {} in {}", self.client_chunks, mib(self.client_chunk_bytes)),
+            format!("Meshes: {}", mib(self.mesh_bytes)),
+            format!("GPU buffers: {}", mib(self.gpu_bytes)),
+        ];
+        let views = [view_distance, view_distance * 3 / 2, view_distance * 2];
+        let estimates: Vec<String> = views
+            .iter()
+            .filter_map(|&d| self.estimate(d).map(|b| format!("{d}: ~{}", mib(b))))
+            .collect();
+        if !estimates.is_empty() {
+            lines.push(format!("View distance {}", estimates.join(", ")));
+        }
+        lines
+    }
+}
+
+fn mib(bytes: usize) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64)
+}
@@ -0,0 +1,102 @@
+/// Oberster Spielzustand; nur `Playing` lässt die Simulation laufen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppState {
+    MainMenu,
+    Playing,
+    Paused,
+    /// Einstellungen (aus dem Pausemenü)
+    Settings,
+    /// Tastenbelegung (aus den Einstellungen)
+    Controls,
+    /// Maus: Empfindlichkeit, Y-Invertierung, Rohdaten
+    Mouse,
+    /// Lautstärke, Musiklautstärke, Zufallswiedergabe
+    Audio,
+    /// Gefundene LAN-Server (aus dem Hauptmenü)
+    Multiplayer,
+}
+
+/// Was ein Menü-Button auslöst
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuAction {
+    Play,
+    Multiplayer,
+    Resume,
+    Settings,
+    Controls,
+    Mouse,
+    Audio,
+    Back,
+    SaveAndQuit,
+    Quit,
+}
+
+impl AppState {
+    pub fn ticking(self) -> bool {
+        self == AppState::Playing
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            AppState::MainMenu => "Voxel Game",
+            AppState::Playing => "",
+            AppState::Paused => "Paused",
+            AppState::Settings => "Settings",
+            AppState::Controls => "Controls",
+            AppState::Mouse => "Mouse",
+            AppState::Audio => "Audio",
+            AppState::Multiplayer => "Multiplayer",
+        }
+    }
+
+    /// Buttons des Menüs von oben nach unten
+    pub fn buttons(self) -> &'static [(&'static str, MenuAction)] {
+        match self {
+            AppState::MainMenu => &[
+                ("Play", MenuAction::Play),
+                ("Multiplayer", MenuAction::Multiplayer),
+                ("Quit", MenuAction::Quit),
+            ],
+            AppState::Playing => &[],
+            AppState::Paused => &[
+                ("Resume", MenuAction::Resume),
+                ("Settings", MenuAction::Settings),
+                ("Save & Quit", MenuAction::SaveAndQuit),
+            ],
+            AppState::Settings => &[
+                ("Controls", MenuAction::Controls),
+                ("Mouse", MenuAction::Mouse),
+                ("Audio", MenuAction::Audio),
+                ("Back", MenuAction::Back),
+            ],
+            // Eigene Layouts (screens::controls_layout, screens::draw_mouse_settings,
+            // screens::draw_audio_settings, Serverliste)
+            AppState::Controls | AppState::Mouse | AppState::Audio | AppState::Multiplayer => &[],
+        }
+    }
+
+    /// Escape: eine Ebene zurück (Hauptmenü bleibt)
+    pub fn on_escape(self) -> AppState {
+        match self {
+            AppState::MainMenu | AppState::Multiplayer => AppState::MainMenu,
+            AppState::Playing => AppState::Paused,
+            AppState::Paused => AppState::Playing,
+            AppState::Settings => AppState::Paused,
+            AppState::Controls | AppState::Mouse | AppState::Audio => AppState::Settings,
+        }
+    }
+
+    /// Folgezustand nach einem Button; `SaveAndQuit`/`Quit` erledigt der Aufrufer zusätzlich
+    pub fn on_action(self, action: MenuAction) -> AppState {
+        match action {
+            MenuAction::Play | MenuAction::Resume => AppState::Playing,
+            MenuAction::Multiplayer => AppState::Multiplayer,
+            MenuAction::Settings => AppState::Settings,
+            MenuAction::Controls => AppState::Controls,
+            MenuAction::Mouse => AppState::Mouse,
+            MenuAction::Audio => AppState::Audio,
+            MenuAction::Back => self.on_escape(),
+            MenuAction::SaveAndQuit | MenuAction::Quit => AppState::MainMenu,
+        }
+    }
+}
@@ -5,6 +5,10 @@ use bytemuck::{Pod, Zeroable};
 pub struct Vertex {
     pub pos: [f32; 3],
     pub color: [f32; 3],
+    /// Licht 0..1: (Himmel, Block) – Helligkeitskurve im Shader
+    pub light: [f32; 2],
+    /// 1.0 = leuchtender Block: volle Helligkeit unabhängig von Licht und Tageszeit
+    pub emissive: f32,
 }
 
 impl Vertex {
@@ -24,7 +28,43 @@ impl Vertex {
                     shader_location: 1,
                     format: wgpu::VertexFormat::Float32x3,
                 },
+                wgpu::VertexAttribute {
+                    offset: (2 * mem::size_of::<[f32; 3]>()) as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: (2 * mem::size_of::<[f32; 3]>() + mem::size_of::<[f32; 2]>())
+                        as wgpu::BufferAddress,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32,
+                },
             ],
         }
     }
 }
+
+/// Kanten eines Quaders als Linienpaare (12 Kanten, volle Helligkeit)
+pub fn box_lines(min: [f32; 3], max: [f32; 3], color: [f32; 3]) -> Vec<Vertex> {
+    let corner = |i: usize| {
+        let pick = |axis: usize| if i & (1 << axis) != 0 { max[axis] } else { min[axis] };
+        Vertex {
+            pos: [pick(0), pick(1), pick(2)],
+            color,
+            light: [1.0, 1.0],
+            emissive: 1.0,
+        }
+    };
+    // Ecken als Bitmaske xyz; Kante = zwei Ecken, die sich in genau einem Bit unterscheiden
+    let mut out = Vec::with_capacity(24);
+    for i in 0..8 {
+        for axis in 0..3 {
+            let j = i | (1 << axis);
+            if j != i {
+                out.push(corner(i));
+                out.push(corner(j));
+            }
+        }
+    }
+    out
+}
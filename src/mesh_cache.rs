@@ -0,0 +1,148 @@
+//! Meshes der geladenen Chunks mit Speicherbudget. Darüber fliegen die am längsten nicht
+//! mehr sichtbaren raus; sie werden neu gemesht, sobald sie wieder ins Bild kommen.
+
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+
+use crate::chunk::ChunkPos;
+use crate::mesh::Vertex;
+
+/// Standardbudget in MiB (Einstellung `graphics.mesh_cache_mb`)
+pub const DEFAULT_BUDGET_MB: u32 = 256;
+
+pub struct CachedMesh {
+    pub verts: Vec<Vertex>,
+    pub inds: Vec<u32>,
+    bytes: usize,
+    /// Durchlauf, in dem der Chunk zuletzt im Bild war
+    last_visible: u64,
+}
+
+pub struct MeshCache {
+    meshes: HashMap<ChunkPos, CachedMesh>,
+    /// Verdrängt, aber noch geladen: fehlt im Cache, ist für den Mesher aber nicht neu
+    evicted: HashSet<ChunkPos>,
+    bytes: usize,
+    budget: usize,
+    /// Zählt die Mesh-Durchläufe des Clients (Maß für "zuletzt sichtbar")
+    frame: u64,
+}
+
+impl MeshCache {
+    pub fn new(budget_bytes: usize) -> Self {
+        Self {
+            meshes: HashMap::new(),
+            evicted: HashSet::new(),
+            bytes: 0,
+            budget: budget_bytes,
+            frame: 0,
+        }
+    }
+
+    pub fn set_budget(&mut self, budget_bytes: usize) {
+        self.budget = budget_bytes;
+    }
+
+    /// Belegter Speicher aller Meshes im Cache
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    pub fn get(&self, cp: ChunkPos) -> Option<&CachedMesh> {
+        self.meshes.get(&cp)
+    }
+
+    pub fn contains(&self, cp: ChunkPos) -> bool {
+        self.meshes.contains_key(&cp)
+    }
+
+    pub fn is_evicted(&self, cp: ChunkPos) -> bool {
+        self.evicted.contains(&cp)
+    }
+
+    pub fn insert(&mut self, cp: ChunkPos, verts: Vec<Vertex>, inds: Vec<u32>) {
+        let bytes = verts.len() * size_of::<Vertex>() + inds.len() * size_of::<u32>();
+        let mesh = CachedMesh {
+            verts,
+            inds,
+            bytes,
+            last_visible: self.frame,
+        };
+        self.bytes += bytes;
+        if let Some(old) = self.meshes.insert(cp, mesh) {
+            self.bytes -= old.bytes;
+        }
+        self.evicted.remove(&cp);
+    }
+
+    /// Chunk entladen: Mesh und Verdrängungsvermerk vergessen
+    pub fn remove(&mut self, cp: ChunkPos) {
+        if let Some(old) = self.meshes.remove(&cp) {
+            self.bytes -= old.bytes;
+        }
+        self.evicted.remove(&cp);
+    }
+
+    /// Alles neu meshen (z.B. Licht-Debug umgeschaltet)
+    pub fn clear(&mut self) {
+        self.meshes.clear();
+        self.evicted.clear();
+        self.bytes = 0;
+    }
+
+    /// Nur Chunks behalten, für die `keep` gilt (geladen)
+    pub fn retain(&mut self, keep: impl Fn(ChunkPos) -> bool) {
+        let before = self.meshes.len();
+        self.meshes.retain(|cp, _| keep(*cp));
+        if self.meshes.len() != before {
+            self.bytes = self.meshes.values().map(|m| m.bytes).sum();
+        }
+        self.evicted.retain(|cp| keep(*cp));
+    }
+
+    /// Neuer Durchlauf: danach `mark_visible` für alles im Bild
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    pub fn mark_visible(&mut self, cp: ChunkPos) {
+        if let Some(mesh) = self.meshes.get_mut(&cp) {
+            mesh.last_visible = self.frame;
+        }
+    }
+
+    /// Über dem Budget die am längsten unsichtbaren Meshes verwerfen; was gerade im Bild
+    /// ist, bleibt auch über dem Budget
+    pub fn evict(&mut self) -> usize {
+        if self.bytes <= self.budget {
+            return 0;
+        }
+        let mut candidates: Vec<(u64, ChunkPos)> = self
+            .meshes
+            .iter()
+            .filter(|(_, m)| m.last_visible < self.frame)
+            .map(|(cp, m)| (m.last_visible, *cp))
+            .collect();
+        candidates.sort_unstable_by_key(|(seen, _)| *seen);
+        let mut count = 0;
+        for (_, cp) in candidates {
+            if self.bytes <= self.budget {
+                break;
+            }
+            if let Some(old) = self.meshes.remove(&cp) {
+                self.bytes -= old.bytes;
+                self.evicted.insert(cp);
+                count += 1;
+            }
+        }
+        count
+    }
+}
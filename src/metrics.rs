@@ -0,0 +1,172 @@
+//! Laufzeitmesswerte (Frame- und Tickzeit, Chunks, Mesh-Rückstand, Speicher). Gesetzt wird
+//! von Render- und Simulations-Thread, daher global; der Server nimmt pro Tick eine Zeile
+//! in den Verlauf auf. Ausgabe als CSV (`/metrics`) oder im Prometheus-Textformat.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Read, Write as _};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+/// Verlauf für 10 Minuten bei 20 TPS
+const MAX_ROWS: usize = 12_000;
+/// Längste Anfragezeile, die der HTTP-Endpunkt liest (Bytes)
+const MAX_REQUEST_LINE: u64 = 8192;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    FrameMs,
+    TickMs,
+    Chunks,
+    /// Geladene Chunks ohne aktuelles Mesh
+    MeshQueue,
+    MeshMib,
+    ChunkMib,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 6] = [
+        Metric::FrameMs,
+        Metric::TickMs,
+        Metric::Chunks,
+        Metric::MeshQueue,
+        Metric::MeshMib,
+        Metric::ChunkMib,
+    ];
+
+    /// Spaltenname im CSV, mit Präfix auch der Prometheus-Name
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::FrameMs => "frame_ms",
+            Metric::TickMs => "tick_ms",
+            Metric::Chunks => "chunks",
+            Metric::MeshQueue => "mesh_queue",
+            Metric::MeshMib => "mesh_mib",
+            Metric::ChunkMib => "chunk_mib",
+        }
+    }
+
+    fn help(self) -> &'static str {
+        match self {
+            Metric::FrameMs => "Time between the last two rendered frames in milliseconds",
+            Metric::TickMs => "Duration of the last simulation tick in milliseconds",
+            Metric::Chunks => "Chunks loaded on the server",
+            Metric::MeshQueue => "Loaded chunks waiting for a mesh on the client",
+            Metric::MeshMib => "Estimated size of the chunk mesh cache in MiB",
+            Metric::ChunkMib => "Estimated size of the loaded chunks in MiB",
+        }
+    }
+}
+
+struct Metrics {
+    started: Instant,
+    /// Aktuelle Werte als f64-Bits, je Metrik ein Eintrag in `Metric::ALL`-Reihenfolge
+    current: [AtomicU64; Metric::ALL.len()],
+    /// Sekunden seit Start und Werte je Zeile
+    history: Mutex<VecDeque<(f64, [f64; Metric::ALL.len()])>>,
+}
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(|| Metrics {
+    started: Instant::now(),
+    current: Default::default(),
+    history: Mutex::new(VecDeque::new()),
+});
+
+fn index(metric: Metric) -> usize {
+    Metric::ALL.iter().position(|&m| m == metric).expect("Metrik in ALL")
+}
+
+pub fn set(metric: Metric, value: f64) {
+    METRICS.current[index(metric)].store(value.to_bits(), Ordering::Relaxed);
+}
+
+/// Dauer in Millisekunden
+pub fn set_duration(metric: Metric, d: Duration) {
+    set(metric, d.as_secs_f64() * 1000.0);
+}
+
+pub fn get(metric: Metric) -> f64 {
+    f64::from_bits(METRICS.current[index(metric)].load(Ordering::Relaxed))
+}
+
+/// Aktuelle Werte als Zeile in den Verlauf (einmal pro Tick)
+pub fn sample() {
+    let values = Metric::ALL.map(get);
+    let at = METRICS.started.elapsed().as_secs_f64();
+    let mut history = METRICS.history.lock().expect("Metrik-Lock");
+    if history.len() == MAX_ROWS {
+        history.pop_front();
+    }
+    history.push_back((at, values));
+}
+
+/// Verlauf als CSV mit Kopfzeile; liefert auch die Zeilenzahl
+pub fn to_csv() -> (String, usize) {
+    let history = METRICS.history.lock().expect("Metrik-Lock");
+    let mut csv = String::from("time_s");
+    for m in Metric::ALL {
+        csv.push(',');
+        csv.push_str(m.name());
+    }
+    csv.push('\n');
+    for (at, values) in history.iter() {
+        let _ = write!(csv, "{at:.3}");
+        for v in values {
+            let _ = write!(csv, ",{v:.3}");
+        }
+        csv.push('\n');
+    }
+    (csv, history.len())
+}
+
+/// Aktuelle Werte im Prometheus-Textformat
+pub fn to_prometheus() -> String {
+    let mut out = String::new();
+    for m in Metric::ALL {
+        let name = format!("voxel_{}", m.name());
+        let _ = writeln!(out, "# HELP {name} {}", m.help());
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        let _ = writeln!(out, "{name} {}", get(m));
+    }
+    out
+}
+
+/// HTTP-Endpunkt `GET /metrics` auf eigenem Thread (dedizierter Server, `--metrics-port`)
+pub fn serve(port: u16) -> Result<()> {
+    let listener =
+        TcpListener::bind(("0.0.0.0", port)).with_context(|| format!("bind metrics port {port}"))?;
+    thread::Builder::new()
+        .name("metrics".into())
+        .spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(e) = respond(stream) {
+                    log::debug!("Metrik-Anfrage: {e:#}");
+                }
+            }
+        })
+        .context("spawn metrics thread")?;
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    // Nur die Anfragezeile; ein Client ohne Zeilenende füllt so nicht den Speicher
+    let mut request = String::new();
+    BufReader::new((&stream).take(MAX_REQUEST_LINE)).read_line(&mut request)?;
+    let (status, body) = match request.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", "/metrics", ..] => ("200 OK", to_prometheus()),
+        _ => ("404 Not Found", "try /metrics\n".to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! Minikarte oben rechts: Draufsicht um den Spieler wie `map_image`, aber aus dem
+//! Weltspiegel des Clients und als UI-Rechtecke. Wahlweise genordet oder mit dem Blick
+//! gedreht; die Säulen werden nur nach Bewegung oder alle paar Ticks neu abgetastet.
+
+use crate::block::Block;
+use crate::level::Waypoint;
+use crate::map_image;
+use crate::screens::WAYPOINT_COLOR;
+use crate::ui::{Rect, UiBatch};
+use crate::world::World;
+
+/// Zellen vom Mittelpunkt bis zum Rand
+const MAP_CELLS: i32 = 32;
+const CELL_PX: f32 = 2.0;
+const MARGIN: f32 = 8.0;
+/// Blöcke pro Zelle je Zoomstufe
+const ZOOM_LEVELS: [i32; 4] = [1, 2, 4, 8];
+const DEFAULT_ZOOM: usize = 1;
+/// Abgetastet wird von so weit über bis so weit unter den Füßen
+const SCAN_UP: i32 = 16;
+const SCAN_DOWN: i32 = 32;
+/// Spätestens nach so vielen Ticks neu abtasten (abgebaute Blöcke, nachgeladene Chunks)
+const REFRESH_TICKS: u32 = 20;
+
+const BACKGROUND: [f32; 4] = [0.05, 0.05, 0.07, 0.7];
+const PLAYER: [f32; 4] = [0.3, 0.8, 1.0, 1.0];
+const HEADING: [f32; 4] = [1.0, 0.3, 0.3, 1.0];
+
+pub struct Minimap {
+    pub visible: bool,
+    zoom: usize,
+    /// Oberster Block je Zelle (Höhe, Block), zeilenweise von Norden, `2 * radius + 1` breit
+    columns: Vec<Option<(i32, Block)>>,
+    /// Wofür `columns` gilt: Zelle des Spielers, Fußhöhe, Zoomstufe und Radius
+    sampled: Option<((i32, i32), i32, usize, i32)>,
+    age: u32,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            zoom: DEFAULT_ZOOM,
+            columns: Vec::new(),
+            sampled: None,
+            age: 0,
+        }
+    }
+}
+
+impl Minimap {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Näher heran (`+1`) oder weiter weg (`-1`)
+    pub fn zoom(&mut self, steps: i32) {
+        let zoom = self.zoom as i32 - steps;
+        self.zoom = zoom.clamp(0, ZOOM_LEVELS.len() as i32 - 1) as usize;
+    }
+
+    /// Blöcke pro Zelle
+    pub fn scale(&self) -> i32 {
+        ZOOM_LEVELS[self.zoom]
+    }
+
+    /// Einmal pro Tick: Säulen neu abtasten, wenn sich der Spieler eine Zelle weiter bewegt
+    /// hat, der Zoom geändert wurde oder die letzte Abtastung zu alt ist. Gedreht braucht
+    /// die Karte die Ecken des Quadrats, also einen größeren Radius.
+    pub fn update(&mut self, world: &World, (x, y, z): (f32, f32, f32), rotate: bool) {
+        if !self.visible {
+            return;
+        }
+        let scale = self.scale();
+        let cell = ((x.floor() as i32).div_euclid(scale), (z.floor() as i32).div_euclid(scale));
+        let feet = y.floor() as i32;
+        let radius = if rotate { MAP_CELLS * 3 / 2 } else { MAP_CELLS };
+        let key = (cell, feet, self.zoom, radius);
+        self.age += 1;
+        if self.sampled == Some(key) && self.age < REFRESH_TICKS {
+            return;
+        }
+        self.sampled = Some(key);
+        self.age = 0;
+        self.columns.clear();
+        for cz in -radius..=radius {
+            for cx in -radius..=radius {
+                // Mitte der Zelle
+                let bx = (cell.0 + cx) * scale + scale / 2;
+                let bz = (cell.1 + cz) * scale + scale / 2;
+                self.columns.push(world.top_block(bx, bz, feet - SCAN_DOWN, feet + SCAN_UP));
+            }
+        }
+    }
+
+    fn column(&self, cx: i32, cz: i32) -> Option<(i32, Block)> {
+        let (_, _, _, radius) = self.sampled?;
+        if cx.abs() > radius || cz.abs() > radius {
+            return None;
+        }
+        let width = 2 * radius + 1;
+        self.columns[((cz + radius) * width + cx + radius) as usize]
+    }
+
+    /// Oben rechts zeichnen; `yaw` wie beim Spieler (Blick `(sin, cos)` in XZ).
+    /// Wegmarken außerhalb der Karte sitzen am Rand.
+    pub fn draw(
+        &self,
+        batch: &mut UiBatch,
+        (x, z): (f32, f32),
+        yaw: f32,
+        rotate: bool,
+        waypoints: &[Waypoint],
+    ) {
+        let Some(((pcx, pcz), feet, _, _)) = self.sampled.filter(|_| self.visible) else {
+            return;
+        };
+        let size = (2 * MAP_CELLS + 1) as f32 * CELL_PX;
+        let panel = Rect::new(batch.width() - size - MARGIN, MARGIN, size, size);
+        batch.rect(panel.inset(-2.0), BACKGROUND);
+
+        // Bildschirm oben = Blickrichtung (gedreht) bzw. Norden; rechts = rechts davon
+        let (fx, fz) = if rotate { (yaw.sin(), yaw.cos()) } else { (0.0, -1.0) };
+        let scale = self.scale() as f32;
+        let range = (feet - SCAN_DOWN, feet + SCAN_UP);
+        for v in -MAP_CELLS..=MAP_CELLS {
+            for u in -MAP_CELLS..=MAP_CELLS {
+                let (u_f, v_f) = (u as f32, v as f32);
+                let wx = x + (-u_f * fz - v_f * fx) * scale;
+                let wz = z + (u_f * fx - v_f * fz) * scale;
+                let cx = (wx / scale).floor() as i32 - pcx;
+                let cz = (wz / scale).floor() as i32 - pcz;
+                let Some((h, b)) = self.column(cx, cz) else {
+                    continue;
+                };
+                let north = self.column(cx, cz - 1).map(|(ny, _)| ny);
+                let [r, g, bl] = map_image::shade(b, h, range, north);
+                let px = panel.x + (u + MAP_CELLS) as f32 * CELL_PX;
+                let py = panel.y + (v + MAP_CELLS) as f32 * CELL_PX;
+                batch.rect(Rect::new(px, py, CELL_PX, CELL_PX), [r, g, bl, 1.0]);
+            }
+        }
+
+        // Spieler in der Mitte, Punkte in Blickrichtung
+        let (cx, cy) = (panel.x + size * 0.5, panel.y + size * 0.5);
+        for w in waypoints {
+            // Umkehrung der Zellabbildung oben
+            let (dx, dz) = ((w.pos.0 - x) / scale, (w.pos.2 - z) / scale);
+            let edge = MAP_CELLS as f32;
+            let u = (-dx * fz + dz * fx).clamp(-edge, edge);
+            let v = (-dx * fx - dz * fz).clamp(-edge, edge);
+            let (px, py) = (cx + u * CELL_PX, cy + v * CELL_PX);
+            batch.rect(Rect::new(px - 2.0, py - 2.0, 4.0, 4.0), WAYPOINT_COLOR);
+        }
+        let (dx, dy) = if rotate { (0.0, -1.0) } else { (yaw.sin(), yaw.cos()) };
+        for i in 1..=4 {
+            let d = i as f32 * 3.0;
+            batch.rect(Rect::new(cx + dx * d - 1.0, cy + dy * d - 1.0, 2.0, 2.0), HEADING);
+        }
+        batch.rect(Rect::new(cx - 2.0, cy - 2.0, 4.0, 4.0), PLAYER);
+
+        // Gedreht: Norden am Rand markieren
+        if rotate {
+            let (nx, ny) = (-fx, fz);
+            let th = UiBatch::text_height(1.0);
+            let r = size * 0.5 - th;
+            batch.text(cx + nx * r - 2.5, cy + ny * r - th * 0.5, 1.0, "N", HEADING);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Spielerbewegung und Kollision. Server und Client-Vorhersage rechnen mit denselben
+//! Funktionen, damit die Vorhersage nur bei echten Abweichungen korrigiert werden muss.
+
+use crate::input::InputState;
+use crate::player::look_dir;
+use crate::world::World;
+
+/// Länge eines Ticks bei 20 TPS
+const TICK_DT: f32 = 0.05;
+/// Blöcke pro Sekunde
+const WALK_SPEED: f32 = 4.0;
+const SPRINT_SPEED: f32 = 5.6;
+const FLY_SPEED: f32 = 6.0;
+/// Blöcke/s^2
+const GRAVITY: f32 = 18.0;
+const JUMP_VELOCITY: f32 = 7.0;
+/// Höchste Stufe, die man ohne Springen hochläuft
+const STEP_HEIGHT: f32 = 0.51;
+/// Hitbox (Minecraft-ish): Breite ~0.6, Höhe ~1.8
+const HALF_WIDTH: f32 = 0.3;
+const HEIGHT: f32 = 1.8;
+/// Augenhöhe über den Füßen
+pub const EYE_HEIGHT: f32 = 0.9;
+
+/// Kinematischer Zustand des Spielers (Füße in der Mitte der Hitbox)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Body {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Vertikale Geschwindigkeit (Springen/Fallen)
+    pub vy: f32,
+    pub on_ground: bool,
+    pub flying: bool,
+}
+
+/// Was im vertikalen Schritt passiert ist (Hunger, Geräusche, Fallschaden macht das Game)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Vertical {
+    pub jumped: bool,
+    pub landed: bool,
+}
+
+impl Body {
+    pub fn eye_pos(&self) -> (f32, f32, f32) {
+        (self.x, self.y + EYE_HEIGHT, self.z)
+    }
+
+    /// Horizontal laufen; liefert die zurückgelegte Strecke (0 = keine Richtung gedrückt)
+    pub fn walk(
+        &mut self,
+        world: &World,
+        input: InputState,
+        (yaw, pitch): (f32, f32),
+        sprint: bool,
+    ) -> f32 {
+        let speed = if sprint { SPRINT_SPEED } else { WALK_SPEED };
+        let step = speed * TICK_DT;
+
+        // Vorwärtsrichtung nur in XZ (ohne hoch/runter), normalisiert
+        let (dx, _dy, dz) = look_dir(yaw, pitch);
+        let mut fwd_x = dx;
+        let mut fwd_z = dz;
+        let len = (fwd_x * fwd_x + fwd_z * fwd_z).sqrt();
+        if len > 0.0001 {
+            fwd_x /= len;
+            fwd_z /= len;
+        }
+
+        // Rechtsvektor (90° gedreht)
+        let right_x = fwd_z;
+        let right_z = -fwd_x;
+
+        let mut mx = 0.0_f32;
+        let mut mz = 0.0_f32;
+
+        if input.move_fwd {
+            mx += fwd_x;
+            mz += fwd_z;
+        }
+        if input.move_back {
+            mx -= fwd_x;
+            mz -= fwd_z;
+        }
+        if input.move_right {
+            mx += right_x;
+            mz += right_z;
+        }
+        if input.move_left {
+            mx -= right_x;
+            mz -= right_z;
+        }
+
+        // Diagonal nicht schneller
+        let mlen = (mx * mx + mz * mz).sqrt();
+        if mlen <= 0.0001 {
+            return 0.0;
+        }
+        mx /= mlen;
+        mz /= mlen;
+
+        let target_x = self.x + mx * step;
+        let target_z = self.z + mz * step;
+
+        // erst X bewegen, sonst Stufe hoch versuchen
+        if !collides_at(world, target_x, self.y, self.z) {
+            self.x = target_x;
+        } else {
+            self.try_step_up(world, target_x, self.z);
+        }
+
+        // dann Z bewegen
+        if !collides_at(world, self.x, self.y, target_z) {
+            self.z = target_z;
+        } else {
+            self.try_step_up(world, self.x, target_z);
+        }
+        step
+    }
+
+    fn try_step_up(&mut self, world: &World, new_x: f32, new_z: f32) -> bool {
+        let y_up = self.y + STEP_HEIGHT;
+
+        // Platz über uns und an der Zielposition in der Luft frei?
+        if collides_at(world, self.x, y_up, self.z) || collides_at(world, new_x, y_up, new_z) {
+            return false;
+        }
+
+        self.y = y_up;
+        self.x = new_x;
+        self.z = new_z;
+        true
+    }
+
+    /// Springen, Schwerkraft und Landen bzw. Fliegen
+    pub fn vertical(&mut self, world: &World, input: InputState) -> Vertical {
+        let mut out = Vertical::default();
+        if self.flying {
+            self.fly(world, input);
+            return out;
+        }
+
+        // Jump (one-shot)
+        if input.jump && self.on_ground {
+            self.vy = JUMP_VELOCITY;
+            self.on_ground = false;
+            out.jumped = true;
+        }
+
+        self.vy -= GRAVITY * TICK_DT;
+
+        // Kollision nur auf Y testen
+        let new_y = self.y + self.vy * TICK_DT;
+        if !collides_at(world, self.x, new_y, self.z) {
+            self.y = new_y;
+            self.on_ground = false;
+        } else {
+            // Wenn wir nach unten fallen und kollidieren -> auf Boden stehen
+            if self.vy < 0.0 {
+                out.landed = !self.on_ground;
+                self.on_ground = true;
+            }
+            self.vy = 0.0;
+
+            // Mini-Fix gegen Einsinken durch Rundung
+            let mut y_fix = self.y;
+            for _ in 0..5 {
+                if !collides_at(world, self.x, y_fix, self.z) {
+                    break;
+                }
+                y_fix += 0.01;
+            }
+            self.y = y_fix;
+        }
+        out
+    }
+
+    /// Fliegen: keine Schwerkraft, Leertaste steigt, Shift sinkt
+    fn fly(&mut self, world: &World, input: InputState) {
+        let dir = input.jump_held as i32 - input.sneak as i32;
+        self.vy = 0.0;
+        if dir == 0 {
+            return;
+        }
+        let new_y = self.y + dir as f32 * FLY_SPEED * TICK_DT;
+        if !collides_at(world, self.x, new_y, self.z) {
+            self.y = new_y;
+            self.on_ground = false;
+        } else if dir < 0 {
+            // gelandet
+            self.flying = false;
+            self.on_ground = true;
+        }
+    }
+}
+
+/// Überschneidet die Hitbox mit Füßen bei (px, py, pz) einen festen Block?
+pub fn collides_at(world: &World, px: f32, py: f32, pz: f32) -> bool {
+    let x0 = (px - HALF_WIDTH).floor() as i32;
+    let x1 = (px + HALF_WIDTH).floor() as i32;
+    let y0 = py.floor() as i32;
+    let y1 = (py + HEIGHT).floor() as i32;
+    let z0 = (pz - HALF_WIDTH).floor() as i32;
+    let z1 = (pz + HALF_WIDTH).floor() as i32;
+
+    for y in y0..=y1 {
+        for z in z0..=z1 {
+            for x in x0..=x1 {
+                if world.is_solid(x, y, z) {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
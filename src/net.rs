@@ -0,0 +1,168 @@
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::block::{self, BLOCKS_ASSET};
+use crate::protocol::{
+    check_player_name, read_frame, write_frame, ClientMsg, Hello, ServerMsg, WorldParams,
+    PROTOCOL_VERSION,
+};
+
+/// Standardport des dedizierten Servers
+pub const DEFAULT_PORT: u16 = 25580;
+/// So lange darf die Gegenseite im Handshake schweigen
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Neuer Spieler nach erfolgreichem Handshake
+pub struct Connection {
+    pub hello: Hello,
+    pub to_client: Sender<ServerMsg>,
+    pub from_client: Receiver<ClientMsg>,
+}
+
+/// Mit einem Server verbinden. Danach laufen Nachrichten über dieselben Kanaltypen wie
+/// beim integrierten Server, der `Client` merkt keinen Unterschied.
+pub fn connect(
+    addr: &str,
+    name: &str,
+) -> Result<(WorldParams, Sender<ClientMsg>, Receiver<ServerMsg>)> {
+    // Ohne Port: Standardport
+    let addr = if addr.contains(':') { addr.to_string() } else { format!("{addr}:{DEFAULT_PORT}") };
+    let stream = TcpStream::connect(&addr).with_context(|| format!("verbinden mit {addr}"))?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    let hello = Hello {
+        version: PROTOCOL_VERSION,
+        name: name.to_string(),
+    };
+    write_frame(&mut &stream, &ClientMsg::Hello(hello).encode())?;
+    let params = match ServerMsg::decode(&read_frame(&mut &stream)?)? {
+        ServerMsg::Welcome(params) => params,
+        ServerMsg::Disconnect(reason) => bail!("Server lehnt ab: {reason}"),
+        other => bail!("unerwartete Antwort im Handshake: {other:?}"),
+    };
+    // Blockdaten gehen nicht übers Netz: mit anderen würden Client und Server auseinanderlaufen
+    if params.blocks != block::table_checksum() {
+        bail!("Server nutzt andere Blockdaten ({BLOCKS_ASSET})");
+    }
+    stream.set_read_timeout(None)?;
+
+    let (to_server, from_server) = bridge(stream, ClientMsg::encode, ServerMsg::decode)?;
+    Ok((params, to_server, from_server))
+}
+
+/// Server-Seite des Handshakes für eine angenommene Verbindung
+pub fn accept(stream: TcpStream, params: &WorldParams) -> Result<Connection> {
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    let ClientMsg::Hello(hello) = ClientMsg::decode(&read_frame(&mut &stream)?)? else {
+        bail!("Client beginnt nicht mit Hello");
+    };
+    if hello.version != PROTOCOL_VERSION {
+        let reason = format!(
+            "Protocol version {} is not supported (server uses {PROTOCOL_VERSION})",
+            hello.version
+        );
+        write_frame(&mut &stream, &ServerMsg::Disconnect(reason).encode())?;
+        bail!("{:?}: Protokoll v{} abgelehnt", hello.name, hello.version);
+    }
+    // Doppelte Namen kennt erst der Server (`Server::attach`)
+    if let Err(e) = check_player_name(&hello.name) {
+        write_frame(&mut &stream, &ServerMsg::Disconnect(e.to_string()).encode())?;
+        bail!("Name {:?} abgelehnt: {e}", hello.name);
+    }
+    write_frame(&mut &stream, &ServerMsg::Welcome(params.clone()).encode())?;
+    stream.set_read_timeout(None)?;
+
+    let (to_client, from_client) = bridge(stream, ServerMsg::encode, ClientMsg::decode)?;
+    Ok(Connection {
+        hello,
+        to_client,
+        from_client,
+    })
+}
+
+/// Nimmt im Hintergrund Verbindungen an; jede mit fertigem Handshake landet im Kanal.
+/// Jeder Handshake läuft in einem eigenen Thread, ein stummer Client hält niemanden auf.
+pub fn listen(port: u16, params: WorldParams) -> Result<Receiver<Connection>> {
+    let listener = TcpListener::bind(("0.0.0.0", port)).with_context(|| format!("Port {port}"))?;
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .name("net-listen".into())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+                let (tx, params) = (tx.clone(), params.clone());
+                let handshake = thread::Builder::new()
+                    .name("net-handshake".into())
+                    .spawn(move || match accept(stream, &params) {
+                        Ok(conn) => {
+                            log::info!("{:?} verbunden ({peer})", conn.hello.name);
+                            // Server beendet: Verbindung fällt mit `conn`
+                            let _ = tx.send(conn);
+                        }
+                        Err(e) => log::warn!("{peer}: Handshake fehlgeschlagen: {e:#}"),
+                    });
+                if let Err(e) = handshake {
+                    log::warn!("Handshake-Thread nicht gestartet: {e}");
+                }
+            }
+        })?;
+    Ok(rx)
+}
+
+/// Je ein Thread liest und schreibt Frames; Verbindungsende = Kanal geschlossen
+fn bridge<Out, In>(
+    stream: TcpStream,
+    encode: fn(&Out) -> Vec<u8>,
+    decode: fn(&[u8]) -> Result<In>,
+) -> Result<(Sender<Out>, Receiver<In>)>
+where
+    Out: Send + 'static,
+    In: Send + 'static,
+{
+    let peer = stream.peer_addr()?;
+    let (out_tx, out_rx) = mpsc::channel::<Out>();
+    let (in_tx, in_rx) = mpsc::channel();
+
+    let writer = stream.try_clone()?;
+    thread::Builder::new()
+        .name("net-write".into())
+        .spawn(move || {
+            for msg in out_rx {
+                if let Err(e) = write_frame(&mut &writer, &encode(&msg)) {
+                    log::info!("{peer}: Senden beendet: {e:#}");
+                    break;
+                }
+            }
+            let _ = writer.shutdown(Shutdown::Both);
+        })?;
+
+    thread::Builder::new()
+        .name("net-read".into())
+        .spawn(move || {
+            loop {
+                let msg = match read_frame(&mut &stream).and_then(|f| decode(&f)) {
+                    Ok(msg) => msg,
+                    Err(e) => {
+                        log::info!("{peer}: Verbindung beendet: {e:#}");
+                        break;
+                    }
+                };
+                if in_tx.send(msg).is_err() {
+                    break;
+                }
+            }
+            let _ = stream.shutdown(Shutdown::Both);
+        })?;
+
+    Ok((out_tx, in_rx))
+}
+
+
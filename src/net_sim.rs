@@ -0,0 +1,180 @@
+//! Netzwerk im Prozess für Tests: Nachrichten laufen über dieselben Kanaltypen wie bei
+//! `net::connect` und unterwegs als Frames durch `encode`/`decode`, kommen aber erst nach
+//! simulierter Latenz an. Die Zeit läuft nur mit
+//! `step`, der Zufall hängt am Seed; gleiche Aufrufe ergeben damit immer denselben Lauf.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::client::Client;
+use crate::protocol::{read_frame, write_frame, ClientMsg, ServerMsg};
+use crate::rng::Rng;
+use crate::server::Server;
+
+/// Verlorene Pakete kommen nach dieser Zeit erneut (wie ein TCP-Retransmit)
+const RETRANSMIT_DELAY: Duration = Duration::from_millis(200);
+/// Öfter wird ein Paket nicht neu gesendet, sonst hängt die Leitung bei `loss` = 1
+const MAX_RETRANSMITS: u32 = 5;
+
+/// Eigenschaften der Leitung, für beide Richtungen gleich
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinkConfig {
+    /// Einfache Strecke
+    pub latency: Duration,
+    /// Zusätzlich gleichverteilt 0..jitter
+    pub jitter: Duration,
+    /// Anteil der Pakete (0..1), die neu gesendet werden müssen
+    pub loss: f32,
+}
+
+impl LinkConfig {
+    pub const PERFECT: Self = Self {
+        latency: Duration::ZERO,
+        jitter: Duration::ZERO,
+        loss: 0.0,
+    };
+}
+
+/// Eine Richtung. Wie bei TCP geht nichts verloren und die Reihenfolge bleibt: ein
+/// verlorenes Paket hält alles dahinter auf, bis es neu gesendet ist.
+struct Pipe<T> {
+    from: Receiver<T>,
+    /// None, sobald der Sender weg und alles zugestellt ist (Gegenseite merkt das Ende)
+    to: Option<Sender<T>>,
+    /// Ankunftszeit und Frame wie auf dem Socket, aufsteigend
+    in_flight: VecDeque<(Duration, Vec<u8>)>,
+    sender_gone: bool,
+    encode: fn(&T) -> Vec<u8>,
+    decode: fn(&[u8]) -> Result<T>,
+}
+
+impl<T> Pipe<T> {
+    fn new(
+        encode: fn(&T) -> Vec<u8>,
+        decode: fn(&[u8]) -> Result<T>,
+    ) -> (Self, Sender<T>, Receiver<T>) {
+        let (tx, from) = mpsc::channel();
+        let (to, rx) = mpsc::channel();
+        let pipe = Self {
+            from,
+            to: Some(to),
+            in_flight: VecDeque::new(),
+            sender_gone: false,
+            encode,
+            decode,
+        };
+        (pipe, tx, rx)
+    }
+
+    /// Neue Nachrichten losschicken (Sendezeit `now`)
+    fn send(&mut self, now: Duration, config: &LinkConfig, rng: &mut Rng) {
+        loop {
+            let msg = match self.from.try_recv() {
+                Ok(msg) => msg,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.sender_gone = true;
+                    break;
+                }
+            };
+            let mut delay = config.latency + config.jitter.mul_f32(unit(rng));
+            for _ in 0..MAX_RETRANSMITS {
+                if unit(rng) >= config.loss {
+                    break;
+                }
+                delay += RETRANSMIT_DELAY;
+            }
+            let mut frame = Vec::new();
+            write_frame(&mut frame, &(self.encode)(&msg))
+                .expect("Nachricht zu groß für einen Frame");
+            let last = self.in_flight.back().map_or(Duration::ZERO, |(at, _)| *at);
+            self.in_flight.push_back(((now + delay).max(last), frame));
+        }
+    }
+
+    /// Alles zustellen, was bis `now` angekommen ist
+    fn deliver(&mut self, now: Duration) {
+        while self.in_flight.front().is_some_and(|(at, _)| *at <= now) {
+            let (_, frame) = self.in_flight.pop_front().unwrap();
+            // Wie `net::bridge`: was sich nicht lesen lässt, beendet die Verbindung
+            let msg = match read_frame(&mut frame.as_slice()).and_then(|f| (self.decode)(&f)) {
+                Ok(msg) => msg,
+                Err(e) => {
+                    log::warn!("Simulierte Leitung: Nachricht unlesbar: {e:#}");
+                    self.in_flight.clear();
+                    self.to = None;
+                    return;
+                }
+            };
+            // Empfänger weg: wie eine geschlossene Verbindung, Rest verfällt
+            if self.to.as_ref().is_some_and(|to| to.send(msg).is_err()) {
+                self.in_flight.clear();
+            }
+        }
+        if self.sender_gone && self.in_flight.is_empty() {
+            self.to = None;
+        }
+    }
+}
+
+/// Gleichverteilt in [0, 1)
+fn unit(rng: &mut Rng) -> f32 {
+    (rng.next_u32() >> 8) as f32 / (1 << 24) as f32
+}
+
+/// Simulierte Leitung zwischen einem Client und dem Server
+pub struct SimLink {
+    config: LinkConfig,
+    rng: Rng,
+    now: Duration,
+    up: Pipe<ClientMsg>,
+    down: Pipe<ServerMsg>,
+}
+
+impl SimLink {
+    /// Neuer Spieler `name` an `server`; der Client hängt am anderen Ende der Leitung.
+    /// Lehnt der Server ab, bekommt der Client `Disconnect` wie übers Netz.
+    pub fn connect(
+        server: &mut Server,
+        name: &str,
+        view_distance: i32,
+        config: LinkConfig,
+        seed: u64,
+    ) -> (Self, Client) {
+        let (up, to_server, from_client) = Pipe::new(ClientMsg::encode, ClientMsg::decode);
+        let (down, to_client, from_server) = Pipe::new(ServerMsg::encode, ServerMsg::decode);
+        if let Err(e) = server.attach(name.to_string(), to_client, from_client) {
+            log::info!("Simulierter Spieler abgelehnt: {e:#}");
+        }
+        let link = Self {
+            config,
+            rng: Rng::new(seed),
+            now: Duration::ZERO,
+            up,
+            down,
+        };
+        (link, Client::networked(to_server, from_server, view_distance))
+    }
+
+    /// Bisher Gesendetes abschicken, `dt` weiterzählen und Angekommenes zustellen
+    pub fn step(&mut self, dt: Duration) {
+        self.up.send(self.now, &self.config, &mut self.rng);
+        self.down.send(self.now, &self.config, &mut self.rng);
+        self.now += dt;
+        self.up.deliver(self.now);
+        self.down.deliver(self.now);
+    }
+
+    /// Simulierte Zeit seit dem Verbinden
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// Nachrichten unterwegs (beide Richtungen)
+    pub fn in_flight(&self) -> usize {
+        self.up.in_flight.len() + self.down.in_flight.len()
+    }
+}
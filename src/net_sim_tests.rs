@@ -0,0 +1,182 @@
+//! Client und Server über die simulierte Leitung: Vorhersage, Mitspieler, Verbindungsende
+
+use std::time::Duration;
+
+use crate::client::Client;
+use crate::console::{self, MAX_INPUT};
+use crate::chunk::ChunkPos;
+use crate::game::{chunk_window_around, Game};
+use crate::input::InputState;
+use crate::net_sim::{LinkConfig, SimLink};
+use crate::server::Server;
+use crate::settings::VIEW_DISTANCE_MAX;
+
+const SEED: u64 = 7;
+const VIEW: i32 = 2;
+const TICK: Duration = Duration::from_millis(50);
+const LOSSY: LinkConfig = LinkConfig {
+    latency: Duration::from_millis(80),
+    jitter: Duration::from_millis(40),
+    loss: 0.1,
+};
+
+fn walk() -> InputState {
+    InputState {
+        move_fwd: true,
+        ..InputState::default()
+    }
+}
+
+/// Ein Tick: Clients senden, der Server tickt, die Leitungen laufen, die Clients lesen
+fn tick(server: &mut Server, players: &mut [(SimLink, Client)], input: InputState) {
+    for (_, client) in players.iter_mut() {
+        client.send_tick(input);
+    }
+    server.tick();
+    for (link, client) in players.iter_mut() {
+        link.step(TICK);
+        client.poll();
+    }
+}
+
+/// Host läuft `walking` Ticks geradeaus und steht dann `idle` Ticks
+fn walk_then_stop(config: LinkConfig, seed: u64, walking: usize, idle: usize) -> (Server, Client) {
+    let mut server = Server::detached(Game::new_deterministic(SEED), VIEW);
+    let mut players = vec![SimLink::connect(&mut server, "A", VIEW, config, seed)];
+    for i in 0..walking + idle {
+        let input = if i < walking { walk() } else { InputState::default() };
+        tick(&mut server, &mut players, input);
+    }
+    let (_, client) = players.pop().unwrap();
+    (server, client)
+}
+
+fn distance(a: (f32, f32, f32), b: (f32, f32, f32)) -> f32 {
+    let (dx, dy, dz) = (a.0 - b.0, a.1 - b.1, a.2 - b.2);
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
+#[test]
+fn prediction_matches_server_on_perfect_link() {
+    let (server, client) = walk_then_stop(LinkConfig::PERFECT, 1, 20, 20);
+    let server_eye = server.game().player_body().eye_pos();
+    assert!(distance(client.camera_pos_dir().0, server_eye) < 1e-3);
+}
+
+#[test]
+fn prediction_converges_over_lossy_link() {
+    let (server, client) = walk_then_stop(LOSSY, 1, 40, 60);
+    assert_eq!(client.disconnected(), None);
+    let server_body = server.game().player_body();
+    let spawn = server.world_params().spawn;
+    // Wirklich gelaufen, und der Client ist nach dem Stehenbleiben wieder beim Server
+    assert!(distance((server_body.x, server_body.y, server_body.z), spawn) > 3.0);
+    assert!(distance(client.camera_pos_dir().0, server_body.eye_pos()) < 0.05);
+}
+
+#[test]
+fn same_seed_gives_same_run() {
+    let (a_server, a_client) = walk_then_stop(LOSSY, 3, 15, 5);
+    let (b_server, b_client) = walk_then_stop(LOSSY, 3, 15, 5);
+    assert_eq!(a_client.camera_pos_dir(), b_client.camera_pos_dir());
+    assert_eq!(a_server.game().player_body(), b_server.game().player_body());
+}
+
+#[test]
+fn players_appear_and_leave() {
+    let mut server = Server::detached(Game::new_deterministic(SEED), VIEW);
+    let mut players = vec![
+        SimLink::connect(&mut server, "A", VIEW, LOSSY, 1),
+        SimLink::connect(&mut server, "B", VIEW, LOSSY, 2),
+    ];
+    for _ in 0..20 {
+        tick(&mut server, &mut players, walk());
+    }
+    let names: Vec<&str> = players[1].1.remote_players().iter().map(|(n, _)| *n).collect();
+    assert_eq!(names, ["A"]);
+
+    // A schließt das Spiel, die Leitung reißt ab
+    drop(players.remove(0));
+    for _ in 0..20 {
+        tick(&mut server, &mut players, InputState::default());
+    }
+    assert_eq!(server.player_count(), 1);
+    assert!(players[0].1.remote_players().is_empty());
+}
+
+#[test]
+fn overlong_chat_is_cut() {
+    let mut server = Server::detached(Game::new_deterministic(SEED), VIEW);
+    let mut players = vec![SimLink::connect(&mut server, "A", VIEW, LinkConfig::PERFECT, 1)];
+    players[0].1.send_chat("x".repeat(10_000));
+    for _ in 0..3 {
+        tick(&mut server, &mut players, InputState::default());
+    }
+    let line = server.game().console().lines_since(0).find(|l| l.starts_with("<A>")).unwrap();
+    assert_eq!(line, format!("<A> {}", "x".repeat(MAX_INPUT)));
+}
+
+#[test]
+fn hostile_view_distance_is_clamped() {
+    let mut server = Server::detached(Game::new_deterministic(SEED), VIEW);
+    let (mut link, mut client) = SimLink::connect(&mut server, "A", VIEW, LinkConfig::PERFECT, 1);
+    client.set_view_distance(1_000_000);
+    // Ein Tick lädt das neue Fenster; mehr (und der Client) kosten hier nur Zeit
+    link.step(TICK);
+    server.tick();
+    let limit = chunk_window_around(ChunkPos::new(0, 0, 0), VIEW_DISTANCE_MAX).len();
+    let loaded = server.game().world().memory_report().chunks;
+    assert!(loaded > chunk_window_around(ChunkPos::new(0, 0, 0), VIEW).len());
+    assert!(loaded <= limit, "{loaded} > {limit}");
+}
+
+#[test]
+fn taken_or_broken_names_are_refused() {
+    let mut server = Server::detached(Game::new_deterministic(SEED), VIEW);
+    server.set_operator("A", true);
+    let long = "x".repeat(17);
+    let mut players: Vec<_> = ["A", "A", "", long.as_str(), "A\n<B> hi"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| SimLink::connect(&mut server, name, VIEW, LinkConfig::PERFECT, i as u64))
+        .collect();
+    for _ in 0..3 {
+        tick(&mut server, &mut players, InputState::default());
+    }
+    assert_eq!(server.player_count(), 1);
+    assert_eq!(players[0].1.disconnected(), None);
+    // Zweite Verbindung mit dem Namen eines Operators bekäme sonst dessen Rechte
+    let reason = players[1].1.disconnected().unwrap();
+    assert!(reason.contains("already connected"), "{reason}");
+    assert!(players[2..].iter().all(|(_, client)| client.disconnected().is_some()));
+}
+
+#[test]
+fn cheats_need_an_operator() {
+    for line in ["give stone 64", "gamemode creative", "time set day", "schem save x"] {
+        assert!(console::needs_operator(line), "{line}");
+    }
+    for line in ["help", "tp 0 64 0", "seed", "stats"] {
+        assert!(!console::needs_operator(line), "{line}");
+    }
+}
+
+#[test]
+fn command_output_goes_only_to_its_sender() {
+    let mut server = Server::detached(Game::new_deterministic(SEED), VIEW);
+    let mut players = vec![
+        SimLink::connect(&mut server, "A", VIEW, LinkConfig::PERFECT, 1),
+        SimLink::connect(&mut server, "B", VIEW, LinkConfig::PERFECT, 2),
+    ];
+    players[0].1.send_chat("/seed".to_string());
+    players[0].1.send_chat("hi".to_string());
+    for _ in 0..3 {
+        tick(&mut server, &mut players, InputState::default());
+    }
+    let seen = |client: &Client, prefix: &str| {
+        client.console().lines_since(0).any(|line| line.starts_with(prefix))
+    };
+    assert!(seen(&players[0].1, "Seed:"));
+    assert!(!seen(&players[1].1, "Seed:"));
+    assert!(seen(&players[1].1, "<A> hi"));
+}
@@ -0,0 +1,84 @@
+/// Ganzzahl-Hash -> [0, 1), deterministisch pro Seed
+#[inline]
+pub fn hash2(seed: u64, x: i32, z: i32) -> f32 {
+    let mut h = seed
+        ^ (x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (z as u32 as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    h = (h ^ (h >> 31)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 29)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^= h >> 32;
+    (h & 0xFF_FFFF) as f32 / 16_777_216.0
+}
+
+#[inline]
+fn smooth(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// 2D-Value-Noise mit Gitterabstand `scale`, Ergebnis in [0, 1)
+pub fn value_noise_2d(seed: u64, x: f32, z: f32, scale: f32) -> f32 {
+    let fx = x / scale;
+    let fz = z / scale;
+    let x0 = fx.floor();
+    let z0 = fz.floor();
+    let tx = smooth(fx - x0);
+    let tz = smooth(fz - z0);
+    let (ix, iz) = (x0 as i32, z0 as i32);
+
+    let a = hash2(seed, ix, iz);
+    let b = hash2(seed, ix + 1, iz);
+    let c = hash2(seed, ix, iz + 1);
+    let d = hash2(seed, ix + 1, iz + 1);
+
+    let top = a + (b - a) * tx;
+    let bottom = c + (d - c) * tx;
+    top + (bottom - top) * tz
+}
+
+/// Perlin-Überblendung (weicher als `smooth`, auch die zweite Ableitung ist stetig)
+#[inline]
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Beitrag eines Gitterpunkts: Skalarprodukt seines Gradienten mit dem Abstand
+#[inline]
+fn gradient_dot(seed: u64, ix: i32, iz: i32, dx: f32, dz: f32) -> f32 {
+    let (s, c) = (hash2(seed, ix, iz) * std::f32::consts::TAU).sin_cos();
+    c * dx + s * dz
+}
+
+/// 2D-Perlin-Noise mit Gitterabstand `scale`, Ergebnis in etwa [-1, 1]
+pub fn perlin_2d(seed: u64, x: f32, z: f32, scale: f32) -> f32 {
+    let fx = x / scale;
+    let fz = z / scale;
+    let x0 = fx.floor();
+    let z0 = fz.floor();
+    let (dx, dz) = (fx - x0, fz - z0);
+    let (ix, iz) = (x0 as i32, z0 as i32);
+
+    let a = gradient_dot(seed, ix, iz, dx, dz);
+    let b = gradient_dot(seed, ix + 1, iz, dx - 1.0, dz);
+    let c = gradient_dot(seed, ix, iz + 1, dx, dz - 1.0);
+    let d = gradient_dot(seed, ix + 1, iz + 1, dx - 1.0, dz - 1.0);
+
+    let (tx, tz) = (fade(dx), fade(dz));
+    let top = a + (b - a) * tx;
+    let bottom = c + (d - c) * tx;
+    // Einheitsgradienten erreichen höchstens ±√½
+    (top + (bottom - top) * tz) * std::f32::consts::SQRT_2
+}
+
+/// Mehrere Oktaven Perlin-Noise, jede halb so groß und halb so stark; in etwa [-1, 1]
+pub fn fbm_2d(seed: u64, x: f32, z: f32, scale: f32, octaves: u32) -> f32 {
+    let (mut sum, mut weight, mut total) = (0.0, 1.0, 0.0);
+    let mut scale = scale;
+    for octave in 0..octaves as u64 {
+        let seed = seed.wrapping_add(octave.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        sum += perlin_2d(seed, x, z, scale) * weight;
+        total += weight;
+        weight *= 0.5;
+        scale *= 0.5;
+    }
+    if total > 0.0 { sum / total } else { 0.0 }
+}
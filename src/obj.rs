@@ -0,0 +1,20 @@
+use std::fmt::Write;
+
+use crate::mesh::Vertex;
+
+/// Wavefront OBJ mit Vertexfarben (`v x y z r g b`, liest z.B. Blender).
+/// `origin` wird zum Nullpunkt, damit das Modell nicht weit draußen liegt.
+pub fn encode(verts: &[Vertex], inds: &[u32], origin: [f32; 3]) -> String {
+    let mut out = String::new();
+    out.push_str("# rust_game export\n");
+    for v in verts {
+        let [x, y, z] = [0, 1, 2].map(|i| v.pos[i] - origin[i]);
+        let [r, g, b] = v.color;
+        let _ = writeln!(out, "v {x} {y} {z} {r:.3} {g:.3} {b:.3}");
+    }
+    for tri in inds.chunks_exact(3) {
+        // OBJ zählt ab 1
+        let _ = writeln!(out, "f {} {} {}", tri[0] + 1, tri[1] + 1, tri[2] + 1);
+    }
+    out
+}
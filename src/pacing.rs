@@ -0,0 +1,141 @@
+//! Frame-Pacing: Ticks laufen im festen Takt, gezeichnet wird je nach Fensterzustand.
+//! Mit Fokus durchgehend (Kamera zwischen den letzten beiden Ticks interpoliert), ohne
+//! Fokus nur mit jedem paar Ticks ein Bild, minimiert oder verdeckt gar nicht.
+
+use std::time::{Duration, Instant};
+
+use winit::dpi::PhysicalSize;
+use winit::event_loop::ControlFlow;
+
+/// Ohne Fokus ein Bild alle so viele Ticks
+const BACKGROUND_FRAME_TICKS: u32 = 5;
+/// Liegt der Tick-Takt weiter zurück (Suspend, Debugger), wird neu aufgesetzt statt aufgeholt
+const MAX_BEHIND_TICKS: u32 = 10;
+/// Weiter als so viele Blöcke pro Tick ist ein Sprung (Teleport, Respawn), kein Gleiten
+const SNAP_DISTANCE: f32 = 4.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Durchgehend zeichnen
+    Focused,
+    /// Weiter ticken, selten zeichnen
+    Background,
+    /// Minimiert oder verdeckt: nur ticken
+    Hidden,
+}
+
+pub struct FramePacer {
+    tick_dt: Duration,
+    next_tick: Instant,
+    /// Planzeit des letzten Ticks, Bezug für die Interpolation
+    last_tick: Instant,
+    focused: bool,
+    minimized: bool,
+    occluded: bool,
+    ticks_since_frame: u32,
+}
+
+impl FramePacer {
+    pub fn new(tick_dt: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            tick_dt,
+            next_tick: now + tick_dt,
+            last_tick: now,
+            focused: true,
+            minimized: false,
+            occluded: false,
+            ticks_since_frame: 0,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        if self.minimized || self.occluded {
+            Mode::Hidden
+        } else if self.focused {
+            Mode::Focused
+        } else {
+            Mode::Background
+        }
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn set_occluded(&mut self, occluded: bool) {
+        self.occluded = occluded;
+    }
+
+    /// Minimieren meldet winit als Größe 0
+    pub fn resized(&mut self, size: PhysicalSize<u32>) {
+        self.minimized = size.width == 0 || size.height == 0;
+    }
+
+    pub fn tick_due(&self, now: Instant) -> bool {
+        now >= self.next_tick
+    }
+
+    /// Nach einem Tick: Takt weiterschalten; true = jetzt ein Bild anfordern
+    pub fn ticked(&mut self, now: Instant) -> bool {
+        self.last_tick = self.next_tick;
+        self.next_tick += self.tick_dt;
+        if now > self.next_tick + self.tick_dt * MAX_BEHIND_TICKS {
+            log::debug!("Tick-Takt {:?} im Rückstand, setze neu auf", now - self.next_tick);
+            self.last_tick = now;
+            self.next_tick = now + self.tick_dt;
+        }
+        match self.mode() {
+            Mode::Focused => true,
+            Mode::Background => {
+                self.ticks_since_frame += 1;
+                self.ticks_since_frame >= BACKGROUND_FRAME_TICKS
+            }
+            Mode::Hidden => false,
+        }
+    }
+
+    /// Gezeichnet wurde (auch vom System angefordert, z.B. nach Resize)
+    pub fn rendered(&mut self) {
+        self.ticks_since_frame = 0;
+    }
+
+    /// Mit Fokus jede Runde ein Bild anfordern, sonst bis zum nächsten Tick schlafen
+    pub fn control_flow(&self) -> ControlFlow {
+        match self.mode() {
+            Mode::Focused => ControlFlow::Poll,
+            Mode::Background | Mode::Hidden => ControlFlow::WaitUntil(self.next_tick),
+        }
+    }
+
+    /// Anteil (0..=1) der Zeit seit dem letzten Tick am Tick-Abstand
+    pub fn alpha(&self, now: Instant) -> f32 {
+        let since = now.saturating_duration_since(self.last_tick);
+        (since.as_secs_f32() / self.tick_dt.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Wert der letzten beiden Ticks, zum Zeichnen dazwischen interpoliert
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Smoothed {
+    prev: (f32, f32, f32),
+    cur: (f32, f32, f32),
+}
+
+impl Smoothed {
+    pub fn push(&mut self, v: (f32, f32, f32)) {
+        let (dx, dy, dz) = (v.0 - self.cur.0, v.1 - self.cur.1, v.2 - self.cur.2);
+        let jumped = dx * dx + dy * dy + dz * dz > SNAP_DISTANCE * SNAP_DISTANCE;
+        self.prev = if jumped { v } else { self.cur };
+        self.cur = v;
+    }
+
+    pub fn at(&self, alpha: f32) -> (f32, f32, f32) {
+        let (a, b) = (self.prev, self.cur);
+        (
+            a.0 + (b.0 - a.0) * alpha,
+            a.1 + (b.1 - a.1) * alpha,
+            a.2 + (b.2 - a.2) * alpha,
+        )
+    }
+}
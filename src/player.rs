@@ -1,3 +1,31 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::block::Block;
+use crate::gamemode::GameMode;
+use crate::inventory::{Inventory, ItemStack};
+use crate::item::Item;
+use crate::movement::{Body, EYE_HEIGHT};
+use crate::serial::{ByteReader, ByteWriter};
+
+/// 9 Hotbar-Slots + 27 Hauptinventar
+pub const PLAYER_INV_SLOTS: usize = 36;
+/// Hotbar = Inventarslots 0..HOTBAR_SLOTS
+pub const HOTBAR_SLOTS: usize = 9;
+/// Lebenspunkte (2 pro Herz)
+pub const MAX_HEALTH: u8 = 20;
+/// Hungerpunkte (2 pro Keule)
+pub const MAX_HUNGER: u8 = 20;
+/// So viel Erschöpfung kostet einen Hungerpunkt
+const EXHAUSTION_PER_HUNGER: f32 = 4.0;
+/// Ticks zwischen Heilen bzw. Verhungern (4 s)
+const VITALS_INTERVAL: u32 = 80;
+/// Ab diesem Hunger heilt man
+const REGEN_MIN_HUNGER: u8 = 18;
+/// Sprinten nur mit etwas im Magen
+pub const SPRINT_MIN_HUNGER: u8 = 7;
+/// Fallhöhe ohne Schaden (Blöcke)
+const SAFE_FALL: f32 = 3.0;
+
 #[derive(Debug)]
 pub struct Player {
     pub x: f32,
@@ -10,51 +38,292 @@ pub struct Player {
 
     pub vy: f32, // vertikale Geschwindigkeit (für Springen/Fallen)
     pub on_ground: bool,
+
+    pub inventory: Inventory,
+    /// Aktiver Hotbar-Slot (0..HOTBAR_SLOTS)
+    pub selected_slot: usize,
+
+    pub game_mode: GameMode,
+    /// Fliegen (nur in Modi mit `can_fly`)
+    pub flying: bool,
+
+    pub health: u8,
+    pub hunger: u8,
+    /// Sammelt sich durch Springen/Sprinten; je 4.0 kosten einen Hungerpunkt
+    pub exhaustion: f32,
+    /// Ticks bis zum nächsten Heilen/Verhungern
+    vitals_timer: u32,
+    /// Höchster Punkt seit dem letzten Bodenkontakt (Fallschaden)
+    pub fall_start_y: f32,
+    /// Am Boden gelaufene Strecke seit dem letzten Schrittgeräusch
+    pub step_distance: f32,
+}
+
+/// Größter Blickwinkel nach oben/unten (~89°)
+pub const PITCH_LIMIT: f32 = 1.55;
+
+/// Vorwärtsrichtung aus Blickwinkeln (yaw: links/rechts, pitch: hoch/runter)
+pub fn look_dir(yaw: f32, pitch: f32) -> (f32, f32, f32) {
+    let (sy, cy) = (yaw.sin(), yaw.cos());
+    let (sp, cp) = (pitch.sin(), pitch.cos());
+    (sy * cp, sp, cy * cp)
+}
+
+/// Standard-Spawnpunkt (neue Welten, Welten ohne level.toml)
+pub const SPAWN: (f32, f32, f32) = (3.5, 1.0, 3.5);
+/// Name im Chat, solange keiner gesetzt ist (Einzelspieler)
+pub const DEFAULT_NAME: &str = "Player";
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Player {
     pub fn new() -> Self {
         Self {
-            x: 3.5,
-            y: 1.0,
-            z: 3.5,
+            x: SPAWN.0,
+            y: SPAWN.1,
+            z: SPAWN.2,
             yaw: 0.0,
             pitch: 0.35,
             vy: 0.0,
             on_ground: false,
+            inventory: starter_inventory(),
+            selected_slot: 0,
+            game_mode: GameMode::default(),
+            flying: false,
+            health: MAX_HEALTH,
+            hunger: MAX_HUNGER,
+            exhaustion: 0.0,
+            vitals_timer: 0,
+            fall_start_y: 1.0,
+            step_distance: 0.0,
         }
     }
 
+    /// Hunger-Verbrauch (nur mit Vitalwerten)
+    pub fn exhaust(&mut self, amount: f32) {
+        if !self.game_mode.has_vitals() {
+            return;
+        }
+        self.exhaustion += amount;
+        while self.exhaustion >= EXHAUSTION_PER_HUNGER {
+            self.exhaustion -= EXHAUSTION_PER_HUNGER;
+            self.hunger = self.hunger.saturating_sub(1);
+        }
+    }
+
+    pub fn damage(&mut self, amount: u8) {
+        if self.game_mode.has_vitals() {
+            self.health = self.health.saturating_sub(amount);
+        }
+    }
+
+    /// Essen: false, wenn satt (Item wird dann nicht verbraucht)
+    pub fn eat(&mut self, food: u8) -> bool {
+        if food == 0 || self.hunger >= MAX_HUNGER {
+            return false;
+        }
+        self.hunger = (self.hunger + food).min(MAX_HUNGER);
+        true
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+
+    /// Pro Tick: satt -> heilen (kostet Hunger), leer -> verhungern (bis 1 Leben)
+    pub fn tick_vitals(&mut self) {
+        if !self.game_mode.has_vitals() {
+            return;
+        }
+        self.vitals_timer += 1;
+        if self.vitals_timer < VITALS_INTERVAL {
+            return;
+        }
+        self.vitals_timer = 0;
+        if self.hunger >= REGEN_MIN_HUNGER && self.health < MAX_HEALTH {
+            self.health += 1;
+            self.exhaust(3.0);
+        } else if self.hunger == 0 && self.health > 1 {
+            self.health -= 1;
+        }
+    }
+
+    /// Landung: Schaden für jeden Block über der sicheren Fallhöhe
+    pub fn land(&mut self) {
+        let fall = self.fall_start_y - self.y;
+        if fall > SAFE_FALL && !self.flying {
+            self.damage((fall - SAFE_FALL).ceil() as u8);
+        }
+        self.fall_start_y = self.y;
+    }
+
+    /// Nach dem Tod: zurück zum Spawn, Vitalwerte voll, Inventar bleibt
+    pub fn respawn(&mut self, spawn: (f32, f32, f32)) {
+        (self.x, self.y, self.z) = spawn;
+        self.vy = 0.0;
+        self.health = MAX_HEALTH;
+        self.hunger = MAX_HUNGER;
+        self.exhaustion = 0.0;
+        self.fall_start_y = self.y;
+    }
+
+    pub fn set_game_mode(&mut self, mode: GameMode) {
+        self.game_mode = mode;
+        if !mode.can_fly() {
+            self.flying = false;
+        }
+    }
+
+    pub fn select_slot(&mut self, slot: usize) {
+        if slot < HOTBAR_SLOTS {
+            self.selected_slot = slot;
+        }
+    }
+
+    /// Mausrad: weiterblättern mit Umlauf
+    pub fn scroll_hotbar(&mut self, steps: i32) {
+        let n = HOTBAR_SLOTS as i32;
+        self.selected_slot = (self.selected_slot as i32 + steps).rem_euclid(n) as usize;
+    }
+
+    /// Pick-Block: Hotbar-Treffer auswählen, sonst aus dem Hauptinventar in den aktiven
+    /// Slot tauschen (Creative: neuen vollen Stack anlegen)
+    pub fn pick_block(&mut self, item: Item) {
+        let slot_of = |inv: &Inventory, range: std::ops::Range<usize>| {
+            range.into_iter().find(|&i| inv.get(i).is_some_and(|s| s.item == item))
+        };
+        if let Some(i) = slot_of(&self.inventory, 0..HOTBAR_SLOTS) {
+            self.selected_slot = i;
+        } else if let Some(i) = slot_of(&self.inventory, HOTBAR_SLOTS..self.inventory.len()) {
+            let picked = self.inventory.take(i);
+            let old = self.inventory.take(self.selected_slot);
+            self.inventory.set(self.selected_slot, picked);
+            self.inventory.set(i, old);
+        } else if !self.game_mode.consumes_items() {
+            self.inventory.set(self.selected_slot, Some(ItemStack::full(item)));
+        }
+    }
+
+    /// Stack im aktiven Hotbar-Slot
+    pub fn selected_stack(&self) -> Option<ItemStack> {
+        self.inventory.get(self.selected_slot)
+    }
+
     pub fn eye_pos(&self) -> (f32, f32, f32) {
-        (self.x, self.y + 0.9, self.z)
+        (self.x, self.y + EYE_HEIGHT, self.z)
     }
 
-    pub fn dir(&self) -> (f32, f32, f32) {
-        // yaw: links/rechts, pitch: hoch/runter
-        let cy = self.yaw.cos();
-        let sy = self.yaw.sin();
-        let cp = self.pitch.cos();
-        let sp = self.pitch.sin();
+    pub fn body(&self) -> Body {
+        Body {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            vy: self.vy,
+            on_ground: self.on_ground,
+            flying: self.flying,
+        }
+    }
 
-        // Vorwärtsrichtung
-        let dx = sy * cp;
-        let dy = sp;
-        let dz = cy * cp;
+    pub fn set_body(&mut self, b: Body) {
+        (self.x, self.y, self.z) = (b.x, b.y, b.z);
+        (self.vy, self.on_ground, self.flying) = (b.vy, b.on_ground, b.flying);
+    }
 
-        (dx, dy, dz)
+    pub fn dir(&self) -> (f32, f32, f32) {
+        look_dir(self.yaw, self.pitch)
     }
 
     pub fn add_look(&mut self, delta_yaw: f32, delta_pitch: f32) {
         self.yaw += delta_yaw;
-        self.pitch += delta_pitch;
-
         // clamp pitch (nicht über Kopf drehen)
-        let limit = 1.55; // ~89°
-        if self.pitch > limit {
-            self.pitch = limit;
+        self.pitch = (self.pitch + delta_pitch).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    pub fn heap_bytes(&self) -> usize {
+        size_of::<Self>() + self.inventory.heap_bytes()
+    }
+}
+
+const PLAYER_MAGIC: &[u8; 4] = b"VXPL";
+/// v2: Spielmodus, v3: Knochenmehl als Item im Inventar statt eigenem Zähler,
+/// v4: Leben und Hunger
+const PLAYER_FORMAT_VERSION: u16 = 4;
+
+impl Player {
+    /// Position, Blickrichtung, Hotbar-Auswahl und Inventar
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = ByteWriter::new();
+        w.bytes(PLAYER_MAGIC);
+        w.u16(PLAYER_FORMAT_VERSION);
+        for v in [self.x, self.y, self.z, self.yaw, self.pitch] {
+            w.f32(v);
         }
-        if self.pitch < -limit {
-            self.pitch = -limit;
+        w.u8(self.selected_slot as u8);
+        self.inventory.write(&mut w);
+        w.u8(self.game_mode.id());
+        w.u8(self.flying as u8);
+        w.u8(self.health);
+        w.u8(self.hunger);
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        if r.bytes(4)? != PLAYER_MAGIC {
+            bail!("keine Spieler-Datei");
+        }
+        let version = r.u16()?;
+        if version == 0 || version > PLAYER_FORMAT_VERSION {
+            bail!("Spieler-Format v{version} nicht unterstützt");
+        }
+        let mut p = Player::new();
+        p.x = r.f32()?;
+        p.y = r.f32()?;
+        p.z = r.f32()?;
+        p.yaw = r.f32()?;
+        p.pitch = r.f32()?;
+        p.select_slot(r.u8()? as usize);
+        let bonemeal = if version < 3 { r.u16()? } else { 0 };
+        p.inventory = Inventory::read(&mut r)?;
+        if bonemeal > 0 {
+            p.inventory.add(ItemStack::new(Item::Bonemeal, bonemeal));
         }
+        if version >= 2 {
+            let id = r.u8()?;
+            let mode =
+                GameMode::from_id(id).ok_or_else(|| anyhow!("unbekannter Spielmodus {id}"))?;
+            p.set_game_mode(mode);
+            p.flying = r.u8()? != 0 && mode.can_fly();
+        }
+        if version >= 4 {
+            p.health = r.u8()?.min(MAX_HEALTH);
+            p.hunger = r.u8()?.min(MAX_HUNGER);
+            if p.health == 0 {
+                p.respawn(SPAWN);
+            }
+        }
+        p.fall_start_y = p.y;
+        Ok(p)
     }
 }
+
+fn starter_inventory() -> Inventory {
+    let mut inv = Inventory::new(PLAYER_INV_SLOTS);
+    inv.set(0, Some(ItemStack::new(Block::Stone, 64)));
+    inv.set(1, Some(ItemStack::new(Block::Dirt, 64)));
+    inv.set(2, Some(ItemStack::new(Block::StoneSlab, 32)));
+    inv.set(3, Some(ItemStack::new(Block::Log, 16)));
+    inv.set(4, Some(ItemStack::new(Block::Glass, 16)));
+    inv.set(5, Some(ItemStack::new(Block::Torch, 16)));
+    inv.set(6, Some(ItemStack::new(Block::Door, 4)));
+    inv.set(7, Some(ItemStack::new(Block::Sapling, 8)));
+    inv.set(8, Some(ItemStack::new(Block::Chest, 2)));
+    inv.set(9, Some(ItemStack::new(Item::Bonemeal, 16)));
+    inv.set(10, Some(ItemStack::new(Item::Seeds, 8)));
+    inv.set(11, Some(ItemStack::new(Item::Bread, 8)));
+    inv
+}
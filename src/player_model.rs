@@ -0,0 +1,223 @@
+//! Spielermodell aus Quadern (Kopf, Körper, Arme, Beine) für andere Spieler und die
+//! eigene Figur in der Außenansicht. Arme und Beine schwingen mit der zurückgelegten
+//! Strecke; unter den Füßen liegt ein weicher runder Schatten, bis es echte Schatten gibt.
+
+use std::f32::consts::TAU;
+use std::time::Instant;
+
+use glam::Vec3;
+
+use crate::mesh::Vertex;
+use crate::protocol::PlayerState;
+use crate::world::World;
+
+/// Flächenhelligkeit: oben, Seiten (x, z), unten
+const SHADE_TOP: f32 = 1.0;
+const SHADE_X: f32 = 0.8;
+const SHADE_Z: f32 = 0.65;
+const SHADE_BOTTOM: f32 = 0.5;
+/// Modellteile in Spielerkoordinaten (x rechts, y hoch ab den Füßen, z vorne): min, max, Farbe
+const TORSO: ([f32; 3], [f32; 3], [f32; 3]) =
+    ([-0.25, 0.75, -0.125], [0.25, 1.3, 0.125], [0.2, 0.6, 0.65]);
+/// Kopf relativ zum Hals, kippt mit dem Blick
+const NECK_HEIGHT: f32 = 1.3;
+const HEAD: ([f32; 3], [f32; 3], [f32; 3]) =
+    ([-0.25, 0.0, -0.25], [0.25, 0.5, 0.25], [0.85, 0.7, 0.55]);
+/// Beine und Arme hängen an Hüfte bzw. Schulter (x des Gelenks, Höhe) und schwingen dort
+const HIP: (f32, f32) = (0.125, 0.75);
+const LEG: ([f32; 3], [f32; 3], [f32; 3]) =
+    ([-0.125, -0.75, -0.125], [0.125, 0.0, 0.125], [0.4, 0.3, 0.7]);
+const SHOULDER: (f32, f32) = (0.35, 1.3);
+const ARM: ([f32; 3], [f32; 3], [f32; 3]) =
+    ([-0.1, -0.55, -0.1], [0.1, 0.0, 0.1], [0.2, 0.6, 0.65]);
+/// Schwingwinkel pro gelaufenem Block und größter Ausschlag (Radiant)
+const STRIDE: f32 = 2.5;
+const MAX_SWING: f32 = 0.8;
+/// Ab dieser Geschwindigkeit (Blöcke/s) voller Ausschlag
+const FULL_SWING_SPEED: f32 = 4.0;
+/// Sprünge zwischen zwei Positionen (Teleport) zählen nicht als Schritt
+const MAX_STEP: f32 = 2.0;
+/// Schatten: Radius, Dunkelheit in der Mitte, Segmente am Rand
+const SHADOW_RADIUS: f32 = 0.45;
+const SHADOW_DARKNESS: f32 = 0.45;
+const SHADOW_SEGMENTS: u32 = 12;
+/// So tief wird unter den Füßen nach Boden gesucht; der Schatten verblasst bis dahin
+const SHADOW_DEPTH: i32 = 4;
+/// Abstand über der Oberfläche gegen Z-Fighting
+const SHADOW_LIFT: f32 = 0.02;
+
+/// Haltung eines Spielers für einen Frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pose {
+    /// Füße
+    pub feet: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub stride: Stride,
+}
+
+impl Pose {
+    pub fn new(state: &PlayerState, stride: Stride) -> Self {
+        let (x, y, z) = state.pos;
+        Self {
+            feet: Vec3::new(x, y, z),
+            yaw: state.yaw,
+            pitch: state.pitch,
+            stride,
+        }
+    }
+
+    /// Ausschlag von Armen und Beinen; ruht im Stand
+    fn swing(&self) -> f32 {
+        let amount = (self.stride.speed / FULL_SWING_SPEED).min(1.0);
+        (self.stride.walked * STRIDE % TAU).sin() * MAX_SWING * amount
+    }
+}
+
+/// Gelaufene Strecke in XZ und aktuelle Geschwindigkeit, aus aufeinanderfolgenden Positionen
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stride {
+    pub walked: f32,
+    /// Blöcke/s
+    pub speed: f32,
+    last: Option<(Instant, f32, f32)>,
+}
+
+impl Stride {
+    pub fn step(&mut self, at: Instant, (x, _, z): (f32, f32, f32)) {
+        if let Some((t, lx, lz)) = self.last {
+            let dist = ((x - lx).powi(2) + (z - lz).powi(2)).sqrt();
+            let dist = if dist > MAX_STEP { 0.0 } else { dist };
+            let dt = at.saturating_duration_since(t).as_secs_f32();
+            if dt > 0.0 {
+                self.walked += dist;
+                self.speed = dist / dt;
+            }
+        }
+        self.last = Some((at, x, z));
+    }
+
+    /// Zwischenstand zweier Messungen (`k` 0..1)
+    pub fn lerp(self, other: Stride, k: f32) -> Stride {
+        Stride {
+            walked: self.walked + (other.walked - self.walked) * k,
+            speed: self.speed + (other.speed - self.speed) * k,
+            last: other.last,
+        }
+    }
+}
+
+/// Alle Modelle in einem Mesh; der Kopf folgt dem Blick
+pub fn model_mesh(poses: &[Pose]) -> (Vec<Vertex>, Vec<u32>) {
+    let mut verts = Vec::with_capacity(poses.len() * 144);
+    let mut inds = Vec::with_capacity(poses.len() * 216);
+    for pose in poses {
+        let feet = pose.feet;
+        // Achsen wie in `Body::walk`: vorwärts nur in XZ, rechts daneben
+        let (sy, cy) = pose.yaw.sin_cos();
+        let fwd = Vec3::new(sy, 0.0, cy);
+        let right = Vec3::new(cy, 0.0, -sy);
+        let body = [right, Vec3::Y, fwd];
+        push_box(&mut verts, &mut inds, feet, body, TORSO);
+
+        // Gegengleich: linkes Bein mit rechtem Arm
+        let swing = pose.swing();
+        for (side, angle) in [(-1.0, swing), (1.0, -swing)] {
+            let (sa, ca) = angle.sin_cos();
+            let limb = [right, Vec3::Y * ca - fwd * sa, fwd * ca + Vec3::Y * sa];
+            let hip = feet + right * (side * HIP.0) + Vec3::Y * HIP.1;
+            push_box(&mut verts, &mut inds, hip, limb, LEG);
+
+            let (sa, ca) = (-angle).sin_cos();
+            let limb = [right, Vec3::Y * ca - fwd * sa, fwd * ca + Vec3::Y * sa];
+            let shoulder = feet + right * (side * SHOULDER.0) + Vec3::Y * SHOULDER.1;
+            push_box(&mut verts, &mut inds, shoulder, limb, ARM);
+        }
+
+        let (sp, cp) = pose.pitch.sin_cos();
+        let head = [right, Vec3::Y * cp - fwd * sp, fwd * cp + Vec3::Y * sp];
+        push_box(&mut verts, &mut inds, feet + Vec3::Y * NECK_HEIGHT, head, HEAD);
+    }
+    (verts, inds)
+}
+
+/// Runde Schattenflecken auf dem Boden unter den Füßen: Farbe = Faktor fürs Multiplizieren
+/// (innen dunkel, außen 1). Wer höher als `SHADOW_DEPTH` über dem Boden ist, wirft keinen.
+pub fn shadow_mesh(world: &World, poses: &[Pose]) -> (Vec<Vertex>, Vec<u32>) {
+    let mut verts = Vec::new();
+    let mut inds = Vec::new();
+    for pose in poses {
+        let Some(ground) = ground_below(world, pose.feet) else {
+            continue;
+        };
+        let height = pose.feet.y - ground;
+        let fade = (1.0 - height / SHADOW_DEPTH as f32).clamp(0.0, 1.0);
+        let center = 1.0 - SHADOW_DARKNESS * fade;
+        let radius = SHADOW_RADIUS * (1.0 - 0.3 * (1.0 - fade));
+        let at = Vec3::new(pose.feet.x, ground + SHADOW_LIFT, pose.feet.z);
+
+        let base = verts.len() as u32;
+        let vertex = |pos: Vec3, shade: f32| Vertex {
+            pos: pos.to_array(),
+            color: [shade; 3],
+            light: [0.0, 0.0],
+            emissive: 1.0,
+        };
+        verts.push(vertex(at, center));
+        for i in 0..SHADOW_SEGMENTS {
+            let a = i as f32 / SHADOW_SEGMENTS as f32 * TAU;
+            verts.push(vertex(at + Vec3::new(a.cos(), 0.0, a.sin()) * radius, 1.0));
+        }
+        for i in 0..SHADOW_SEGMENTS {
+            let next = (i + 1) % SHADOW_SEGMENTS;
+            inds.extend([base, base + 1 + next, base + 1 + i]);
+        }
+    }
+    (verts, inds)
+}
+
+/// Oberkante des ersten festen Blocks unter den Füßen
+fn ground_below(world: &World, feet: Vec3) -> Option<f32> {
+    let (x, z) = (feet.x.floor() as i32, feet.z.floor() as i32);
+    let top = (feet.y - 0.01).floor() as i32;
+    (top - SHADOW_DEPTH..=top)
+        .rev()
+        .find(|&y| world.is_solid(x, y, z))
+        .map(|y| y as f32 + 1.0)
+}
+
+/// Quader in den Achsen `axes` (rechts, oben, vorne) ab `origin`
+fn push_box(
+    verts: &mut Vec<Vertex>,
+    inds: &mut Vec<u32>,
+    origin: Vec3,
+    axes: [Vec3; 3],
+    (min, max, color): ([f32; 3], [f32; 3], [f32; 3]),
+) {
+    // Ecken als Bitmaske xyz (gesetzt = positive Seite)
+    let corner = |i: usize| {
+        let pick = |a: usize| if i & (1 << a) != 0 { max[a] } else { min[a] };
+        origin + axes[0] * pick(0) + axes[1] * pick(1) + axes[2] * pick(2)
+    };
+    // Je Fläche vier Ecken gegen den Uhrzeigersinn von außen gesehen
+    const FACES: [([usize; 4], f32); 6] = [
+        ([0b100, 0b101, 0b111, 0b110], SHADE_Z),
+        ([0b101, 0b001, 0b011, 0b111], SHADE_X),
+        ([0b001, 0b000, 0b010, 0b011], SHADE_Z),
+        ([0b000, 0b100, 0b110, 0b010], SHADE_X),
+        ([0b110, 0b111, 0b011, 0b010], SHADE_TOP),
+        ([0b000, 0b001, 0b101, 0b100], SHADE_BOTTOM),
+    ];
+    for (quad, shade) in FACES {
+        let base = verts.len() as u32;
+        for i in quad {
+            verts.push(Vertex {
+                pos: corner(i).to_array(),
+                color: color.map(|c| c * shade),
+                light: [1.0, 0.0],
+                emissive: 0.0,
+            });
+        }
+        inds.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+}
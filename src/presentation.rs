@@ -0,0 +1,111 @@
+//! Fensterrahmen: Icon und Titel. Der Titel kommt aus einem kleinen Zustand (Welt,
+//! Sitzung, FPS) und wird höchstens einmal pro Sekunde und nur bei Änderung gesetzt.
+
+use std::time::{Duration, Instant};
+
+use rust_game::rng::Rng;
+use winit::window::{Icon, Window};
+
+pub const APP_NAME: &str = "Voxel Engine";
+/// Abstand der FPS-Messungen
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+/// Kantenlänge des Icons in Pixeln
+const ICON_SIZE: u32 = 32;
+/// Grasdecke oben auf dem Icon-Block (Pixelzeilen)
+const ICON_GRASS: u32 = 10;
+
+/// Woher die Welt kommt, die gerade läuft
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Session {
+    Singleplayer,
+    /// Einzelspieler mit Aufzeichnung (`--record`)
+    Recording,
+    Replay,
+    Multiplayer { addr: String },
+}
+
+pub struct Presentation {
+    pub world: String,
+    pub session: Session,
+    /// Bilder pro Sekunde der letzten vollen Messung
+    fps: Option<u32>,
+    frames: u32,
+    since: Instant,
+    /// Zuletzt gesetzter Titel
+    shown: String,
+}
+
+impl Presentation {
+    pub fn new(world: impl Into<String>, session: Session) -> Self {
+        Self {
+            world: world.into(),
+            session,
+            fps: None,
+            frames: 0,
+            since: Instant::now(),
+            shown: String::new(),
+        }
+    }
+
+    /// Ein Bild wurde gezeichnet
+    pub fn frame(&mut self) {
+        self.frames += 1;
+    }
+
+    pub fn title(&self) -> String {
+        let mut parts = vec![APP_NAME.to_string()];
+        if !self.world.is_empty() {
+            parts.push(self.world.clone());
+        }
+        parts.push(match &self.session {
+            Session::Singleplayer => "Singleplayer".to_string(),
+            Session::Recording => "Singleplayer (recording)".to_string(),
+            Session::Replay => "Replay".to_string(),
+            Session::Multiplayer { addr } => format!("Multiplayer @ {addr}"),
+        });
+        if let Some(fps) = self.fps {
+            parts.push(format!("{fps} FPS"));
+        }
+        parts.join(" – ")
+    }
+
+    /// Jede Sekunde die FPS neu messen und den Titel setzen, falls er sich geändert hat
+    /// (ohne gezeichnete Bilder, etwa minimiert, stehen dort 0 FPS)
+    pub fn update(&mut self, window: &Window, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.since);
+        if elapsed >= FPS_WINDOW {
+            self.fps = Some((self.frames as f32 / elapsed.as_secs_f32()).round() as u32);
+            self.frames = 0;
+            self.since = now;
+        }
+        let title = self.title();
+        if title != self.shown {
+            window.set_title(&title);
+            self.shown = title;
+        }
+    }
+}
+
+/// Grasblock als Icon, pixelig wie die Welt; immer dieselben Pixel (fester Seed)
+pub fn icon() -> Option<Icon> {
+    let mut rng = Rng::new(0x1C0_6A55);
+    let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+    for y in 0..ICON_SIZE {
+        for x in 0..ICON_SIZE {
+            let edge = x == 0 || y == 0 || x == ICON_SIZE - 1 || y == ICON_SIZE - 1;
+            // Gras franst unten unregelmäßig in die Erde aus
+            let grass = y < ICON_GRASS || (y < ICON_GRASS + 2 && rng.below(3) == 0);
+            let base: [f32; 3] = if grass { [0.36, 0.62, 0.24] } else { [0.52, 0.36, 0.22] };
+            let shade = if edge { 0.45 } else { 0.8 + rng.below(5) as f32 * 0.05 };
+            rgba.extend(base.map(|c| (c * shade * 255.0) as u8));
+            rgba.push(255);
+        }
+    }
+    match Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE) {
+        Ok(icon) => Some(icon),
+        Err(e) => {
+            log::warn!("Fenster-Icon: {e}");
+            None
+        }
+    }
+}
@@ -0,0 +1,630 @@
+use std::f32::consts::{PI, TAU};
+use std::io::{Read, Write};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::block::{Block, SoundGroup};
+use crate::chunk::{Chunk, ChunkPos, CHUNK_SIZE, CHUNK_VOL};
+use crate::mesh::Vertex;
+use crate::movement::Body;
+use crate::replay::ReplayFrame;
+use crate::serial::{ByteReader, ByteWriter};
+use crate::sound::{SoundEvent, SoundKind};
+use crate::world::BlockPos;
+
+/// Erhöhen, sobald sich eine Nachricht ändert; der Handshake lehnt andere Versionen ab
+pub const PROTOCOL_VERSION: u16 = 11;
+const PROTOCOL_MAGIC: &[u8; 4] = b"VXNP";
+/// Größter erlaubter Frame (ein Chunk samt Licht braucht gut 12 KiB, mit `chunk32` 96 KiB)
+pub const MAX_FRAME_LEN: usize = 1 << 20;
+/// Auflösung der Spielerpositionen auf der Leitung (Blöcke)
+const POS_STEP: f32 = 1.0 / 32.0;
+/// Welche Teile eines `PlayerDelta` mitkommen
+const DELTA_POS: u8 = 1;
+const DELTA_LOOK: u8 = 2;
+const DELTA_TARGET: u8 = 4;
+
+const SOUND_KINDS: [SoundKind; 5] = [
+    SoundKind::Break,
+    SoundKind::Place,
+    SoundKind::Step,
+    SoundKind::Jump,
+    SoundKind::Land,
+];
+const SOUND_GROUPS: [SoundGroup; 7] = [
+    SoundGroup::None,
+    SoundGroup::Stone,
+    SoundGroup::Wood,
+    SoundGroup::Grass,
+    SoundGroup::Gravel,
+    SoundGroup::Glass,
+    SoundGroup::Water,
+];
+
+/// Erste Nachricht des Clients
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hello {
+    pub version: u16,
+    pub name: String,
+}
+
+/// Längster Spielername in Zeichen
+pub const MAX_NAME_LEN: usize = 16;
+
+/// Namen stehen in Chat- und Beitrittszeilen und in Befehlen wie /tp: nicht leer, kurz und
+/// nur Buchstaben, Ziffern, `_` oder `-`. Der Fehler geht als Begründung an den Client.
+pub fn check_player_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("Player name must not be empty");
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        bail!("Player name is longer than {MAX_NAME_LEN} characters");
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        bail!("Player name may only contain letters, digits, '_' and '-'");
+    }
+    Ok(())
+}
+
+/// Weltparameter, die der Server im Handshake mitschickt
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorldParams {
+    pub name: String,
+    pub seed: u64,
+    pub generator: String,
+    pub spawn: (f32, f32, f32),
+    /// `block::table_checksum` des Servers; andere Blockdaten lehnt der Client ab
+    pub blocks: u64,
+}
+
+/// Client -> Server
+#[derive(Clone, Debug)]
+pub enum ClientMsg {
+    /// Handshake; nur als erste Nachricht gültig
+    Hello(Hello),
+    /// Eingaben eines Ticks samt Blickrichtung und Konsolen-/Chatzeilen, fortlaufend nummeriert
+    Tick(u32, ReplayFrame),
+    /// Chunk-Radius, den der Client sehen will
+    ViewDistance(i32),
+    /// Chatzeile oder Befehl ('/'), läuft mit dem nächsten Tick des Spielers
+    Chat(String),
+}
+
+/// Was der Client über den eigenen Spieler wissen muss
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerView {
+    pub body: Body,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Nummer der letzten verarbeiteten Eingabe (0 = noch keine)
+    pub ack: u32,
+    /// Darf sprinten (hängt am Hunger, den nur der Server kennt)
+    pub can_sprint: bool,
+}
+
+/// Momentaufnahme eines anderen Spielers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerState {
+    pub id: u32,
+    /// Füße
+    pub pos: (f32, f32, f32),
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Anvisierter Block
+    pub target: Option<BlockPos>,
+}
+
+/// `PlayerState` so, wie er über die Leitung geht. Server und Client rechnen Deltas auf
+/// genau diesem Stand, damit sich Rundungsfehler nicht aufsummieren.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PackedPlayer {
+    /// Füße in `POS_STEP`
+    pub pos: [i32; 3],
+    /// Volle Umdrehung = 65536
+    pub yaw: u16,
+    /// Halbe Umdrehung = 32768
+    pub pitch: i16,
+    pub target: Option<BlockPos>,
+}
+
+/// Bewegung eines sichtbaren Spielers seit dem letzten Stand; nur Geändertes ist gesetzt
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerDelta {
+    pub id: u32,
+    /// In `POS_STEP`
+    pub pos: Option<[i16; 3]>,
+    pub look: Option<(u16, i16)>,
+    pub target: Option<Option<BlockPos>>,
+}
+
+impl PackedPlayer {
+    pub fn pack(st: &PlayerState) -> Self {
+        let q = |v: f32| (v / POS_STEP).round() as i32;
+        Self {
+            pos: [q(st.pos.0), q(st.pos.1), q(st.pos.2)],
+            // 65536 (knapp unter TAU aufgerundet) wird wieder zu 0
+            yaw: (st.yaw.rem_euclid(TAU) / TAU * 65536.0).round() as u32 as u16,
+            pitch: (st.pitch / PI * 32768.0).round() as i16,
+            target: st.target,
+        }
+    }
+
+    pub fn unpack(&self, id: u32) -> PlayerState {
+        let [x, y, z] = self.pos.map(|v| v as f32 * POS_STEP);
+        PlayerState {
+            id,
+            pos: (x, y, z),
+            yaw: self.yaw as f32 / 65536.0 * TAU,
+            pitch: self.pitch as f32 / 32768.0 * PI,
+            target: self.target,
+        }
+    }
+
+    /// Was sich bis `newer` geändert hat; None, wenn der Sprung nicht in ein Delta passt
+    pub fn delta(&self, id: u32, newer: &Self) -> Option<PlayerDelta> {
+        let mut pos = [0; 3];
+        for (d, (a, b)) in pos.iter_mut().zip(self.pos.iter().zip(&newer.pos)) {
+            *d = i16::try_from(b.checked_sub(*a)?).ok()?;
+        }
+        let look = (newer.yaw, newer.pitch);
+        Some(PlayerDelta {
+            id,
+            pos: (pos != [0; 3]).then_some(pos),
+            look: ((self.yaw, self.pitch) != look).then_some(look),
+            target: (self.target != newer.target).then_some(newer.target),
+        })
+    }
+
+    pub fn apply(&mut self, delta: &PlayerDelta) {
+        if let Some(d) = delta.pos {
+            for (p, d) in self.pos.iter_mut().zip(d) {
+                *p = p.wrapping_add(d as i32);
+            }
+        }
+        if let Some((yaw, pitch)) = delta.look {
+            (self.yaw, self.pitch) = (yaw, pitch);
+        }
+        if let Some(target) = delta.target {
+            self.target = target;
+        }
+    }
+}
+
+impl PlayerDelta {
+    pub fn is_empty(&self) -> bool {
+        self.pos.is_none() && self.look.is_none() && self.target.is_none()
+    }
+}
+
+/// Server -> Client
+#[derive(Clone, Debug)]
+pub enum ServerMsg {
+    /// Antwort auf `Hello`
+    Welcome(WorldParams),
+    /// Verbindung wird getrennt (z.B. falsche Protokollversion)
+    Disconnect(String),
+    /// Neuer Chunk samt Licht
+    Chunk(Box<Chunk<Block>>),
+    /// Chunk ist aus der Sichtweite gefallen
+    UnloadChunk(ChunkPos),
+    /// Einzelner Block in einem Chunk, den der Client schon hat
+    BlockChange(BlockPos, Block, u8),
+    /// Neu berechnetes Licht eines Chunks
+    ChunkLight(ChunkPos, Box<[u8]>),
+    Player(PlayerView),
+    /// Himmelslicht 0..1
+    Daylight(f32),
+    Sound(SoundEvent),
+    /// Linien-Overlay (Auswahlbox) als fertige Vertices
+    Overlay(Vec<Vertex>),
+    /// Konsolen- und Chatzeile
+    Chat(String),
+    /// Anderer Spieler ist online (Name für das Namensschild)
+    PlayerJoined(u32, String),
+    PlayerLeft(u32),
+    /// Spieler kommt in Reichweite oder ist gesprungen: Interpolation beginnt neu
+    PlayerSpawn(u32, PackedPlayer),
+    /// Spieler ist aus der Reichweite
+    PlayerDespawn(u32),
+    /// Einmal pro Tick, solange jemand in Reichweite ist; fehlende Spieler stehen still
+    PlayerMoves(Vec<PlayerDelta>),
+    /// Niederschlag 0..1 und ob er am Spieler als Schnee fällt
+    Weather { rain: f32, snow: bool },
+    /// Tageszeit 0..1 und vergangene Tage, für Sterne und Mond
+    Clock { time_of_day: f32, day: u64 },
+}
+
+impl ClientMsg {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = ByteWriter::new();
+        match self {
+            ClientMsg::Hello(hello) => {
+                w.u8(0);
+                w.bytes(PROTOCOL_MAGIC);
+                w.u16(hello.version);
+                w.str(&hello.name);
+            }
+            ClientMsg::Tick(seq, frame) => {
+                w.u8(1);
+                w.u32(*seq);
+                frame.write(&mut w);
+            }
+            ClientMsg::ViewDistance(d) => {
+                w.u8(2);
+                w.i32(*d);
+            }
+            ClientMsg::Chat(line) => {
+                w.u8(3);
+                w.str(line);
+            }
+        }
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        let msg = match r.u8()? {
+            0 => {
+                if r.bytes(4)? != PROTOCOL_MAGIC {
+                    bail!("kein Client dieses Spiels");
+                }
+                ClientMsg::Hello(Hello {
+                    version: r.u16()?,
+                    name: r.str()?,
+                })
+            }
+            1 => ClientMsg::Tick(r.u32()?, ReplayFrame::read(&mut r)?),
+            2 => ClientMsg::ViewDistance(r.i32()?),
+            3 => ClientMsg::Chat(r.str()?),
+            tag => bail!("unbekannte Client-Nachricht {tag}"),
+        };
+        finish(&r, msg)
+    }
+}
+
+impl ServerMsg {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = ByteWriter::new();
+        match self {
+            ServerMsg::Welcome(params) => {
+                w.u8(0);
+                w.u16(PROTOCOL_VERSION);
+                w.u8(CHUNK_SIZE as u8);
+                w.str(&params.name);
+                w.u64(params.seed);
+                w.str(&params.generator);
+                write_vec3(&mut w, params.spawn);
+                w.u64(params.blocks);
+            }
+            ServerMsg::Disconnect(reason) => {
+                w.u8(1);
+                w.str(reason);
+            }
+            ServerMsg::Chunk(ch) => {
+                w.u8(2);
+                let data = ch.encode();
+                w.u32(data.len() as u32);
+                w.bytes(&data);
+                w.bytes(ch.light_data());
+            }
+            ServerMsg::UnloadChunk(cp) => {
+                w.u8(3);
+                write_chunk_pos(&mut w, *cp);
+            }
+            ServerMsg::BlockChange((x, y, z), b, state) => {
+                w.u8(4);
+                w.i32(*x);
+                w.i32(*y);
+                w.i32(*z);
+                w.u8(*b as u8);
+                w.u8(*state);
+            }
+            ServerMsg::ChunkLight(cp, light) => {
+                w.u8(5);
+                write_chunk_pos(&mut w, *cp);
+                w.bytes(light);
+            }
+            ServerMsg::Player(view) => {
+                w.u8(6);
+                let b = &view.body;
+                write_vec3(&mut w, (b.x, b.y, b.z));
+                w.f32(b.vy);
+                w.u8(b.on_ground as u8 | (b.flying as u8) << 1 | (view.can_sprint as u8) << 2);
+                w.f32(view.yaw);
+                w.f32(view.pitch);
+                w.u32(view.ack);
+            }
+            ServerMsg::Daylight(d) => {
+                w.u8(7);
+                w.f32(*d);
+            }
+            ServerMsg::Sound(ev) => {
+                w.u8(8);
+                w.u8(SOUND_KINDS.iter().position(|k| *k == ev.kind).unwrap_or(0) as u8);
+                w.u8(SOUND_GROUPS.iter().position(|g| *g == ev.group).unwrap_or(0) as u8);
+                write_vec3(&mut w, ev.pos);
+                w.f32(ev.pitch);
+            }
+            ServerMsg::Overlay(verts) => {
+                w.u8(9);
+                w.u32(verts.len() as u32);
+                for v in verts {
+                    let floats = v.pos.iter().chain(&v.color).chain(&v.light);
+                    for f in floats.chain([&v.emissive]) {
+                        w.f32(*f);
+                    }
+                }
+            }
+            ServerMsg::Chat(line) => {
+                w.u8(10);
+                w.str(line);
+            }
+            ServerMsg::PlayerJoined(id, name) => {
+                w.u8(11);
+                w.u32(*id);
+                w.str(name);
+            }
+            ServerMsg::PlayerLeft(id) => {
+                w.u8(12);
+                w.u32(*id);
+            }
+            ServerMsg::PlayerSpawn(id, p) => {
+                w.u8(13);
+                w.u32(*id);
+                for v in p.pos {
+                    w.i32(v);
+                }
+                w.u16(p.yaw);
+                w.i16(p.pitch);
+                write_target(&mut w, p.target);
+            }
+            ServerMsg::PlayerDespawn(id) => {
+                w.u8(14);
+                w.u32(*id);
+            }
+            ServerMsg::PlayerMoves(moves) => {
+                w.u8(15);
+                w.u32(moves.len() as u32);
+                for d in moves {
+                    w.u32(d.id);
+                    let flags = d.pos.map_or(0, |_| DELTA_POS)
+                        | d.look.map_or(0, |_| DELTA_LOOK)
+                        | d.target.map_or(0, |_| DELTA_TARGET);
+                    w.u8(flags);
+                    for v in d.pos.into_iter().flatten() {
+                        w.i16(v);
+                    }
+                    if let Some((yaw, pitch)) = d.look {
+                        w.u16(yaw);
+                        w.i16(pitch);
+                    }
+                    if let Some(target) = d.target {
+                        write_target(&mut w, target);
+                    }
+                }
+            }
+            ServerMsg::Weather { rain, snow } => {
+                w.u8(16);
+                w.f32(*rain);
+                w.u8(*snow as u8);
+            }
+            ServerMsg::Clock { time_of_day, day } => {
+                w.u8(17);
+                w.f32(*time_of_day);
+                w.u64(*day);
+            }
+        }
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        let msg = match r.u8()? {
+            0 => {
+                let version = r.u16()?;
+                if version != PROTOCOL_VERSION {
+                    bail!("Server spricht Protokoll v{version}, wir v{PROTOCOL_VERSION}");
+                }
+                // Chunks gehen roh übers Netz: beide Seiten brauchen dieselbe Größe
+                let size = r.u8()?;
+                if size as i32 != CHUNK_SIZE {
+                    bail!("Server nutzt {size}er-Chunks, wir {CHUNK_SIZE}er");
+                }
+                ServerMsg::Welcome(WorldParams {
+                    name: r.str()?,
+                    seed: r.u64()?,
+                    generator: r.str()?,
+                    spawn: read_vec3(&mut r)?,
+                    blocks: r.u64()?,
+                })
+            }
+            1 => ServerMsg::Disconnect(r.str()?),
+            2 => {
+                let len = r.u32()? as usize;
+                let mut ch = Chunk::decode(r.bytes(len)?)?;
+                ch.set_light_data(r.bytes(CHUNK_VOL)?);
+                ServerMsg::Chunk(Box::new(ch))
+            }
+            3 => ServerMsg::UnloadChunk(read_chunk_pos(&mut r)?),
+            4 => {
+                let pos = (r.i32()?, r.i32()?, r.i32()?);
+                let id = r.u8()?;
+                let b = Block::from_id(id).ok_or_else(|| anyhow!("unbekannte Block-ID {id}"))?;
+                ServerMsg::BlockChange(pos, b, r.u8()?)
+            }
+            5 => {
+                let cp = read_chunk_pos(&mut r)?;
+                ServerMsg::ChunkLight(cp, r.bytes(CHUNK_VOL)?.into())
+            }
+            6 => {
+                let (x, y, z) = read_vec3(&mut r)?;
+                let vy = r.f32()?;
+                let flags = r.u8()?;
+                let body = Body {
+                    x,
+                    y,
+                    z,
+                    vy,
+                    on_ground: flags & 1 != 0,
+                    flying: flags & 2 != 0,
+                };
+                ServerMsg::Player(PlayerView {
+                    body,
+                    yaw: r.f32()?,
+                    pitch: r.f32()?,
+                    ack: r.u32()?,
+                    can_sprint: flags & 4 != 0,
+                })
+            }
+            7 => ServerMsg::Daylight(r.f32()?),
+            8 => {
+                let kind = *SOUND_KINDS.get(r.u8()? as usize).context("unbekannte Sound-Art")?;
+                let group = *SOUND_GROUPS.get(r.u8()? as usize).context("unbekannte Sound-Gruppe")?;
+                ServerMsg::Sound(SoundEvent {
+                    kind,
+                    group,
+                    pos: read_vec3(&mut r)?,
+                    pitch: r.f32()?,
+                })
+            }
+            9 => {
+                let n = r.u32()? as usize;
+                let mut verts = Vec::with_capacity(n.min(MAX_FRAME_LEN / 36));
+                for _ in 0..n {
+                    let mut f = [0.0; 9];
+                    for v in &mut f {
+                        *v = r.f32()?;
+                    }
+                    verts.push(Vertex {
+                        pos: [f[0], f[1], f[2]],
+                        color: [f[3], f[4], f[5]],
+                        light: [f[6], f[7]],
+                        emissive: f[8],
+                    });
+                }
+                ServerMsg::Overlay(verts)
+            }
+            10 => ServerMsg::Chat(r.str()?),
+            11 => ServerMsg::PlayerJoined(r.u32()?, r.str()?),
+            12 => ServerMsg::PlayerLeft(r.u32()?),
+            13 => {
+                let id = r.u32()?;
+                let packed = PackedPlayer {
+                    pos: [r.i32()?, r.i32()?, r.i32()?],
+                    yaw: r.u16()?,
+                    pitch: r.i16()?,
+                    target: read_target(&mut r)?,
+                };
+                ServerMsg::PlayerSpawn(id, packed)
+            }
+            14 => ServerMsg::PlayerDespawn(r.u32()?),
+            15 => {
+                let n = r.u32()? as usize;
+                let mut moves = Vec::with_capacity(n.min(MAX_FRAME_LEN / 5));
+                for _ in 0..n {
+                    let id = r.u32()?;
+                    let flags = r.u8()?;
+                    let pos = match flags & DELTA_POS {
+                        0 => None,
+                        _ => Some([r.i16()?, r.i16()?, r.i16()?]),
+                    };
+                    let look = match flags & DELTA_LOOK {
+                        0 => None,
+                        _ => Some((r.u16()?, r.i16()?)),
+                    };
+                    let target = match flags & DELTA_TARGET {
+                        0 => None,
+                        _ => Some(read_target(&mut r)?),
+                    };
+                    moves.push(PlayerDelta {
+                        id,
+                        pos,
+                        look,
+                        target,
+                    });
+                }
+                ServerMsg::PlayerMoves(moves)
+            }
+            16 => ServerMsg::Weather {
+                rain: r.f32()?,
+                snow: r.u8()? != 0,
+            },
+            17 => ServerMsg::Clock {
+                time_of_day: r.f32()?,
+                day: r.u64()?,
+            },
+            tag => bail!("unbekannte Server-Nachricht {tag}"),
+        };
+        finish(&r, msg)
+    }
+}
+
+/// Überzählige Bytes deuten auf eine andere Protokollversion hin
+fn finish<T>(r: &ByteReader, msg: T) -> Result<T> {
+    if !r.is_empty() {
+        bail!("Nachricht länger als erwartet");
+    }
+    Ok(msg)
+}
+
+fn write_vec3(w: &mut ByteWriter, (x, y, z): (f32, f32, f32)) {
+    w.f32(x);
+    w.f32(y);
+    w.f32(z);
+}
+
+fn read_vec3(r: &mut ByteReader) -> Result<(f32, f32, f32)> {
+    Ok((r.f32()?, r.f32()?, r.f32()?))
+}
+
+fn write_target(w: &mut ByteWriter, target: Option<BlockPos>) {
+    w.u8(target.is_some() as u8);
+    if let Some((x, y, z)) = target {
+        w.i32(x);
+        w.i32(y);
+        w.i32(z);
+    }
+}
+
+fn read_target(r: &mut ByteReader) -> Result<Option<BlockPos>> {
+    Ok(match r.u8()? {
+        0 => None,
+        _ => Some((r.i32()?, r.i32()?, r.i32()?)),
+    })
+}
+
+fn write_chunk_pos(w: &mut ByteWriter, cp: ChunkPos) {
+    w.i32(cp.cx);
+    w.i32(cp.cy);
+    w.i32(cp.cz);
+}
+
+fn read_chunk_pos(r: &mut ByteReader) -> Result<ChunkPos> {
+    Ok(ChunkPos::new(r.i32()?, r.i32()?, r.i32()?))
+}
+
+/// Frame: u32-Länge (Little Endian), dann die Nachricht
+pub fn write_frame(out: &mut impl Write, payload: &[u8]) -> Result<()> {
+    if payload.len() > MAX_FRAME_LEN {
+        bail!("Frame zu groß ({} Bytes)", payload.len());
+    }
+    // in einem Stück, sonst wird es bei TCP_NODELAY zu zwei Paketen
+    let mut frame = Vec::with_capacity(4 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(payload);
+    out.write_all(&frame)?;
+    out.flush()?;
+    Ok(())
+}
+
+pub fn read_frame(inp: &mut impl Read) -> Result<Vec<u8>> {
+    let mut len = [0; 4];
+    inp.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        bail!("Frame zu groß ({len} Bytes)");
+    }
+    let mut payload = vec![0; len];
+    inp.read_exact(&mut payload)?;
+    Ok(payload)
+}
@@ -0,0 +1,107 @@
+//! Andere Spieler auf dem Client: Momentaufnahmen vom Server werden leicht verzögert
+//! dargestellt, damit zwischen zwei Ticks immer interpoliert statt geraten wird.
+
+use std::collections::VecDeque;
+use std::f32::consts::{PI, TAU};
+use std::time::{Duration, Instant};
+
+use crate::mesh::{box_lines, Vertex};
+use crate::player_model::{Pose, Stride};
+use crate::protocol::{PackedPlayer, PlayerDelta, PlayerState};
+
+/// So weit liegt die Darstellung hinter dem Empfang (zwei Ticks plus Jitter-Reserve)
+pub const INTERP_DELAY: Duration = Duration::from_millis(100);
+/// Ältere Momentaufnahmen werden nicht mehr gebraucht
+const MAX_SNAPSHOTS: usize = 20;
+
+pub struct RemotePlayer {
+    pub name: String,
+    id: u32,
+    /// Letzter Stand vom Server, Basis für das nächste Delta
+    latest: PackedPlayer,
+    /// Schrittzähler fürs Modell, je Momentaufnahme festgehalten
+    stride: Stride,
+    snapshots: VecDeque<(Instant, PlayerState, Stride)>,
+}
+
+impl RemotePlayer {
+    pub fn new(id: u32, name: String, at: Instant, state: PackedPlayer) -> Self {
+        let mut player = Self {
+            name,
+            id,
+            latest: state,
+            stride: Stride::default(),
+            snapshots: VecDeque::new(),
+        };
+        player.snapshot(at);
+        player
+    }
+
+    /// Neuer Stand ohne Übergang (Teleport)
+    pub fn reset(&mut self, at: Instant, state: PackedPlayer) {
+        self.latest = state;
+        self.snapshots.clear();
+        self.snapshot(at);
+    }
+
+    pub fn apply(&mut self, delta: &PlayerDelta) {
+        self.latest.apply(delta);
+    }
+
+    /// Aktuellen Stand festhalten; einmal je Tick, auch wenn sich nichts bewegt hat
+    pub fn snapshot(&mut self, at: Instant) {
+        if self.snapshots.len() == MAX_SNAPSHOTS {
+            self.snapshots.pop_front();
+        }
+        let state = self.latest.unpack(self.id);
+        self.stride.step(at, state.pos);
+        self.snapshots.push_back((at, state, self.stride));
+    }
+
+    /// Zustand zum Zeitpunkt `at`; davor/danach der erste bzw. letzte bekannte
+    pub fn sample(&self, at: Instant) -> Option<PlayerState> {
+        self.interpolate(at).map(|(state, _)| state)
+    }
+
+    /// Haltung fürs Modell zum Zeitpunkt `at`
+    pub fn pose(&self, at: Instant) -> Option<Pose> {
+        self.interpolate(at).map(|(state, stride)| Pose::new(&state, stride))
+    }
+
+    fn interpolate(&self, at: Instant) -> Option<(PlayerState, Stride)> {
+        let after = self.snapshots.iter().position(|(t, _, _)| *t > at);
+        let i = match after {
+            Some(0) => return self.snapshots.front().map(|(_, s, w)| (*s, *w)),
+            Some(i) => i,
+            None => return self.snapshots.back().map(|(_, s, w)| (*s, *w)),
+        };
+        let ((t0, a, wa), (t1, b, wb)) = (self.snapshots[i - 1], self.snapshots[i]);
+        let span = (t1 - t0).as_secs_f32();
+        let k = if span > 0.0 { (at - t0).as_secs_f32() / span } else { 1.0 };
+        let lerp = |x: f32, y: f32| x + (y - x) * k;
+        let state = PlayerState {
+            id: b.id,
+            pos: (lerp(a.pos.0, b.pos.0), lerp(a.pos.1, b.pos.1), lerp(a.pos.2, b.pos.2)),
+            // Kürzester Weg, sonst dreht sich das Modell beim Übergang 0/TAU einmal herum
+            yaw: a.yaw + ((b.yaw - a.yaw + PI).rem_euclid(TAU) - PI) * k,
+            pitch: lerp(a.pitch, b.pitch),
+            // Blöcke springen ohnehin von einem zum nächsten
+            target: if k < 0.5 { a.target } else { b.target },
+        };
+        Some((state, wa.lerp(wb, k)))
+    }
+}
+
+/// Umrandung der Blöcke, die andere Spieler gerade anvisieren
+pub fn target_lines(states: &[PlayerState]) -> Vec<Vertex> {
+    let e = 0.004;
+    states
+        .iter()
+        .filter_map(|st| st.target)
+        .flat_map(|(x, y, z)| {
+            let lo = [x as f32 - e, y as f32 - e, z as f32 - e];
+            let hi = [x as f32 + 1.0 + e, y as f32 + 1.0 + e, z as f32 + 1.0 + e];
+            box_lines(lo, hi, [0.9, 0.9, 0.9])
+        })
+        .collect()
+}
@@ -34,10 +34,11 @@ impl DebugRenderer {
             for x in 0..size {
                 let b = game.highest_solid_in_column(x, z);
                 let (r, g, bl) = match b {
-                    None => (25, 25, 30),
-                    Some(Block::Dirt) => (110, 80, 45),
-                    Some(Block::Stone) => (130, 130, 135),
-                    Some(Block::Air) => (25, 25, 30),
+                    None | Some(Block::Air) => (25, 25, 30),
+                    Some(b) => {
+                        let c = b.color();
+                        ((c[0] * 220.0) as u8, (c[1] * 220.0) as u8, (c[2] * 220.0) as u8)
+                    }
                 };
 
                 let px0 = off_x + x * cell;
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::input::InputState;
+use crate::serial::{ByteReader, ByteWriter};
+
+const REPLAY_MAGIC: &[u8; 4] = b"VXRP";
+const REPLAY_FORMAT_VERSION: u16 = 1;
+
+/// Eingaben eines Ticks: reicht zusammen mit dem Seed, um die Simulation nachzurechnen
+#[derive(Clone, Debug, Default)]
+pub struct ReplayFrame {
+    pub input: InputState,
+    /// Blickrichtung zu Tickbeginn (Mausbewegung kommt zwischen den Ticks)
+    pub yaw: f32,
+    pub pitch: f32,
+    /// UI-Größe (Klicks in Screens hängen vom Layout ab)
+    pub screen: (f32, f32),
+    /// In diesem Tick abgeschickte Konsolenzeilen
+    pub lines: Vec<String>,
+}
+
+impl ReplayFrame {
+    /// Auch das Netzwerkprotokoll schickt Ticks in diesem Format
+    pub fn write(&self, w: &mut ByteWriter) {
+        self.input.write(w);
+        w.f32(self.yaw);
+        w.f32(self.pitch);
+        w.f32(self.screen.0);
+        w.f32(self.screen.1);
+        w.u8(self.lines.len() as u8);
+        for line in &self.lines {
+            w.str(line);
+        }
+    }
+
+    pub fn read(r: &mut ByteReader) -> Result<Self> {
+        let input = InputState::read(r)?;
+        let (yaw, pitch) = (r.f32()?, r.f32()?);
+        let screen = (r.f32()?, r.f32()?);
+        let lines = (0..r.u8()?).map(|_| r.str()).collect::<Result<_>>()?;
+        Ok(Self {
+            input,
+            yaw,
+            pitch,
+            screen,
+            lines,
+        })
+    }
+}
+
+/// Aufzeichnung ab einer frischen, deterministischen Welt
+#[derive(Clone, Debug, Default)]
+pub struct Replay {
+    pub seed: u64,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = ByteWriter::new();
+        w.bytes(REPLAY_MAGIC);
+        w.u16(REPLAY_FORMAT_VERSION);
+        w.u64(self.seed);
+        w.u32(self.frames.len() as u32);
+        for f in &self.frames {
+            f.write(&mut w);
+        }
+        w.into_bytes()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let mut r = ByteReader::new(data);
+        if r.bytes(4)? != REPLAY_MAGIC {
+            bail!("keine Replay-Datei");
+        }
+        let version = r.u16()?;
+        if version != REPLAY_FORMAT_VERSION {
+            bail!("Replay-Format v{version} nicht unterstützt");
+        }
+        let seed = r.u64()?;
+        let n = r.u32()?;
+        let frames = (0..n).map(|_| ReplayFrame::read(&mut r)).collect::<Result<_>>()?;
+        Ok(Self { seed, frames })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+        Self::decode(&data).with_context(|| format!("decode {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+        }
+        fs::write(path, self.encode()).with_context(|| format!("write {}", path.display()))
+    }
+}
+
+/// Abspielen: liefert Frame für Frame; optional mit freier Zuschauerkamera
+pub struct Playback {
+    replay: Replay,
+    next: usize,
+    pub spectator: Option<Spectator>,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next: 0,
+            spectator: None,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    /// None = Aufzeichnung zu Ende
+    pub fn next_frame(&mut self) -> Option<&ReplayFrame> {
+        let frame = self.replay.frames.get(self.next)?;
+        self.next += 1;
+        Some(frame)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next >= self.replay.frames.len()
+    }
+}
+
+/// Freie Kamera beim Abspielen (fliegt mit den normalen Bewegungstasten)
+#[derive(Clone, Copy, Debug)]
+pub struct Spectator {
+    pub pos: (f32, f32, f32),
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Spectator {
+    /// Blöcke pro Sekunde
+    const SPEED: f32 = 10.0;
+
+    pub fn add_look(&mut self, dyaw: f32, dpitch: f32) {
+        self.yaw += dyaw;
+        self.pitch = (self.pitch + dpitch).clamp(-1.55, 1.55);
+    }
+
+    /// Gleiche Winkelkonvention wie `Player::dir`
+    pub fn dir(&self) -> (f32, f32, f32) {
+        let (sy, cy) = self.yaw.sin_cos();
+        let (sp, cp) = self.pitch.sin_cos();
+        (sy * cp, sp, cy * cp)
+    }
+
+    pub fn fly(&mut self, input: &InputState, dt: f32) {
+        let (dx, _, dz) = self.dir();
+        let fwd = (input.move_fwd as i32 - input.move_back as i32) as f32;
+        let side = (input.move_right as i32 - input.move_left as i32) as f32;
+        let up = (input.jump_held as i32 - input.sneak as i32) as f32;
+        let step = Self::SPEED * dt;
+        // rechts = (dz, -dx) wie in Game::apply_movement
+        self.pos.0 += (dx * fwd + dz * side) * step;
+        self.pos.2 += (dz * fwd - dx * side) * step;
+        self.pos.1 += up * step;
+    }
+}
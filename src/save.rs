@@ -0,0 +1,211 @@
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::block::Block;
+use crate::chunk::{Chunk, ChunkPos, CHUNK_SIZE};
+use crate::level::{LevelInfo, LEVEL_FILE};
+use crate::player::Player;
+use crate::stats::{Stats, STATS_FILE};
+
+/// So viele ältere Stände von level.toml bleiben als level.toml.1 .. .N liegen
+pub const LEVEL_BACKUPS: usize = 3;
+
+/// Hängt `suffix` an den Dateinamen an (`level.toml` -> `level.toml.tmp`)
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Schreibt erst `<datei>.tmp`, synct und benennt dann um. Ein Absturz mitten im
+/// Speichern hinterlässt so die alte oder die neue Datei, nie eine halbe.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp = with_suffix(path, ".tmp");
+    let result = (|| {
+        let mut file = File::create(&tmp)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp);
+        return Err(e).with_context(|| format!("write {}", path.display()));
+    }
+    // Umbenennung selbst dauerhaft machen (Verzeichnis-fsync geht nur auf Unix)
+    #[cfg(unix)]
+    if let Some(dir) = path.parent()
+        && let Ok(dir) = File::open(if dir.as_os_str().is_empty() { Path::new(".") } else { dir })
+    {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Pfad `<dir>/<name>.<ext>` für einen Namen aus einem Befehl. Der Name darf kein Pfad
+/// sein, sonst landen Dateien außerhalb von `dir` (`/metrics ../../x`).
+pub fn named_file(dir: &str, name: &str, ext: &str) -> Result<PathBuf> {
+    if name.is_empty()
+        || name.contains(['/', '\\', ':'])
+        || name.contains("..")
+        || Path::new(name).is_absolute()
+    {
+        bail!("'{name}' is not a valid name (no paths or '..')");
+    }
+    Ok(Path::new(dir).join(format!("{name}.{ext}")))
+}
+
+/// Schiebt `path.1` .. `path.N-1` eine Stelle weiter und kopiert `path` nach `path.1`
+fn rotate_backups(path: &Path, keep: usize) -> Result<()> {
+    if keep == 0 || !path.exists() {
+        return Ok(());
+    }
+    for i in (1..keep).rev() {
+        let from = with_suffix(path, &format!(".{i}"));
+        if from.exists() {
+            let to = with_suffix(path, &format!(".{}", i + 1));
+            fs::rename(&from, &to).with_context(|| format!("rename {}", from.display()))?;
+        }
+    }
+    let newest = with_suffix(path, ".1");
+    let data = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    write_atomic(&newest, &data)
+}
+
+/// Spielstand auf der Platte: ein Verzeichnis pro Welt, eine Datei pro Chunk + Spielerdatei
+pub struct WorldStorage {
+    dir: PathBuf,
+}
+
+impl WorldStorage {
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Gibt es schon gespeicherte Chunks? (sonst: neue Welt)
+    pub fn has_data(&self) -> bool {
+        fs::read_dir(self.chunk_dir())
+            .map(|mut it| it.next().is_some())
+            .unwrap_or(false)
+    }
+
+    /// Andere Chunkgrößen in eigenem Ordner, damit sie sich nicht mit 16³ vermischen
+    fn chunk_dir(&self) -> PathBuf {
+        match CHUNK_SIZE {
+            16 => self.dir.join("chunks"),
+            s => self.dir.join(format!("chunks{s}")),
+        }
+    }
+
+    fn chunk_path(&self, cp: ChunkPos) -> PathBuf {
+        self.chunk_dir()
+            .join(format!("c.{}.{}.{}.bin", cp.cx, cp.cy, cp.cz))
+    }
+
+    /// Positionen aller gespeicherten Chunks (aus den Dateinamen)
+    pub fn saved_chunks(&self) -> Vec<ChunkPos> {
+        let Ok(entries) = fs::read_dir(self.chunk_dir()) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|e| {
+                let name = e.ok()?.file_name().into_string().ok()?;
+                let coords = name.strip_prefix("c.")?.strip_suffix(".bin")?;
+                let mut it = coords.split('.').map(|v| v.parse::<i32>().ok());
+                match (it.next()??, it.next()??, it.next()??, it.next()) {
+                    (cx, cy, cz, None) => Some(ChunkPos::new(cx, cy, cz)),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Lädt einen Chunk, falls gespeichert. Kaputte Dateien werden geloggt und ignoriert.
+    pub fn load_chunk(&self, cp: ChunkPos) -> Option<Chunk<Block>> {
+        let path = self.chunk_path(cp);
+        let data = fs::read(&path).ok()?;
+        match Chunk::decode(&data) {
+            Ok(ch) => Some(ch),
+            Err(e) => {
+                log::warn!("Chunk {:?} nicht lesbar ({}): {e:#}", cp, path.display());
+                None
+            }
+        }
+    }
+
+    pub fn save_chunk(&self, ch: &Chunk<Block>) -> Result<()> {
+        fs::create_dir_all(self.chunk_dir())
+            .with_context(|| format!("create {}", self.chunk_dir().display()))?;
+        let path = self.chunk_path(ch.pos);
+        write_atomic(&path, &ch.encode())
+    }
+
+    fn level_path(&self) -> PathBuf {
+        self.dir.join(LEVEL_FILE)
+    }
+
+    /// Weltinfo, falls gespeichert; unbekannte Felder behalten `defaults`.
+    /// Fehlt level.toml, wird die neueste Sicherung genommen.
+    pub fn load_level(&self, defaults: LevelInfo) -> Option<LevelInfo> {
+        let path = self.level_path();
+        let candidates = std::iter::once(path.clone())
+            .chain((1..=LEVEL_BACKUPS).map(|i| with_suffix(&path, &format!(".{i}"))));
+        for candidate in candidates {
+            if let Ok(text) = fs::read_to_string(&candidate) {
+                if candidate != path {
+                    log::warn!("{} fehlt, nutze Sicherung {}", path.display(), candidate.display());
+                }
+                return Some(LevelInfo::parse(&text, defaults));
+            }
+        }
+        None
+    }
+
+    pub fn save_level(&self, level: &LevelInfo) -> Result<()> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("create {}", self.dir.display()))?;
+        let path = self.level_path();
+        rotate_backups(&path, LEVEL_BACKUPS)?;
+        write_atomic(&path, level.to_text().as_bytes())
+    }
+
+    /// Statistik, falls gespeichert
+    pub fn load_stats(&self) -> Option<Stats> {
+        let text = fs::read_to_string(self.dir.join(STATS_FILE)).ok()?;
+        Some(Stats::parse(&text))
+    }
+
+    pub fn save_stats(&self, stats: &Stats) -> Result<()> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("create {}", self.dir.display()))?;
+        write_atomic(&self.dir.join(STATS_FILE), stats.to_text().as_bytes())
+    }
+
+    fn player_path(&self) -> PathBuf {
+        self.dir.join("player.bin")
+    }
+
+    /// Lädt den Spieler, falls gespeichert. Kaputte Dateien werden geloggt und ignoriert.
+    pub fn load_player(&self) -> Option<Player> {
+        let path = self.player_path();
+        let data = fs::read(&path).ok()?;
+        match Player::decode(&data) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                log::warn!("Spieler nicht lesbar ({}): {e:#}", path.display());
+                None
+            }
+        }
+    }
+
+    pub fn save_player(&self, player: &Player) -> Result<()> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("create {}", self.dir.display()))?;
+        let path = self.player_path();
+        write_atomic(&path, &player.encode())
+    }
+}
@@ -0,0 +1,427 @@
+//! Property-Tests: zufällige Spielstände und Netzwerknachrichten müssen Schreiben und Lesen
+//! unverändert überstehen; kaputte Nachrichten werden abgelehnt
+
+use proptest::collection::{hash_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::select;
+use winit::keyboard::KeyCode;
+
+use crate::block::{Block, SoundGroup};
+use crate::block_buffer::BlockBuffer;
+use crate::block_entity::BlockEntity;
+use crate::chunk::{Chunk, ChunkPos, CHUNK_SIZE, CHUNK_VOL};
+use crate::chunk_bench::{self, Encoding};
+use crate::gamemode::GameMode;
+use crate::input::InputState;
+use crate::inventory::{Inventory, ItemStack};
+use crate::item::Item;
+use crate::keybinds::Action;
+use crate::level::{GameRules, LevelInfo};
+use crate::mesh::Vertex;
+use crate::movement::Body;
+use crate::player::{Player, HOTBAR_SLOTS, MAX_HEALTH, MAX_HUNGER};
+use crate::protocol::{
+    read_frame, write_frame, ClientMsg, Hello, PackedPlayer, PlayerDelta, PlayerView, ServerMsg,
+    WorldParams, MAX_FRAME_LEN,
+};
+use crate::replay::ReplayFrame;
+use crate::save::named_file;
+use crate::serial::{ByteReader, ByteWriter};
+use crate::settings::Settings;
+use crate::sound::{SoundEvent, SoundKind};
+use crate::stats::Stats;
+use crate::weather::Weather;
+
+fn arb_block() -> impl Strategy<Value = Block> {
+    select(Block::ALL.to_vec())
+}
+
+fn arb_item() -> impl Strategy<Value = Item> {
+    prop_oneof![arb_block().prop_map(Item::Block), select(Item::EXTRA.to_vec())]
+}
+
+fn arb_inventory() -> impl Strategy<Value = Inventory> {
+    let stack = (arb_item(), 1..=u16::MAX).prop_map(|(item, n)| ItemStack::new(item, n));
+    vec(option::of(stack), 0..40).prop_map(|slots| {
+        let mut inv = Inventory::new(slots.len());
+        for (i, s) in slots.into_iter().enumerate() {
+            inv.set(i, s);
+        }
+        inv
+    })
+}
+
+fn arb_block_entity() -> impl Strategy<Value = BlockEntity> {
+    prop_oneof![
+        arb_inventory().prop_map(BlockEntity::Container),
+        ".{0,40}".prop_map(|text| BlockEntity::Sign { text }),
+    ]
+}
+
+/// Lokale Koordinaten aus einem Chunk-Index (Umkehrung von `chunk::idx`)
+fn local(i: usize) -> (i32, i32, i32) {
+    let s = CHUNK_SIZE as usize;
+    ((i % s) as i32, (i / (s * s)) as i32, ((i / s) % s) as i32)
+}
+
+prop_compose! {
+    fn arb_chunk()(
+        pos in any::<(i32, i32, i32)>(),
+        cells in vec((0..CHUNK_VOL, arb_block(), any::<u8>()), 0..300),
+        entities in hash_map(0..CHUNK_VOL, arb_block_entity(), 0..6),
+    ) -> Chunk<Block> {
+        let mut ch = Chunk::new(ChunkPos::new(pos.0, pos.1, pos.2));
+        for (i, b, state) in cells {
+            let (x, y, z) = local(i);
+            ch.set_local_with_state(x, y, z, b, state);
+        }
+        for (i, be) in entities {
+            let (x, y, z) = local(i);
+            ch.insert_block_entity(x, y, z, be);
+        }
+        ch
+    }
+}
+
+prop_compose! {
+    fn arb_player()(
+        pos in [-1.0e6f32..1.0e6, -512.0f32..512.0, -1.0e6f32..1.0e6],
+        yaw in -10.0f32..10.0,
+        pitch in -1.5f32..1.5,
+        slot in 0..HOTBAR_SLOTS,
+        inventory in arb_inventory(),
+        creative in any::<bool>(),
+        flying in any::<bool>(),
+        health in 1..=MAX_HEALTH,
+        hunger in 0..=MAX_HUNGER,
+    ) -> Player {
+        let mut p = Player::new();
+        [p.x, p.y, p.z] = pos;
+        (p.yaw, p.pitch) = (yaw, pitch);
+        p.select_slot(slot);
+        p.inventory = inventory;
+        p.set_game_mode(if creative { GameMode::Creative } else { GameMode::Survival });
+        p.flying = flying && creative;
+        (p.health, p.hunger) = (health, hunger);
+        p
+    }
+}
+
+prop_compose! {
+    fn arb_frame()(
+        held in any::<[bool; 4]>(),
+        hotbar_select in option::of(0..HOTBAR_SLOTS),
+        hotbar_scroll in any::<i32>(),
+        cursor in any::<(f32, f32)>(),
+        look in any::<(f32, f32)>(),
+        screen in any::<(f32, f32)>(),
+        lines in vec("\\PC{0,40}", 0..3),
+    ) -> ReplayFrame {
+        let [move_fwd, jump, break_held, sneak] = held;
+        let input = InputState {
+            move_fwd,
+            jump,
+            break_held,
+            sneak,
+            hotbar_select,
+            hotbar_scroll,
+            cursor,
+            ..InputState::default()
+        };
+        ReplayFrame { input, yaw: look.0, pitch: look.1, screen, lines }
+    }
+}
+
+fn arb_client_msg() -> impl Strategy<Value = ClientMsg> {
+    prop_oneof![
+        (any::<u16>(), "\\PC{0,20}").prop_map(|(version, name)| {
+            ClientMsg::Hello(Hello { version, name })
+        }),
+        (any::<u32>(), arb_frame()).prop_map(|(seq, frame)| ClientMsg::Tick(seq, frame)),
+        any::<i32>().prop_map(ClientMsg::ViewDistance),
+        "\\PC{0,80}".prop_map(ClientMsg::Chat),
+    ]
+}
+
+fn arb_target() -> impl Strategy<Value = Option<(i32, i32, i32)>> {
+    option::of(any::<(i32, i32, i32)>())
+}
+
+fn arb_packed() -> impl Strategy<Value = PackedPlayer> {
+    (any::<[i32; 3]>(), any::<u16>(), any::<i16>(), arb_target())
+        .prop_map(|(pos, yaw, pitch, target)| PackedPlayer { pos, yaw, pitch, target })
+}
+
+fn arb_delta() -> impl Strategy<Value = PlayerDelta> {
+    let look = option::of(any::<(u16, i16)>());
+    (any::<u32>(), option::of(any::<[i16; 3]>()), look, option::of(arb_target()))
+        .prop_map(|(id, pos, look, target)| PlayerDelta { id, pos, look, target })
+}
+
+fn arb_server_msg() -> impl Strategy<Value = ServerMsg> {
+    let vertex = any::<[f32; 9]>().prop_map(|f| Vertex {
+        pos: [f[0], f[1], f[2]],
+        color: [f[3], f[4], f[5]],
+        light: [f[6], f[7]],
+        emissive: f[8],
+    });
+    let sound = (select(vec![SoundKind::Break, SoundKind::Land]), any::<(f32, f32, f32)>())
+        .prop_map(|(kind, pos)| SoundEvent { kind, group: SoundGroup::Wood, pos, pitch: 1.0 });
+    prop_oneof![
+        ("\\PC{0,20}", any::<[u64; 2]>(), any::<(f32, f32, f32)>()).prop_map(
+            |(name, [seed, blocks], spawn)| {
+                let generator = "noise".into();
+                ServerMsg::Welcome(WorldParams { name, seed, generator, spawn, blocks })
+            }
+        ),
+        "\\PC{0,40}".prop_map(ServerMsg::Disconnect),
+        arb_chunk().prop_map(|ch| ServerMsg::Chunk(Box::new(ch))),
+        any::<(i32, i32, i32)>()
+            .prop_map(|(x, y, z)| ServerMsg::UnloadChunk(ChunkPos::new(x, y, z))),
+        (any::<(i32, i32, i32)>(), arb_block(), any::<u8>())
+            .prop_map(|(pos, b, state)| ServerMsg::BlockChange(pos, b, state)),
+        (any::<(i32, i32, i32)>(), vec(any::<u8>(), CHUNK_VOL)).prop_map(|((x, y, z), light)| {
+            ServerMsg::ChunkLight(ChunkPos::new(x, y, z), light.into())
+        }),
+        (any::<[f32; 6]>(), any::<[bool; 3]>(), any::<u32>()).prop_map(|(f, flags, ack)| {
+            let [x, y, z, vy, yaw, pitch] = f;
+            let [on_ground, flying, can_sprint] = flags;
+            let body = Body { x, y, z, vy, on_ground, flying };
+            ServerMsg::Player(PlayerView { body, yaw, pitch, ack, can_sprint })
+        }),
+        sound.prop_map(ServerMsg::Sound),
+        vec(vertex, 0..8).prop_map(ServerMsg::Overlay),
+        "\\PC{0,80}".prop_map(ServerMsg::Chat),
+        (any::<u32>(), "\\PC{0,16}").prop_map(|(id, name)| ServerMsg::PlayerJoined(id, name)),
+        (any::<u32>(), arb_packed()).prop_map(|(id, p)| ServerMsg::PlayerSpawn(id, p)),
+        any::<u32>().prop_map(ServerMsg::PlayerDespawn),
+        vec(arb_delta(), 0..5).prop_map(ServerMsg::PlayerMoves),
+        (any::<f32>(), any::<bool>()).prop_map(|(rain, snow)| ServerMsg::Weather { rain, snow }),
+        (any::<f32>(), any::<u64>())
+            .prop_map(|(time_of_day, day)| ServerMsg::Clock { time_of_day, day }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn chunk_roundtrip(ch in arb_chunk()) {
+        let bytes = ch.encode();
+        let back = Chunk::decode(&bytes).unwrap();
+        prop_assert_eq!(back.pos, ch.pos);
+        prop_assert_eq!(back.blocks(), ch.blocks());
+        for i in 0..CHUNK_VOL {
+            let (x, y, z) = local(i);
+            prop_assert_eq!(back.get_state_local(x, y, z), ch.get_state_local(x, y, z));
+            prop_assert_eq!(back.block_entity_local(x, y, z), ch.block_entity_local(x, y, z));
+        }
+        prop_assert_eq!(back.encode(), bytes);
+    }
+
+    #[test]
+    fn bench_encodings_roundtrip(ch in arb_chunk()) {
+        let cells = chunk_bench::cells(&ch);
+        for &encoding in Encoding::ALL {
+            prop_assert_eq!(encoding.decode(&encoding.encode(&cells)).unwrap(), cells.clone());
+        }
+    }
+
+    #[test]
+    fn inventory_roundtrip(inv in arb_inventory()) {
+        let mut w = ByteWriter::new();
+        inv.write(&mut w);
+        let bytes = w.into_bytes();
+        let mut r = ByteReader::new(&bytes);
+        prop_assert_eq!(Inventory::read(&mut r).unwrap(), inv);
+    }
+
+    #[test]
+    fn block_entity_roundtrip(be in arb_block_entity()) {
+        let mut w = ByteWriter::new();
+        be.write(&mut w);
+        let bytes = w.into_bytes();
+        let mut r = ByteReader::new(&bytes);
+        prop_assert_eq!(BlockEntity::read(&mut r).unwrap(), be);
+    }
+
+    #[test]
+    fn player_roundtrip(p in arb_player()) {
+        let back = Player::decode(&p.encode()).unwrap();
+        prop_assert_eq!((back.x, back.y, back.z), (p.x, p.y, p.z));
+        prop_assert_eq!((back.yaw, back.pitch), (p.yaw, p.pitch));
+        prop_assert_eq!(back.selected_slot, p.selected_slot);
+        prop_assert_eq!(&back.inventory, &p.inventory);
+        prop_assert_eq!(back.game_mode, p.game_mode);
+        prop_assert_eq!(back.flying, p.flying);
+        prop_assert_eq!((back.health, back.hunger), (p.health, p.hunger));
+    }
+
+    #[test]
+    fn schematic_roundtrip(
+        size in (1..6, 1..6, 1..6),
+        origin in any::<(i32, i32, i32)>(),
+        cells in vec((arb_block(), any::<u8>()), 216),
+    ) {
+        let mut buf = BlockBuffer::new(size, origin);
+        let coords = (0..size.0)
+            .flat_map(|x| (0..size.1).flat_map(move |y| (0..size.2).map(move |z| (x, y, z))));
+        for (p, entry) in coords.zip(cells) {
+            buf.set(p, entry);
+        }
+        prop_assert_eq!(BlockBuffer::decode(&buf.encode()).unwrap(), buf);
+    }
+
+    #[test]
+    fn level_roundtrip(
+        name in "[ -~]{0,30}",
+        seed in any::<u64>(),
+        spawn in [-4000i32..4000, -400..400, -4000..4000],
+        play_ticks in any::<u64>(),
+        day_ticks in 0u64..24_000,
+        day in any::<u64>(),
+        weather in select(Weather::ALL.to_vec()),
+        weather_ticks in any::<u64>(),
+        daylight_cycle in any::<bool>(),
+        random_tick_speed in any::<u32>(),
+        waypoints in vec(("[a-zA-Z0-9_-]{1,12}", [-4000i32..4000, -400..400, -4000..4000]), 0..5),
+    ) {
+        // to_text schreibt zwei Nachkommastellen: Viertel sind exakt
+        let [x, y, z] = spawn.map(|v| v as f32 / 4.0);
+        let mut level = LevelInfo {
+            name,
+            seed,
+            spawn: (x, y, z),
+            play_ticks,
+            day_ticks,
+            day,
+            weather,
+            weather_ticks,
+            rules: GameRules { daylight_cycle, random_tick_speed },
+            ..LevelInfo::new("")
+        };
+        for (name, pos) in waypoints {
+            let [x, y, z] = pos.map(|v| v as f32 / 4.0);
+            level.set_waypoint(&name, (x, y, z));
+        }
+        prop_assert_eq!(LevelInfo::parse(&level.to_text(), LevelInfo::new("other")), level);
+    }
+
+    #[test]
+    fn stats_roundtrip(
+        broken in vec(any::<u64>(), Block::COUNT),
+        placed in vec(any::<u64>(), Block::COUNT),
+        walked in 0u32..4_000_000,
+        jumps in any::<u64>(),
+        deaths in any::<u64>(),
+    ) {
+        // to_text schreibt eine Nachkommastelle: Halbe sind exakt
+        let stats = Stats { broken, placed, distance_walked: walked as f64 / 2.0, jumps, deaths };
+        prop_assert_eq!(Stats::parse(&stats.to_text()), stats);
+    }
+
+    // Nachrichten vergleichen sich über ihre Bytes (Chunks und NaN haben kein sinnvolles ==)
+    #[test]
+    fn client_msg_roundtrip(msg in arb_client_msg()) {
+        let bytes = msg.encode();
+        prop_assert_eq!(ClientMsg::decode(&bytes).unwrap().encode(), bytes);
+    }
+
+    #[test]
+    fn server_msg_roundtrip(msg in arb_server_msg()) {
+        let bytes = msg.encode();
+        let mut framed = Vec::new();
+        write_frame(&mut framed, &bytes).unwrap();
+        let payload = read_frame(&mut framed.as_slice()).unwrap();
+        prop_assert_eq!(ServerMsg::decode(&payload).unwrap().encode(), bytes);
+    }
+
+    #[test]
+    fn cut_or_padded_messages_are_rejected(
+        client in arb_client_msg(),
+        server in arb_server_msg(),
+        cut in any::<prop::sample::Index>(),
+        extra in any::<u8>(),
+    ) {
+        let (mut c, mut s) = (client.encode(), server.encode());
+        prop_assert!(ClientMsg::decode(&c[..cut.index(c.len())]).is_err());
+        prop_assert!(ServerMsg::decode(&s[..cut.index(s.len())]).is_err());
+        c.push(extra);
+        s.push(extra);
+        prop_assert!(ClientMsg::decode(&c).is_err());
+        prop_assert!(ServerMsg::decode(&s).is_err());
+    }
+}
+
+#[test]
+fn long_string_is_cut_at_char_boundary() {
+    // 'ä' hat zwei Bytes, u16::MAX ist ungerade: das letzte Zeichen passt nur halb
+    let long = "ä".repeat(40_000);
+    let mut w = ByteWriter::new();
+    w.str(&long);
+    w.u8(7);
+    let bytes = w.into_bytes();
+    let mut r = ByteReader::new(&bytes);
+    assert_eq!(r.str().unwrap(), "ä".repeat(32_767));
+    assert_eq!(r.u8().unwrap(), 7);
+}
+
+#[test]
+fn settings_are_valid_toml() {
+    // Viertel überstehen die zwei Nachkommastellen von to_text exakt
+    let mut settings = Settings::default();
+    settings.mouse.sensitivity = 1.75;
+    settings.mouse.invert_y = true;
+    settings.graphics.fov = 90.5;
+    settings.graphics.view_distance = 9;
+    settings.audio.shuffle = false;
+    settings.gameplay.click_repeat_ticks = 0;
+    settings.bindings.set(Action::Jump, KeyCode::KeyQ);
+    let text = settings.to_text();
+
+    let table: toml::Table = text.parse().unwrap();
+    assert_eq!(table["mouse"]["sensitivity"].as_float(), Some(1.75));
+    assert_eq!(table["graphics"]["view_distance"].as_integer(), Some(9));
+    assert_eq!(table["audio"]["shuffle"].as_bool(), Some(false));
+    assert_eq!(table["key"]["jump"].as_str(), Some("KeyQ"));
+    assert_eq!(Settings::parse(&text), settings);
+}
+
+#[test]
+fn old_settings_lines_still_load() {
+    let settings = Settings::parse("mouse.invert_y = true\nkey.jump = KeyQ\n");
+    assert!(settings.mouse.invert_y);
+    assert_eq!(settings.bindings.key(Action::Jump), KeyCode::KeyQ);
+}
+
+#[test]
+fn unknown_messages_and_bad_frames_are_rejected() {
+    for tag in 4..=u8::MAX {
+        assert!(ClientMsg::decode(&[tag, 0, 0, 0, 0]).is_err(), "Client {tag}");
+    }
+    for tag in 18..=u8::MAX {
+        assert!(ServerMsg::decode(&[tag, 0, 0, 0, 0]).is_err(), "Server {tag}");
+    }
+    assert!(ClientMsg::decode(&[]).is_err());
+    let mut hello = ClientMsg::Hello(Hello { version: 1, name: "A".into() }).encode();
+    hello[1] ^= 0xFF;
+    assert!(ClientMsg::decode(&hello).is_err());
+
+    // Länge über dem Limit wird abgelehnt, bevor Speicher dafür angelegt wird
+    let huge = (MAX_FRAME_LEN as u32 + 1).to_le_bytes();
+    assert!(read_frame(&mut huge.as_slice()).is_err());
+    assert!(read_frame(&mut u32::MAX.to_le_bytes().as_slice()).is_err());
+    assert!(write_frame(&mut Vec::new(), &vec![0; MAX_FRAME_LEN + 1]).is_err());
+    // Frame kürzer als angekündigt
+    let mut short = 10u32.to_le_bytes().to_vec();
+    short.extend([1, 2, 3]);
+    assert!(read_frame(&mut short.as_slice()).is_err());
+}
+
+#[test]
+fn file_names_cannot_escape_the_directory() {
+    let path = named_file("saves/metrics", "run-1", "csv").unwrap();
+    assert_eq!(path, std::path::Path::new("saves/metrics/run-1.csv"));
+    for name in ["", "../x", "..", "a/b", "a\\b", "/etc/passwd", "C:x", "a..b"] {
+        assert!(named_file("saves/metrics", name, "csv").is_err(), "{name:?}");
+    }
+}
@@ -0,0 +1,645 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use crate::block::{Block, Interaction};
+use crate::console::{Console, RECENT_TICKS};
+use crate::crafting::CraftGrid;
+use crate::inventory::{Inventory, ItemStack};
+use crate::item::{Icon, Item};
+use crate::keybinds::{key_label, Action, KeyBindings};
+use crate::level::Waypoint;
+use crate::player::HOTBAR_SLOTS;
+use crate::settings::{AudioSettings, MouseSettings, SENSITIVITY_MAX, SENSITIVITY_MIN};
+use crate::ui::{Rect, UiBatch};
+
+pub const SLOT_SIZE: f32 = 40.0;
+pub const SLOT_GAP: f32 = 4.0;
+const COLUMNS: usize = 9;
+
+const PANEL_BG: [f32; 4] = [0.12, 0.12, 0.14, 0.92];
+const SLOT_BG: [f32; 4] = [0.25, 0.25, 0.28, 1.0];
+const SLOT_HOVER: [f32; 4] = [0.40, 0.40, 0.45, 1.0];
+const TEXT: [f32; 4] = [0.95, 0.95, 0.95, 1.0];
+const SELECTED: [f32; 4] = [1.0, 1.0, 1.0, 0.95];
+
+/// Welcher Slot in einem Container-Screen gemeint ist
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotRef {
+    Container(usize),
+    Player(usize),
+}
+
+/// Slot-Rechtecke eines Container-Screens: Container oben, Spielerinventar unten
+/// (Hotbar = Spielerslots 0..9 ganz unten, abgesetzt)
+pub struct ContainerLayout {
+    pub panel: Rect,
+    pub container: Vec<Rect>,
+    pub player: Vec<Rect>,
+}
+
+impl ContainerLayout {
+    pub fn new(screen_w: f32, screen_h: f32, container_slots: usize, player_slots: usize) -> Self {
+        let cell = SLOT_SIZE + SLOT_GAP;
+        let container_rows = container_slots.div_ceil(COLUMNS);
+        let player_rows = player_slots.div_ceil(COLUMNS);
+
+        let title_h = 24.0;
+        let section_gap = 16.0;
+        let grid_w = COLUMNS as f32 * cell - SLOT_GAP;
+        let grid_h = (container_rows + player_rows) as f32 * cell + 2.0 * section_gap;
+        let pad = 12.0;
+
+        let panel = Rect::new(
+            (screen_w - grid_w) * 0.5 - pad,
+            (screen_h - grid_h - title_h) * 0.5 - pad,
+            grid_w + 2.0 * pad,
+            grid_h + title_h + 2.0 * pad,
+        );
+
+        let x0 = panel.x + pad;
+        let mut y0 = panel.y + pad + title_h;
+
+        let grid = |count: usize, y: f32| -> Vec<Rect> {
+            (0..count)
+                .map(|i| {
+                    let (col, row) = (i % COLUMNS, i / COLUMNS);
+                    Rect::new(x0 + col as f32 * cell, y + row as f32 * cell, SLOT_SIZE, SLOT_SIZE)
+                })
+                .collect()
+        };
+
+        let container = grid(container_slots, y0);
+        y0 += container_rows as f32 * cell + section_gap;
+
+        // Spielerinventar: Hauptteil (ab Slot 9) zuerst, Hotbar als letzte Reihe
+        let main_rows = player_rows.saturating_sub(1);
+        let mut player = vec![Rect::new(0.0, 0.0, 0.0, 0.0); player_slots];
+        let main = grid(player_slots.saturating_sub(COLUMNS), y0);
+        for (i, r) in main.into_iter().enumerate() {
+            player[i + COLUMNS] = r;
+        }
+        let hotbar = grid(COLUMNS.min(player_slots), y0 + main_rows as f32 * cell + section_gap);
+        for (i, r) in hotbar.into_iter().enumerate() {
+            player[i] = r;
+        }
+
+        Self {
+            panel,
+            container,
+            player,
+        }
+    }
+
+    /// Crafting-Screen: Raster oben links, Ergebnis rechts daneben als letzter Container-Slot
+    pub fn crafting(screen_w: f32, screen_h: f32, grid: usize, player_slots: usize) -> Self {
+        let mut layout = Self::new(screen_w, screen_h, grid * COLUMNS, player_slots);
+        let cells = std::mem::take(&mut layout.container);
+        let at = |col: usize, row: usize| cells[row * COLUMNS + col];
+        layout.container = (0..grid * grid).map(|i| at(1 + i % grid, i / grid)).collect();
+        layout.container.push(at(grid + 3, (grid - 1) / 2));
+        layout
+    }
+
+    pub fn slot_at(&self, px: f32, py: f32) -> Option<SlotRef> {
+        if let Some(i) = self.container.iter().position(|r| r.contains(px, py)) {
+            return Some(SlotRef::Container(i));
+        }
+        self.player
+            .iter()
+            .position(|r| r.contains(px, py))
+            .map(SlotRef::Player)
+    }
+}
+
+/// Item-Icon plus Anzahl unten rechts
+pub fn draw_stack(batch: &mut UiBatch, r: Rect, stack: ItemStack) {
+    draw_icon(batch, r, stack.item.icon());
+    if stack.count > 1 {
+        let s = stack.count.to_string();
+        let scale = 2.0;
+        let tw = UiBatch::text_width(&s, scale);
+        let th = UiBatch::text_height(scale);
+        batch.text(r.x + r.w - tw - 1.0, r.y + r.h - th - 2.0, scale, &s, TEXT);
+    }
+}
+
+pub fn draw_container_screen(
+    batch: &mut UiBatch,
+    title: &str,
+    container: &Inventory,
+    player: &Inventory,
+    cursor_stack: Option<ItemStack>,
+    cursor: (f32, f32),
+) {
+    let layout = ContainerLayout::new(batch.width(), batch.height(), container.len(), player.len());
+    draw_slot_screen(
+        batch,
+        title,
+        &layout,
+        container.iter().copied(),
+        player,
+        cursor_stack,
+        cursor,
+    );
+}
+
+/// Crafting-Raster plus Ergebnis-Vorschau (2×2 Inventar, 3×3 Werkbank)
+pub fn draw_crafting_screen(
+    batch: &mut UiBatch,
+    grid: &CraftGrid,
+    player: &Inventory,
+    cursor_stack: Option<ItemStack>,
+    cursor: (f32, f32),
+) {
+    let (w, h) = (batch.width(), batch.height());
+    let layout = ContainerLayout::crafting(w, h, grid.size(), player.len());
+    let title = if grid.size() > 2 { "Crafting" } else { "Inventory" };
+    let slots = grid.slots.iter().copied().chain([grid.result()]);
+    draw_slot_screen(batch, title, &layout, slots, player, cursor_stack, cursor);
+
+    // Pfeil zwischen Raster und Ergebnis
+    if let Some(out) = layout.container.last() {
+        let scale = 2.0;
+        let th = UiBatch::text_height(scale);
+        let x = out.x - SLOT_SIZE - SLOT_GAP;
+        batch.text(x + 8.0, out.y + (out.h - th) * 0.5, scale, "->", TEXT);
+    }
+}
+
+fn draw_slot_screen(
+    batch: &mut UiBatch,
+    title: &str,
+    layout: &ContainerLayout,
+    container: impl Iterator<Item = Option<ItemStack>>,
+    player: &Inventory,
+    cursor_stack: Option<ItemStack>,
+    cursor: (f32, f32),
+) {
+    let hover = layout.slot_at(cursor.0, cursor.1);
+
+    batch.rect(layout.panel, PANEL_BG);
+    batch.text(layout.panel.x + 12.0, layout.panel.y + 10.0, 2.0, title, TEXT);
+
+    let slots = layout
+        .container
+        .iter()
+        .zip(container)
+        .enumerate()
+        .map(|(i, (r, st))| (SlotRef::Container(i), *r, st))
+        .chain(
+            layout
+                .player
+                .iter()
+                .zip(player.iter())
+                .enumerate()
+                .map(|(i, (r, st))| (SlotRef::Player(i), *r, *st)),
+        );
+
+    for (slot, r, st) in slots {
+        let bg = if hover == Some(slot) { SLOT_HOVER } else { SLOT_BG };
+        batch.rect(r, bg);
+        if let Some(st) = st {
+            draw_stack(batch, r, st);
+        }
+    }
+
+    // Gehaltener Stack folgt dem Mauszeiger
+    if let Some(st) = cursor_stack {
+        let r = Rect::new(
+            cursor.0 - SLOT_SIZE * 0.5,
+            cursor.1 - SLOT_SIZE * 0.5,
+            SLOT_SIZE,
+            SLOT_SIZE,
+        );
+        draw_stack(batch, r, st);
+    }
+}
+
+pub fn draw_icon(batch: &mut UiBatch, r: Rect, icon: Icon) {
+    let rgba = |c: [f32; 3]| [c[0], c[1], c[2], 1.0];
+    match icon {
+        Icon::Block(c) => batch.rect(r.inset(7.0), rgba(c)),
+        Icon::Small(c) => batch.rect(r.inset(13.0), rgba(c)),
+        Icon::Bar(c) => {
+            let w = r.w * 0.2;
+            batch.rect(Rect::new(r.x + (r.w - w) * 0.5, r.y + 6.0, w, r.h - 12.0), rgba(c));
+        }
+        Icon::Tool { head, handle } => {
+            let w = r.w * 0.15;
+            batch.rect(Rect::new(r.x + (r.w - w) * 0.5, r.y + 12.0, w, r.h - 18.0), rgba(handle));
+            batch.rect(Rect::new(r.x + 8.0, r.y + 6.0, r.w - 16.0, 7.0), rgba(head));
+        }
+    }
+}
+
+/// Layout des Block-Pickers: Registry-Raster oben, Hotbar unten
+pub fn picker_layout(screen_w: f32, screen_h: f32) -> ContainerLayout {
+    ContainerLayout::new(screen_w, screen_h, Item::all().count(), HOTBAR_SLOTS)
+}
+
+/// Creative-Picker über alle Items; Klick füllt den aktiven Hotbar-Slot
+pub fn draw_block_picker(
+    batch: &mut UiBatch,
+    hotbar: &Inventory,
+    selected: usize,
+    cursor: (f32, f32),
+) {
+    let layout = picker_layout(batch.width(), batch.height());
+    let hover = layout.slot_at(cursor.0, cursor.1);
+
+    batch.rect(layout.panel, PANEL_BG);
+    batch.text(layout.panel.x + 12.0, layout.panel.y + 10.0, 2.0, "Items", TEXT);
+
+    for (i, (r, item)) in layout.container.iter().zip(Item::all()).enumerate() {
+        let bg = if hover == Some(SlotRef::Container(i)) { SLOT_HOVER } else { SLOT_BG };
+        batch.rect(*r, bg);
+        draw_icon(batch, *r, item.icon());
+    }
+    for (i, r) in layout.player.iter().enumerate() {
+        let bg = if hover == Some(SlotRef::Player(i)) { SLOT_HOVER } else { SLOT_BG };
+        batch.rect(*r, bg);
+        if let Some(st) = hotbar.get(i) {
+            draw_stack(batch, *r, st);
+        }
+        if i == selected {
+            batch.outline(r.inset(-2.0), 3.0, SELECTED);
+        }
+    }
+
+    // Name des Items unter der Maus
+    if let Some(SlotRef::Container(i)) = hover
+        && let Some(item) = Item::all().nth(i)
+    {
+        let scale = 2.0;
+        let tw = UiBatch::text_width(item.name(), scale);
+        let p = layout.panel;
+        batch.text(p.x + p.w - tw - 12.0, p.y + 10.0, scale, item.name(), TEXT);
+    }
+}
+
+/// Voller Stack eines Picker-Eintrags
+pub fn picker_stack(index: usize) -> Option<ItemStack> {
+    Item::all().nth(index).map(ItemStack::full)
+}
+
+/// HUD-Hotbar unten mittig; aktiver Slot mit hellem Rahmen
+pub fn draw_hotbar(batch: &mut UiBatch, inventory: &Inventory, selected: usize) {
+    let cell = SLOT_SIZE + SLOT_GAP;
+    let w = HOTBAR_SLOTS as f32 * cell - SLOT_GAP;
+    let x0 = (batch.width() - w) * 0.5;
+    let y0 = batch.height() - SLOT_SIZE - 12.0;
+
+    batch.rect(Rect::new(x0, y0, w, SLOT_SIZE).inset(-4.0), PANEL_BG);
+    for i in 0..HOTBAR_SLOTS {
+        let r = Rect::new(x0 + i as f32 * cell, y0, SLOT_SIZE, SLOT_SIZE);
+        batch.rect(r, SLOT_BG);
+        if let Some(st) = inventory.get(i) {
+            draw_stack(batch, r, st);
+        }
+        if i == selected {
+            batch.outline(r.inset(-2.0), 3.0, SELECTED);
+        }
+    }
+}
+
+/// Herzen links und Hunger rechts über der Hotbar; je Symbol 2 Punkte, halbe Symbole halb gefüllt
+pub fn draw_vitals(batch: &mut UiBatch, health: u8, hunger: u8) {
+    const HEART: [f32; 4] = [0.85, 0.15, 0.15, 1.0];
+    const FOOD: [f32; 4] = [0.75, 0.50, 0.22, 1.0];
+    const EMPTY: [f32; 4] = [0.10, 0.10, 0.10, 0.7];
+    let icon = 14.0;
+    let gap = 2.0;
+    let cell = SLOT_SIZE + SLOT_GAP;
+    let bar_w = HOTBAR_SLOTS as f32 * cell - SLOT_GAP;
+    let x0 = (batch.width() - bar_w) * 0.5;
+    let y = batch.height() - SLOT_SIZE - 12.0 - 8.0 - icon;
+
+    let mut row = |x_of: &dyn Fn(usize) -> f32, value: u8, color: [f32; 4]| {
+        for i in 0..10 {
+            let r = Rect::new(x_of(i), y, icon, icon);
+            batch.rect(r, EMPTY);
+            let points = value.saturating_sub(i as u8 * 2).min(2);
+            if points > 0 {
+                let w = r.w * points as f32 * 0.5;
+                batch.rect(Rect::new(r.x, r.y, w, r.h).inset(2.0), color);
+            }
+        }
+    };
+    row(&|i| x0 + i as f32 * (icon + gap), health, HEART);
+    // Hunger füllt sich von rechts nach links
+    row(&|i| x0 + bar_w - icon - i as f32 * (icon + gap), hunger, FOOD);
+}
+
+/// Fadenkreuz in Bildschirmmitte; über einem benutzbaren Block farbig mit Rahmen und
+/// einem Hinweis, was ein Rechtsklick tut
+pub fn draw_crosshair(batch: &mut UiBatch, interaction: Option<Interaction>) {
+    let (cx, cy) = (batch.width() * 0.5, batch.height() * 0.5);
+    let Some(interaction) = interaction else {
+        let col = [1.0, 1.0, 1.0, 0.8];
+        batch.rect(Rect::new(cx - 8.0, cy - 1.0, 16.0, 2.0), col);
+        batch.rect(Rect::new(cx - 1.0, cy - 8.0, 2.0, 16.0), col);
+        return;
+    };
+    let col = INTERACT_COLOR;
+    batch.rect(Rect::new(cx - 6.0, cy - 1.0, 12.0, 2.0), col);
+    batch.rect(Rect::new(cx - 1.0, cy - 6.0, 2.0, 12.0), col);
+    batch.outline(Rect::new(cx - 12.0, cy - 12.0, 24.0, 24.0), 2.0, col);
+    let label = interaction.label();
+    batch.text(cx + 18.0, cy - UiBatch::text_height(2.0) * 0.5, 2.0, label, col);
+}
+
+/// Abbaufortschritt (0..1) als Balken unter dem Fadenkreuz
+pub fn draw_break_progress(batch: &mut UiBatch, progress: f32) {
+    let (cx, cy) = (batch.width() * 0.5, batch.height() * 0.5);
+    let r = Rect::new(cx - 20.0, cy + 14.0, 40.0, 4.0);
+    batch.rect(r, PANEL_BG);
+    batch.rect(Rect::new(r.x, r.y, r.w * progress.clamp(0.0, 1.0), r.h), TEXT);
+}
+
+/// Fadenkreuz über Kiste, Tür, Werkbank
+const INTERACT_COLOR: [f32; 4] = [1.0, 0.85, 0.35, 0.95];
+
+const BUTTON_W: f32 = 240.0;
+const BUTTON_H: f32 = 36.0;
+const BUTTON_GAP: f32 = 10.0;
+
+/// Buttons eines Menüs, senkrecht zentriert untereinander
+pub fn menu_buttons(screen_w: f32, screen_h: f32, count: usize) -> Vec<Rect> {
+    let total = count as f32 * (BUTTON_H + BUTTON_GAP) - BUTTON_GAP;
+    let x = (screen_w - BUTTON_W) * 0.5;
+    let y0 = (screen_h - total) * 0.5;
+    (0..count)
+        .map(|i| Rect::new(x, y0 + i as f32 * (BUTTON_H + BUTTON_GAP), BUTTON_W, BUTTON_H))
+        .collect()
+}
+
+/// Vollbild-Menü: abgedunkelte Welt, Titel, Buttons mit Hover
+pub fn draw_menu(batch: &mut UiBatch, title: &str, labels: &[&str], cursor: (f32, f32)) {
+    let (w, h) = (batch.width(), batch.height());
+    batch.rect(Rect::new(0.0, 0.0, w, h), [0.0, 0.0, 0.0, 0.55]);
+
+    let buttons = menu_buttons(w, h, labels.len());
+    if let Some(first) = buttons.first() {
+        let scale = 4.0;
+        let tw = UiBatch::text_width(title, scale);
+        let th = UiBatch::text_height(scale);
+        batch.text((w - tw) * 0.5, first.y - th - 30.0, scale, title, TEXT);
+    }
+
+    for (r, label) in buttons.iter().zip(labels) {
+        draw_button(batch, *r, label, cursor);
+    }
+}
+
+/// Debug-Overlay (F3): Textzeilen oben links auf halbtransparentem Grund
+pub fn draw_debug_lines(batch: &mut UiBatch, lines: &[String]) {
+    let scale = 2.0;
+    let line_h = UiBatch::text_height(scale) + 4.0;
+    let w = lines.iter().map(|l| UiBatch::text_width(l, scale)).fold(0.0, f32::max);
+    let r = Rect::new(8.0, 8.0, w + 12.0, lines.len() as f32 * line_h + 8.0);
+    batch.rect(r, [0.0, 0.0, 0.0, 0.45]);
+    for (i, line) in lines.iter().enumerate() {
+        batch.text(r.x + 6.0, r.y + 6.0 + i as f32 * line_h, scale, line, TEXT);
+    }
+}
+
+/// Einzeilige Info unten mittig (z.B. Weltinfo im Hauptmenü)
+pub fn draw_caption(batch: &mut UiBatch, text: &str) {
+    let scale = 2.0;
+    let tw = UiBatch::text_width(text, scale);
+    let th = UiBatch::text_height(scale);
+    batch.text((batch.width() - tw) * 0.5, batch.height() - th - 24.0, scale, text, TEXT);
+}
+
+/// Name über einem anderen Spieler; `(x, y)` = Unterkante Mitte in Pixeln
+pub fn draw_name_tag(batch: &mut UiBatch, (x, y): (f32, f32), name: &str) {
+    let scale = 2.0;
+    let tw = UiBatch::text_width(name, scale);
+    let th = UiBatch::text_height(scale);
+    let r = Rect::new(x - tw * 0.5 - 4.0, y - th - 8.0, tw + 8.0, th + 8.0);
+    batch.rect(r, [0.0, 0.0, 0.0, 0.45]);
+    batch.text(r.x + 4.0, r.y + 4.0, scale, name, TEXT);
+}
+
+pub const WAYPOINT_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+const COMPASS_W: f32 = 240.0;
+/// Sichtbarer Winkel links und rechts der Blickrichtung
+const COMPASS_SPAN: f32 = FRAC_PI_2;
+/// Himmelsrichtungen als Winkel wie beim Spieler (`atan2(dx, dz)`): Norden = -Z, Osten = +X
+const CARDINALS: [(&str, f32); 4] = [("N", PI), ("E", FRAC_PI_2), ("S", 0.0), ("W", -FRAC_PI_2)];
+
+/// Nächste Himmelsrichtung zum Blick
+pub fn facing(yaw: f32) -> &'static str {
+    let quarter = (yaw / FRAC_PI_2).round().rem_euclid(4.0) as usize;
+    ["S", "E", "N", "W"][quarter]
+}
+
+/// Wegmarke im Bild: Raute an `(x, y)`, darüber Name und Entfernung
+pub fn draw_waypoint_marker(batch: &mut UiBatch, (x, y): (f32, f32), name: &str, dist: f32) {
+    batch.rect(Rect::new(x - 3.0, y - 3.0, 6.0, 6.0), WAYPOINT_COLOR);
+    draw_name_tag(batch, (x, y - 6.0), &format!("{name} {dist:.0}m"));
+}
+
+/// Kompassleiste oben mittig: Himmelsrichtungen und Wegmarken relativ zu `yaw`;
+/// Wegmarken außerhalb des Ausschnitts sitzen blass am Rand
+pub fn draw_compass(batch: &mut UiBatch, (x, z): (f32, f32), yaw: f32, waypoints: &[Waypoint]) {
+    let r = Rect::new((batch.width() - COMPASS_W) * 0.5, 8.0, COMPASS_W, 20.0);
+    batch.rect(r, [0.0, 0.0, 0.0, 0.45]);
+    let center = r.x + r.w * 0.5;
+    // Winkel relativ zum Blick -> Pixel; rechts vom Blick ist der Winkel negativ
+    let offset = |bearing: f32| {
+        let rel = (bearing - yaw + PI).rem_euclid(TAU) - PI;
+        -rel / COMPASS_SPAN * r.w * 0.5
+    };
+    let th = UiBatch::text_height(1.0);
+    for (label, bearing) in CARDINALS {
+        let dx = offset(bearing);
+        if dx.abs() <= r.w * 0.5 - 4.0 {
+            let tw = UiBatch::text_width(label, 1.0);
+            batch.text(center + dx - tw * 0.5, r.y + (r.h - th) * 0.5, 1.0, label, TEXT);
+        }
+    }
+    batch.rect(Rect::new(center - 0.5, r.y + r.h - 4.0, 1.0, 4.0), TEXT);
+    for w in waypoints {
+        let (dx, dz) = (w.pos.0 - x, w.pos.2 - z);
+        let px = offset(dx.atan2(dz));
+        let edge = r.w * 0.5 - 3.0;
+        let mut color = WAYPOINT_COLOR;
+        if px.abs() > edge {
+            color[3] = 0.4;
+        }
+        let px = center + px.clamp(-edge, edge);
+        batch.rect(Rect::new(px - 2.0, r.y + 2.0, 4.0, 4.0), color);
+    }
+}
+
+/// Koordinatenzeile unter dem Kompass: Blockposition der Füße, Himmelsrichtung und
+/// (falls bekannt) der anvisierte Block
+pub fn draw_coordinates(
+    batch: &mut UiBatch,
+    (x, y, z): (f32, f32, f32),
+    yaw: f32,
+    target: Option<Block>,
+) {
+    let (bx, by, bz) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+    let mut text = format!("{bx} {by} {bz}  {}", facing(yaw));
+    if let Some(block) = target {
+        text += &format!("  {}", block.name());
+    }
+    let scale = 2.0;
+    let tw = UiBatch::text_width(&text, scale);
+    let th = UiBatch::text_height(scale);
+    let r = Rect::new((batch.width() - tw) * 0.5 - 4.0, 32.0, tw + 8.0, th + 8.0);
+    batch.rect(r, [0.0, 0.0, 0.0, 0.45]);
+    batch.text(r.x + 4.0, r.y + 4.0, scale, &text, TEXT);
+}
+
+fn draw_button(batch: &mut UiBatch, r: Rect, label: &str, cursor: (f32, f32)) {
+    let bg = if r.contains(cursor.0, cursor.1) { SLOT_HOVER } else { SLOT_BG };
+    batch.rect(r, bg);
+    let scale = 2.0;
+    let tw = UiBatch::text_width(label, scale);
+    let th = UiBatch::text_height(scale);
+    batch.text(r.x + (r.w - tw) * 0.5, r.y + (r.h - th) * 0.5, scale, label, TEXT);
+}
+
+const ROW_W: f32 = 300.0;
+const ROW_H: f32 = 28.0;
+const ROW_GAP: f32 = 6.0;
+
+/// Steuerungsmenü: eine Zeile je Aktion in zwei Spalten, zuletzt der Zurück-Button
+pub fn controls_layout(screen_w: f32, screen_h: f32) -> Vec<Rect> {
+    let per_col = Action::ALL.len().div_ceil(2);
+    let total = (per_col as f32 + 1.0) * (ROW_H + ROW_GAP) + BUTTON_H;
+    let x0 = (screen_w - 2.0 * ROW_W - ROW_GAP * 4.0) * 0.5;
+    let y0 = (screen_h - total) * 0.5;
+    let mut rects: Vec<Rect> = (0..Action::ALL.len())
+        .map(|i| {
+            let (col, row) = (i / per_col, i % per_col);
+            let x = x0 + col as f32 * (ROW_W + ROW_GAP * 4.0);
+            Rect::new(x, y0 + row as f32 * (ROW_H + ROW_GAP), ROW_W, ROW_H)
+        })
+        .collect();
+    let back_y = y0 + (per_col as f32 + 1.0) * (ROW_H + ROW_GAP);
+    rects.push(Rect::new((screen_w - BUTTON_W) * 0.5, back_y, BUTTON_W, BUTTON_H));
+    rects
+}
+
+/// Tastenbelegung; `waiting` = Aktion, die gerade auf eine neue Taste wartet
+pub fn draw_controls(
+    batch: &mut UiBatch,
+    bindings: &KeyBindings,
+    waiting: Option<Action>,
+    cursor: (f32, f32),
+) {
+    let (w, h) = (batch.width(), batch.height());
+    batch.rect(Rect::new(0.0, 0.0, w, h), [0.0, 0.0, 0.0, 0.55]);
+
+    let rects = controls_layout(w, h);
+    let scale = 2.0;
+    let th = UiBatch::text_height(scale);
+    let title = "Controls";
+    let tw = UiBatch::text_width(title, 4.0);
+    let title_y = rects[0].y - UiBatch::text_height(4.0) - 30.0;
+    batch.text((w - tw) * 0.5, title_y, 4.0, title, TEXT);
+
+    for (r, action) in rects.iter().zip(Action::ALL) {
+        let hover = r.contains(cursor.0, cursor.1) || waiting == Some(action);
+        batch.rect(*r, if hover { SLOT_HOVER } else { SLOT_BG });
+        let ty = r.y + (r.h - th) * 0.5;
+        batch.text(r.x + 8.0, ty, scale, action.label(), TEXT);
+        let key = if waiting == Some(action) {
+            "> ? <".to_string()
+        } else {
+            key_label(bindings.key(action))
+        };
+        let kw = UiBatch::text_width(&key, scale);
+        batch.text(r.x + r.w - kw - 8.0, ty, scale, &key, TEXT);
+    }
+
+    draw_button(batch, rects[rects.len() - 1], "Back", cursor);
+}
+
+/// Maus-Einstellungen: Slider, zwei Umschalter, Zurück (Reihenfolge wie `menu_buttons(.., 4)`)
+pub fn draw_mouse_settings(batch: &mut UiBatch, mouse: &MouseSettings, cursor: (f32, f32)) {
+    let on_off = |b: bool| if b { "On" } else { "Off" };
+    let labels = [
+        format!("Sensitivity: {:.2}", mouse.sensitivity),
+        format!("Invert Y: {}", on_off(mouse.invert_y)),
+        format!("Raw Input: {}", on_off(mouse.raw_input)),
+        "Back".to_string(),
+    ];
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+    draw_menu(batch, "Mouse", &labels, cursor);
+
+    // Füllstand des Sliders unter die erste Zeile
+    let (w, h) = (batch.width(), batch.height());
+    let r = menu_buttons(w, h, labels.len())[0];
+    let frac = slider_fraction(mouse.sensitivity);
+    batch.rect(Rect::new(r.x, r.y + r.h - 4.0, r.w * frac, 4.0), SELECTED);
+}
+
+/// Empfindlichkeit <-> Sliderposition 0..1
+pub fn slider_fraction(sensitivity: f32) -> f32 {
+    (sensitivity - SENSITIVITY_MIN) / (SENSITIVITY_MAX - SENSITIVITY_MIN)
+}
+
+pub fn slider_value(r: Rect, px: f32) -> f32 {
+    SENSITIVITY_MIN + slider_at(r, px) * (SENSITIVITY_MAX - SENSITIVITY_MIN)
+}
+
+/// Sliderposition 0..1 unter dem Mauszeiger
+pub fn slider_at(r: Rect, px: f32) -> f32 {
+    ((px - r.x) / r.w).clamp(0.0, 1.0)
+}
+
+/// Ton-Einstellungen: zwei Slider, Umschalter, Zurück (wie `menu_buttons(.., 4)`)
+pub fn draw_audio_settings(batch: &mut UiBatch, audio: &AudioSettings, cursor: (f32, f32)) {
+    let labels = [
+        format!("Volume: {:.0}%", audio.volume * 100.0),
+        format!("Music: {:.0}%", audio.music_volume * 100.0),
+        format!("Shuffle: {}", if audio.shuffle { "On" } else { "Off" }),
+        "Back".to_string(),
+    ];
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+    draw_menu(batch, "Audio", &labels, cursor);
+
+    let (w, h) = (batch.width(), batch.height());
+    let rects = menu_buttons(w, h, labels.len());
+    for (r, frac) in rects.iter().zip([audio.volume, audio.music_volume]) {
+        batch.rect(Rect::new(r.x, r.y + r.h - 4.0, r.w * frac, 4.0), SELECTED);
+    }
+}
+
+/// Konsole unten links: offen mit Eingabezeile und Verlauf, sonst nur frische Meldungen
+pub fn draw_console(batch: &mut UiBatch, console: &Console, tick: u64) {
+    let scale = 2.0;
+    let line_h = UiBatch::text_height(scale) + 6.0;
+    let w = (batch.width() * 0.5).max(320.0);
+    let mut y = batch.height() - 8.0 - line_h;
+
+    if console.is_open() {
+        let r = Rect::new(8.0, y, w, line_h);
+        batch.rect(r, PANEL_BG);
+        let text = format!("{}_", console.input());
+        batch.text(r.x + 4.0, r.y + 3.0, scale, &text, TEXT);
+        y -= line_h + 4.0;
+    } else {
+        // Platz für Hotbar und Vitalwerte lassen
+        y -= SLOT_SIZE + 40.0;
+    }
+
+    let lines: Vec<&(String, u64)> = if console.is_open() {
+        console.visible(12).collect()
+    } else {
+        console
+            .visible(5)
+            .filter(|(_, t)| tick < t + RECENT_TICKS)
+            .collect()
+    };
+    for (line, _) in lines.into_iter().rev() {
+        let r = Rect::new(8.0, y, w, line_h);
+        batch.rect(r, [0.0, 0.0, 0.0, 0.45]);
+        batch.text(r.x + 4.0, r.y + 3.0, scale, line, TEXT);
+        y -= line_h;
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use anyhow::{anyhow, Context, Result};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Scope, AST};
+
+use crate::block::Block;
+use crate::console;
+use crate::world::BlockPos;
+
+/// Skripte für alle Welten
+pub const SCRIPT_DIR: &str = "saves/scripts";
+
+/// Obergrenze pro Skriptaufruf (Endlosschleifen brechen ab statt das Spiel einzufrieren)
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING: usize = 4096;
+const MAX_ARRAY: usize = 10_000;
+
+/// Zugriff eines Skripts auf das Spiel; beantwortet vom Game auf dem Hauptthread
+#[derive(Debug)]
+pub enum Request {
+    GetBlock(BlockPos),
+    SetBlock(BlockPos, Block),
+    PlayerPos,
+    Teleport(f32, f32, f32),
+    Message(String),
+}
+
+#[derive(Debug)]
+pub enum Answer {
+    None,
+    Block(Block),
+    Changed(bool),
+    Pos(f32, f32, f32),
+}
+
+enum Job {
+    /// Alle *.rhai eines Ordners laden und ihren Top-Level-Code ausführen
+    Load(PathBuf),
+    Call { function: String, args: Vec<String> },
+}
+
+enum Event {
+    Request(Request, Sender<Answer>),
+    Register { command: String, function: String },
+    Done(Result<()>),
+}
+
+/// Rhai-Engine auf eigenem Thread (wie der Licht-Worker). Solange ein Job läuft,
+/// bedient der Hauptthread dessen Anfragen – Skripte sehen die Welt also synchron,
+/// kommen aber an nichts außer der API unten (kein Dateizugriff, keine Module).
+pub struct ScriptHost {
+    jobs: Sender<Job>,
+    events: Receiver<Event>,
+    /// Konsolenbefehl -> Skriptfunktion
+    commands: HashMap<String, String>,
+}
+
+impl ScriptHost {
+    pub fn spawn() -> Self {
+        let (job_tx, job_rx) = mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("scripts".into())
+            .spawn(move || worker(job_rx, event_tx))
+            .expect("Skript-Thread starten");
+        Self {
+            jobs: job_tx,
+            events: event_rx,
+            commands: HashMap::new(),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        dir: &Path,
+        serve: impl FnMut(Request) -> Answer,
+    ) -> Result<()> {
+        self.commands.clear();
+        self.run(Job::Load(dir.to_path_buf()), serve)
+    }
+
+    /// Von einem Skript registrierter Befehl? Dann ausführen (`line` ohne "/")
+    pub fn command(
+        &mut self,
+        line: &str,
+        serve: impl FnMut(Request) -> Answer,
+    ) -> Option<Result<()>> {
+        let mut words = line.split_whitespace();
+        let function = self.commands.get(words.next()?)?.clone();
+        let args = words.map(str::to_string).collect();
+        Some(self.run(Job::Call { function, args }, serve))
+    }
+
+    pub fn command_names(&self) -> impl Iterator<Item = &str> {
+        self.commands.keys().map(String::as_str)
+    }
+
+    /// Job abschicken und bis zum Ende Anfragen beantworten
+    fn run(
+        &mut self,
+        job: Job,
+        mut serve: impl FnMut(Request) -> Answer,
+    ) -> Result<()> {
+        self.jobs.send(job).map_err(|_| anyhow!("Skript-Thread beendet"))?;
+        loop {
+            match self.events.recv().map_err(|_| anyhow!("Skript-Thread beendet"))? {
+                Event::Request(req, reply) => {
+                    let _ = reply.send(serve(req));
+                }
+                Event::Register { command, function } => {
+                    self.commands.insert(command, function);
+                }
+                Event::Done(result) => return result,
+            }
+        }
+    }
+}
+
+fn worker(jobs: Receiver<Job>, events: Sender<Event>) {
+    let engine = engine(&events);
+    let mut ast = AST::empty();
+    while let Ok(job) = jobs.recv() {
+        let result = match job {
+            Job::Load(dir) => load_dir(&engine, &dir).map(|loaded| ast = loaded),
+            Job::Call { function, args } => call(&engine, &ast, &function, args, &events),
+        };
+        if events.send(Event::Done(result)).is_err() {
+            break;
+        }
+    }
+}
+
+fn load_dir(engine: &Engine, dir: &Path) -> Result<AST> {
+    let mut merged = AST::empty();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        // kein Ordner = keine Skripte
+        Err(_) => return Ok(merged),
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let src = fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+        let ast = engine
+            .compile(&src)
+            .map_err(|e| anyhow!("{}: {e}", path.display()))?;
+        engine.run_ast(&ast).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+        merged = merged.merge(&ast);
+    }
+    Ok(merged)
+}
+
+fn call(
+    engine: &Engine,
+    ast: &AST,
+    function: &str,
+    args: Vec<String>,
+    events: &Sender<Event>,
+) -> Result<()> {
+    let args: Array = args.into_iter().map(Dynamic::from).collect();
+    let options = CallFnOptions::new().eval_ast(false);
+    let ret: Dynamic = engine
+        .call_fn_with_options(options, &mut Scope::new(), ast, function, (args,))
+        .map_err(|e| anyhow!("{function}: {e}"))?;
+    // Rückgabewert (außer ()) landet in der Konsole
+    if !ret.is_unit() {
+        ask(events, Request::Message(ret.to_string())).map_err(|e| anyhow!("{e}"))?;
+    }
+    Ok(())
+}
+
+fn ask(events: &Sender<Event>, req: Request) -> Result<Answer, Box<EvalAltResult>> {
+    let (tx, rx) = mpsc::channel();
+    events.send(Event::Request(req, tx)).map_err(|_| "game closed")?;
+    Ok(rx.recv().map_err(|_| "game closed")?)
+}
+
+/// Zahl aus einem Skriptwert (int oder float)
+fn number(v: &Dynamic) -> Result<f64, Box<EvalAltResult>> {
+    match (v.as_int(), v.as_float()) {
+        (Ok(i), _) => Ok(i as f64),
+        (_, Ok(f)) => Ok(f),
+        _ => Err(format!("expected a number, got {}", v.type_name()).into()),
+    }
+}
+
+fn block_pos(x: &Dynamic, y: &Dynamic, z: &Dynamic) -> Result<BlockPos, Box<EvalAltResult>> {
+    let c = |v| number(v).map(|n| n.floor() as i32);
+    Ok((c(x)?, c(y)?, c(z)?))
+}
+
+/// Engine mit Limits und der Spiel-API
+fn engine(events: &Sender<Event>) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_string_size(MAX_STRING)
+        .set_max_array_size(MAX_ARRAY)
+        .set_max_map_size(MAX_ARRAY)
+        .set_module_resolver(DummyModuleResolver::new());
+
+    let tx = events.clone();
+    engine.on_print(move |text| {
+        let _ = ask(&tx, Request::Message(text.to_string()));
+    });
+
+    let tx = events.clone();
+    engine.register_fn("message", move |text: &str| -> Result<(), Box<EvalAltResult>> {
+        ask(&tx, Request::Message(text.to_string())).map(|_| ())
+    });
+
+    let tx = events.clone();
+    engine.register_fn(
+        "get_block",
+        move |x: Dynamic, y: Dynamic, z: Dynamic| -> Result<String, Box<EvalAltResult>> {
+            match ask(&tx, Request::GetBlock(block_pos(&x, &y, &z)?))? {
+                Answer::Block(b) => Ok(b.name().to_string()),
+                other => Err(format!("unexpected answer {other:?}").into()),
+            }
+        },
+    );
+
+    let tx = events.clone();
+    engine.register_fn(
+        "set_block",
+        move |x: Dynamic, y: Dynamic, z: Dynamic, name: &str| -> Result<bool, Box<EvalAltResult>> {
+            let block = Block::by_name(name).ok_or_else(|| format!("unknown block '{name}'"))?;
+            match ask(&tx, Request::SetBlock(block_pos(&x, &y, &z)?, block))? {
+                Answer::Changed(changed) => Ok(changed),
+                other => Err(format!("unexpected answer {other:?}").into()),
+            }
+        },
+    );
+
+    let tx = events.clone();
+    engine.register_fn("player_pos", move || -> Result<Array, Box<EvalAltResult>> {
+        match ask(&tx, Request::PlayerPos)? {
+            Answer::Pos(x, y, z) => Ok([x, y, z].map(|v| Dynamic::from_float(v as f64)).to_vec()),
+            other => Err(format!("unexpected answer {other:?}").into()),
+        }
+    });
+
+    let tx = events.clone();
+    engine.register_fn(
+        "teleport",
+        move |x: Dynamic, y: Dynamic, z: Dynamic| -> Result<(), Box<EvalAltResult>> {
+            let (x, y, z) = (number(&x)? as f32, number(&y)? as f32, number(&z)? as f32);
+            ask(&tx, Request::Teleport(x, y, z)).map(|_| ())
+        },
+    );
+
+    let tx = events.clone();
+    engine.register_fn(
+        "register_command",
+        move |command: &str, function: &str| -> Result<(), Box<EvalAltResult>> {
+            if console::is_builtin(command) {
+                return Err(format!("/{command} is a built-in command").into());
+            }
+            let valid = !command.is_empty()
+                && command.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(format!("invalid command name '{command}'").into());
+            }
+            let event = Event::Register {
+                command: command.to_string(),
+                function: function.to_string(),
+            };
+            tx.send(event).map_err(|_| "game closed".into())
+        },
+    );
+
+    engine
+}
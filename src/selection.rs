@@ -0,0 +1,39 @@
+use crate::world::BlockPos;
+
+/// Quader-Auswahl für Befehle wie /set; Ecken werden im Auswahlmodus per Klick gesetzt
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Selection {
+    /// Auswahlmodus (Zauberstab-Taste): Links-/Rechtsklick setzen Ecke 1/2 statt abzubauen/bauen
+    pub active: bool,
+    pub corners: [Option<BlockPos>; 2],
+}
+
+impl Selection {
+    pub fn set_corner(&mut self, i: usize, pos: BlockPos) {
+        self.corners[i] = Some(pos);
+    }
+
+    /// Kleinste und größte Ecke, sobald beide gesetzt sind
+    pub fn bounds(&self) -> Option<(BlockPos, BlockPos)> {
+        let [Some(a), Some(b)] = self.corners else {
+            return None;
+        };
+        let min = (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2));
+        let max = (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2));
+        Some((min, max))
+    }
+
+    pub fn volume(&self) -> Option<i64> {
+        self.bounds().map(|(min, max)| region_volume(min, max))
+    }
+}
+
+/// Blockanzahl eines Quaders (beide Ecken inklusive)
+pub fn region_volume(min: BlockPos, max: BlockPos) -> i64 {
+    (max.0 - min.0 + 1) as i64 * (max.1 - min.1 + 1) as i64 * (max.2 - min.2 + 1) as i64
+}
+
+/// Liegt `p` auf der Hülle des Quaders?
+pub fn on_shell(p: BlockPos, min: BlockPos, max: BlockPos) -> bool {
+    p.0 == min.0 || p.0 == max.0 || p.1 == min.1 || p.1 == max.1 || p.2 == min.2 || p.2 == max.2
+}
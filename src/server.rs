@@ -0,0 +1,512 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::time::Instant;
+
+use anyhow::{bail, Result};
+
+use crate::biome;
+use crate::block;
+use crate::chunk::{chunk_coord, ChunkPos, CHUNK_SIZE};
+use crate::client::Client;
+use crate::console;
+use crate::event::{BlockChanged, Subscription};
+use crate::game::{Game, Seat};
+use crate::input::InputState;
+use crate::level::LevelInfo;
+use crate::metrics::{self, Metric};
+use crate::player::DEFAULT_NAME;
+use crate::protocol::{
+    check_player_name, ClientMsg, PackedPlayer, PlayerState, PlayerView, ServerMsg, WorldParams,
+};
+use crate::replay::ReplayFrame;
+use crate::serial::truncate_at_char;
+use crate::settings::{VIEW_DISTANCE_MAX, VIEW_DISTANCE_MIN};
+
+/// Andere Spieler verschwinden erst so weit (Blöcke) hinter der Sichtweite wieder,
+/// damit niemand an der Grenze ständig auf- und abtaucht
+const INTEREST_MARGIN: f32 = 8.0;
+
+/// Autoritative Simulation. Spricht mit den Clients nur über Nachrichten; im
+/// Einzelspieler läuft sie als integrierter Server im selben Thread.
+pub struct Server {
+    game: Game,
+    sessions: Vec<Session>,
+    next_id: u32,
+    /// Sichtweite für neue Clients und für den Host-Spieler, solange ihn niemand steuert
+    view_distance: i32,
+    /// Stand der Konsole beim letzten Sync (neue Zeilen gehen als Chat raus)
+    printed: u64,
+    /// Befehlsausgaben seit dem letzten Sync und die Session, deren Befehl sie erzeugt hat
+    replies: Vec<(Range<u64>, u32)>,
+    /// Spielernamen, die alle Befehle benutzen dürfen
+    operators: HashSet<String>,
+    /// Blockänderungen seit dem letzten Sync (gehen an Clients, die den Chunk haben)
+    block_changes: Subscription<BlockChanged>,
+}
+
+/// Ein verbundener Client
+struct Session {
+    id: u32,
+    name: String,
+    from_client: Receiver<ClientMsg>,
+    to_client: Sender<ServerMsg>,
+    /// Chunks, die der Client gerade hat
+    sent: HashSet<ChunkPos>,
+    /// Andere Spieler in Reichweite, mit dem zuletzt geschickten Stand
+    visible: HashMap<u32, PackedPlayer>,
+    /// Chunk-Radius um den eigenen Spieler
+    view_distance: i32,
+    /// Letzte verarbeitete Eingabenummer des Clients
+    ack: u32,
+    /// None = steuert den Host-Spieler im Game selbst
+    seat: Option<Seat>,
+    /// Noch nicht geprüfte Chatzeilen und Befehle
+    chat: Vec<String>,
+    /// Kanal zu, wird beim nächsten Tick entfernt
+    closed: bool,
+}
+
+impl Session {
+    /// Nächste Tick-Eingabe aus der Warteschlange; Sichtweite nebenbei übernehmen
+    fn next_frame(&mut self) -> Option<ReplayFrame> {
+        loop {
+            match self.from_client.try_recv() {
+                Ok(ClientMsg::Hello(hello)) => {
+                    log::warn!("Unerwartetes Hello von {:?} ignoriert", hello.name);
+                }
+                Ok(ClientMsg::Tick(seq, mut frame)) => {
+                    self.ack = seq;
+                    // Zeilen aus dem Frame durchlaufen dieselbe Rechteprüfung
+                    for line in frame.lines.drain(..) {
+                        self.push_chat(line);
+                    }
+                    return Some(frame);
+                }
+                Ok(ClientMsg::ViewDistance(d)) => self.view_distance = self.checked_view(d),
+                Ok(ClientMsg::Chat(line)) => self.push_chat(line),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    self.closed = true;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Sichtweite aus dem Netz auf den Bereich der Einstellungen begrenzen; größere Werte
+    /// würden riesige Chunk-Fenster erzeugen
+    fn checked_view(&self, d: i32) -> i32 {
+        let clamped = d.clamp(VIEW_DISTANCE_MIN, VIEW_DISTANCE_MAX);
+        if clamped != d {
+            log::info!("{}: Sichtweite {d} auf {clamped} begrenzt", self.name);
+        }
+        clamped
+    }
+
+    /// Zeilen über der Länge der Konsoleneingabe kommen nur gekürzt an
+    fn push_chat(&mut self, mut line: String) {
+        if line.len() > console::MAX_INPUT {
+            log::info!("{}: Chatzeile mit {} Bytes gekürzt", self.name, line.len());
+            line.truncate(truncate_at_char(&line, console::MAX_INPUT).len());
+        }
+        self.chat.push(line);
+    }
+
+    fn send(&mut self, msg: ServerMsg) {
+        if self.to_client.send(msg).is_err() {
+            self.closed = true;
+        }
+    }
+}
+
+/// `f` für den Spieler einer Session (Host oder Mitspieler)
+fn as_player<R>(game: &mut Game, seat: &mut Option<Seat>, f: impl FnOnce(&mut Game) -> R) -> R {
+    match seat {
+        Some(seat) => game.with_seat(seat, f),
+        None => f(game),
+    }
+}
+
+/// Einzelspieler: Server und Client über lokale Kanäle verbunden. Der Server läuft danach
+/// auf eigenem Thread (`SimThread`), der Client sagt die eigene Bewegung daher voraus.
+pub fn integrated(game: Game, view_distance: i32) -> (Server, Client) {
+    let (client_tx, client_rx) = mpsc::channel();
+    let (server_tx, server_rx) = mpsc::channel();
+    let mut server = Server::detached(game, view_distance);
+    server.set_operator(DEFAULT_NAME, true);
+    server.attach(DEFAULT_NAME.to_string(), server_tx, client_rx).expect("erster Spieler");
+    (server, Client::networked(client_tx, server_rx, view_distance))
+}
+
+impl Server {
+    /// Noch ohne Clients; die Welt um den Host-Spieler ist trotzdem geladen
+    pub fn detached(game: Game, view_distance: i32) -> Self {
+        let block_changes = game.world().events().subscribe();
+        let printed = game.console().printed();
+        let mut server = Self {
+            game,
+            sessions: Vec::new(),
+            next_id: 1,
+            view_distance: view_distance.clamp(VIEW_DISTANCE_MIN, VIEW_DISTANCE_MAX),
+            printed,
+            replies: Vec::new(),
+            operators: HashSet::new(),
+            block_changes,
+        };
+        server.sync();
+        server
+    }
+
+    /// Neuen Client anhängen. Der erste steuert den Host-Spieler, weitere bekommen einen
+    /// eigenen Spieler am Spawnpunkt. Kanäle können lokal sein oder über `net` laufen.
+    /// Ungültige oder schon verbundene Namen bekommen `Disconnect` (Operator-Rechte hängen
+    /// am Namen).
+    pub fn attach(
+        &mut self,
+        name: String,
+        to_client: Sender<ServerMsg>,
+        from_client: Receiver<ClientMsg>,
+    ) -> Result<()> {
+        let check = check_player_name(&name).and_then(|()| {
+            if self.sessions.iter().any(|s| s.name == name) {
+                bail!("A player named {name} is already connected");
+            }
+            Ok(())
+        });
+        if let Err(e) = check {
+            let _ = to_client.send(ServerMsg::Disconnect(e.to_string()));
+            return Err(e);
+        }
+        let host_taken = self.sessions.iter().any(|s| s.seat.is_none());
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut session = Session {
+            id,
+            name: name.clone(),
+            from_client,
+            to_client,
+            sent: HashSet::new(),
+            visible: HashMap::new(),
+            view_distance: self.view_distance,
+            ack: 0,
+            seat: host_taken.then(|| self.game.new_seat(name.clone())),
+            chat: Vec::new(),
+            closed: false,
+        };
+        if !host_taken {
+            self.game.set_player_name(name.clone());
+        }
+        self.game.message(format!("{name} joined the game"));
+        for other in &mut self.sessions {
+            other.send(ServerMsg::PlayerJoined(id, session.name.clone()));
+            session.send(ServerMsg::PlayerJoined(other.id, other.name.clone()));
+        }
+        self.sessions.push(session);
+        self.sync();
+        Ok(())
+    }
+
+    /// Chatzeile oder Befehl des Host-Spielers, der direkt in die Konsole des Spiels tippt;
+    /// läuft wie `ClientMsg::Chat` mit seinem nächsten Tick
+    pub fn host_chat(&mut self, line: String) {
+        if let Some(host) = self.sessions.iter_mut().find(|s| s.seat.is_none()) {
+            host.chat.push(line);
+        }
+    }
+
+    /// Operatoren dürfen auch Befehle, die die Welt verändern (/fill, /time, Skripte ...)
+    pub fn set_operator(&mut self, name: &str, op: bool) {
+        if op {
+            self.operators.insert(name.to_string());
+        } else {
+            self.operators.remove(name);
+        }
+    }
+
+    pub fn operators(&self) -> impl Iterator<Item = &str> {
+        self.operators.iter().map(String::as_str)
+    }
+
+    /// Verbundene Clients (für den LAN-Ruf und `status`)
+    pub fn player_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Für den Handshake mit einem Netzwerk-Client
+    pub fn world_params(&self) -> WorldParams {
+        // Replay-Welten haben keine level.toml
+        let level = self.game.level_info().unwrap_or_else(|| LevelInfo {
+            seed: self.game.world().seed(),
+            ..LevelInfo::new("")
+        });
+        WorldParams {
+            name: level.name,
+            seed: level.seed,
+            generator: level.generator,
+            spawn: level.spawn,
+            blocks: block::table_checksum(),
+        }
+    }
+
+    /// HUD, Menüs und Konsole lesen die Simulation noch direkt
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+
+    /// Gleichschritt (Wiedergabe): je wartender Eingabe sofort ein Tick
+    pub fn poll(&mut self) {
+        let mut changed = false;
+        for i in 0..self.sessions.len() {
+            let view = self.sessions[i].view_distance;
+            while let Some(frame) = self.sessions[i].next_frame() {
+                let start = Instant::now();
+                self.run_tick(i, Some(frame));
+                self.record_metrics(start);
+                changed = true;
+            }
+            changed |= self.sessions[i].view_distance != view;
+        }
+        self.drop_closed();
+        if changed {
+            self.sync();
+        }
+    }
+
+    /// Ein Tick im eigenen Takt (dedizierter Server, Simulations-Thread), je Client mit der
+    /// ältesten wartenden Eingabe. Die Welt tickt mit dem Host-Spieler, auch wenn ihn
+    /// gerade niemand steuert.
+    pub fn tick(&mut self) {
+        let start = Instant::now();
+        let mut frames: Vec<_> = self.sessions.iter_mut().map(Session::next_frame).collect();
+        // Host zuerst: mit ihm tickt die Welt
+        let mut order: Vec<usize> = (0..self.sessions.len()).collect();
+        order.sort_by_key(|&i| self.sessions[i].seat.is_some());
+        if !self.sessions.iter().any(|s| s.seat.is_none()) {
+            self.game.tick(InputState::default());
+        }
+        for i in order {
+            self.run_tick(i, frames[i].take());
+        }
+        self.record_metrics(start);
+        self.drop_closed();
+        self.sync();
+    }
+
+    /// Tickdauer seit `start` und Weltgröße messen, dann eine Verlaufszeile
+    fn record_metrics(&self, start: Instant) {
+        metrics::set_duration(Metric::TickMs, start.elapsed());
+        let memory = self.game.world().memory_report();
+        metrics::set(Metric::Chunks, memory.chunks as f64);
+        metrics::set(Metric::ChunkMib, memory.chunk_bytes as f64 / (1 << 20) as f64);
+        metrics::sample();
+    }
+
+    /// Ein Tick für den Spieler von Session `i`; ohne Frame steht er still
+    fn run_tick(&mut self, i: usize, frame: Option<ReplayFrame>) {
+        let lines = self.allowed_lines(i);
+        match &mut self.sessions[i].seat {
+            Some(seat) => {
+                let mut frame = frame.unwrap_or_else(|| seat.idle_frame());
+                frame.lines = lines;
+                self.game.seat_tick(seat, &frame);
+            }
+            None => {
+                for line in lines {
+                    self.game.queue_line(line);
+                }
+                match frame {
+                    Some(frame) => self.game.replay_tick(&frame),
+                    None => self.game.tick(InputState::default()),
+                }
+            }
+        }
+        let id = self.sessions[i].id;
+        self.replies.extend(self.game.take_command_output().into_iter().map(|r| (r, id)));
+    }
+
+    /// Wartende Zeilen eines Clients; Befehle mit Folgen für alle nur von Operatoren
+    fn allowed_lines(&mut self, i: usize) -> Vec<String> {
+        let session = &mut self.sessions[i];
+        let op = self.operators.contains(&session.name);
+        let mut allowed = Vec::new();
+        for line in std::mem::take(&mut session.chat) {
+            match line.strip_prefix('/') {
+                Some(cmd) if !op && console::needs_operator(cmd) => {
+                    let name = cmd.split_whitespace().next().unwrap_or("");
+                    log::info!("{}: /{name} ohne Operator-Rechte abgelehnt", session.name);
+                    session.send(ServerMsg::Chat(format!("Only operators may use /{name}")));
+                }
+                _ => allowed.push(line),
+            }
+        }
+        allowed
+    }
+
+    /// Getrennte Clients entfernen und den übrigen Bescheid geben
+    fn drop_closed(&mut self) {
+        let (closed, open): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.sessions).into_iter().partition(|s| s.closed);
+        self.sessions = open;
+        for gone in closed {
+            log::info!("{} hat die Verbindung beendet", gone.name);
+            self.game.message(format!("{} left the game", gone.name));
+            for other in &mut self.sessions {
+                other.visible.remove(&gone.id);
+                other.send(ServerMsg::PlayerLeft(gone.id));
+            }
+        }
+    }
+
+    /// Chunk-Fenster nachziehen und allen Clients schicken, was sich geändert hat
+    fn sync(&mut self) {
+        // Eigene Sicht je Client, dazu was die anderen von ihm sehen
+        let mut views = Vec::with_capacity(self.sessions.len());
+        let mut states = Vec::with_capacity(self.sessions.len());
+        for session in &mut self.sessions {
+            let radius = session.view_distance;
+            let (window, view, overlay, state) =
+                as_player(&mut self.game, &mut session.seat, |game| {
+                    let body = game.player_body();
+                    let (yaw, pitch) = game.look_angles();
+                    let view = PlayerView {
+                        body,
+                        yaw,
+                        pitch,
+                        ack: 0,
+                        can_sprint: game.can_sprint(),
+                    };
+                    let state = PlayerState {
+                        id: 0,
+                        pos: (body.x, body.y, body.z),
+                        yaw,
+                        pitch,
+                        target: game.target_block(),
+                    };
+                    (game.chunk_window(radius), view, game.overlay_lines(), state)
+                });
+            views.push((window, PlayerView { ack: session.ack, ..view }, overlay));
+            states.push(PlayerState { id: session.id, ..state });
+        }
+
+        // Ein Ticket-Satz für alle: wo sich Fenster überlappen, wird nur einmal geladen.
+        // Die Welt tickt um den Host-Spieler, also bleibt sein Fenster auch ohne Client.
+        let mut tickets: HashSet<ChunkPos> = HashSet::new();
+        if !self.sessions.iter().any(|s| s.seat.is_none()) {
+            tickets.extend(self.game.chunk_window(self.view_distance));
+        }
+        for (window, ..) in &views {
+            tickets.extend(window);
+        }
+        self.game.retain_chunks(&tickets);
+
+        let world = self.game.world_mut();
+        let block_changes: Vec<BlockChanged> =
+            world.events().read(&mut self.block_changes).copied().collect();
+        let relit: HashSet<ChunkPos> =
+            tickets.iter().copied().filter(|&cp| world.take_light_changed(cp)).collect();
+        let daylight = self.game.daylight();
+        let rain = self.game.world().rain_level();
+        let seed = self.game.world().seed();
+        let (time_of_day, day) = (self.game.world().time_of_day(), self.game.world().day());
+        let sounds = self.game.drain_sound_events();
+        let console = self.game.console();
+        let chat: Vec<String> = console.lines_since(self.printed).map(str::to_string).collect();
+        // Zählerstand der ersten Zeile in `chat`
+        let first = console.printed() - chat.len() as u64;
+        self.printed = console.printed();
+        let replies = std::mem::take(&mut self.replies);
+
+        for (session, (window, view, overlay)) in self.sessions.iter_mut().zip(views) {
+            let world = self.game.world();
+            let mut out = Vec::new();
+            for &cp in session.sent.difference(&window) {
+                out.push(ServerMsg::UnloadChunk(cp));
+            }
+            session.sent.retain(|cp| window.contains(cp));
+
+            // Bekannte Chunks: einzelne Blöcke und neues Licht statt des ganzen Chunks
+            for change in &block_changes {
+                let (x, y, z) = change.pos;
+                let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+                if session.sent.contains(&cp) {
+                    out.push(ServerMsg::BlockChange(change.pos, change.block, change.state));
+                }
+            }
+            for &cp in session.sent.intersection(&relit) {
+                if let Some(ch) = world.chunk(cp) {
+                    out.push(ServerMsg::ChunkLight(cp, ch.light_data().into()));
+                }
+            }
+            for &cp in &window {
+                if !session.sent.contains(&cp)
+                    && let Some(ch) = world.chunk(cp)
+                {
+                    out.push(ServerMsg::Chunk(Box::new(ch.clone())));
+                    session.sent.insert(cp);
+                }
+            }
+
+            out.push(ServerMsg::Player(view));
+            let me = states.iter().find(|st| st.id == session.id).map_or((0.0, 0.0), |st| {
+                (st.pos.0, st.pos.2)
+            });
+            let radius = (session.view_distance * CHUNK_SIZE) as f32;
+            let mut moves = Vec::new();
+            for st in states.iter().filter(|st| st.id != session.id) {
+                let (dx, dz) = (st.pos.0 - me.0, st.pos.2 - me.1);
+                let dist = (dx * dx + dz * dz).sqrt();
+                let packed = PackedPlayer::pack(st);
+                match session.visible.get_mut(&st.id) {
+                    Some(_) if dist > radius + INTEREST_MARGIN => {
+                        session.visible.remove(&st.id);
+                        out.push(ServerMsg::PlayerDespawn(st.id));
+                    }
+                    Some(last) => {
+                        match last.delta(st.id, &packed) {
+                            Some(delta) if delta.is_empty() => {}
+                            Some(delta) => moves.push(delta),
+                            None => out.push(ServerMsg::PlayerSpawn(st.id, packed)),
+                        }
+                        *last = packed;
+                    }
+                    None if dist <= radius => {
+                        session.visible.insert(st.id, packed);
+                        out.push(ServerMsg::PlayerSpawn(st.id, packed));
+                    }
+                    None => {}
+                }
+            }
+            if !session.visible.is_empty() {
+                out.push(ServerMsg::PlayerMoves(moves));
+            }
+            out.push(ServerMsg::Daylight(daylight));
+            out.push(ServerMsg::Weather {
+                rain,
+                snow: biome::is_cold(seed, me.0, me.1),
+            });
+            out.push(ServerMsg::Clock { time_of_day, day });
+            out.extend(sounds.iter().cloned().map(ServerMsg::Sound));
+            out.push(ServerMsg::Overlay(overlay));
+            // Befehlsausgaben nur an den Absender, alles andere an alle
+            for (n, line) in (first..).zip(&chat) {
+                let owner = replies.iter().find(|(range, _)| range.contains(&n));
+                if owner.is_none_or(|&(_, id)| id == session.id) {
+                    out.push(ServerMsg::Chat(line.clone()));
+                }
+            }
+
+            for msg in out {
+                // Client weg (Fenster zu): nichts mehr zu tun
+                session.send(msg);
+                if session.closed {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+
+use crate::keybinds::{key_by_name, key_name, Action, KeyBindings};
+use crate::mesh_cache::DEFAULT_BUDGET_MB;
+use crate::save;
+
+/// Einstellungsdatei (TOML: `[bereich]` und darunter `schlüssel = wert`, eine Zeile pro Eintrag)
+pub const SETTINGS_FILE: &str = "saves/settings.toml";
+/// Frühere Dateiname; wird gelesen, solange es noch keine settings.toml gibt
+const LEGACY_SETTINGS_FILE: &str = "saves/settings.txt";
+
+/// Vertikales Sichtfeld in Grad
+pub const DEFAULT_FOV: f32 = 45.0;
+pub const FOV_MIN: f32 = 30.0;
+pub const FOV_MAX: f32 = 110.0;
+/// Renderauflösung der 3D-Szene relativ zum Fenster
+pub const RENDER_SCALE_MIN: f32 = 0.25;
+/// Sichtweite in Chunks (Radius um den Spieler)
+pub const VIEW_DISTANCE_MIN: i32 = 2;
+pub const VIEW_DISTANCE_MAX: i32 = 12;
+/// Speicher für Chunk-Meshes in MiB
+pub const MESH_CACHE_MB_MIN: u32 = 16;
+pub const MESH_CACHE_MB_MAX: u32 = 4096;
+/// Längster Abstand der Klickwiederholung in Ticks
+pub const CLICK_REPEAT_MAX: u32 = 40;
+
+/// Radiant pro Mauszählschritt bei Empfindlichkeit 1.0
+const BASE_SENSITIVITY: f32 = 0.002;
+/// Bereich des Empfindlichkeits-Sliders
+pub const SENSITIVITY_MIN: f32 = 0.1;
+pub const SENSITIVITY_MAX: f32 = 4.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MouseSettings {
+    /// Faktor auf `BASE_SENSITIVITY`
+    pub sensitivity: f32,
+    pub invert_y: bool,
+    /// true: rohe Deltas (DeviceEvent), false: Cursorposition mit Zurücksetzen zur Fenstermitte
+    pub raw_input: bool,
+}
+
+impl Default for MouseSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 1.0,
+            invert_y: false,
+            raw_input: true,
+        }
+    }
+}
+
+impl MouseSettings {
+    /// Mausbewegung in Pixeln/Counts -> Blickänderung (Yaw, Pitch)
+    pub fn look(&self, dx: f64, dy: f64) -> (f32, f32) {
+        let s = BASE_SENSITIVITY * self.sensitivity;
+        let dy = if self.invert_y { -dy } else { dy };
+        (dx as f32 * s, dy as f32 * s)
+    }
+}
+
+/// Rechter Stick: eigene Kurve statt linearer Mausskalierung
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GamepadSettings {
+    /// Drehrate bei Vollausschlag (Radiant pro Sekunde)
+    pub look_speed: f32,
+    /// Auslenkung, unter der der Stick als ruhend gilt
+    pub deadzone: f32,
+    /// Exponent der Antwortkurve (1 = linear, größer = feiner um die Mitte)
+    pub curve: f32,
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        Self {
+            look_speed: 3.0,
+            deadzone: 0.15,
+            curve: 2.0,
+        }
+    }
+}
+
+impl GamepadSettings {
+    /// Stickwert -1..1 -> Drehrate (Radiant pro Sekunde)
+    pub fn look_rate(&self, v: f32) -> f32 {
+        let mag = ((v.abs() - self.deadzone) / (1.0 - self.deadzone)).clamp(0.0, 1.0);
+        v.signum() * mag.powf(self.curve) * self.look_speed
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GraphicsSettings {
+    /// Vertikales Sichtfeld in Grad
+    pub fov: f32,
+    /// Geladene Chunks um den Spieler
+    pub view_distance: i32,
+    /// Budget der Chunk-Meshes in MiB; darüber werden unsichtbare verworfen
+    pub mesh_cache_mb: u32,
+    /// Minikarte mit dem Blick drehen statt genordet
+    pub minimap_rotate: bool,
+    /// Zeile mit Koordinaten, Himmelsrichtung und Zielblock unter dem Kompass
+    pub show_coordinates: bool,
+    /// Auflösung der 3D-Szene als Anteil der Fenstergröße; die UI bleibt scharf
+    pub render_scale: f32,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            fov: DEFAULT_FOV,
+            view_distance: 4,
+            mesh_cache_mb: DEFAULT_BUDGET_MB,
+            minimap_rotate: false,
+            show_coordinates: true,
+            render_scale: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AudioSettings {
+    /// Gesamtlautstärke 0..1
+    pub volume: f32,
+    /// Musik relativ zur Gesamtlautstärke 0..1
+    pub music_volume: f32,
+    /// Musikstücke gemischt statt in Namensreihenfolge
+    pub shuffle: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            music_volume: 0.5,
+            shuffle: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameplaySettings {
+    /// Automatisch speichern alle N Minuten (0 = aus)
+    pub autosave_minutes: u32,
+    /// Gehaltene Maustaste: alle N Ticks erneut bauen (Creative: abbauen); 0 = nur Einzelklicks
+    pub click_repeat_ticks: u32,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            autosave_minutes: 5,
+            click_repeat_ticks: 4,
+        }
+    }
+}
+
+/// Vom Spieler änderbare Einstellungen
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Settings {
+    pub bindings: KeyBindings,
+    pub mouse: MouseSettings,
+    pub gamepad: GamepadSettings,
+    pub graphics: GraphicsSettings,
+    pub audio: AudioSettings,
+    pub gameplay: GameplaySettings,
+}
+
+impl Settings {
+    /// Laden; fehlende Datei oder kaputte Zeilen fallen auf die Standardwerte zurück
+    pub fn load(path: &Path) -> Self {
+        let text = fs::read_to_string(path).or_else(|e| {
+            if path == Path::new(SETTINGS_FILE) {
+                fs::read_to_string(LEGACY_SETTINGS_FILE)
+            } else {
+                Err(e)
+            }
+        });
+        match text {
+            Ok(text) => Self::parse(&text),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Von außen geänderte Datei übernehmen, soweit das im laufenden Spiel sicher geht.
+    /// Tastenbelegung und Rohdaten-Maus wirken erst nach einem Neustart.
+    pub fn apply_live(&mut self, new: &Settings) {
+        if new.bindings != self.bindings || new.mouse.raw_input != self.mouse.raw_input {
+            log::info!("Tastenbelegung/Mausmodus geändert: wirkt nach Neustart");
+        }
+        self.mouse.sensitivity = new.mouse.sensitivity;
+        self.mouse.invert_y = new.mouse.invert_y;
+        self.gamepad = new.gamepad;
+        self.graphics = new.graphics;
+        self.audio = new.audio;
+        self.gameplay = new.gameplay;
+    }
+
+    /// Versteht `[bereich]`-Köpfe und ältere Dateien mit `bereich.schlüssel` pro Zeile
+    pub fn parse(text: &str) -> Self {
+        let mut settings = Self::default();
+        let mut section = String::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_string();
+                continue;
+            }
+            if let Err(e) = settings.apply_line(&section, line) {
+                log::warn!("Einstellungen Zeile {}: {e}", n + 1);
+            }
+        }
+        settings
+    }
+
+    fn apply_line(&mut self, section: &str, line: &str) -> Result<()> {
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("kein '=' in '{line}'"))?;
+        let (key, value) = (key.trim(), value.trim());
+        let key = if section.is_empty() { key.to_string() } else { format!("{section}.{key}") };
+        if let Some(name) = key.strip_prefix("key.") {
+            let action =
+                Action::by_name(name).with_context(|| format!("unbekannte Aktion '{name}'"))?;
+            // Ältere Dateien schreiben die Taste ohne Anführungszeichen
+            let value = if value.starts_with('"') { parse_string(value)? } else { value.into() };
+            let code = key_by_name(&value).with_context(|| format!("unbekannte Taste '{value}'"))?;
+            self.bindings.set(action, code);
+            return Ok(());
+        }
+        match key.as_str() {
+            "mouse.sensitivity" => {
+                self.mouse.sensitivity = parse_f32(value)?.clamp(SENSITIVITY_MIN, SENSITIVITY_MAX);
+            }
+            "mouse.invert_y" => self.mouse.invert_y = parse_bool(value)?,
+            "mouse.raw_input" => self.mouse.raw_input = parse_bool(value)?,
+            "gamepad.look_speed" => self.gamepad.look_speed = parse_f32(value)?.max(0.0),
+            "gamepad.deadzone" => self.gamepad.deadzone = parse_f32(value)?.clamp(0.0, 0.9),
+            "gamepad.curve" => self.gamepad.curve = parse_f32(value)?.clamp(0.5, 4.0),
+            "graphics.fov" => self.graphics.fov = parse_f32(value)?.clamp(FOV_MIN, FOV_MAX),
+            "graphics.view_distance" => {
+                let v: i32 = value.parse().with_context(|| format!("keine Zahl: '{value}'"))?;
+                self.graphics.view_distance = v.clamp(VIEW_DISTANCE_MIN, VIEW_DISTANCE_MAX);
+            }
+            "graphics.mesh_cache_mb" => {
+                let v: u32 = value.parse().with_context(|| format!("keine Zahl: '{value}'"))?;
+                self.graphics.mesh_cache_mb = v.clamp(MESH_CACHE_MB_MIN, MESH_CACHE_MB_MAX);
+            }
+            "graphics.minimap_rotate" => self.graphics.minimap_rotate = parse_bool(value)?,
+            "graphics.show_coordinates" => self.graphics.show_coordinates = parse_bool(value)?,
+            "graphics.render_scale" => {
+                self.graphics.render_scale = parse_f32(value)?.clamp(RENDER_SCALE_MIN, 1.0);
+            }
+            "audio.volume" => self.audio.volume = parse_f32(value)?.clamp(0.0, 1.0),
+            "audio.music_volume" => self.audio.music_volume = parse_f32(value)?.clamp(0.0, 1.0),
+            "audio.shuffle" => self.audio.shuffle = parse_bool(value)?,
+            "gameplay.autosave_minutes" => {
+                self.gameplay.autosave_minutes =
+                    value.parse().with_context(|| format!("keine Zahl: '{value}'"))?;
+            }
+            "gameplay.click_repeat_ticks" => {
+                let v: u32 = value.parse().with_context(|| format!("keine Zahl: '{value}'"))?;
+                self.gameplay.click_repeat_ticks = v.min(CLICK_REPEAT_MAX);
+            }
+            _ => bail!("unbekannter Schlüssel '{key}'"),
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# Voxel-Einstellungen\n");
+        text += "\n[mouse]\n";
+        text += &format!("sensitivity = {:.2}\n", self.mouse.sensitivity);
+        text += &format!("invert_y = {}\n", self.mouse.invert_y);
+        text += &format!("raw_input = {}\n", self.mouse.raw_input);
+        text += "\n[gamepad]\n";
+        text += &format!("look_speed = {:.2}\n", self.gamepad.look_speed);
+        text += &format!("deadzone = {:.2}\n", self.gamepad.deadzone);
+        text += &format!("curve = {:.2}\n", self.gamepad.curve);
+        text += "\n[graphics]\n";
+        text += &format!("fov = {:.1}\n", self.graphics.fov);
+        text += &format!("view_distance = {}\n", self.graphics.view_distance);
+        text += &format!("mesh_cache_mb = {}\n", self.graphics.mesh_cache_mb);
+        text += &format!("minimap_rotate = {}\n", self.graphics.minimap_rotate);
+        text += &format!("show_coordinates = {}\n", self.graphics.show_coordinates);
+        text += &format!("render_scale = {:.2}\n", self.graphics.render_scale);
+        text += "\n[audio]\n";
+        text += &format!("volume = {:.2}\n", self.audio.volume);
+        text += &format!("music_volume = {:.2}\n", self.audio.music_volume);
+        text += &format!("shuffle = {}\n", self.audio.shuffle);
+        text += "\n[gameplay]\n";
+        text += &format!("autosave_minutes = {}\n", self.gameplay.autosave_minutes);
+        text += &format!("click_repeat_ticks = {}\n", self.gameplay.click_repeat_ticks);
+        text += "\n[key]\n";
+        for action in Action::ALL {
+            text += &format!("{} = {:?}\n", action.name(), key_name(self.bindings.key(action)));
+        }
+        text
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+        }
+        save::write_atomic(path, self.to_text().as_bytes())
+    }
+}
+
+/// Änderungszeit der Datei (None = fehlt); Grundlage fürs Neuladen im Spiel
+pub fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+pub fn parse_bool(s: &str) -> Result<bool> {
+    match s {
+        "true" | "on" | "1" => Ok(true),
+        "false" | "off" | "0" => Ok(false),
+        _ => bail!("kein Wahrheitswert: '{s}'"),
+    }
+}
+
+pub fn parse_f32(s: &str) -> Result<f32> {
+    s.parse().with_context(|| format!("keine Zahl: '{s}'"))
+}
+
+/// TOML-String in Anführungszeichen (nur \" und \\ als Escapes)
+pub fn parse_string(s: &str) -> Result<String> {
+    let inner = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .with_context(|| format!("kein String: '{s}'"))?;
+    Ok(inner.replace("\\\"", "\"").replace("\\\\", "\\"))
+}
@@ -1,30 +0,0 @@
-struct Camera {
-  view_proj: mat4x4<f32>,
-};
-
-@group(0) @binding(0)
-var<uniform> camera: Camera;
-
-struct VSIn {
-  @location(0) pos: vec3<f32>,
-  @location(1) color: vec3<f32>,
-};
-
-struct VSOut {
-  @builtin(position) clip_pos: vec4<f32>,
-  @location(0) color: vec3<f32>,
-};
-
-@vertex
-fn vs_main(input: VSIn) -> VSOut {
-  var out: VSOut;
-  out.clip_pos = camera.view_proj * vec4<f32>(input.pos, 1.0);
-  out.color = input.color;
-  return out;
-}
-
-@fragment
-fn fs_main(input: VSOut) -> @location(0) vec4<f32> {
-  return vec4<f32>(input.color, 1.0);
-}
-
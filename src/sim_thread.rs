@@ -0,0 +1,172 @@
+//! Integrierter Server auf eigenem Thread mit eigenem Tick-Takt: lange Ticks halten das
+//! Bild nicht auf und langsame Frames nicht die Simulation. Der Render-Thread schickt nur
+//! Eingaben (über den `Client`) und Aufträge; die Welt kommt wie übers Netz als Nachrichten
+//! zurück, HUD und Screen-Zustand liefert der Thread nach jedem Takt als Momentaufnahme.
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::block::Block;
+use crate::level::LevelInfo;
+use crate::memory::MemoryReport;
+use crate::server::Server;
+use crate::ui::UiBatch;
+
+/// Liegt der Takt weiter zurück (Suspend, Debugger), wird neu aufgesetzt statt aufgeholt
+const MAX_BEHIND_TICKS: u32 = 10;
+
+/// Auftrag an den Server, läuft zwischen zwei Ticks
+pub type SimCommand = Box<dyn FnOnce(&mut Server) + Send>;
+
+enum Msg {
+    Run(SimCommand),
+    /// Cursor und Fenstergröße für die UI der nächsten Momentaufnahmen
+    View { cursor: (f32, f32), size: (u32, u32) },
+    Paused(bool),
+    Stop,
+}
+
+/// Was der Render-Thread vom Spiel direkt braucht
+pub struct SimSnapshot {
+    /// Hotbar, Screens und Konsole, gebaut für Cursor und Fenstergröße des letzten `poll`
+    pub ui: UiBatch,
+    pub screen_open: bool,
+    pub console_open: bool,
+    pub level: Option<LevelInfo>,
+    /// Anvisierter Block des Spielers
+    pub target: Option<Block>,
+    /// Speicher der Simulation (Client und Renderer tragen ihren Teil selbst ein)
+    pub memory: MemoryReport,
+}
+
+pub struct SimThread {
+    commands: Sender<Msg>,
+    snapshots: Receiver<SimSnapshot>,
+    latest: SimSnapshot,
+    handle: JoinHandle<Server>,
+}
+
+impl SimThread {
+    /// Tickt alle `tick_dt`; `lockstep` (Wiedergabe) tickt stattdessen genau mit jeder
+    /// eingetroffenen Eingabe, damit kein Leerlauf-Tick den Ablauf verändert. Startet
+    /// pausiert.
+    pub fn spawn(mut server: Server, tick_dt: Duration, lockstep: bool) -> Self {
+        let latest = snapshot(&mut server, (0.0, 0.0), (1, 1));
+        let (commands, command_rx) = mpsc::channel();
+        let (snapshot_tx, snapshots) = mpsc::channel();
+        let clock = Clock { tick_dt, lockstep };
+        let handle = thread::Builder::new()
+            .name("sim".into())
+            .spawn(move || run(server, clock, command_rx, snapshot_tx))
+            .expect("Simulations-Thread starten");
+        Self {
+            commands,
+            snapshots,
+            latest,
+            handle,
+        }
+    }
+
+    /// `f` auf dem Simulations-Thread ausführen, ohne auf das Ergebnis zu warten
+    pub fn run(&self, f: impl FnOnce(&mut Server) + Send + 'static) {
+        self.send(Msg::Run(Box::new(f)));
+    }
+
+    /// Cursor und Fenstergröße, für die Hotbar und Screens gebaut werden
+    pub fn set_view(&self, cursor: (f32, f32), size: (u32, u32)) {
+        self.send(Msg::View { cursor, size });
+    }
+
+    /// Pausiert tickt die Welt nicht, solange niemand sonst mitspielt (LAN)
+    pub fn set_paused(&self, paused: bool) {
+        self.send(Msg::Paused(paused));
+    }
+
+    /// Neueste Momentaufnahme (nicht blockierend; sonst die vorige)
+    pub fn snapshot(&mut self) -> &SimSnapshot {
+        if let Some(newest) = self.snapshots.try_iter().last() {
+            self.latest = newest;
+        }
+        &self.latest
+    }
+
+    /// Eigene Änderung vorwegnehmen, bis die nächste Momentaufnahme sie bestätigt
+    /// (z.B. Konsole geöffnet: die nächsten Tasten sind schon Text)
+    pub fn snapshot_mut(&mut self) -> &mut SimSnapshot {
+        self.snapshot();
+        &mut self.latest
+    }
+
+    /// Wartende Aufträge noch ausführen, dann den Server zurückgeben (Speichern, Beenden)
+    pub fn stop(self) -> Server {
+        self.send(Msg::Stop);
+        self.handle.join().expect("Simulations-Thread abgestürzt")
+    }
+
+    fn send(&self, msg: Msg) {
+        if self.commands.send(msg).is_err() {
+            log::error!("Simulations-Thread beendet");
+        }
+    }
+}
+
+/// Takt des Simulations-Threads
+struct Clock {
+    tick_dt: Duration,
+    lockstep: bool,
+}
+
+fn run(
+    mut server: Server,
+    clock: Clock,
+    commands: Receiver<Msg>,
+    snapshots: Sender<SimSnapshot>,
+) -> Server {
+    let (mut cursor, mut size) = ((0.0, 0.0), (1, 1));
+    let mut paused = true;
+    let mut next_tick = Instant::now() + clock.tick_dt;
+    loop {
+        // Aufträge bis zum nächsten Tick sofort ausführen
+        let now = Instant::now();
+        if now < next_tick {
+            match commands.recv_timeout(next_tick - now) {
+                Ok(Msg::Run(f)) => f(&mut server),
+                Ok(Msg::View { cursor: c, size: s }) => (cursor, size) = (c, s),
+                Ok(Msg::Paused(p)) => paused = p,
+                // Ohne Render-Thread gibt es niemanden mehr, der `stop` ruft
+                Ok(Msg::Stop) | Err(RecvTimeoutError::Disconnected) => return server,
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+            continue;
+        }
+
+        if clock.lockstep {
+            server.poll();
+        } else if !paused || server.player_count() > 1 {
+            server.tick();
+        }
+        if snapshots.send(snapshot(&mut server, cursor, size)).is_err() {
+            return server;
+        }
+        next_tick += clock.tick_dt;
+        if now.saturating_duration_since(next_tick) > clock.tick_dt * MAX_BEHIND_TICKS {
+            log::warn!("Simulation hängt hinterher, Takt neu aufgesetzt");
+            next_tick = now + clock.tick_dt;
+        }
+    }
+}
+
+fn snapshot(server: &mut Server, cursor: (f32, f32), (w, h): (u32, u32)) -> SimSnapshot {
+    let game = server.game_mut();
+    let mut ui = UiBatch::new(w, h);
+    game.build_ui(&mut ui, cursor);
+    SimSnapshot {
+        ui,
+        screen_open: game.screen_open(),
+        console_open: game.console_open(),
+        level: game.level_info(),
+        target: game.target().map(|hit| hit.block),
+        memory: game.memory_report(),
+    }
+}
@@ -0,0 +1,45 @@
+//! Simulations-Thread: eigener Takt ohne Eingaben vom Render-Thread, Pause hält an.
+//! Tickstände werden per Auftrag abgefragt; Aufträge laufen in Reihenfolge mit der Pause,
+//! die Tests hängen also nicht davon ab, wie viele Takte in eine Wartezeit fallen.
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::game::Game;
+use crate::server;
+use crate::sim_thread::SimThread;
+
+const TICK: Duration = Duration::from_millis(2);
+/// Großzügig: auch ein überlasteter Testrechner schafft ein paar Ticks in dieser Zeit
+const PATIENCE: Duration = Duration::from_secs(10);
+
+/// Tickstand zwischen zwei Takten, nach allen vorher geschickten Aufträgen
+fn ticks(sim: &SimThread) -> u64 {
+    let (tx, rx) = mpsc::channel();
+    sim.run(move |server| {
+        let _ = tx.send(server.game().ticks());
+    });
+    rx.recv_timeout(PATIENCE).expect("Simulations-Thread antwortet")
+}
+
+#[test]
+fn ticks_on_its_own_clock_unless_paused() {
+    let (server, _client) = server::integrated(Game::new_deterministic(3), 2);
+    let sim = SimThread::spawn(server, TICK, false);
+    let start = ticks(&sim);
+    thread::sleep(TICK * 10);
+    assert_eq!(ticks(&sim), start, "startet pausiert");
+
+    sim.set_paused(false);
+    let deadline = Instant::now() + PATIENCE;
+    while ticks(&sim) < start + 5 {
+        assert!(Instant::now() < deadline, "ohne Eingaben getickt");
+        thread::sleep(TICK);
+    }
+    sim.set_paused(true);
+    let paused = ticks(&sim);
+    thread::sleep(TICK * 20);
+    assert_eq!(ticks(&sim), paused, "pausiert stehen geblieben");
+    assert_eq!(sim.stop().game().ticks(), paused);
+}
@@ -0,0 +1,129 @@
+//! Nachthimmel: Sterne und Mond als Geometrie auf einer Kugel um die Kamera. Der Himmel
+//! dreht sich mit der Tageszeit um die Z-Achse (Sonne geht bei +X auf), der Mond steht
+//! der Sonne gegenüber und wechselt mit jedem Tag die Phase. Gezeichnet wird additiv vor
+//! der Welt, so dass Sterne und Mond mit `night` einfach ausblenden.
+
+use std::f32::consts::TAU;
+
+use glam::Vec3;
+
+use crate::mesh::Vertex;
+use crate::rng::Rng;
+
+/// Bezugsabstand für die Größen unten; gezeichnet wird im Abstand `radius`, skaliert
+const SKY_RADIUS: f32 = 150.0;
+const STAR_COUNT: usize = 600;
+/// Halbe Kantenlänge eines Sterns (Blöcke in `SKY_RADIUS` Entfernung)
+const STAR_SIZE: (f32, f32) = (0.25, 0.6);
+const MOON_SIZE: f32 = 7.0;
+/// Zellen pro Kante der Mondscheibe; die Phasengrenze verläuft zwischen Zellen
+const MOON_CELLS: usize = 12;
+const MOON_LIT: [f32; 3] = [0.95, 0.93, 0.82];
+const MOON_DARK: [f32; 3] = [0.06, 0.06, 0.08];
+/// Tage für einen Phasenzyklus (Tag 0 = Vollmond)
+pub const MOON_PHASES: u64 = 8;
+
+/// Sichtbarkeit der Nacht 0..1 zur Tageszeit (0 = Mittag); Gegenstück zu `World::daylight`
+/// ohne Wetter, mit der gleichen kurzen Dämmerung
+pub fn night(time_of_day: f32) -> f32 {
+    let sun_height = (time_of_day * TAU).cos();
+    (0.5 - sun_height * 2.0).clamp(0.0, 1.0)
+}
+
+/// Richtung zur Sonne; der Mond steht gegenüber
+pub fn sun_dir(time_of_day: f32) -> Vec3 {
+    let angle = time_of_day * TAU;
+    Vec3::new(-angle.sin(), angle.cos(), 0.0)
+}
+
+/// Sterne und Mond um `camera` im Abstand `radius` (innerhalb der Fernebene); `night`
+/// (0..1) skaliert die Helligkeit
+pub fn sky_mesh(
+    (cx, cy, cz): (f32, f32, f32),
+    radius: f32,
+    time_of_day: f32,
+    day: u64,
+    night: f32,
+) -> (Vec<Vertex>, Vec<u32>) {
+    let mut verts = Vec::new();
+    let mut inds = Vec::new();
+    if night <= 0.0 {
+        return (verts, inds);
+    }
+    let camera = Vec3::new(cx, cy, cz);
+    let scale = radius / SKY_RADIUS;
+    let angle = time_of_day * TAU;
+    let (sin, cos) = angle.sin_cos();
+    // Himmelskugel mitdrehen (gleiche Drehung wie `sun_dir`)
+    let rotate = |d: Vec3| Vec3::new(d.x * cos - d.y * sin, d.x * sin + d.y * cos, d.z);
+
+    // Immer dieselben Sterne: fester Seed, gleichverteilt auf der Kugel
+    let mut rng = Rng::new(0x57A2_F1E1);
+    let mut unit = || rng.next_u32() as f32 / u32::MAX as f32;
+    for _ in 0..STAR_COUNT {
+        let (u, v, size, glow) = (unit(), unit(), unit(), unit());
+        let y = u * 2.0 - 1.0;
+        let r = (1.0 - y * y).sqrt();
+        let dir = rotate(Vec3::new(r * (v * TAU).cos(), y, r * (v * TAU).sin()));
+        // Unter dem Horizont verdeckt ohnehin das Gelände
+        if dir.y < -0.1 {
+            continue;
+        }
+        let half = (STAR_SIZE.0 + (STAR_SIZE.1 - STAR_SIZE.0) * size) * scale;
+        let b = night * (0.4 + 0.6 * glow);
+        push_quad(&mut verts, &mut inds, camera + dir * radius, dir, half, [b, b, b * 1.05]);
+    }
+
+    // Mond gegenüber der Sonne; Scheibe aus Zellen, beleuchteter Teil nach Phase
+    let dir = -sun_dir(time_of_day);
+    let (right, up) = tangents(dir);
+    let phase = (day % MOON_PHASES) as f32 / MOON_PHASES as f32;
+    let edge = (phase * TAU).cos();
+    let center = camera + dir * radius;
+    let moon = MOON_SIZE * scale;
+    let cell = 2.0 / MOON_CELLS as f32;
+    for i in 0..MOON_CELLS {
+        for j in 0..MOON_CELLS {
+            let (x, y) = ((i as f32 + 0.5) * cell - 1.0, (j as f32 + 0.5) * cell - 1.0);
+            if x * x + y * y > 1.0 {
+                continue;
+            }
+            // abnehmend: Schatten wächst von rechts, zunehmend: Licht kommt von rechts zurück
+            let lit = if phase <= 0.5 { x < edge } else { x > -edge };
+            let color = if lit { MOON_LIT } else { MOON_DARK }.map(|c| c * night);
+            let at = center + (right * x + up * y) * moon;
+            push_quad(&mut verts, &mut inds, at, dir, cell * 0.5 * moon, color);
+        }
+    }
+    (verts, inds)
+}
+
+/// Zwei Achsen senkrecht zu `dir` (für Quads, die zur Kamera zeigen)
+fn tangents(dir: Vec3) -> (Vec3, Vec3) {
+    let helper = if dir.z.abs() < 0.9 { Vec3::Z } else { Vec3::X };
+    let right = dir.cross(helper).normalize();
+    (right, right.cross(dir))
+}
+
+/// Quadrat mit halber Kantenlänge `half` um `at`, senkrecht zu `dir`
+fn push_quad(
+    verts: &mut Vec<Vertex>,
+    inds: &mut Vec<u32>,
+    at: Vec3,
+    dir: Vec3,
+    half: f32,
+    color: [f32; 3],
+) {
+    let (right, up) = tangents(dir);
+    let base = verts.len() as u32;
+    for (sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+        let p = at + (right * sx + up * sy) * half;
+        verts.push(Vertex {
+            pos: p.to_array(),
+            color,
+            light: [0.0, 0.0],
+            emissive: 1.0,
+        });
+    }
+    inds.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+}
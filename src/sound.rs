@@ -0,0 +1,110 @@
+use crate::block::SoundGroup;
+use crate::rng::Rng;
+
+/// Abtastrate der erzeugten Klänge (Mono)
+pub const SAMPLE_RATE: u32 = 22_050;
+/// Weiter entfernt (Blöcke) ist nichts mehr zu hören
+pub const HEARING_DISTANCE: f32 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundKind {
+    Break,
+    Place,
+    Step,
+    Jump,
+    Land,
+}
+
+impl SoundKind {
+    /// Länge in Sekunden und Spitzenpegel des Klangs
+    fn shape(self) -> (f32, f32) {
+        match self {
+            SoundKind::Break => (0.25, 0.9),
+            SoundKind::Place => (0.12, 0.8),
+            SoundKind::Step => (0.08, 0.35),
+            SoundKind::Jump => (0.10, 0.3),
+            SoundKind::Land => (0.15, 0.6),
+        }
+    }
+}
+
+/// Vom Spiel erzeugtes Geräusch-Ereignis; das Audio-System entscheidet anhand der Gruppe,
+/// welcher Sound gespielt wird (keine Block-spezifischen Matches nötig).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoundEvent {
+    pub kind: SoundKind,
+    pub group: SoundGroup,
+    pub pos: (f32, f32, f32),
+    /// Abspielgeschwindigkeit (1.0 = Originalhöhe)
+    pub pitch: f32,
+}
+
+impl SoundEvent {
+    /// Ereignis an einer Blockposition (Blockmitte)
+    pub fn at_block(kind: SoundKind, group: SoundGroup, x: i32, y: i32, z: i32) -> Self {
+        Self {
+            kind,
+            group,
+            pos: (x as f32 + 0.5, y as f32 + 0.5, z as f32 + 0.5),
+            pitch: 1.0,
+        }
+    }
+
+    pub fn with_pitch(self, pitch: f32) -> Self {
+        Self { pitch, ..self }
+    }
+
+    /// Pegel beim Zuhörer: nimmt linear mit der Entfernung ab, `volume` ist die
+    /// Gesamtlautstärke aus den Einstellungen
+    pub fn gain(&self, listener: (f32, f32, f32), volume: f32) -> f32 {
+        let (dx, dy, dz) = (
+            self.pos.0 - listener.0,
+            self.pos.1 - listener.1,
+            self.pos.2 - listener.2,
+        );
+        let dist = (dx * dx + dy * dy + dz * dz).sqrt();
+        volume * (1.0 - dist / HEARING_DISTANCE).max(0.0)
+    }
+}
+
+/// Klangfarbe einer Gruppe: Tiefpass über Rauschen (0..1, klein = dumpf) und ein
+/// beigemischter Ton (Frequenz, Anteil)
+fn timbre(group: SoundGroup) -> (f32, f32, f32) {
+    match group {
+        SoundGroup::None => (0.3, 0.0, 0.0),
+        SoundGroup::Stone => (0.6, 90.0, 0.2),
+        SoundGroup::Wood => (0.25, 180.0, 0.5),
+        SoundGroup::Grass => (0.15, 0.0, 0.0),
+        SoundGroup::Gravel => (0.45, 0.0, 0.0),
+        SoundGroup::Glass => (0.9, 2200.0, 0.6),
+        SoundGroup::Water => (0.08, 400.0, 0.3),
+    }
+}
+
+/// Kurzer synthetischer Klang (Sounddateien gibt es noch nicht): gefiltertes Rauschen
+/// mit Ton, schneller Anstieg und exponentielles Abklingen. Deterministisch je Art und
+/// Gruppe, das Audio-System kann das Ergebnis zwischenspeichern.
+pub fn synthesize(kind: SoundKind, group: SoundGroup) -> Vec<f32> {
+    let (duration, peak) = kind.shape();
+    let (smooth, freq, tone) = timbre(group);
+    let mut rng = Rng::new((kind as u64) << 8 | group as u64);
+    let len = (duration * SAMPLE_RATE as f32) as usize;
+    let attack = SAMPLE_RATE as f32 * 0.005;
+    let mut noise = 0.0;
+    let mut samples: Vec<f32> = (0..len)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let white = rng.next_u32() as f32 / u32::MAX as f32 * 2.0 - 1.0;
+            noise += (white - noise) * smooth;
+            let sine = (t * freq * std::f32::consts::TAU).sin();
+            let env = (i as f32 / attack).min(1.0) * (-5.0 * t / duration).exp();
+            (noise * (1.0 - tone) + sine * tone) * env
+        })
+        .collect();
+    // Tiefpass macht dumpfe Gruppen leiser: auf den Spitzenpegel der Art normieren
+    let max = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    if max > 0.0 {
+        samples.iter_mut().for_each(|s| *s *= peak / max);
+    }
+    samples
+}
@@ -0,0 +1,218 @@
+//! Klangkulisse: Ambient-Schleifen je nach Umgebung (Oberfläche bei Tag oder Nacht, unter
+//! Tage) mit Überblendung, dazu eine Musik-Wiedergabeliste aus `assets/music`. Hier steckt
+//! nur die Logik und die Klangerzeugung; abgespielt wird im Frontend (Feature `audio`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::assets::ASSET_DIR;
+use crate::rng::Rng;
+use crate::sound::SAMPLE_RATE;
+
+/// Unterverzeichnis von `assets/` mit den Musikstücken
+pub const MUSIC_DIR: &str = "music";
+/// Abspielbare Formate (rodio mit `wav` und `vorbis`)
+const MUSIC_EXTENSIONS: [&str; 3] = ["wav", "ogg", "oga"];
+/// Ab so wenig Himmelslicht am Spieler gilt er als unter Tage
+const CAVE_SKY_LIGHT: u8 = 4;
+/// Tageslicht, ab dem die Oberfläche nach Tag klingt
+const DAY_THRESHOLD: f32 = 0.35;
+/// Dauer einer vollen Überblendung
+const CROSSFADE_SECS: f32 = 3.0;
+/// Länge einer Ambient-Schleife
+const LOOP_SECS: f32 = 8.0;
+/// Überblendung am Schleifenende, damit die Naht nicht knackt
+const LOOP_FADE_SECS: f32 = 0.5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ambience {
+    Day,
+    Night,
+    Cave,
+}
+
+impl Ambience {
+    pub const ALL: [Ambience; 3] = [Ambience::Day, Ambience::Night, Ambience::Cave];
+
+    /// Umgebung am Spieler: wenig Himmelslicht heißt unter Tage, sonst nach Tageszeit
+    pub fn at(daylight: f32, sky_light: u8) -> Self {
+        if sky_light <= CAVE_SKY_LIGHT {
+            Ambience::Cave
+        } else if daylight >= DAY_THRESHOLD {
+            Ambience::Day
+        } else {
+            Ambience::Night
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Nahtlos wiederholbare Schleife (Mono, `SAMPLE_RATE`): Tag = Wind, Nacht = leiser
+    /// Wind mit Grillen, Höhle = tiefes Brummen mit Tropfen
+    pub fn synthesize(self) -> Vec<f32> {
+        let len = (LOOP_SECS * SAMPLE_RATE as f32) as usize;
+        let fade = (LOOP_FADE_SECS * SAMPLE_RATE as f32) as usize;
+        let mut rng = Rng::new(0xA3B1 + self as u64);
+        let mut wind = 0.0;
+        // Zeitpunkte der Tropfen in der Höhle
+        let drips: Vec<usize> = (0..5).map(|_| rng.below(len as u32) as usize).collect();
+        let samples: Vec<f32> = (0..len + fade)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                let white = rng.next_u32() as f32 / u32::MAX as f32 * 2.0 - 1.0;
+                wind += (white - wind) * 0.02;
+                // Böen: langsame Schwankung mit ganzzahligen Perioden pro Schleife
+                let gust = 0.6 + 0.4 * (t / LOOP_SECS * 2.0 * std::f32::consts::TAU).sin();
+                match self {
+                    Ambience::Day => wind * gust * 4.0,
+                    Ambience::Night => {
+                        // Grillen: kurze Zirp-Folgen zweimal pro Sekunde
+                        let phase = (t * 2.0).fract();
+                        let chirp = if phase < 0.15 && (t * 30.0).fract() < 0.5 {
+                            (t * 4200.0 * std::f32::consts::TAU).sin() * 0.15
+                        } else {
+                            0.0
+                        };
+                        wind * gust * 2.0 + chirp
+                    }
+                    Ambience::Cave => {
+                        let drone = (t * 55.0 * std::f32::consts::TAU).sin() * 0.2;
+                        let drip: f32 = drips
+                            .iter()
+                            .filter(|&&d| i >= d)
+                            .map(|&d| {
+                                let dt = (i - d) as f32 / SAMPLE_RATE as f32;
+                                (dt * 1800.0 * std::f32::consts::TAU).sin() * (-dt * 40.0).exp()
+                            })
+                            .sum();
+                        drone + wind + drip * 0.3
+                    }
+                }
+            })
+            .collect();
+        seamless(samples, fade)
+    }
+}
+
+/// Das Ende (`fade` Samples über `len`) in den Anfang einblenden, dann abschneiden
+fn seamless(mut samples: Vec<f32>, fade: usize) -> Vec<f32> {
+    let len = samples.len() - fade;
+    for i in 0..fade {
+        let w = i as f32 / fade as f32;
+        samples[i] = samples[i] * w + samples[len + i] * (1.0 - w);
+    }
+    samples.truncate(len);
+    samples
+}
+
+/// Pegel der Ambient-Schleifen (je 0..1); bewegt sich über `CROSSFADE_SECS` zur Umgebung
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Crossfade {
+    levels: [f32; Ambience::ALL.len()],
+}
+
+impl Crossfade {
+    pub fn update(&mut self, target: Ambience, dt: f32) {
+        let step = dt / CROSSFADE_SECS;
+        for a in Ambience::ALL {
+            let level = &mut self.levels[a.index()];
+            *level = if a == target {
+                (*level + step).min(1.0)
+            } else {
+                (*level - step).max(0.0)
+            };
+        }
+    }
+
+    pub fn level(&self, a: Ambience) -> f32 {
+        self.levels[a.index()]
+    }
+}
+
+/// Musikstücke in Namensreihenfolge oder gemischt; gemischt wird nach jedem Durchlauf neu
+pub struct Playlist {
+    tracks: Vec<PathBuf>,
+    order: Vec<usize>,
+    next: usize,
+    shuffle: bool,
+    rng: Rng,
+}
+
+impl Playlist {
+    pub fn new(mut tracks: Vec<PathBuf>, shuffle: bool, seed: u64) -> Self {
+        tracks.sort();
+        let mut list = Self {
+            order: (0..tracks.len()).collect(),
+            tracks,
+            next: 0,
+            shuffle,
+            rng: Rng::new(seed),
+        };
+        list.reorder();
+        list
+    }
+
+    /// Abspielbare Dateien aus `assets/music` (fehlendes Verzeichnis = keine Musik)
+    pub fn scan(shuffle: bool, seed: u64) -> Self {
+        let dir = Path::new(ASSET_DIR).join(MUSIC_DIR);
+        let tracks = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| {
+                let ext = p.extension().and_then(|e| e.to_str()).unwrap_or_default();
+                MUSIC_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+            })
+            .collect::<Vec<_>>();
+        log::info!("{} Musikstücke in {}", tracks.len(), dir.display());
+        Self::new(tracks, shuffle, seed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Umschalten wirkt ab dem nächsten Durchlauf
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+    }
+
+    /// Nicht abspielbares Stück aus der Liste nehmen
+    pub fn remove(&mut self, path: &Path) {
+        self.tracks.retain(|t| t != path);
+        self.order = (0..self.tracks.len()).collect();
+        self.next = self.order.len();
+    }
+
+    /// Nächstes Stück (None ohne Musik)
+    pub fn next_track(&mut self) -> Option<&Path> {
+        if self.tracks.is_empty() {
+            return None;
+        }
+        if self.next == self.order.len() {
+            self.next = 0;
+            self.reorder();
+        }
+        let track = self.order[self.next];
+        self.next += 1;
+        Some(&self.tracks[track])
+    }
+
+    /// Fisher-Yates gemischt oder sortiert
+    fn reorder(&mut self) {
+        self.order.sort_unstable();
+        if self.shuffle {
+            for i in (1..self.order.len()).rev() {
+                let j = self.rng.below(i as u32 + 1) as usize;
+                self.order.swap(i, j);
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Statistik einer Welt: abgebaute und platzierte Blöcke je Typ, gelaufene Strecke,
+//! Sprünge und Tode. Gezählt wird über den Event-Bus, gespeichert als `stats.toml` im
+//! Weltordner. Die Spielzeit steht schon in der Weltinfo.
+
+use anyhow::{bail, Context, Result};
+
+use crate::block::Block;
+use crate::event::{
+    BlockBroken, BlockPlaced, EventBus, PlayerDied, PlayerJumped, PlayerWalked, Subscription,
+};
+
+/// Dateiname im Weltordner (TOML-kompatibles `key = value`)
+pub const STATS_FILE: &str = "stats.toml";
+/// So viele Blocktypen je Liste zeigt /stats
+const SUMMARY_BLOCKS: usize = 5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// Je Block (Index = `Block as usize`)
+    pub broken: Vec<u64>,
+    pub placed: Vec<u64>,
+    /// Am Boden gelaufene Blöcke
+    pub distance_walked: f64,
+    pub jumps: u64,
+    pub deaths: u64,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            broken: vec![0; Block::COUNT],
+            placed: vec![0; Block::COUNT],
+            distance_walked: 0.0,
+            jumps: 0,
+            deaths: 0,
+        }
+    }
+}
+
+impl Stats {
+    /// Fehlende oder kaputte Zeilen zählen als 0
+    pub fn parse(text: &str) -> Self {
+        let mut stats = Self::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Err(e) = stats.apply_line(line) {
+                log::warn!("{STATS_FILE} Zeile {}: {e}", n + 1);
+            }
+        }
+        stats
+    }
+
+    fn apply_line(&mut self, line: &str) -> Result<()> {
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("kein '=' in '{line}'"))?;
+        let (key, value) = (key.trim(), value.trim());
+        let count = || value.parse::<u64>().with_context(|| format!("keine Zahl: '{value}'"));
+        match key {
+            "distance_walked" => {
+                self.distance_walked =
+                    value.parse().with_context(|| format!("keine Zahl: '{value}'"))?
+            }
+            "jumps" => self.jumps = count()?,
+            "deaths" => self.deaths = count()?,
+            _ => {
+                let (list, name) = key
+                    .split_once('.')
+                    .with_context(|| format!("unbekannter Schlüssel '{key}'"))?;
+                let block = Block::by_name(name).with_context(|| format!("kein Block '{name}'"))?;
+                match list {
+                    "broken" => self.broken[block as usize] = count()?,
+                    "placed" => self.placed[block as usize] = count()?,
+                    _ => bail!("unbekannter Schlüssel '{key}'"),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# Statistik\n");
+        text += &format!("distance_walked = {:.1}\n", self.distance_walked);
+        text += &format!("jumps = {}\n", self.jumps);
+        text += &format!("deaths = {}\n", self.deaths);
+        for (list, counts) in [("broken", &self.broken), ("placed", &self.placed)] {
+            for (block, n) in Block::ALL.iter().zip(counts).filter(|(_, n)| **n > 0) {
+                text += &format!("{list}.{} = {n}\n", block.name());
+            }
+        }
+        text
+    }
+
+    /// Zeilen für /stats; `played` ist die schon formatierte Spielzeit
+    pub fn summary(&self, played: &str) -> Vec<String> {
+        vec![
+            format!(
+                "Played {played}  Walked {:.0}m  Jumps {}  Deaths {}",
+                self.distance_walked, self.jumps, self.deaths
+            ),
+            format!("Broken: {}", top_blocks(&self.broken)),
+            format!("Placed: {}", top_blocks(&self.placed)),
+        ]
+    }
+}
+
+/// Häufigste Blöcke mit Anzahl, dazu die Gesamtsumme
+fn top_blocks(counts: &[u64]) -> String {
+    let total: u64 = counts.iter().sum();
+    if total == 0 {
+        return "nothing yet".to_string();
+    }
+    let mut top: Vec<(Block, u64)> =
+        Block::ALL.iter().copied().zip(counts.iter().copied()).filter(|(_, n)| *n > 0).collect();
+    top.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    let mut parts: Vec<String> = top
+        .iter()
+        .take(SUMMARY_BLOCKS)
+        .map(|(b, n)| format!("{} {n}", b.name()))
+        .collect();
+    if top.len() > SUMMARY_BLOCKS {
+        parts.push("...".to_string());
+    }
+    format!("{total} ({})", parts.join(", "))
+}
+
+/// Abos auf die Ereignisse, die in die Statistik eingehen
+pub struct StatsRecorder {
+    broken: Subscription<BlockBroken>,
+    placed: Subscription<BlockPlaced>,
+    walked: Subscription<PlayerWalked>,
+    jumped: Subscription<PlayerJumped>,
+    died: Subscription<PlayerDied>,
+}
+
+impl StatsRecorder {
+    pub fn new(events: &EventBus) -> Self {
+        Self {
+            broken: events.subscribe(),
+            placed: events.subscribe(),
+            walked: events.subscribe(),
+            jumped: events.subscribe(),
+            died: events.subscribe(),
+        }
+    }
+
+    /// Alles seit dem letzten Aufruf einrechnen (mindestens einmal pro Tick)
+    pub fn record(&mut self, events: &EventBus, stats: &mut Stats) {
+        for ev in events.read(&mut self.broken) {
+            stats.broken[ev.block as usize] += 1;
+        }
+        for ev in events.read(&mut self.placed) {
+            stats.placed[ev.block as usize] += 1;
+        }
+        for ev in events.read(&mut self.walked) {
+            stats.distance_walked += ev.distance as f64;
+        }
+        stats.jumps += events.read(&mut self.jumped).count() as u64;
+        stats.deaths += events.read(&mut self.died).count() as u64;
+    }
+}
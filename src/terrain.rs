@@ -0,0 +1,81 @@
+//! Gelände für neue Welten: sanfte Hügel aus Perlin-Noise, oben Gras, darunter einige
+//! Blöcke Erde, dann Stein. Jeder Chunk entsteht allein aus Seed und Position, so dass
+//! unveränderte Chunks nicht gespeichert werden müssen.
+
+use crate::block::Block;
+use crate::chunk::{Chunk, CHUNK_SIZE};
+use crate::level::{GENERATOR_FLAT, GENERATOR_NOISE};
+use crate::noise::fbm_2d;
+
+/// Höhe, über die sich das Gelände verteilt, in Blöcken und unabhängig von `CHUNK_SIZE`.
+/// Die Oberfläche liegt zwischen `BASE_HEIGHT - HILL_HEIGHT` und `BASE_HEIGHT + HILL_HEIGHT`
+/// und darf über Chunkgrenzen reichen; geladen werden die Schichten um den Spieler.
+const TERRAIN_HEIGHT: i32 = 32;
+const BASE_HEIGHT: i32 = TERRAIN_HEIGHT / 2;
+const HILL_HEIGHT: f32 = TERRAIN_HEIGHT as f32 * 0.375;
+/// Gitterabstand der größten Hügel in Blöcken
+const HILL_SCALE: f32 = 64.0;
+const HILL_OCTAVES: u32 = 3;
+/// Erde unter dem Gras, bevor Stein beginnt
+const DIRT_DEPTH: i32 = 3;
+/// Eigene Noise, unabhängig von der Temperatur der Biome
+const TERRAIN_SALT: u64 = 0x7E22_A1A5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Terrain {
+    seed: u64,
+}
+
+impl Terrain {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Generator zum Namen aus level.toml; `flat` (und Unbekanntes) erzeugt nichts
+    pub fn by_name(name: &str, seed: u64) -> Option<Self> {
+        match name {
+            GENERATOR_NOISE => Some(Self::new(seed)),
+            GENERATOR_FLAT => None,
+            other => {
+                log::warn!("Unbekannter Generator '{other}', Welt bleibt flach");
+                None
+            }
+        }
+    }
+
+    /// y des obersten festen Blocks (Gras) in dieser Spalte
+    pub fn surface(&self, x: i32, z: i32) -> i32 {
+        let (x, z) = (x as f32 + 0.5, z as f32 + 0.5);
+        let hills = fbm_2d(self.seed ^ TERRAIN_SALT, x, z, HILL_SCALE, HILL_OCTAVES);
+        // fbm bleibt in [-1, 1], die Oberfläche also im Bereich über y = 0
+        BASE_HEIGHT + (hills * HILL_HEIGHT).round() as i32
+    }
+
+    /// Block an einer Stelle mit bekannter Oberfläche der Spalte
+    fn block(surface: i32, y: i32) -> Block {
+        if y > surface {
+            Block::Air
+        } else if y == surface {
+            Block::Grass
+        } else if y >= surface - DIRT_DEPTH {
+            Block::Dirt
+        } else {
+            Block::Stone
+        }
+    }
+
+    /// Frischen (leeren) Chunk füllen
+    pub fn fill(&self, ch: &mut Chunk<Block>) {
+        let (x0, y0, z0) = (ch.pos.cx * CHUNK_SIZE, ch.pos.cy * CHUNK_SIZE, ch.pos.cz * CHUNK_SIZE);
+        for lz in 0..CHUNK_SIZE {
+            for lx in 0..CHUNK_SIZE {
+                let surface = self.surface(x0 + lx, z0 + lz);
+                // Über der Oberfläche bleibt die Luft aus `Chunk::new`
+                let top = (surface - y0).min(CHUNK_SIZE - 1);
+                for ly in 0..=top {
+                    ch.set_local(lx, ly, lz, Self::block(surface, y0 + ly));
+                }
+            }
+        }
+    }
+}
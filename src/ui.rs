@@ -0,0 +1,150 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::font::{glyph, GLYPH_H, GLYPH_W};
+
+/// 2D-Vertex für das UI-Overlay (Position bereits in NDC)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct UiVertex {
+    pub pos: [f32; 2],
+    pub color: [f32; 4],
+}
+
+impl UiVertex {
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        use std::mem;
+        wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<UiVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+            ],
+        }
+    }
+}
+
+/// Rechteck in Pixelkoordinaten (Ursprung oben links)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Rect {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    pub fn contains(&self, px: f32, py: f32) -> bool {
+        px >= self.x && py >= self.y && px < self.x + self.w && py < self.y + self.h
+    }
+
+    /// Nach innen verkleinert
+    pub fn inset(&self, d: f32) -> Rect {
+        Rect::new(self.x + d, self.y + d, self.w - 2.0 * d, self.h - 2.0 * d)
+    }
+}
+
+/// Sammelt UI-Quads eines Frames; Gfx lädt das Ergebnis in eigene Buffer.
+pub struct UiBatch {
+    width: f32,
+    height: f32,
+    pub verts: Vec<UiVertex>,
+    pub inds: Vec<u32>,
+}
+
+impl UiBatch {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width: width.max(1) as f32,
+            height: height.max(1) as f32,
+            verts: Vec::new(),
+            inds: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inds.is_empty()
+    }
+
+    /// `other` darüber zeichnen (für dieselbe Fenstergröße gebaut)
+    pub fn append(&mut self, other: &UiBatch) {
+        let base = self.verts.len() as u32;
+        self.verts.extend_from_slice(&other.verts);
+        self.inds.extend(other.inds.iter().map(|i| base + i));
+    }
+
+    pub fn rect(&mut self, r: Rect, color: [f32; 4]) {
+        // Pixel -> NDC (y nach oben)
+        let x0 = r.x / self.width * 2.0 - 1.0;
+        let x1 = (r.x + r.w) / self.width * 2.0 - 1.0;
+        let y0 = 1.0 - r.y / self.height * 2.0;
+        let y1 = 1.0 - (r.y + r.h) / self.height * 2.0;
+
+        let base = self.verts.len() as u32;
+        for pos in [[x0, y0], [x0, y1], [x1, y1], [x1, y0]] {
+            self.verts.push(UiVertex { pos, color });
+        }
+        // gegen den Uhrzeigersinn in NDC (kein Culling im UI, aber konsistent)
+        self.inds
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    pub fn outline(&mut self, r: Rect, t: f32, color: [f32; 4]) {
+        self.rect(Rect::new(r.x, r.y, r.w, t), color);
+        self.rect(Rect::new(r.x, r.y + r.h - t, r.w, t), color);
+        self.rect(Rect::new(r.x, r.y, t, r.h), color);
+        self.rect(Rect::new(r.x + r.w - t, r.y, t, r.h), color);
+    }
+
+    /// Text mit dem 5x7-Font; `scale` = Pixel pro Fontpixel
+    pub fn text(&mut self, x: f32, y: f32, scale: f32, s: &str, color: [f32; 4]) {
+        let mut cx = x;
+        for c in s.chars() {
+            let rows = glyph(c);
+            for (row, bits) in rows.iter().enumerate() {
+                for col in 0..GLYPH_W {
+                    if bits & (1 << (GLYPH_W - 1 - col)) != 0 {
+                        self.rect(
+                            Rect::new(
+                                cx + col as f32 * scale,
+                                y + row as f32 * scale,
+                                scale,
+                                scale,
+                            ),
+                            color,
+                        );
+                    }
+                }
+            }
+            cx += (GLYPH_W + 1) as f32 * scale;
+        }
+    }
+
+    pub fn text_width(s: &str, scale: f32) -> f32 {
+        s.chars().count() as f32 * (GLYPH_W + 1) as f32 * scale
+    }
+
+    pub fn text_height(scale: f32) -> f32 {
+        GLYPH_H as f32 * scale
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{bail, Result};
+
+use crate::block::Block;
+use crate::block_buffer::BlockBuffer;
+use crate::serial::ByteWriter;
+
+/// MagicaVoxel erlaubt höchstens 256 Voxel pro Achse und Modell
+pub const MAX_VOX_SIZE: i32 = 256;
+const VOX_VERSION: u32 = 150;
+
+/// Blockquader als MagicaVoxel-Datei (ein Modell, Palette aus den Blockfarben).
+/// .vox ist z-up: y wird zu z, z wird (gespiegelt, damit rechtshändig) zu y.
+pub fn encode(buf: &BlockBuffer) -> Result<Vec<u8>> {
+    check_size(buf.size)?;
+    let (w, h, d) = buf.size;
+
+    let mut palette: Vec<Block> = Vec::new();
+    let mut voxels = ByteWriter::new();
+    let mut count = 0u32;
+    for x in 0..w {
+        for y in 0..h {
+            for z in 0..d {
+                let (b, _) = buf.get((x, y, z));
+                if b == Block::Air {
+                    continue;
+                }
+                let i = match palette.iter().position(|p| *p == b) {
+                    Some(i) => i,
+                    None => {
+                        palette.push(b);
+                        palette.len() - 1
+                    }
+                };
+                // Farbindex 0 ist "leer"; Palette beginnt bei 1
+                voxels.bytes(&[x as u8, (d - 1 - z) as u8, y as u8, i as u8 + 1]);
+                count += 1;
+            }
+        }
+    }
+    if palette.len() > 255 {
+        bail!("too many block types for a .vox palette ({})", palette.len());
+    }
+
+    let mut size = ByteWriter::new();
+    for v in [w, d, h] {
+        size.i32(v);
+    }
+    let mut xyzi = ByteWriter::new();
+    xyzi.u32(count);
+    xyzi.bytes(&voxels.into_bytes());
+    let mut rgba = ByteWriter::new();
+    for i in 0..256 {
+        match palette.get(i) {
+            Some(b) => {
+                let [r, g, b] = b.def().color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                rgba.bytes(&[r, g, b, 255]);
+            }
+            None => rgba.bytes(&[0, 0, 0, 0]),
+        }
+    }
+
+    let mut children = ByteWriter::new();
+    chunk(&mut children, b"SIZE", &size.into_bytes());
+    chunk(&mut children, b"XYZI", &xyzi.into_bytes());
+    chunk(&mut children, b"RGBA", &rgba.into_bytes());
+    let children = children.into_bytes();
+
+    let mut w = ByteWriter::new();
+    w.bytes(b"VOX ");
+    w.u32(VOX_VERSION);
+    w.bytes(b"MAIN");
+    w.u32(0);
+    w.u32(children.len() as u32);
+    w.bytes(&children);
+    Ok(w.into_bytes())
+}
+
+pub fn check_size((w, h, d): (i32, i32, i32)) -> Result<()> {
+    if w > MAX_VOX_SIZE || h > MAX_VOX_SIZE || d > MAX_VOX_SIZE {
+        bail!("{w}x{h}x{d} is too large for .vox (max {MAX_VOX_SIZE} per axis)");
+    }
+    Ok(())
+}
+
+/// RIFF-artiger Chunk ohne Kinder
+fn chunk(w: &mut ByteWriter, id: &[u8; 4], content: &[u8]) {
+    w.bytes(id);
+    w.u32(content.len() as u32);
+    w.u32(0);
+    w.bytes(content);
+}
@@ -1,24 +1,23 @@
-use crate::block::Block;
+use crate::biome::{tint_at, Tint};
+use crate::block::{face_visible, state, Block, Opacity, Shape};
 use crate::chunk::{ChunkPos, CHUNK_SIZE};
 use crate::mesh::Vertex;
-use crate::world::World;
+use crate::world::{BlockPos, World, MAX_LIGHT};
 
-fn block_color(b: Block) -> [f32; 3] {
-    match b {
-        Block::Air => [0.0, 0.0, 0.0], // wird nicht gerendert
-        Block::Dirt => [0.55, 0.40, 0.20],
-        Block::Stone => [0.60, 0.60, 0.60],
-    }
-}
-
-#[inline]
-fn is_air(b: Block) -> bool {
-    b == Block::Air
-}
+/// Reihenfolge der Faces: +X, -X, +Y, -Y, +Z, -Z
+const FACE_DIRS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
 
-/// Baut das Mesh f�r genau einen Chunk (ohne Greedy-Meshing).
-/// Faces werden erzeugt, wenn der Nachbar Air ist (chunk�bergreifend via World).
-pub fn mesh_chunk(world: &World, cp: ChunkPos) -> (Vec<Vertex>, Vec<u32>) {
+/// Baut das Mesh für genau einen Chunk (ohne Greedy-Meshing).
+/// Faces werden nach den Opacity-Regeln der Registry erzeugt (chunkübergreifend via World).
+/// `light_debug`: Faces zeigen statt Blockfarben die Lichtwerte als Heatmap.
+pub fn mesh_chunk(world: &World, cp: ChunkPos, light_debug: bool) -> (Vec<Vertex>, Vec<u32>) {
     let mut verts: Vec<Vertex> = Vec::new();
     let mut inds: Vec<u32> = Vec::new();
 
@@ -27,6 +26,9 @@ pub fn mesh_chunk(world: &World, cp: ChunkPos) -> (Vec<Vertex>, Vec<u32>) {
     let oy = cp.cy * CHUNK_SIZE;
     let oz = cp.cz * CHUNK_SIZE;
 
+    // Wiederverwendeter Puffer für die Boxen eines Blocks
+    let mut boxes: Vec<BlockBox> = Vec::with_capacity(2);
+
     for ly in 0..CHUNK_SIZE {
         for lz in 0..CHUNK_SIZE {
             for lx in 0..CHUNK_SIZE {
@@ -35,66 +37,78 @@ pub fn mesh_chunk(world: &World, cp: ChunkPos) -> (Vec<Vertex>, Vec<u32>) {
                 let z = oz + lz;
 
                 let b = world.get_block(x, y, z);
-                if is_air(b) {
+                if b.opacity() == Opacity::Invisible {
                     continue;
                 }
+                let st = world.get_state(x, y, z);
 
-                let col = block_color(b);
-
-                // F�r jede Seite: wenn Nachbar Air -> Face hinzuf�gen
-                // +X
-                if is_air(world.get_block(x + 1, y, z)) {
-                    push_face(&mut verts, &mut inds, col,
-                        [x as f32 + 1.0, y as f32, z as f32],
-                        [x as f32 + 1.0, y as f32 + 1.0, z as f32],
-                        [x as f32 + 1.0, y as f32 + 1.0, z as f32 + 1.0],
-                        [x as f32 + 1.0, y as f32, z as f32 + 1.0],
-                    );
-                }
-                // -X
-                if is_air(world.get_block(x - 1, y, z)) {
-                    push_face(&mut verts, &mut inds, col,
-                        [x as f32, y as f32, z as f32 + 1.0],
-                        [x as f32, y as f32 + 1.0, z as f32 + 1.0],
-                        [x as f32, y as f32 + 1.0, z as f32],
-                        [x as f32, y as f32, z as f32],
-                    );
+                // Boxen des Blocks (lokal 0..1) – abhängig von Shape + State
+                boxes.clear();
+                shape_boxes(b.shape(), st, &mut boxes);
+
+                // Nachbarn nur einmal pro Block abfragen; Licht kommt aus der Zelle vor der Face
+                let own_light = light_at(world, x, y, z);
+                let mut neighbor_hides = [false; 6];
+                let mut neighbor_light = [[0.0; 2]; 6];
+                for (f, (dx, dy, dz)) in FACE_DIRS.iter().enumerate() {
+                    neighbor_hides[f] = !face_visible(b, world.get_block(x + dx, y + dy, z + dz));
+                    neighbor_light[f] = light_at(world, x + dx, y + dy, z + dz);
                 }
-                // +Y (top)
-                if is_air(world.get_block(x, y + 1, z)) {
-                    push_face(&mut verts, &mut inds, col,
-                        [x as f32, y as f32 + 1.0, z as f32],
-                        [x as f32, y as f32 + 1.0, z as f32 + 1.0],
-                        [x as f32 + 1.0, y as f32 + 1.0, z as f32 + 1.0],
-                        [x as f32 + 1.0, y as f32 + 1.0, z as f32],
-                    );
+
+                let base = [x as f32, y as f32, z as f32];
+                let first_vert = verts.len();
+                for &(min, max) in &boxes {
+                    // Für jede Seite: wenn Nachbar die Face nicht verdeckt -> Face hinzufügen.
+                    // Faces, die nicht auf der Blockgrenze liegen (Slab-Innenseite), sind immer sichtbar.
+                    let mut visible = [false; 6];
+                    let mut light = [[0.0; 2]; 6];
+                    for (f, v) in visible.iter_mut().enumerate() {
+                        let on_border = match f {
+                            0 => max[0] >= 1.0,
+                            1 => min[0] <= 0.0,
+                            2 => max[1] >= 1.0,
+                            3 => min[1] <= 0.0,
+                            4 => max[2] >= 1.0,
+                            _ => min[2] <= 0.0,
+                        };
+                        *v = !on_border || !neighbor_hides[f];
+                        light[f] = if on_border { neighbor_light[f] } else { own_light };
+                    }
+
+                    // Volle Würfel: weiches Licht + AO pro Ecke; Sonderformen flach pro Face
+                    let smooth = b.shape() == Shape::Cube;
+                    let shade = |f: usize, corner: [f32; 3]| {
+                        if smooth {
+                            corner_shade(world, (x, y, z), f, corner)
+                        } else {
+                            (light[f], 1.0)
+                        }
+                    };
+                    let color = |f| face_color(b, st, f);
+                    push_box(&mut verts, &mut inds, base, (min, max), visible, shade, color);
                 }
-                // -Y (bottom)
-                if is_air(world.get_block(x, y - 1, z)) {
-                    push_face(&mut verts, &mut inds, col,
-                        [x as f32 + 1.0, y as f32, z as f32],
-                        [x as f32 + 1.0, y as f32, z as f32 + 1.0],
-                        [x as f32, y as f32, z as f32 + 1.0],
-                        [x as f32, y as f32, z as f32],
-                    );
+
+                if light_debug {
+                    for v in &mut verts[first_vert..] {
+                        v.color = light_heat(v.light);
+                        // volle Helligkeit, unabhängig von Tageszeit und Licht
+                        v.emissive = 1.0;
+                    }
+                    continue;
                 }
-                // +Z
-                if is_air(world.get_block(x, y, z + 1)) {
-                    push_face(&mut verts, &mut inds, col,
-                        [x as f32 + 1.0, y as f32, z as f32 + 1.0],
-                        [x as f32 + 1.0, y as f32 + 1.0, z as f32 + 1.0],
-                        [x as f32, y as f32 + 1.0, z as f32 + 1.0],
-                        [x as f32, y as f32, z as f32 + 1.0],
-                    );
+                if b.is_emissive() {
+                    for v in &mut verts[first_vert..] {
+                        v.emissive = 1.0;
+                    }
                 }
-                // -Z
-                if is_air(world.get_block(x, y, z - 1)) {
-                    push_face(&mut verts, &mut inds, col,
-                        [x as f32, y as f32, z as f32],
-                        [x as f32, y as f32 + 1.0, z as f32],
-                        [x as f32 + 1.0, y as f32 + 1.0, z as f32],
-                        [x as f32 + 1.0, y as f32, z as f32],
-                    );
+
+                // Biom-Tint pro Vertex (an der Vertex-Position ausgewertet -> weiche Übergänge)
+                let tint = b.tint();
+                if tint != Tint::None {
+                    for v in &mut verts[first_vert..] {
+                        let t = tint_at(world.seed(), tint, v.pos[0], v.pos[2]);
+                        v.color = [v.color[0] * t[0], v.color[1] * t[1], v.color[2] * t[2]];
+                    }
                 }
             }
         }
@@ -103,23 +117,219 @@ pub fn mesh_chunk(world: &World, cp: ChunkPos) -> (Vec<Vertex>, Vec<u32>) {
     (verts, inds)
 }
 
+/// (Himmel, Block)-Licht einer Zelle, normiert auf 0..1
+fn light_at(world: &World, x: i32, y: i32, z: i32) -> [f32; 2] {
+    [
+        world.sky_light(x, y, z) as f32 / MAX_LIGHT as f32,
+        world.block_light(x, y, z) as f32 / MAX_LIGHT as f32,
+    ]
+}
+
+/// Debug-Heatmap: Himmelslicht blau, Blocklicht rot/orange, dunkel = schwarz
+fn light_heat([sky, block]: [f32; 2]) -> [f32; 3] {
+    [block, block * 0.5 + sky * 0.3, sky]
+}
+
+/// Helligkeit je AO-Stufe (0 = Ecke ganz zugebaut, 3 = frei)
+const AO_CURVE: [f32; 4] = [0.55, 0.7, 0.85, 1.0];
+
+/// Weiches Licht + Ambient Occlusion für eine Ecke einer Würfelseite:
+/// Mittel der (nicht deckenden) vier Zellen vor der Face, die an die Ecke grenzen.
+/// (Ohne Greedy-Meshing ist jede Face ein Block groß, Quads müssen nicht geteilt werden.)
+fn corner_shade(world: &World, (x, y, z): BlockPos, face: usize, corner: [f32; 3]) -> ([f32; 2], f32) {
+    let (nx, ny, nz) = FACE_DIRS[face];
+    let normal = [nx, ny, nz];
+    let block = [x, y, z];
+
+    // Zwei Tangentenrichtungen, jeweils zur Ecke hin
+    let mut tangents = [[0; 3]; 2];
+    let mut k = 0;
+    for a in 0..3 {
+        if normal[a] == 0 {
+            tangents[k][a] = if corner[a] - block[a] as f32 > 0.5 { 1 } else { -1 };
+            k += 1;
+        }
+    }
+
+    let front = (x + nx, y + ny, z + nz);
+    let offset = |p: BlockPos, t: [i32; 3]| (p.0 + t[0], p.1 + t[1], p.2 + t[2]);
+    let side1 = offset(front, tangents[0]);
+    let side2 = offset(front, tangents[1]);
+    let diag = offset(side1, tangents[1]);
+
+    let occludes = |p: BlockPos| world.get_block(p.0, p.1, p.2).occludes();
+    let (o1, o2) = (occludes(side1), occludes(side2));
+    // beide Seiten zu -> die Diagonale ist nicht mehr sichtbar
+    let oc = (o1 && o2) || occludes(diag);
+    let ao = if o1 && o2 { 0 } else { 3 - (o1 as usize + o2 as usize + oc as usize) };
+
+    let mut sum = [0.0; 2];
+    let mut n = 0.0;
+    for (p, occluded) in [(front, false), (side1, o1), (side2, o2), (diag, oc)] {
+        if !occluded {
+            let l = light_at(world, p.0, p.1, p.2);
+            sum[0] += l[0];
+            sum[1] += l[1];
+            n += 1.0;
+        }
+    }
+    ([sum[0] / n, sum[1] / n], AO_CURVE[ao])
+}
+
+/// Achsparallele Box in lokalen Blockkoordinaten (min, max)
+type BlockBox = ([f32; 3], [f32; 3]);
+
+fn shape_boxes(shape: Shape, st: u8, out: &mut Vec<BlockBox>) {
+    const T: f32 = 1.0 / 16.0;
+    match shape {
+        Shape::Cube => out.push(([0.0, 0.0, 0.0], [1.0, 1.0, 1.0])),
+        Shape::Slab if st == state::SLAB_TOP => out.push(([0.0, 0.5, 0.0], [1.0, 1.0, 1.0])),
+        Shape::Slab => out.push(([0.0, 0.0, 0.0], [1.0, 0.5, 1.0])),
+        Shape::Torch => out.push(torch_box(st)),
+        Shape::Farmland => out.push(([0.0, 0.0, 0.0], [1.0, 15.0 * T, 1.0])),
+        Shape::Crop => {
+            // zwei gekreuzte, dünne Flächen; wächst mit der Stufe
+            let h = (st.min(state::CROP_MAX_STAGE) as f32 + 1.0) / 8.0;
+            out.push(([2.0 * T, -T, 7.5 * T], [14.0 * T, h - T, 8.5 * T]));
+            out.push(([7.5 * T, -T, 2.0 * T], [8.5 * T, h - T, 14.0 * T]));
+        }
+        Shape::Cross => {
+            out.push(([2.0 * T, 0.0, 7.5 * T], [14.0 * T, 1.0, 8.5 * T]));
+            out.push(([7.5 * T, 0.0, 2.0 * T], [8.5 * T, 1.0, 14.0 * T]));
+        }
+        Shape::Door => out.push(door_box(st)),
+        Shape::Layer => {
+            let h = (st.min(state::SNOW_MAX_LAYER) as f32 + 1.0) / 8.0;
+            out.push(([0.0, 0.0, 0.0], [1.0, h, 1.0]));
+        }
+    }
+}
+
+/// Tür: 3/16 dicke Platte an der Kante in Facing-Richtung; offen um 90° weitergedreht
+fn door_box(st: u8) -> BlockBox {
+    const D: f32 = 3.0 / 16.0;
+    let mut edge = st & state::FACING_MASK;
+    if st & state::DOOR_OPEN != 0 {
+        edge = (edge + 1) & state::FACING_MASK;
+    }
+    match edge {
+        0 => ([0.0, 0.0, 0.0], [1.0, 1.0, D]),
+        1 => ([1.0 - D, 0.0, 0.0], [1.0, 1.0, 1.0]),
+        2 => ([0.0, 0.0, 1.0 - D], [1.0, 1.0, 1.0]),
+        _ => ([0.0, 0.0, 0.0], [D, 1.0, 1.0]),
+    }
+}
+
+/// Fackel: 2/16 dicker Stab, an der Wand nach oben versetzt und an die Wand gerückt
+fn torch_box(st: u8) -> BlockBox {
+    const T: f32 = 1.0 / 16.0;
+    match st {
+        state::TORCH_WALL_PX => ([0.0, 3.0 * T, 7.0 * T], [2.0 * T, 13.0 * T, 9.0 * T]),
+        state::TORCH_WALL_NX => ([14.0 * T, 3.0 * T, 7.0 * T], [1.0, 13.0 * T, 9.0 * T]),
+        state::TORCH_WALL_PZ => ([7.0 * T, 3.0 * T, 0.0], [9.0 * T, 13.0 * T, 2.0 * T]),
+        state::TORCH_WALL_NZ => ([7.0 * T, 3.0 * T, 14.0 * T], [9.0 * T, 13.0 * T, 1.0]),
+        _ => ([7.0 * T, 0.0, 7.0 * T], [9.0 * T, 10.0 * T, 9.0 * T]),
+    }
+}
+
+/// Farbe pro Face – rotierte Blöcke (Log) zeigen die Stirnseite in Achsrichtung
+fn face_color(b: Block, st: u8, face: usize) -> [f32; 3] {
+    let col = b.color();
+    match b {
+        Block::Log => {
+            let face_axis = match face {
+                0 | 1 => state::AXIS_X,
+                2 | 3 => state::AXIS_Y,
+                _ => state::AXIS_Z,
+            };
+            if face_axis == st {
+                // Jahresringe: heller als die Rinde
+                [col[0] * 1.6, col[1] * 1.6, col[2] * 1.5]
+            } else {
+                col
+            }
+        }
+        // Flamme oben
+        Block::Torch if face == 2 => [1.0, 0.85, 0.35],
+        // Obere Hälfte hoher Pflanzen etwas heller (Spitzen)
+        Block::TallGrass if st & state::UPPER_HALF != 0 => [col[0] * 1.1, col[1] * 1.1, col[2] * 1.1],
+        // Weizen: von grün zu golden je nach Stufe
+        Block::Wheat => {
+            let t = st.min(state::CROP_MAX_STAGE) as f32 / state::CROP_MAX_STAGE as f32;
+            let ripe = [0.85, 0.72, 0.28];
+            [
+                col[0] + (ripe[0] - col[0]) * t,
+                col[1] + (ripe[1] - col[1]) * t,
+                col[2] + (ripe[2] - col[2]) * t,
+            ]
+        }
+        _ => col,
+    }
+}
+
+/// Fügt die sichtbaren Faces einer achsparallelen Box hinzu (Koordinaten relativ zu `base`)
+fn push_box(
+    verts: &mut Vec<Vertex>,
+    inds: &mut Vec<u32>,
+    base: [f32; 3],
+    (min, max): BlockBox,
+    visible: [bool; 6],
+    shade: impl Fn(usize, [f32; 3]) -> ([f32; 2], f32),
+    color: impl Fn(usize) -> [f32; 3],
+) {
+    let [bx, by, bz] = base;
+    let (x0, y0, z0) = (bx + min[0], by + min[1], bz + min[2]);
+    let (x1, y1, z1) = (bx + max[0], by + max[1], bz + max[2]);
+
+    let faces: [[[f32; 3]; 4]; 6] = [
+        // +X
+        [[x1, y0, z0], [x1, y1, z0], [x1, y1, z1], [x1, y0, z1]],
+        // -X
+        [[x0, y0, z1], [x0, y1, z1], [x0, y1, z0], [x0, y0, z0]],
+        // +Y (top)
+        [[x0, y1, z0], [x0, y1, z1], [x1, y1, z1], [x1, y1, z0]],
+        // -Y (bottom)
+        [[x1, y0, z0], [x1, y0, z1], [x0, y0, z1], [x0, y0, z0]],
+        // +Z
+        [[x1, y0, z1], [x1, y1, z1], [x0, y1, z1], [x0, y0, z1]],
+        // -Z
+        [[x0, y0, z0], [x0, y1, z0], [x1, y1, z0], [x1, y0, z0]],
+    ];
+
+    for (f, corners) in faces.into_iter().enumerate() {
+        if visible[f] {
+            let shaded = corners.map(|p| shade(f, p));
+            push_face(verts, inds, color(f), corners, shaded);
+        }
+    }
+}
+
+/// Ein Quad; `shaded` = (Licht, AO) pro Ecke
 #[inline]
 fn push_face(
     verts: &mut Vec<Vertex>,
     inds: &mut Vec<u32>,
     color: [f32; 3],
-    p0: [f32; 3],
-    p1: [f32; 3],
-    p2: [f32; 3],
-    p3: [f32; 3],
+    corners: [[f32; 3]; 4],
+    shaded: [([f32; 2], f32); 4],
 ) {
     let base = verts.len() as u32;
 
-    verts.push(Vertex { pos: p0, color });
-    verts.push(Vertex { pos: p1, color });
-    verts.push(Vertex { pos: p2, color });
-    verts.push(Vertex { pos: p3, color });
+    for (pos, (light, ao)) in corners.into_iter().zip(shaded) {
+        let color = [color[0] * ao, color[1] * ao, color[2] * ao];
+        verts.push(Vertex {
+            pos,
+            color,
+            light,
+            emissive: 0.0,
+        });
+    }
 
-    // zwei Dreiecke (0,1,2) und (0,2,3)
-    inds.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    // zwei Dreiecke; Diagonale so wählen, dass AO-Verläufe nicht kippen
+    let bright = |i: usize| shaded[i].1 + shaded[i].0[0] + shaded[i].0[1];
+    if bright(0) + bright(2) >= bright(1) + bright(3) {
+        inds.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    } else {
+        inds.extend_from_slice(&[base + 1, base + 2, base + 3, base + 1, base + 3, base]);
+    }
 }
@@ -0,0 +1,211 @@
+//! Wetter: Zustandsautomat klar / Regen / Gewitter mit zufälliger Dauer je Zustand.
+//! Niederschlag und Gewitter blenden über ein paar Sekunden ein und aus und dunkeln das
+//! Himmelslicht ab; im Gewitter blitzt es ab und zu. Dazu die Regen- und Schneestriche,
+//! die der Client um die Kamera zeichnet.
+
+use crate::mesh::Vertex;
+use crate::rng::Rng;
+use crate::world::{World, MAX_LIGHT};
+
+/// Dauer je Zustand in Ticks (20 TPS): (min, max)
+const CLEAR_TICKS: (u32, u32) = (12_000, 36_000);
+const RAIN_TICKS: (u32, u32) = (3_600, 12_000);
+const THUNDER_TICKS: (u32, u32) = (2_400, 6_000);
+/// Ein- und Ausblenden von Niederschlag und Gewitter: Anteil pro Tick (10 s für 0..1)
+const FADE_PER_TICK: f32 = 1.0 / 200.0;
+/// Abdunklung des Himmelslichts bei vollem Regen, das Gewitter kommt noch dazu
+const RAIN_DARKEN: f32 = 0.35;
+const THUNDER_DARKEN: f32 = 0.2;
+/// Im vollen Gewitter blitzt es im Schnitt alle so viele Ticks, jeweils `FLASH_TICKS` lang
+const FLASH_EVERY: u32 = 300;
+const FLASH_TICKS: u8 = 3;
+
+/// Striche in einem Quader um die Kamera, der mit der Welt (nicht der Kamera) gekachelt ist
+const STREAK_RADIUS: f32 = 12.0;
+const STREAK_HEIGHT: f32 = 16.0;
+const MAX_STREAKS: usize = 400;
+const RAIN_COLOR: [f32; 3] = [0.55, 0.62, 0.78];
+const SNOW_COLOR: [f32; 3] = [0.95, 0.96, 1.0];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weather {
+    Clear,
+    Rain,
+    Thunder,
+}
+
+impl Weather {
+    pub const ALL: [Weather; 3] = [Weather::Clear, Weather::Rain, Weather::Thunder];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Weather::Clear => "clear",
+            Weather::Rain => "rain",
+            Weather::Thunder => "thunder",
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Weather> {
+        Weather::ALL.into_iter().find(|w| w.name() == name)
+    }
+
+    /// Regnet (bzw. schneit) es?
+    pub fn precipitation(self) -> bool {
+        self != Weather::Clear
+    }
+
+    fn duration(self, rng: &mut Rng) -> u64 {
+        let (lo, hi) = match self {
+            Weather::Clear => CLEAR_TICKS,
+            Weather::Rain => RAIN_TICKS,
+            Weather::Thunder => THUNDER_TICKS,
+        };
+        (lo + rng.below(hi - lo + 1)) as u64
+    }
+
+    /// Nächster Zustand nach Ablauf der Dauer
+    fn next(self, rng: &mut Rng) -> Weather {
+        match self {
+            Weather::Clear | Weather::Rain if rng.below(4) == 0 => Weather::Thunder,
+            Weather::Clear | Weather::Thunder => Weather::Rain,
+            Weather::Rain => Weather::Clear,
+        }
+    }
+}
+
+/// Wetter einer Welt. Eigener Zufall, damit das Wetter die Random-Ticks nicht verschiebt.
+#[derive(Clone, Debug)]
+pub struct WeatherState {
+    kind: Weather,
+    /// Ticks bis zum nächsten Wechsel
+    ticks_left: u64,
+    /// Niederschlag und Gewitter 0..1, folgen `kind` langsam
+    rain: f32,
+    storm: f32,
+    flash: u8,
+    rng: Rng,
+}
+
+impl WeatherState {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed ^ 0x3EA7_4E12);
+        Self {
+            kind: Weather::Clear,
+            ticks_left: Weather::Clear.duration(&mut rng),
+            rain: 0.0,
+            storm: 0.0,
+            flash: 0,
+            rng,
+        }
+    }
+
+    pub fn kind(&self) -> Weather {
+        self.kind
+    }
+
+    pub fn ticks_left(&self) -> u64 {
+        self.ticks_left
+    }
+
+    /// Zustand setzen; ohne Dauer wird eine zufällige gewürfelt. Blendet normal über.
+    pub fn set(&mut self, kind: Weather, ticks: Option<u64>) {
+        self.kind = kind;
+        self.ticks_left = ticks.unwrap_or_else(|| kind.duration(&mut self.rng)).max(1);
+    }
+
+    /// Wie `set`, aber ohne Überblendung (geladener Spielstand)
+    pub fn restore(&mut self, kind: Weather, ticks: u64) {
+        self.set(kind, Some(ticks));
+        self.rain = if kind.precipitation() { 1.0 } else { 0.0 };
+        self.storm = if kind == Weather::Thunder { 1.0 } else { 0.0 };
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        if self.ticks_left == 0 {
+            let next = self.kind.next(&mut self.rng);
+            log::info!("Wetter: {} -> {}", self.kind.name(), next.name());
+            self.set(next, None);
+        }
+        let fade = |level: f32, on: bool| {
+            let target = if on { 1.0 } else { 0.0 };
+            level + (target - level).clamp(-FADE_PER_TICK, FADE_PER_TICK)
+        };
+        self.rain = fade(self.rain, self.kind.precipitation());
+        self.storm = fade(self.storm, self.kind == Weather::Thunder);
+
+        self.flash = self.flash.saturating_sub(1);
+        if self.storm >= 1.0 && self.rng.below(FLASH_EVERY) == 0 {
+            self.flash = FLASH_TICKS;
+        }
+    }
+
+    /// Niederschlag 0..1
+    pub fn rain(&self) -> f32 {
+        self.rain
+    }
+
+    /// Faktor fürs Himmelslicht (1 = klar)
+    pub fn darkening(&self) -> f32 {
+        1.0 - self.rain * RAIN_DARKEN - self.storm * THUNDER_DARKEN
+    }
+
+    /// Gerade blitzt es (Himmel kurz taghell)
+    pub fn flash(&self) -> bool {
+        self.flash > 0
+    }
+
+    /// Zufall für wetterabhängige Blockänderungen (Schnee)
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+}
+
+/// Regen- bzw. Schneestriche um `camera` als Linien. Jeder Strich hat einen festen Platz
+/// in einer mit der Welt gekachelten Zelle und fällt mit `time` (Sekunden); Striche ohne
+/// freien Himmel (drinnen, unter Tage) entfallen.
+pub fn precipitation_lines(
+    world: &World,
+    (cx, cy, cz): (f32, f32, f32),
+    rain: f32,
+    snow: bool,
+    time: f32,
+) -> Vec<Vertex> {
+    let count = (MAX_STREAKS as f32 * rain.clamp(0.0, 1.0)) as usize;
+    // Fallgeschwindigkeit (Blöcke/s), Länge und Schräglage
+    let (speed, len, slant, color) = if snow {
+        (2.0, 0.12, 0.0, SNOW_COLOR)
+    } else {
+        (14.0, 0.7, 0.08, RAIN_COLOR)
+    };
+    let size = 2.0 * STREAK_RADIUS;
+    // Koordinate in die Kachel um `center` falten
+    let wrap = |v: f32, center: f32, extent: f32| {
+        let lo = center - extent * 0.5;
+        lo + (v - lo).rem_euclid(extent)
+    };
+    let mut rng = Rng::new(0x5EED_4A1B);
+    let mut out = Vec::with_capacity(count * 2);
+    for _ in 0..count {
+        let unit = |rng: &mut Rng| rng.next_u32() as f32 / u32::MAX as f32;
+        let (ox, oy, oz, phase) = (unit(&mut rng), unit(&mut rng), unit(&mut rng), unit(&mut rng));
+        // Schnee treibt seitlich
+        let drift = if snow { (time * 1.3 + phase * 6.0).sin() * 0.4 } else { 0.0 };
+        let x = wrap(ox * size + drift, cx, size);
+        let z = wrap(oz * size, cz, size);
+        let y = wrap(oy * STREAK_HEIGHT - time * speed, cy, STREAK_HEIGHT);
+        let (bx, by, bz) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+        if world.sky_light(bx, by, bz) < MAX_LIGHT || world.is_solid(bx, by, bz) {
+            continue;
+        }
+        let vertex = |pos: [f32; 3]| Vertex {
+            pos,
+            color,
+            light: [1.0, 0.0],
+            emissive: 0.0,
+        };
+        out.push(vertex([x, y, z]));
+        out.push(vertex([x + slant, y + len, z + slant]));
+    }
+    out
+}
@@ -1,25 +1,234 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
-use crate::block::Block;
+use crate::biome;
+use crate::block::{state, Block};
+use crate::block_entity::BlockEntity;
+use crate::event::{BlockChanged, ChunkLoaded, EventBus};
+use crate::inventory::ItemStack;
+use crate::level::GameRules;
+use crate::memory::MemoryReport;
+use crate::rng::Rng;
+use crate::save::WorldStorage;
+use crate::terrain::Terrain;
+use crate::weather::{Weather, WeatherState};
 use crate::chunk::{CHUNK_SIZE, Chunk, ChunkPos, chunk_coord, in_chunk};
 
+mod light;
+
+use light::{LightJob, LightResult, LightWorker};
+pub use light::MAX_LIGHT;
+
+/// Seed neuer Welten (bis es Weltoptionen gibt)
+pub const DEFAULT_SEED: u64 = 0x5EED;
+
+/// Zufällige Blöcke pro geladenem Chunk und Tick (Standard der Spielregel)
+pub const RANDOM_TICKS_PER_CHUNK: u32 = 3;
+/// Wie weit nach oben auf Himmelszugang geprüft wird
+const SKY_SCAN_HEIGHT: i32 = 64;
+
+/// Die 6 direkten Nachbarn
+const NEIGHBORS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+];
+
+/// Länge eines Tag-Nacht-Zyklus in Ticks (20 Minuten bei 20 TPS)
+pub const DAY_LENGTH_TICKS: u64 = 24_000;
+/// Himmelslicht-Faktor um Mitternacht (Mondlicht)
+const NIGHT_DAYLIGHT: f32 = 0.2;
+/// Schnee bleibt erst liegen, wenn es richtig schneit
+const SNOW_MIN_RAIN: f32 = 0.5;
+/// Pro Chunk und Tick bekommt im Schnitt einer von so vielen eine Schneeschicht
+const SNOW_CHANCE: u32 = 16;
+
+/// Feindliche Mobs erscheinen nur bei höchstens diesem Licht
+pub const HOSTILE_SPAWN_MAX_LIGHT: u8 = 7;
+
+/// Maximale Entfernung (über Blätter) zum nächsten Stamm, bevor Blätter verrotten
+const LEAF_RANGE: i32 = 4;
+
+/// Längster Zielstrahl in Blöcken; begrenzt die Schritte auch bei unsinniger Reichweite
+pub const MAX_RAYCAST_DIST: f32 = 1024.0;
+/// Ab hier unterscheidet f32 keine ganzen Blöcke mehr; Strahlen von weiter draußen treffen
+/// nichts (und laufen so auch nicht über den i32-Rand)
+pub const MAX_RAYCAST_COORD: f32 = 16_777_216.0;
+
+/// Liegengebliebene Drops verschwinden nach 5 Minuten
+pub const DROP_DESPAWN_TICKS: u64 = 6_000;
+
+/// Block-Koordinate in Weltkoordinaten
+pub type BlockPos = (i32, i32, i32);
+
+/// Item, das an einer Blockposition liegt, bis jemand es einsammelt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemDrop {
+    pub pos: BlockPos,
+    pub stack: ItemStack,
+    /// `age_ticks` der Welt beim Fallenlassen
+    since: u64,
+}
+
+/// Seite eines Blocks (Norden = -Z, Osten = +X)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Face {
+    West,
+    East,
+    Down,
+    Up,
+    North,
+    South,
+}
+
+impl Face {
+    /// Nach außen zeigende Normale
+    pub fn normal(self) -> (i32, i32, i32) {
+        match self {
+            Face::West => (-1, 0, 0),
+            Face::East => (1, 0, 0),
+            Face::Down => (0, -1, 0),
+            Face::Up => (0, 1, 0),
+            Face::North => (0, 0, -1),
+            Face::South => (0, 0, 1),
+        }
+    }
+}
+
+/// Raycast-Treffer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RaycastHit {
+    pub pos: BlockPos,
+    pub block: Block,
+    /// Getroffene Seite; None, wenn der Strahl im Block beginnt
+    pub face: Option<Face>,
+    /// Strecke vom Ursprung in Blöcken
+    pub distance: f32,
+    /// Schnittpunkt mit der Blockoberfläche
+    pub point: (f32, f32, f32),
+}
+
+impl RaycastHit {
+    /// Nachbarblock vor der getroffenen Seite (dort wird platziert)
+    pub fn adjacent(&self) -> BlockPos {
+        let (nx, ny, nz) = self.face.map_or((0, 0, 0), Face::normal);
+        (self.pos.0 + nx, self.pos.1 + ny, self.pos.2 + nz)
+    }
+}
+
 pub struct World {
+    seed: u64,
     age_ticks: u64,
+    /// Tageszeit in Ticks (läuft mit `age_ticks`, aber per Befehl setzbar)
+    day_ticks: u64,
+    /// Vergangene Tage (Mondphase), zählt beim Übergang von `day_ticks` auf 0 hoch
+    day: u64,
     chunks: HashMap<ChunkPos, Chunk<Block>>,
+    /// Beim Abbauen entfernte Block-Entities (das Game schüttet Container jeden Tick aus)
+    removed_block_entities: Vec<(BlockPos, BlockEntity)>,
+    /// None = reine In-Memory-Welt
+    storage: Option<WorldStorage>,
+    rng: Rng,
+    /// Abgefallene/geerntete Items, die das Game einsammelt
+    drops: Vec<ItemDrop>,
+    /// Geplante Block-Ticks: (fällig bei Tick, Position)
+    scheduled: BinaryHeap<Reverse<(u64, BlockPos)>>,
+    /// Lichtberechnung im Hintergrund; Ergebnisse werden im Tick übernommen
+    light: LightWorker,
+    /// Replay-Modus: Licht wird jeden Tick abgewartet statt asynchron übernommen
+    deterministic: bool,
+    rules: GameRules,
+    /// Blockänderungen, geladene Chunks und was das Game selbst veröffentlicht
+    events: EventBus,
+    weather: WeatherState,
+    /// Füllt neu angelegte Chunks; None = flache Welt, außerhalb des Startbereichs Luft
+    terrain: Option<Terrain>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl World {
     pub fn new() -> Self {
-        let mut w = Self {
-            age_ticks: 0,
-            chunks: HashMap::new(),
-        };
+        let mut w = Self::empty(None);
 
         // Startbereich: Bodenplatte + kleine Wand wie vorher (nur größer, chunk-safe)
         w.ensure_spawn_area();
+        w.wait_for_light();
         w
     }
 
+    /// Frische Welt ohne Spielstand, die bei gleichen Eingaben gleich abläuft (Replays)
+    pub fn deterministic(seed: u64) -> Self {
+        let mut w = Self::empty(None);
+        w.seed = seed;
+        w.rng = Rng::new(seed);
+        w.weather = WeatherState::new(seed);
+        w.deterministic = true;
+        w.ensure_spawn_area();
+        w.wait_for_light();
+        w
+    }
+
+    /// Welt mit Spielstand: vorhandene Chunks werden beim Streaming geladen, fehlende
+    /// erzeugt `generator` (level.toml). Nur eine neue flache Welt bekommt den Startbereich.
+    pub fn with_storage(storage: WorldStorage, seed: u64, generator: &str) -> Self {
+        let fresh = !storage.has_data();
+        let mut w = Self::empty(Some(storage));
+        w.seed = seed;
+        w.rng = Rng::new(seed);
+        w.weather = WeatherState::new(seed);
+        w.terrain = Terrain::by_name(generator, seed);
+        if fresh && w.terrain.is_none() {
+            w.ensure_spawn_area();
+            w.wait_for_light();
+        }
+        w
+    }
+
+    /// Welt ohne Spielstand und Startbereich, nur aus dem Generator (Messungen)
+    pub fn generated(seed: u64, generator: &str) -> Self {
+        let mut w = Self::empty(None);
+        w.seed = seed;
+        w.rng = Rng::new(seed);
+        w.weather = WeatherState::new(seed);
+        w.terrain = Terrain::by_name(generator, seed);
+        w
+    }
+
+    /// Spiegel einer Serverwelt beim Client: keine Generierung, kein Speichern, keine Ticks.
+    /// Chunks kommen samt Licht über `put_chunk`.
+    pub fn remote() -> Self {
+        Self::empty(None)
+    }
+
+    fn empty(storage: Option<WorldStorage>) -> Self {
+        Self {
+            seed: DEFAULT_SEED,
+            age_ticks: 0,
+            day_ticks: 0,
+            day: 0,
+            chunks: HashMap::new(),
+            removed_block_entities: Vec::new(),
+            storage,
+            rng: Rng::new(DEFAULT_SEED),
+            scheduled: BinaryHeap::new(),
+            drops: Vec::new(),
+            light: LightWorker::spawn(),
+            deterministic: false,
+            rules: GameRules::default(),
+            events: EventBus::new(),
+            weather: WeatherState::new(DEFAULT_SEED),
+            terrain: None,
+        }
+    }
+
     pub fn size(&self) -> i32 {
         // Alte API: Mini-Welt war 16. Für jetzt als "default".
         // Kann später raus, wenn Game keine size mehr braucht.
@@ -38,6 +247,30 @@ impl World {
         }
     }
 
+    /// Block an einer Chunk-Kante geändert → angrenzende Chunks neu meshen
+    fn mark_border_dirty(&mut self, x: i32, y: i32, z: i32) {
+        let (cx, cy, cz) = (chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        let (lx, ly, lz) = (in_chunk(x), in_chunk(y), in_chunk(z));
+
+        if lx == 0 {
+            self.mark_dirty(ChunkPos { cx: cx - 1, cy, cz });
+        } else if lx == CHUNK_SIZE - 1 {
+            self.mark_dirty(ChunkPos { cx: cx + 1, cy, cz });
+        }
+
+        if ly == 0 {
+            self.mark_dirty(ChunkPos { cx, cy: cy - 1, cz });
+        } else if ly == CHUNK_SIZE - 1 {
+            self.mark_dirty(ChunkPos { cx, cy: cy + 1, cz });
+        }
+
+        if lz == 0 {
+            self.mark_dirty(ChunkPos { cx, cy, cz: cz - 1 });
+        } else if lz == CHUNK_SIZE - 1 {
+            self.mark_dirty(ChunkPos { cx, cy, cz: cz + 1 });
+        }
+    }
+
     /// Gibt zurück, ob der Chunk 'dirty' war, und setzt dirty=false.
     pub fn take_chunk_dirty(&mut self, cp: ChunkPos) -> bool {
         if let Some(ch) = self.chunks.get_mut(&cp) {
@@ -49,33 +282,552 @@ impl World {
         }
     }
 
+    /// Gibt zurück, ob sich das Licht des Chunks geändert hat, und setzt das Flag zurück
+    pub fn take_light_changed(&mut self, cp: ChunkPos) -> bool {
+        self.chunks
+            .get_mut(&cp)
+            .is_some_and(|ch| std::mem::replace(&mut ch.light_changed, false))
+    }
+
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    pub fn events_mut(&mut self) -> &mut EventBus {
+        &mut self.events
+    }
+
     pub fn tick(&mut self) {
+        self.events.end_tick();
         self.age_ticks += 1;
+        if self.rules.daylight_cycle {
+            self.day_ticks = (self.day_ticks + 1) % DAY_LENGTH_TICKS;
+            if self.day_ticks == 0 {
+                self.day += 1;
+            }
+        }
+        if self.deterministic {
+            self.wait_for_light();
+        } else {
+            self.apply_light_results();
+        }
+        let age = self.age_ticks;
+        self.drops.retain(|d| age - d.since < DROP_DESPAWN_TICKS);
+        self.weather.tick();
+        self.random_ticks();
+        self.snow_ticks();
+        self.run_scheduled_ticks();
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: GameRules) {
+        self.rules = rules;
+    }
+
+    pub fn storage(&self) -> Option<&WorldStorage> {
+        self.storage.as_ref()
+    }
+
+    /// Fertige Lichtdaten übernehmen (betroffene Chunks werden dadurch neu gemesht)
+    pub fn apply_light_results(&mut self) {
+        while let Some(result) = self.light.try_recv() {
+            self.apply_light_result(result);
+        }
+    }
+
+    /// Blockiert, bis alle bisherigen Änderungen beleuchtet sind (Weltstart, Tests)
+    pub fn wait_for_light(&mut self) {
+        self.light.send(LightJob::Flush);
+        while let Some(result) = self.light.recv() {
+            if matches!(result, LightResult::Flushed) {
+                break;
+            }
+            self.apply_light_result(result);
+        }
+    }
+
+    fn apply_light_result(&mut self, result: LightResult) {
+        if let LightResult::Light(chunks) = result {
+            for (cp, data) in chunks {
+                if let Some(ch) = self.chunks.get_mut(&cp) {
+                    ch.set_light_data(&data);
+                }
+            }
+        }
+    }
+
+    /// Oberster Nicht-Luft-Block der Säule (x, z) in `min_y..=max_y` (Draufsichten)
+    pub fn top_block(&self, x: i32, z: i32, min_y: i32, max_y: i32) -> Option<(i32, Block)> {
+        (min_y..=max_y).rev().find_map(|y| {
+            let b = self.get_block(x, y, z);
+            (b != Block::Air).then_some((y, b))
+        })
+    }
+
+    /// Himmelslicht an einer Position (ungeladen = freier Himmel)
+    pub fn sky_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        self.chunks.get(&cp).map_or(MAX_LIGHT, |ch| {
+            ch.sky_light_local(in_chunk(x), in_chunk(y), in_chunk(z))
+        })
+    }
+
+    /// Blocklicht (Fackeln etc.) an einer Position (ungeladen = dunkel)
+    pub fn block_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        self.chunks.get(&cp).map_or(0, |ch| {
+            ch.block_light_local(in_chunk(x), in_chunk(y), in_chunk(z))
+        })
+    }
+
+    /// Wirksames Licht für Spielregeln: Blocklicht oder Himmelslicht nach Tageszeit
+    pub fn light_at(&self, (x, y, z): BlockPos) -> u8 {
+        let sky = (self.sky_light(x, y, z) as f32 * self.daylight()).round() as u8;
+        sky.max(self.block_light(x, y, z))
+    }
+
+    /// Darf hier ein feindlicher Mob erscheinen? Fester Boden, zwei freie Blöcke, dunkel.
+    pub fn can_spawn_hostile(&self, (x, y, z): BlockPos) -> bool {
+        self.get_block(x, y - 1, z).occludes()
+            && !self.is_solid(x, y, z)
+            && !self.is_solid(x, y + 1, z)
+            && self.get_block(x, y, z) != Block::Water
+            && self.light_at((x, y, z)) <= HOSTILE_SPAWN_MAX_LIGHT
+    }
+
+    /// Block-Tick in `delay` Ticks einplanen
+    pub fn schedule_tick(&mut self, x: i32, y: i32, z: i32, delay: u64) {
+        self.scheduled
+            .push(Reverse((self.age_ticks + delay.max(1), (x, y, z))));
+    }
+
+    fn run_scheduled_ticks(&mut self) {
+        while let Some(Reverse((due, _))) = self.scheduled.peek() {
+            if *due > self.age_ticks {
+                break;
+            }
+            let Some(Reverse((_, (x, y, z)))) = self.scheduled.pop() else {
+                break;
+            };
+            let b = self.get_block(x, y, z);
+            self.scheduled_tick(x, y, z, b);
+        }
+    }
+
+    fn scheduled_tick(&mut self, x: i32, y: i32, z: i32, b: Block) {
+        if b == Block::Leaves {
+            self.leaf_decay_tick(x, y, z);
+        }
+    }
+
+    /// Blätter ohne Stamm in Reichweite verschwinden
+    fn leaf_decay_tick(&mut self, x: i32, y: i32, z: i32) {
+        if self.get_state(x, y, z) == state::LEAVES_PERSISTENT {
+            return;
+        }
+        if !self.log_within_leaf_range(x, y, z) {
+            self.set_block(x, y, z, Block::Air);
+        }
+    }
+
+    /// BFS über zusammenhängende Blätter: liegt ein Stamm höchstens LEAF_RANGE entfernt?
+    fn log_within_leaf_range(&self, x: i32, y: i32, z: i32) -> bool {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert((x, y, z));
+        queue.push_back(((x, y, z), 0));
+
+        while let Some(((px, py, pz), dist)) = queue.pop_front() {
+            for (dx, dy, dz) in NEIGHBORS {
+                let n = (px + dx, py + dy, pz + dz);
+                match self.get_block(n.0, n.1, n.2) {
+                    Block::Log => return true,
+                    Block::Leaves if dist + 1 < LEAF_RANGE && seen.insert(n) => {
+                        queue.push_back((n, dist + 1));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        false
+    }
+
+    /// Nach Entfernen eines Stamms/Blatts: Blätter im Umkreis zeitversetzt prüfen
+    fn schedule_leaf_checks(&mut self, x: i32, y: i32, z: i32) {
+        for dy in -LEAF_RANGE..=LEAF_RANGE {
+            for dz in -LEAF_RANGE..=LEAF_RANGE {
+                for dx in -LEAF_RANGE..=LEAF_RANGE {
+                    let (lx, ly, lz) = (x + dx, y + dy, z + dz);
+                    if self.get_block(lx, ly, lz) == Block::Leaves {
+                        let delay = 10 + self.rng.below(60) as u64;
+                        self.schedule_tick(lx, ly, lz, delay);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pro Chunk ein paar zufällige Blöcke auswählen und deren Verhalten ausführen
+    fn random_ticks(&mut self) {
+        // feste Reihenfolge: sonst hinge der Zufall von der HashMap-Reihenfolge ab
+        let mut positions = self.chunk_positions();
+        positions.sort_unstable_by_key(|c| (c.cx, c.cy, c.cz));
+        for cp in positions {
+            for _ in 0..self.rules.random_tick_speed {
+                let (r, s) = (self.rng.next_u32(), CHUNK_SIZE as u32);
+                let x = cp.cx * CHUNK_SIZE + (r % s) as i32;
+                let y = cp.cy * CHUNK_SIZE + (r / s % s) as i32;
+                let z = cp.cz * CHUNK_SIZE + (r / (s * s) % s) as i32;
+
+                let b = self.get_block(x, y, z);
+                if b.def().random_ticks {
+                    self.random_tick(x, y, z, b);
+                }
+            }
+        }
+    }
+
+    fn random_tick(&mut self, x: i32, y: i32, z: i32, b: Block) {
+        match b {
+            Block::Grass => self.grass_tick(x, y, z),
+            // Zugedeckter Acker wird wieder Erde
+            Block::Farmland if self.get_block(x, y + 1, z).occludes() => {
+                self.set_block(x, y, z, Block::Dirt);
+            }
+            Block::Wheat => self.crop_tick(x, y, z, b),
+            Block::Sapling if self.rng.below(7) == 0 => {
+                self.advance_growth(x, y, z, b, 1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Schneefall in kalten Biomen: pro Chunk ab und zu eine Schicht auf die oberste Fläche
+    /// unter freiem Himmel, auf vorhandenem Schnee eine Schicht mehr
+    fn snow_ticks(&mut self) {
+        if !self.weather.kind().precipitation() || self.weather.rain() < SNOW_MIN_RAIN {
+            return;
+        }
+        let mut positions = self.chunk_positions();
+        positions.sort_unstable_by_key(|c| (c.cx, c.cy, c.cz));
+        for cp in positions {
+            let rng = self.weather.rng();
+            if rng.below(SNOW_CHANCE) != 0 {
+                continue;
+            }
+            let r = rng.next_u32();
+            let x = cp.cx * CHUNK_SIZE + (r % CHUNK_SIZE as u32) as i32;
+            let z = cp.cz * CHUNK_SIZE + (r / CHUNK_SIZE as u32 % CHUNK_SIZE as u32) as i32;
+            if !biome::is_cold(self.seed, x as f32 + 0.5, z as f32 + 0.5) {
+                continue;
+            }
+            let bottom = cp.cy * CHUNK_SIZE;
+            let Some((y, b)) = self.top_block(x, z, bottom, bottom + CHUNK_SIZE - 1) else {
+                continue;
+            };
+            if self.sky_light(x, y + 1, z) < MAX_LIGHT {
+                continue;
+            }
+            match b {
+                Block::Snow => {
+                    let layers = self.get_state(x, y, z);
+                    if layers < state::SNOW_MAX_LAYER {
+                        self.set_block_state(x, y, z, b, layers + 1);
+                    }
+                }
+                _ if b.occludes() => {
+                    self.set_block(x, y + 1, z, Block::Snow);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Wachstum um `stages` Stufen; ein ausgewachsener Setzling wird zum Baum
+    fn advance_growth(&mut self, x: i32, y: i32, z: i32, b: Block, stages: u8) -> bool {
+        let stage = self.get_state(x, y, z);
+        match b {
+            Block::Wheat if stage < state::CROP_MAX_STAGE => {
+                let next = (stage + stages).min(state::CROP_MAX_STAGE);
+                self.set_block_state(x, y, z, b, next)
+            }
+            Block::Sapling if stage < state::SAPLING_MAX_STAGE => {
+                let next = (stage + stages).min(state::SAPLING_MAX_STAGE);
+                self.set_block_state(x, y, z, b, next)
+            }
+            Block::Sapling => self.grow_tree(x, y, z),
+            _ => false,
+        }
+    }
+
+    /// Knochenmehl: 2..=4 Stufen auf einmal. `false` = nichts gewachsen (nicht verbrauchen)
+    pub fn apply_bonemeal(&mut self, x: i32, y: i32, z: i32) -> bool {
+        let b = self.get_block(x, y, z);
+        let stages = self.rng.range_i32(2, 4) as u8;
+        self.advance_growth(x, y, z, b, stages)
+    }
+
+    /// Kleiner Baum mit Stamm ab (x,y,z); scheitert, wenn der Stamm keinen Platz hat
+    pub fn grow_tree(&mut self, x: i32, y: i32, z: i32) -> bool {
+        const TRUNK: i32 = 4;
+        if (y + 1..y + TRUNK).any(|yy| self.get_block(x, yy, z) != Block::Air) {
+            return false;
+        }
+        for yy in y..y + TRUNK {
+            self.set_block_state(x, yy, z, Block::Log, state::AXIS_Y);
+        }
+        let top = y + TRUNK - 1;
+        for ly in top - 1..=top + 1 {
+            for lz in z - 2..=z + 2 {
+                for lx in x - 2..=x + 2 {
+                    if (lx - x).abs() + (lz - z).abs() + (ly - top).abs() <= 3
+                        && self.get_block(lx, ly, lz) == Block::Air
+                    {
+                        self.set_block(lx, ly, lz, Block::Leaves);
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Eine Wachstumsstufe weiter (nicht bei jedem Random-Tick)
+    fn crop_tick(&mut self, x: i32, y: i32, z: i32, b: Block) {
+        if self.rng.below(3) == 0 {
+            self.advance_growth(x, y, z, b, 1);
+        }
+    }
+
+    /// Gras verdorrt unter deckenden Blöcken, sonst breitet es sich auf Erde mit Himmel aus
+    fn grass_tick(&mut self, x: i32, y: i32, z: i32) {
+        if self.get_block(x, y + 1, z).occludes() {
+            self.set_block(x, y, z, Block::Dirt);
+            return;
+        }
+
+        for _ in 0..4 {
+            let tx = x + self.rng.range_i32(-1, 1);
+            let ty = y + self.rng.range_i32(-3, 1);
+            let tz = z + self.rng.range_i32(-1, 1);
+            if self.get_block(tx, ty, tz) == Block::Dirt
+                && !self.get_block(tx, ty + 1, tz).occludes()
+                && self.sees_sky(tx, ty, tz)
+            {
+                self.set_block(tx, ty, tz, Block::Grass);
+            }
+        }
+    }
+
+    /// Freie Sicht nach oben (nur deckende Blöcke blockieren; ungeladen = Luft)
+    pub fn sees_sky(&self, x: i32, y: i32, z: i32) -> bool {
+        (y + 1..=y + SKY_SCAN_HEIGHT).all(|yy| !self.get_block(x, yy, z).occludes())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
     }
 
     pub fn age(&self) -> u64 {
         self.age_ticks
     }
 
+    /// Tageszeit 0..1 (0 = Mittag, 0.5 = Mitternacht)
+    pub fn time_of_day(&self) -> f32 {
+        self.day_ticks as f32 / DAY_LENGTH_TICKS as f32
+    }
+
+    pub fn day_ticks(&self) -> u64 {
+        self.day_ticks
+    }
+
+    pub fn set_day_ticks(&mut self, ticks: u64) {
+        self.day_ticks = ticks % DAY_LENGTH_TICKS;
+    }
+
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    pub fn set_day(&mut self, day: u64) {
+        self.day = day;
+    }
+
+    /// Faktor für gespeichertes Himmelslicht nach Sonnenstand (wird im Shader angewendet)
+    pub fn daylight(&self) -> f32 {
+        let sun_height = (self.time_of_day() * std::f32::consts::TAU).cos();
+        // kurze Dämmerung um Sonnenauf-/-untergang
+        let t = (sun_height * 2.0 + 0.5).clamp(0.0, 1.0);
+        if self.weather.flash() {
+            return 1.0;
+        }
+        (NIGHT_DAYLIGHT + (1.0 - NIGHT_DAYLIGHT) * t) * self.weather.darkening()
+    }
+
+    pub fn weather(&self) -> Weather {
+        self.weather.kind()
+    }
+
+    /// Ticks bis zum nächsten Wetterwechsel
+    pub fn weather_ticks_left(&self) -> u64 {
+        self.weather.ticks_left()
+    }
+
+    /// Wetter setzen (ohne Dauer: zufällig); blendet über
+    pub fn set_weather(&mut self, kind: Weather, ticks: Option<u64>) {
+        self.weather.set(kind, ticks);
+    }
+
+    /// Gespeichertes Wetter übernehmen, sofort in voller Stärke
+    pub fn restore_weather(&mut self, kind: Weather, ticks: u64) {
+        self.weather.restore(kind, ticks);
+    }
+
+    /// Niederschlag 0..1
+    pub fn rain_level(&self) -> f32 {
+        self.weather.rain()
+    }
+
     /// Optional: Debug/Info – Anzahl geladener Chunks
     pub fn chunk_count(&self) -> usize {
         self.chunks.len()
     }
 
+    /// Speicher der geladenen Chunks samt Block-Entities und wartender Drops
+    pub fn memory_report(&self) -> MemoryReport {
+        let mut report = MemoryReport {
+            chunks: self.chunks.len(),
+            ..MemoryReport::default()
+        };
+        for ch in self.chunks.values() {
+            report.chunk_bytes += ch.heap_bytes();
+            report.entity_bytes += ch.block_entity_bytes();
+        }
+        report.entity_bytes += self.drops.capacity() * size_of::<ItemDrop>();
+        report
+    }
+
     pub fn chunk_positions(&self) -> Vec<ChunkPos> {
         self.chunks.keys().copied().collect()
     }
 
+    /// Kleinster Quader um alle Nicht-Luft-Blöcke der geladenen Chunks
+    pub fn loaded_bounds(&self) -> Option<(BlockPos, BlockPos)> {
+        let mut bounds: Option<(BlockPos, BlockPos)> = None;
+        for (cp, ch) in &self.chunks {
+            for ly in 0..CHUNK_SIZE {
+                for lz in 0..CHUNK_SIZE {
+                    for lx in 0..CHUNK_SIZE {
+                        if ch.get_local(lx, ly, lz) == Block::Air {
+                            continue;
+                        }
+                        let p = (
+                            cp.cx * CHUNK_SIZE + lx,
+                            cp.cy * CHUNK_SIZE + ly,
+                            cp.cz * CHUNK_SIZE + lz,
+                        );
+                        let (min, max) = bounds.get_or_insert((p, p));
+                        *min = (min.0.min(p.0), min.1.min(p.1), min.2.min(p.2));
+                        *max = (max.0.max(p.0), max.1.max(p.1), max.2.max(p.2));
+                    }
+                }
+            }
+        }
+        bounds
+    }
+
+    pub fn chunk(&self, pos: ChunkPos) -> Option<&Chunk<Block>> {
+        self.chunks.get(&pos)
+    }
+
+    /// Chunk vom Server übernehmen (ersetzt einen vorhandenen, wird neu gemesht)
+    pub fn put_chunk(&mut self, mut ch: Chunk<Block>) {
+        ch.dirty = true;
+        ch.modified = false;
+        self.chunks.insert(ch.pos, ch);
+    }
+
+    /// Blockänderung vom Server in den Clientspiegel übernehmen (ohne Licht und Block-Ticks)
+    pub fn apply_remote_block(&mut self, (x, y, z): BlockPos, b: Block, state: u8) {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        if let Some(ch) = self.chunks.get_mut(&cp) {
+            ch.set_local_with_state(in_chunk(x), in_chunk(y), in_chunk(z), b, state);
+            self.mark_border_dirty(x, y, z);
+        }
+    }
+
+    /// Licht vom Server übernehmen
+    pub fn put_chunk_light(&mut self, cp: ChunkPos, data: &[u8]) {
+        if let Some(ch) = self.chunks.get_mut(&cp) {
+            ch.set_light_data(data);
+        }
+    }
+
+    /// Nur aus dem Speicher entfernen (Clientspiegel; nichts zu speichern)
+    pub fn drop_chunk(&mut self, pos: ChunkPos) -> bool {
+        self.chunks.remove(&pos).is_some()
+    }
+
     pub fn has_chunk(&self, pos: ChunkPos) -> bool {
         self.chunks.contains_key(&pos)
     }
 
     pub fn unload_chunk(&mut self, pos: ChunkPos) -> bool {
-        self.chunks.remove(&pos).is_some()
+        let Some(ch) = self.chunks.remove(&pos) else {
+            return false;
+        };
+        self.light.send(LightJob::Unload(pos));
+        if ch.modified
+            && let Some(storage) = &self.storage
+            && let Err(e) = storage.save_chunk(&ch)
+        {
+            log::warn!("Chunk {:?} nicht gespeichert: {e:#}", pos);
+        }
+        true
+    }
+
+    /// Speichert alle veränderten Chunks
+    pub fn save_all(&mut self) {
+        let Some(storage) = &self.storage else {
+            return;
+        };
+        for ch in self.chunks.values_mut() {
+            if !ch.modified {
+                continue;
+            }
+            match storage.save_chunk(ch) {
+                Ok(()) => ch.modified = false,
+                Err(e) => log::warn!("Chunk {:?} nicht gespeichert: {e:#}", ch.pos),
+            }
+        }
+    }
+
+    /// Neuer Chunk aus dem Generator; gilt als unverändert, wird also nur gespeichert,
+    /// wenn danach jemand daran baut
+    fn generate_chunk(&self, pos: ChunkPos) -> Chunk<Block> {
+        let mut ch = Chunk::new(pos);
+        if let Some(terrain) = &self.terrain {
+            terrain.fill(&mut ch);
+            ch.modified = false;
+        }
+        ch
     }
 
     fn get_or_create_chunk(&mut self, pos: ChunkPos) -> &mut Chunk<Block> {
-        self.chunks.entry(pos).or_insert_with(|| Chunk::new(pos))
+        if !self.chunks.contains_key(&pos) {
+            let _span = tracing::trace_span!("chunk_gen", ?pos).entered();
+            let ch = self
+                .storage
+                .as_ref()
+                .and_then(|s| s.load_chunk(pos))
+                .unwrap_or_else(|| self.generate_chunk(pos));
+            self.light.send(LightJob::Load(pos, ch.blocks().to_vec()));
+            self.chunks.insert(pos, ch);
+            self.events.publish(ChunkLoaded(pos));
+        }
+        self.chunks.get_mut(&pos).expect("Chunk gerade eingefügt")
     }
 
     pub fn get_block(&self, x: i32, y: i32, z: i32) -> Block {
@@ -95,55 +847,245 @@ impl World {
         }
     }
 
+    pub fn get_state(&self, x: i32, y: i32, z: i32) -> u8 {
+        let cp = ChunkPos {
+            cx: chunk_coord(x),
+            cy: chunk_coord(y),
+            cz: chunk_coord(z),
+        };
+
+        match self.chunks.get(&cp) {
+            Some(ch) => ch.get_state_local(in_chunk(x), in_chunk(y), in_chunk(z)),
+            None => 0,
+        }
+    }
+
     pub fn set_block(&mut self, x: i32, y: i32, z: i32, b: Block) -> bool {
-        let cx = chunk_coord(x);
-        let cy = chunk_coord(y);
-        let cz = chunk_coord(z);
+        self.set_block_state(x, y, z, b, 0)
+    }
 
+    pub fn set_block_state(&mut self, x: i32, y: i32, z: i32, b: Block, state: u8) -> bool {
         let lx = in_chunk(x);
         let ly = in_chunk(y);
         let lz = in_chunk(z);
 
-        let cp = ChunkPos { cx, cy, cz };
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+
+        let mut removed_leaf_support = false;
 
         // Chunk anlegen + setzen (setzt dirty ohnehin)
-        {
+        let old = {
             let ch = self.get_or_create_chunk(cp);
-            ch.set_local(lx, ly, lz, b);
-        }
+            let old = ch.get_local(lx, ly, lz);
+            ch.set_local_with_state(lx, ly, lz, b, state);
+
+            // Block-Entity-Lebenszyklus: nur bei Typwechsel (State-Änderung behält Inhalt)
+            if old != b {
+                if matches!(old, Block::Log | Block::Leaves) {
+                    removed_leaf_support = true;
+                }
+                if let Some(be) = ch.remove_block_entity(lx, ly, lz) {
+                    self.removed_block_entities.push(((x, y, z), be));
+                }
+                let ch = self.get_or_create_chunk(cp);
+                if let Some(kind) = b.def().block_entity {
+                    ch.insert_block_entity(lx, ly, lz, kind.create());
+                }
+            }
+            old
+        };
+        self.events.publish(BlockChanged {
+            pos: (x, y, z),
+            old,
+            block: b,
+            state,
+        });
 
         // Wenn an Chunk-Kante geändert → Nachbarn dirty
-        if lx == 0 {
-            self.mark_dirty(ChunkPos { cx: cx - 1, cy, cz });
-        } else if lx == CHUNK_SIZE - 1 {
-            self.mark_dirty(ChunkPos { cx: cx + 1, cy, cz });
+        self.mark_border_dirty(x, y, z);
+
+        if old != b {
+            self.light.send(LightJob::SetBlock((x, y, z), b));
         }
 
-        if ly == 0 {
-            self.mark_dirty(ChunkPos { cx, cy: cy - 1, cz });
-        } else if ly == CHUNK_SIZE - 1 {
-            self.mark_dirty(ChunkPos { cx, cy: cy + 1, cz });
+        if removed_leaf_support {
+            self.schedule_leaf_checks(x, y, z);
         }
 
-        if lz == 0 {
-            self.mark_dirty(ChunkPos { cx, cy, cz: cz - 1 });
-        } else if lz == CHUNK_SIZE - 1 {
-            self.mark_dirty(ChunkPos { cx, cy, cz: cz + 1 });
+        for (dx, dy, dz) in NEIGHBORS {
+            self.neighbor_changed(x + dx, y + dy, z + dz);
         }
 
         true
     }
 
-    pub fn break_block(&mut self, x: i32, y: i32, z: i32) -> bool {
+    /// Bulk-Bearbeitung eines Quaders (min..=max): `f` bekommt Position und alten Block
+    /// und liefert neuen Block samt State (None = unverändert). Rückgabe: Zahl geänderter Blöcke
+    pub fn fill_region(
+        &mut self,
+        min: BlockPos,
+        max: BlockPos,
+        mut f: impl FnMut(BlockPos, Block) -> Option<(Block, u8)>,
+    ) -> usize {
+        let mut changed = 0;
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                for z in min.2..=max.2 {
+                    let old = self.get_block(x, y, z);
+                    if let Some((b, st)) = f((x, y, z), old)
+                        && (b, st) != (old, self.get_state(x, y, z))
+                        && self.set_block_state(x, y, z, b, st)
+                    {
+                        changed += 1;
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    pub fn block_entity(&self, x: i32, y: i32, z: i32) -> Option<&BlockEntity> {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        self.chunks
+            .get(&cp)?
+            .block_entity_local(in_chunk(x), in_chunk(y), in_chunk(z))
+    }
+
+    pub fn block_entity_mut(&mut self, x: i32, y: i32, z: i32) -> Option<&mut BlockEntity> {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        self.chunks
+            .get_mut(&cp)?
+            .block_entity_local_mut(in_chunk(x), in_chunk(y), in_chunk(z))
+    }
+
+    /// Abgebaute Block-Entities abholen (z.B. um Kisteninhalt zu droppen)
+    pub fn take_removed_block_entities(&mut self) -> Vec<(BlockPos, BlockEntity)> {
+        std::mem::take(&mut self.removed_block_entities)
+    }
+
+    /// Nachbar von (x,y,z) hat sich geändert: abhängige Blöcke prüfen
+    fn neighbor_changed(&mut self, x: i32, y: i32, z: i32) {
+        let b = self.get_block(x, y, z);
+        let st = self.get_state(x, y, z);
+
+        // Hohe Blöcke: fehlt die andere Hälfte, verschwindet diese ohne eigenen Drop
+        if b.is_tall() {
+            let partner_y = if st & state::UPPER_HALF != 0 { y - 1 } else { y + 1 };
+            if self.get_block(x, partner_y, z) != b {
+                self.set_block(x, y, z, Block::Air);
+                return;
+            }
+        }
+
+        let unsupported = match b {
+            Block::Torch => !self.has_support(x, y, z, st),
+            Block::Wheat => self.get_block(x, y - 1, z) != Block::Farmland,
+            // nur die untere Hälfte braucht Boden
+            Block::Door | Block::TallGrass if st & state::UPPER_HALF == 0 => {
+                !self.can_stand_on(b, self.get_block(x, y - 1, z))
+            }
+            Block::Sapling => !self.can_stand_on(b, self.get_block(x, y - 1, z)),
+            _ => false,
+        };
+        if unsupported {
+            // fällt ab
+            self.set_block(x, y, z, Block::Air);
+            for stack in b.drops(st) {
+                self.drop_item((x, y, z), stack);
+            }
+        }
+    }
+
+    /// Trägt der Block in `st`-Richtung die Fackel?
+    fn has_support(&self, x: i32, y: i32, z: i32, st: u8) -> bool {
+        let (dx, dy, dz) = state::torch_support(st);
+        self.get_block(x + dx, y + dy, z + dz).occludes()
+    }
+
+    /// Platzierungsregeln aus der Registry/Shape prüfen
+    pub fn can_place(&self, x: i32, y: i32, z: i32, b: Block, st: u8) -> bool {
+        match b {
+            Block::Torch => self.has_support(x, y, z, st),
+            Block::Wheat => self.get_block(x, y - 1, z) == Block::Farmland,
+            Block::Sapling => self.can_stand_on(b, self.get_block(x, y - 1, z)),
+            // hohe Blöcke: Boden unten und Platz für die obere Hälfte
+            _ if b.is_tall() => {
+                self.can_stand_on(b, self.get_block(x, y - 1, z))
+                    && self.get_block(x, y + 1, z) == Block::Air
+            }
+            _ => true,
+        }
+    }
+
+    /// Untergrund für bodengebundene Blöcke
+    fn can_stand_on(&self, b: Block, below: Block) -> bool {
+        match b {
+            Block::TallGrass | Block::Sapling => matches!(below, Block::Grass | Block::Dirt),
+            _ => below.occludes(),
+        }
+    }
+
+    /// Item an `pos` fallen lassen
+    pub fn drop_item(&mut self, pos: BlockPos, stack: ItemStack) {
+        self.drops.push(ItemDrop { pos, stack, since: self.age_ticks });
+    }
+
+    pub fn take_drops(&mut self) -> Vec<ItemDrop> {
+        std::mem::take(&mut self.drops)
+    }
+
+    /// Nicht (ganz) Eingesammeltes wieder hinlegen; das Alter bleibt
+    pub fn restore_drops(&mut self, drops: Vec<ItemDrop>) {
+        self.drops.extend(drops);
+    }
+
+    /// Baut ab; `harvest = false` (falsches Werkzeug) lässt die Drops weg
+    pub fn break_block(&mut self, x: i32, y: i32, z: i32, harvest: bool) -> bool {
+        let b = self.get_block(x, y, z);
+        if harvest {
+            for stack in b.drops(self.get_state(x, y, z)) {
+                self.drop_item((x, y, z), stack);
+            }
+        }
         self.set_block(x, y, z, Block::Air)
     }
 
-    pub fn place_block(&mut self, x: i32, y: i32, z: i32, b: Block) -> bool {
-        self.set_block(x, y, z, b)
+    pub fn place_block(&mut self, x: i32, y: i32, z: i32, b: Block, state: u8) -> bool {
+        if !self.can_place(x, y, z, b, state) {
+            return false;
+        }
+        if b.is_tall() {
+            // beide Hälften erst nach erfolgreicher Prüfung schreiben
+            let lower = state & !state::UPPER_HALF;
+            self.set_block_state(x, y, z, b, lower);
+            return self.set_block_state(x, y + 1, z, b, lower | state::UPPER_HALF);
+        }
+        self.set_block_state(x, y, z, b, state)
+    }
+
+    /// Rechtsklick auf einen Block ohne eigenen Screen (z.B. Tür öffnen/schließen)
+    pub fn interact(&mut self, x: i32, y: i32, z: i32) -> bool {
+        let b = self.get_block(x, y, z);
+        match b {
+            Block::Door => {
+                let st = self.get_state(x, y, z);
+                let lower_y = if st & state::UPPER_HALF != 0 { y - 1 } else { y };
+                let lower = self.get_state(x, lower_y, z) ^ state::DOOR_OPEN;
+                self.set_block_state(x, lower_y, z, b, lower);
+                self.set_block_state(x, lower_y + 1, z, b, lower | state::UPPER_HALF);
+                true
+            }
+            _ => false,
+        }
     }
 
     pub fn is_solid(&self, x: i32, y: i32, z: i32) -> bool {
-        self.get_block(x, y, z) != Block::Air
+        let b = self.get_block(x, y, z);
+        match b {
+            // offene Tür ist begehbar
+            Block::Door => self.get_state(x, y, z) & state::DOOR_OPEN == 0,
+            _ => b.is_solid(),
+        }
     }
 
     /// Stellt sicher, dass ein Chunk existiert. Nützlich für Streaming/Preload.
@@ -159,6 +1101,13 @@ impl World {
             }
         }
 
+        // Graswiese am Spawn – breitet sich per Random-Tick über die Erde aus
+        for x in 0..16 {
+            for z in 0..16 {
+                self.set_block(x, 0, z, Block::Grass);
+            }
+        }
+
         // Test-Wand wie vorher (z=8, x=3..5, y=1..3)
         for y in 1..=3 {
             for x in 3..=5 {
@@ -166,24 +1115,115 @@ impl World {
             }
         }
 
+        // Transparenz-Testobjekte: Glasfenster, Blätterbusch, kleiner Teich
+        for y in 1..=2 {
+            self.set_block(4, y, 8, Block::Glass);
+        }
+        // Kleiner Baum: Stamm + Krone (Blätter verrotten, wenn der Stamm fällt)
+        self.grow_tree(11, 1, 13);
+        // daneben ein Setzling
+        self.place_block(14, 1, 11, Block::Sapling, 0);
+        for z in 4..=6 {
+            for x in 12..=14 {
+                self.set_block(x, 0, z, Block::Water);
+            }
+        }
+
+        // Block-States: liegende/stehende Stämme und Stufen (unten/oben)
+        self.set_block_state(7, 1, 10, Block::Log, state::AXIS_Y);
+        self.set_block_state(7, 2, 10, Block::Log, state::AXIS_Y);
+        for x in 8..=10 {
+            self.set_block_state(x, 1, 10, Block::Log, state::AXIS_X);
+        }
+        self.set_block_state(6, 1, 2, Block::StoneSlab, state::SLAB_BOTTOM);
+        self.set_block_state(7, 1, 2, Block::StoneSlab, state::SLAB_TOP);
+
+        // Fackeln: auf dem Boden und an der Test-Wand
+        self.set_block_state(2, 1, 2, Block::Torch, state::TORCH_FLOOR);
+        self.set_block_state(5, 2, 7, Block::Torch, state::TORCH_WALL_NZ);
+
+        // Tür in der Steinwand und hohes Gras auf der Wiese
+        self.place_block(6, 1, 8, Block::Door, 0);
+        self.place_block(3, 1, 11, Block::TallGrass, 0);
+        self.place_block(9, 1, 3, Block::TallGrass, 0);
+
+        // Kleines Feld: Acker mit Weizen in allen Wachstumsstufen
+        for x in 0..8 {
+            for z in 14..=15 {
+                self.set_block(x, 0, z, Block::Farmland);
+                self.set_block_state(x, 1, z, Block::Wheat, x as u8);
+            }
+        }
+
+        // Kiste mit etwas Inhalt
+        self.set_block(2, 1, 5, Block::Chest);
+        if let Some(BlockEntity::Container(inv)) = self.block_entity_mut(2, 1, 5) {
+            inv.set(0, Some(ItemStack::new(Block::Glass, 16)));
+            inv.set(1, Some(ItemStack::new(Block::Log, 8)));
+            inv.set(13, Some(ItemStack::new(Block::Leaves, 32)));
+        }
+
         // Optional: ein paar Chunks "anlegen", damit HashMap schon gefüllt ist
         // (nicht notwendig, aber manchmal hilfreich beim Debuggen)
         let _ = CHUNK_SIZE; // nur, damit Import nicht als "unused" gilt, falls du’s nicht nutzt
     }
 
-    pub fn raycast_first_solid(
+    /// Zielstrahl: erster anvisierbarer Block ab `origin`. Wasser und hohes Gras werden
+    /// durchschaut (dahinter abbauen, durch Wasser hindurch bauen).
+    /// `dir` muss nicht normiert sein; Entfernungen gelten in Blöcken.
+    pub fn raycast(
         &self,
-        start_x: f32,
-        start_y: f32,
-        start_z: f32,
-        dir_x: f32,
-        dir_y: f32,
-        dir_z: f32,
+        origin: (f32, f32, f32),
+        dir: (f32, f32, f32),
         max_dist: f32,
-    ) -> Option<(i32, i32, i32, Block, (i32, i32, i32))> {
-        if dir_x == 0.0 && dir_y == 0.0 && dir_z == 0.0 {
+    ) -> Option<RaycastHit> {
+        self.raycast_by(origin, dir, max_dist, Block::is_targetable)
+    }
+
+    /// Erste Flüssigkeit entlang des Strahls (Eimer, Unterwasser-Effekte)
+    pub fn raycast_fluid(
+        &self,
+        origin: (f32, f32, f32),
+        dir: (f32, f32, f32),
+        max_dist: f32,
+    ) -> Option<RaycastHit> {
+        self.raycast_by(origin, dir, max_dist, Block::is_fluid)
+    }
+
+    /// Erster Block, für den `hits` gilt (DDA durchs Blockraster). Null-, NaN- oder
+    /// unendliche Richtungen und Ursprünge außerhalb von `MAX_RAYCAST_COORD` treffen nichts.
+    fn raycast_by(
+        &self,
+        origin: (f32, f32, f32),
+        dir: (f32, f32, f32),
+        max_dist: f32,
+        hits: impl Fn(Block) -> bool,
+    ) -> Option<RaycastHit> {
+        let (start_x, start_y, start_z) = origin;
+        let inside = |v: f32| v.abs() < MAX_RAYCAST_COORD;
+        if !(inside(start_x) && inside(start_y) && inside(start_z)) {
             return None;
         }
+        if max_dist.is_nan() || max_dist < 0.0 {
+            return None;
+        }
+        let max_dist = max_dist.min(MAX_RAYCAST_DIST);
+        // Erst auf die größte Komponente skalieren: sehr große Richtungen laufen beim
+        // Quadrieren sonst über, sehr kleine werden zu null
+        let scale = dir.0.abs().max(dir.1.abs()).max(dir.2.abs());
+        if !(scale.is_finite() && scale > 0.0) {
+            return None;
+        }
+        let dir = (dir.0 / scale, dir.1 / scale, dir.2 / scale);
+        let len = (dir.0 * dir.0 + dir.1 * dir.1 + dir.2 * dir.2).sqrt();
+        let (dir_x, dir_y, dir_z) = (dir.0 / len, dir.1 / len, dir.2 / len);
+        let hit = |pos: BlockPos, block, face, t: f32| RaycastHit {
+            pos,
+            block,
+            face,
+            distance: t,
+            point: (start_x + dir_x * t, start_y + dir_y * t, start_z + dir_z * t),
+        };
 
         let mut vx = start_x.floor() as i32;
         let mut vy = start_y.floor() as i32;
@@ -264,35 +1304,36 @@ impl World {
         let t_delta_z = inv_z;
 
         let mut t = 0.0;
-        let mut hit_normal = (0, 0, 0);
+        let mut face;
 
         // Start-Block prüfen
         let b0 = self.get_block(vx, vy, vz);
-        if b0 != Block::Air {
-            return Some((vx, vy, vz, b0, (0, 0, 0)));
+        if hits(b0) {
+            return Some(hit((vx, vy, vz), b0, None, 0.0));
         }
 
         while t <= max_dist {
+            // Die getroffene Face zeigt dem Strahl entgegen
             if t_max_x < t_max_y && t_max_x < t_max_z {
                 vx += step_x;
                 t = t_max_x;
                 t_max_x += t_delta_x;
-                hit_normal = (-step_x, 0, 0);
+                face = if step_x > 0 { Face::West } else { Face::East };
             } else if t_max_y < t_max_z {
                 vy += step_y;
                 t = t_max_y;
                 t_max_y += t_delta_y;
-                hit_normal = (0, -step_y, 0);
+                face = if step_y > 0 { Face::Down } else { Face::Up };
             } else {
                 vz += step_z;
                 t = t_max_z;
                 t_max_z += t_delta_z;
-                hit_normal = (0, 0, -step_z);
+                face = if step_z > 0 { Face::North } else { Face::South };
             }
 
             let b = self.get_block(vx, vy, vz);
-            if b != Block::Air {
-                return Some((vx, vy, vz, b, hit_normal));
+            if hits(b) && t <= max_dist {
+                return Some(hit((vx, vy, vz), b, Some(face), t));
             }
         }
 
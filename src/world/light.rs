@@ -0,0 +1,402 @@
+//! Zwei Lichtkanäle:
+//! - Himmelslicht: volle Stärke senkrecht nach unten, sonst BFS-Ausbreitung mit -1 pro Schritt.
+//! - Blocklicht: von leuchtenden Blöcken der Registry aus per BFS mit -1 pro Schritt.
+//!
+//! Änderungen werden inkrementell nachgeführt (Entfernen in zwei Durchläufen, dann Auffüllen).
+//! Die Berechnung läuft auf einem eigenen Thread mit einer Kopie der Blockdaten; die Welt
+//! schickt Lade-/Entlade-/Blockänderungen in Reihenfolge und übernimmt fertige Lichtdaten.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use super::{BlockPos, NEIGHBORS};
+use crate::block::Block;
+use crate::chunk::{CHUNK_SIZE, CHUNK_VOL, ChunkPos, chunk_coord, idx, in_chunk};
+
+pub const MAX_LIGHT: u8 = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightChannel {
+    Sky,
+    Block,
+}
+
+const CHANNELS: [LightChannel; 2] = [LightChannel::Sky, LightChannel::Block];
+
+/// Lässt der Block Licht durch?
+#[inline]
+fn passes_light(b: Block) -> bool {
+    !b.occludes()
+}
+
+/// Eigene Leuchtkraft eines Blocks im Kanal
+#[inline]
+fn emission(channel: LightChannel, b: Block) -> u8 {
+    match channel {
+        LightChannel::Sky => 0,
+        LightChannel::Block => b.light(),
+    }
+}
+
+/// Aufträge an den Licht-Thread (werden strikt in Reihenfolge abgearbeitet)
+pub enum LightJob {
+    Load(ChunkPos, Vec<Block>),
+    Unload(ChunkPos),
+    SetBlock(BlockPos, Block),
+    /// Antwortet mit `Flushed`, sobald alles davor berechnet ist
+    Flush,
+}
+
+pub enum LightResult {
+    /// Neue Lichtdaten (gepackt wie im Chunk) für veränderte Chunks
+    Light(Vec<(ChunkPos, Vec<u8>)>),
+    Flushed,
+}
+
+/// Handle auf den Licht-Thread; endet, wenn das Handle fallen gelassen wird
+pub struct LightWorker {
+    jobs: Sender<LightJob>,
+    results: Receiver<LightResult>,
+}
+
+impl LightWorker {
+    pub fn spawn() -> Self {
+        let (jobs, job_rx) = mpsc::channel();
+        let (result_tx, results) = mpsc::channel();
+        thread::Builder::new()
+            .name("light".into())
+            .spawn(move || run_worker(job_rx, result_tx))
+            .expect("Licht-Thread konnte nicht gestartet werden");
+        Self { jobs, results }
+    }
+
+    pub fn send(&self, job: LightJob) {
+        if self.jobs.send(job).is_err() {
+            log::error!("Licht-Thread beendet, Auftrag verworfen");
+        }
+    }
+
+    pub fn try_recv(&self) -> Option<LightResult> {
+        self.results.try_recv().ok()
+    }
+
+    pub fn recv(&self) -> Option<LightResult> {
+        self.results.recv().ok()
+    }
+}
+
+fn run_worker(jobs: Receiver<LightJob>, results: Sender<LightResult>) {
+    let mut engine = LightEngine::default();
+    while let Ok(first) = jobs.recv() {
+        // alles Anstehende in einem Rutsch, Ergebnisse erst danach verschicken
+        let mut next = Some(first);
+        while let Some(job) = next {
+            match job {
+                LightJob::Load(cp, blocks) => engine.load(cp, blocks),
+                LightJob::Unload(cp) => engine.unload(cp),
+                LightJob::SetBlock((x, y, z), b) => engine.set_block(x, y, z, b),
+                LightJob::Flush => {
+                    let light = engine.take_changed();
+                    if (!light.is_empty() && results.send(LightResult::Light(light)).is_err())
+                        || results.send(LightResult::Flushed).is_err()
+                    {
+                        return;
+                    }
+                }
+            }
+            next = jobs.try_recv().ok();
+        }
+
+        let light = engine.take_changed();
+        if !light.is_empty() && results.send(LightResult::Light(light)).is_err() {
+            return;
+        }
+    }
+}
+
+struct LightChunk {
+    blocks: Vec<Block>,
+    /// Himmelslicht im oberen, Blocklicht im unteren Nibble
+    light: Vec<u8>,
+}
+
+/// Lichtdaten aller geladenen Chunks (nur auf dem Licht-Thread)
+#[derive(Default)]
+struct LightEngine {
+    chunks: HashMap<ChunkPos, LightChunk>,
+    /// Chunks mit geändertem Licht (inkl. Nachbarn, deren Mesh Randlicht liest)
+    changed: HashSet<ChunkPos>,
+}
+
+impl LightEngine {
+    fn take_changed(&mut self) -> Vec<(ChunkPos, Vec<u8>)> {
+        self.changed
+            .drain()
+            .filter_map(|cp| self.chunks.get(&cp).map(|ch| (cp, ch.light.clone())))
+            .collect()
+    }
+
+    fn get_block(&self, x: i32, y: i32, z: i32) -> Block {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        self.chunks
+            .get(&cp)
+            .map_or(Block::Air, |ch| ch.blocks[idx(in_chunk(x), in_chunk(y), in_chunk(z))])
+    }
+
+    fn light_opt(&self, channel: LightChannel, x: i32, y: i32, z: i32) -> Option<u8> {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        let l = self.chunks.get(&cp)?.light[idx(in_chunk(x), in_chunk(y), in_chunk(z))];
+        Some(match channel {
+            LightChannel::Sky => l >> 4,
+            LightChannel::Block => l & 0x0F,
+        })
+    }
+
+    /// Himmelslicht, ungeladen = freier Himmel
+    fn sky_light(&self, x: i32, y: i32, z: i32) -> u8 {
+        self.light_opt(LightChannel::Sky, x, y, z).unwrap_or(MAX_LIGHT)
+    }
+
+    fn set_light(&mut self, channel: LightChannel, x: i32, y: i32, z: i32, v: u8) {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        let Some(ch) = self.chunks.get_mut(&cp) else {
+            return;
+        };
+        let (lx, ly, lz) = (in_chunk(x), in_chunk(y), in_chunk(z));
+        let l = &mut ch.light[idx(lx, ly, lz)];
+        *l = match channel {
+            LightChannel::Sky => (*l & 0x0F) | (v << 4),
+            LightChannel::Block => (*l & 0xF0) | v,
+        };
+
+        // Faces der Nachbarchunks lesen dieses Licht mit
+        self.changed.insert(cp);
+        let edge = |l: i32| {
+            if l == 0 {
+                -1
+            } else if l == CHUNK_SIZE - 1 {
+                1
+            } else {
+                0
+            }
+        };
+        let (ex, ey, ez) = (edge(lx), edge(ly), edge(lz));
+        for n in [
+            ChunkPos::new(cp.cx + ex, cp.cy, cp.cz),
+            ChunkPos::new(cp.cx, cp.cy + ey, cp.cz),
+            ChunkPos::new(cp.cx, cp.cy, cp.cz + ez),
+        ] {
+            if n != cp && self.chunks.contains_key(&n) {
+                self.changed.insert(n);
+            }
+        }
+    }
+
+    fn unload(&mut self, cp: ChunkPos) {
+        self.chunks.remove(&cp);
+        self.changed.remove(&cp);
+    }
+
+    /// Frisch geladener/erzeugter Chunk: Quellen setzen, Licht der Nachbarn übernehmen
+    fn load(&mut self, cp: ChunkPos, blocks: Vec<Block>) {
+        debug_assert_eq!(blocks.len(), CHUNK_VOL);
+        self.chunks.insert(
+            cp,
+            LightChunk {
+                blocks,
+                light: vec![0; CHUNK_VOL],
+            },
+        );
+        self.changed.insert(cp);
+
+        let (ox, oy, oz) = (cp.cx * CHUNK_SIZE, cp.cy * CHUNK_SIZE, cp.cz * CHUNK_SIZE);
+        let top = oy + CHUNK_SIZE - 1;
+
+        // Himmel: Spalten von oben füllen (Chunk darüber ungeladen = Luft)
+        let mut sky = VecDeque::new();
+        for lz in 0..CHUNK_SIZE {
+            for lx in 0..CHUNK_SIZE {
+                let (x, z) = (ox + lx, oz + lz);
+                if self.sky_light(x, top + 1, z) < MAX_LIGHT {
+                    continue;
+                }
+                for y in (oy..=top).rev() {
+                    if !passes_light(self.get_block(x, y, z)) {
+                        break;
+                    }
+                    self.set_light(LightChannel::Sky, x, y, z, MAX_LIGHT);
+                    sky.push_back((x, y, z));
+                }
+            }
+        }
+
+        // Blocklicht: alle leuchtenden Blöcke im Chunk
+        let mut block = VecDeque::new();
+        for ly in 0..CHUNK_SIZE {
+            for lz in 0..CHUNK_SIZE {
+                for lx in 0..CHUNK_SIZE {
+                    let (x, y, z) = (ox + lx, oy + ly, oz + lz);
+                    let emit = self.get_block(x, y, z).light();
+                    if emit > 0 {
+                        self.set_light(LightChannel::Block, x, y, z, emit);
+                        block.push_back((x, y, z));
+                    }
+                }
+            }
+        }
+
+        // Licht aus angrenzenden Chunks hereinlassen
+        for (x, y, z) in chunk_border_outside(cp) {
+            if self.light_opt(LightChannel::Sky, x, y, z).is_some_and(|l| l > 1) {
+                sky.push_back((x, y, z));
+            }
+            if self.light_opt(LightChannel::Block, x, y, z).is_some_and(|l| l > 1) {
+                block.push_back((x, y, z));
+            }
+        }
+        self.spread_light(LightChannel::Sky, sky);
+        self.spread_light(LightChannel::Block, block);
+
+        // Darunterliegender Chunk hatte hier bisher "Himmel": abgedeckte Spalten abdunkeln
+        let mut removals = Vec::new();
+        for lz in 0..CHUNK_SIZE {
+            for lx in 0..CHUNK_SIZE {
+                let (x, z) = (ox + lx, oz + lz);
+                if self.sky_light(x, oy, z) < MAX_LIGHT
+                    && self.light_opt(LightChannel::Sky, x, oy - 1, z) == Some(MAX_LIGHT)
+                {
+                    self.set_light(LightChannel::Sky, x, oy - 1, z, 0);
+                    removals.push(((x, oy - 1, z), MAX_LIGHT));
+                }
+            }
+        }
+        if !removals.is_empty() {
+            self.remove_light(LightChannel::Sky, removals);
+        }
+    }
+
+    /// Blockwechsel: Licht entfernen bzw. wieder einströmen lassen
+    fn set_block(&mut self, x: i32, y: i32, z: i32, new: Block) {
+        let cp = ChunkPos::new(chunk_coord(x), chunk_coord(y), chunk_coord(z));
+        let Some(ch) = self.chunks.get_mut(&cp) else {
+            return;
+        };
+        let i = idx(in_chunk(x), in_chunk(y), in_chunk(z));
+        let old = std::mem::replace(&mut ch.blocks[i], new);
+
+        for channel in CHANNELS {
+            let same_opacity = passes_light(old) == passes_light(new);
+            if same_opacity && emission(channel, old) == emission(channel, new) {
+                continue;
+            }
+
+            // altes Licht an der Stelle (auch einer entfernten Quelle) in zwei Durchläufen löschen
+            let level = self.light_opt(channel, x, y, z).unwrap_or(0);
+            if level > 0 && (!passes_light(new) || emission(channel, old) > 0) {
+                self.set_light(channel, x, y, z, 0);
+                self.remove_light(channel, vec![((x, y, z), level)]);
+            }
+
+            let emit = emission(channel, new);
+            if emit > 0 {
+                self.set_light(channel, x, y, z, emit);
+                self.spread_light(channel, VecDeque::from([(x, y, z)]));
+            } else if passes_light(new) {
+                // Nachbarn breiten sich erneut aus und füllen die Lücke
+                let queue = NEIGHBORS
+                    .iter()
+                    .map(|(dx, dy, dz)| (x + dx, y + dy, z + dz))
+                    .filter(|&(nx, ny, nz)| {
+                        self.light_opt(channel, nx, ny, nz).is_some_and(|l| l > 0)
+                    })
+                    .collect();
+                self.spread_light(channel, queue);
+            }
+        }
+    }
+
+    /// BFS-Ausbreitung ab den Positionen in `queue` (nur in geladenen Chunks)
+    fn spread_light(&mut self, channel: LightChannel, mut queue: VecDeque<BlockPos>) {
+        while let Some((x, y, z)) = queue.pop_front() {
+            let level = self.light_opt(channel, x, y, z).unwrap_or(0);
+            if level <= 1 {
+                continue;
+            }
+            for (dx, dy, dz) in NEIGHBORS {
+                let (nx, ny, nz) = (x + dx, y + dy, z + dz);
+                let Some(current) = self.light_opt(channel, nx, ny, nz) else {
+                    continue;
+                };
+                if !passes_light(self.get_block(nx, ny, nz)) {
+                    continue;
+                }
+                // Direktes Himmelslicht fällt ohne Verlust nach unten
+                let next = if channel == LightChannel::Sky && dy == -1 && level == MAX_LIGHT {
+                    MAX_LIGHT
+                } else {
+                    level - 1
+                };
+                if current < next {
+                    self.set_light(channel, nx, ny, nz, next);
+                    queue.push_back((nx, ny, nz));
+                }
+            }
+        }
+    }
+
+    /// Erster Durchlauf: alles Licht, das von den entfernten Positionen stammte, auf 0 setzen;
+    /// zweiter Durchlauf: von den hellen Rändern und verbliebenen Quellen aus neu ausbreiten.
+    /// `removed` enthält (Position, vorheriges Licht); die Positionen sind bereits 0.
+    fn remove_light(&mut self, channel: LightChannel, mut removed: Vec<(BlockPos, u8)>) {
+        let mut refill = VecDeque::new();
+
+        while let Some(((x, y, z), level)) = removed.pop() {
+            for (dx, dy, dz) in NEIGHBORS {
+                let (nx, ny, nz) = (x + dx, y + dy, z + dz);
+                let Some(current) = self.light_opt(channel, nx, ny, nz) else {
+                    continue;
+                };
+                if current == 0 {
+                    continue;
+                }
+                let fed_by_removed = current < level
+                    || (channel == LightChannel::Sky
+                        && dy == -1
+                        && level == MAX_LIGHT
+                        && current == MAX_LIGHT);
+                if fed_by_removed {
+                    self.set_light(channel, nx, ny, nz, 0);
+                    removed.push(((nx, ny, nz), current));
+                    // eine andere Quelle leuchtet weiter
+                    let emit = emission(channel, self.get_block(nx, ny, nz));
+                    if emit > 0 {
+                        self.set_light(channel, nx, ny, nz, emit);
+                        refill.push_back((nx, ny, nz));
+                    }
+                } else {
+                    refill.push_back((nx, ny, nz));
+                }
+            }
+        }
+
+        self.spread_light(channel, refill);
+    }
+}
+
+/// Positionen direkt außerhalb der 6 Chunkseiten
+fn chunk_border_outside(cp: ChunkPos) -> Vec<BlockPos> {
+    let (ox, oy, oz) = (cp.cx * CHUNK_SIZE, cp.cy * CHUNK_SIZE, cp.cz * CHUNK_SIZE);
+    let mut out = Vec::with_capacity(6 * (CHUNK_SIZE * CHUNK_SIZE) as usize);
+    for a in 0..CHUNK_SIZE {
+        for b in 0..CHUNK_SIZE {
+            out.push((ox - 1, oy + a, oz + b));
+            out.push((ox + CHUNK_SIZE, oy + a, oz + b));
+            out.push((ox + a, oy - 1, oz + b));
+            out.push((ox + a, oy + CHUNK_SIZE, oz + b));
+            out.push((ox + a, oy + b, oz - 1));
+            out.push((ox + a, oy + b, oz + CHUNK_SIZE));
+        }
+    }
+    out
+}
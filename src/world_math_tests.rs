@@ -0,0 +1,59 @@
+//! Koordinaten-Mathematik und Zielstrahl mit extremen Eingaben (wie die Fuzz-Targets in
+//! fuzz/, aber ohne Nightly lauffähig)
+
+use proptest::num::f32::ANY;
+use proptest::prelude::*;
+
+use crate::block::Block;
+use crate::chunk::{chunk_coord, idx, in_chunk, CHUNK_SIZE, CHUNK_VOL};
+use crate::world::{World, MAX_RAYCAST_DIST};
+
+proptest! {
+    #[test]
+    fn chunk_index_covers_i32(x in any::<i32>(), y in any::<i32>(), z in any::<i32>()) {
+        let mut local = [0; 3];
+        for (l, v) in local.iter_mut().zip([x, y, z]) {
+            *l = in_chunk(v);
+            prop_assert!((0..CHUNK_SIZE).contains(l));
+            prop_assert_eq!(chunk_coord(v) as i64 * CHUNK_SIZE as i64 + *l as i64, v as i64);
+        }
+        prop_assert!(idx(local[0], local[1], local[2]) < CHUNK_VOL);
+    }
+
+    #[test]
+    fn raycast_survives_any_input(
+        origin in [ANY, ANY, ANY],
+        dir in [ANY, ANY, ANY],
+        max_dist in ANY,
+    ) {
+        let world = World::remote();
+        let [ox, oy, oz] = origin;
+        let [dx, dy, dz] = dir;
+        if let Some(hit) = world.raycast((ox, oy, oz), (dx, dy, dz), max_dist) {
+            prop_assert!(hit.distance >= 0.0 && hit.distance <= max_dist.min(MAX_RAYCAST_DIST));
+        }
+    }
+}
+
+#[test]
+fn raycast_rejects_degenerate_rays() {
+    let mut world = World::remote();
+    world.set_block(0, 0, 0, Block::Stone);
+    let origin = (0.5, 3.5, 0.5);
+    let down = (0.0, -1.0, 0.0);
+    for dir in [(0.0, 0.0, 0.0), (f32::NAN, -1.0, 0.0), (0.0, f32::NEG_INFINITY, 0.0)] {
+        assert!(world.raycast(origin, dir, 10.0).is_none(), "{dir:?}");
+    }
+    for max_dist in [f32::NAN, -1.0] {
+        assert!(world.raycast(origin, down, max_dist).is_none());
+    }
+    assert!(world.raycast((f32::NAN, 3.5, 0.5), down, 10.0).is_none());
+    assert!(world.raycast((3.0e9, 3.5, 0.5), down, 10.0).is_none());
+
+    // Riesige oder winzige Richtungen treffen wie normierte, unendliche Reichweite endet
+    for dir in [(0.0, -3.0e38, 0.0), (0.0, -1.0e-40, 0.0)] {
+        assert_eq!(world.raycast(origin, dir, 10.0).map(|h| h.pos), Some((0, 0, 0)));
+    }
+    let up = world.raycast(origin, (0.0, 1.0, 0.0), f32::INFINITY);
+    assert!(up.is_none());
+}